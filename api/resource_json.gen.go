@@ -1766,6 +1766,17 @@ func (this *AIBackend_Provider) UnmarshalJSON(b []byte) error {
 	return ResourceUnmarshaler.Unmarshal(bytes.NewReader(b), this)
 }
 
+// MarshalJSON is a custom marshaler for AIBackend_Provider_Retry
+func (this *AIBackend_Provider_Retry) MarshalJSON() ([]byte, error) {
+	str, err := ResourceMarshaler.MarshalToString(this)
+	return []byte(str), err
+}
+
+// UnmarshalJSON is a custom unmarshaler for AIBackend_Provider_Retry
+func (this *AIBackend_Provider_Retry) UnmarshalJSON(b []byte) error {
+	return ResourceUnmarshaler.Unmarshal(bytes.NewReader(b), this)
+}
+
 // MarshalJSON is a custom marshaler for AIBackend_ProviderGroup
 func (this *AIBackend_ProviderGroup) MarshalJSON() ([]byte, error) {
 	str, err := ResourceMarshaler.MarshalToString(this)
@@ -2131,6 +2131,9 @@ const (
 
 	// RouteTypeRerank processes Cohere `/v2/rerank` format requests.
 	RouteTypeRerank RouteType = "Rerank"
+
+	// RouteTypeTextCompletions processes legacy OpenAI `/v1/completions` format requests.
+	RouteTypeTextCompletions RouteType = "TextCompletions"
 )
 
 // +kubebuilder:validation:AtLeastOneFieldSet
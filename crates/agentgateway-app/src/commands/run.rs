@@ -73,7 +73,20 @@ pub(crate) fn execute(args: RunArgs) -> anyhow::Result<()> {
 				},
 				None => None,
 			};
+			let payload_export = match config.logging.payload_export.as_ref() {
+				Some(cfg) => match agentgateway::telemetry::payload_export::setup(cfg) {
+					Ok(exporter) => Some(exporter),
+					Err(err) => {
+						error!(?err, "failed to initialize payload exporter");
+						return Err(err);
+					},
+				},
+				None => None,
+			};
 			let result = proxy(Arc::new(config), config_resource_store).await;
+			if let Some(payload_export) = payload_export {
+				payload_export.shutdown_and_wait().await;
+			}
 			if let Some(request_log_store) = request_log_store {
 				request_log_store.shutdown_and_wait().await;
 			}
@@ -32,6 +32,13 @@ pub async fn run(
 	let ready = readiness::Ready::new();
 	let state_mgr_task = ready.register_task("state manager");
 	let proxy_task = ready.register_task("agentgateway");
+	if let Some(timeout) = config.tokenizer_preload_timeout {
+		let tokenizers_task = ready.register_task("tokenizers");
+		tokio::spawn(crate::llm::preload_tokenizers_for_readiness(
+			tokenizers_task,
+			timeout,
+		));
+	}
 
 	let readiness_server = crate::management::readiness_server::Server::new(
 		config.readiness_addr.clone(),
@@ -123,6 +130,7 @@ pub async fn run(
 		config_resource_store,
 		stores.clone(),
 		resource_manager,
+		metrics_handle.clone(),
 		shutdown.trigger(),
 		drain_rx.clone(),
 		data_plane_handle.clone(),
@@ -141,6 +149,7 @@ pub async fn run(
 		upstream: client.clone(),
 		ca,
 
+		llm_in_flight: Default::default(),
 		mcp_state: mcp::App::new(stores.clone(), config.session_encoder.clone()),
 	};
 
@@ -177,6 +177,10 @@ impl ContextBuilder {
 	pub fn register_log_request(&mut self) {
 		self.logging_attributes |= Attributes::Request;
 	}
+	/// register_log_llm_payload captures LLM prompts and completions for use once the request completes.
+	pub fn register_log_llm_payload(&mut self) {
+		self.logging_attributes |= Attributes::Llm | Attributes::LlmPrompt | Attributes::LlmCompletion;
+	}
 	fn any_has(&self, attr: impl Into<FlagSet<Attributes>>) -> bool {
 		let x = attr.into();
 		self.request_attributes.contains(x)
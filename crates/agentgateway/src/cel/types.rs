@@ -64,6 +64,9 @@ pub struct Executor<'a> {
 	#[dynamic(rename = "llmRequest")]
 	pub llm_request: Option<&'a serde_json::Value>,
 
+	#[dynamic(rename = "llmDelta")]
+	pub llm_delta: Option<&'a str>,
+
 	pub mcp: Option<&'a MCPInfo>,
 
 	pub backend: ExtensionOrDirect<'a, BackendContext>,
@@ -623,6 +626,14 @@ impl<'a> Executor<'a> {
 		this.llm_request = Some(llm_body);
 		this
 	}
+	pub fn new_llm_delta(req: Option<&'a RequestSnapshot>, delta: &'a str) -> Self {
+		let mut this = Self::new_empty();
+		if let Some(req) = req {
+			this.set_request_snapshot(req);
+		}
+		this.llm_delta = Some(delta);
+		this
+	}
 	pub fn new_logger(
 		req: Option<&'a RequestSnapshot>,
 		resp: Option<&'a ResponseSnapshot>,
@@ -1374,6 +1385,10 @@ pub struct LLMContext {
 	#[dynamic(rename = "serviceTier")]
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub service_tier: Option<Strng>,
+	/// The backend configuration fingerprint reported by the provider.
+	#[dynamic(rename = "systemFingerprint")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub system_fingerprint: Option<Strng>,
 	// For now, not exposed to CEL; only used to piggy-back this field for metrics.
 	#[serde(skip)]
 	#[dynamic(skip)]
@@ -1437,6 +1452,7 @@ impl LLMContext {
 			cached_input_tokens: resp.cached_input_tokens,
 			cache_creation_input_tokens: resp.cache_creation_input_tokens,
 			service_tier: resp.service_tier.clone(),
+			system_fingerprint: resp.system_fingerprint.clone(),
 			response_model: resp.provider_model.clone(),
 			// Not always set
 			completion: resp.completion.clone(),
@@ -1518,6 +1534,7 @@ impl From<llm::LLMRequest> for LLMContext {
 			cached_input_tokens: None,
 			cache_creation_input_tokens: None,
 			service_tier: None,
+			system_fingerprint: None,
 			cost: None,
 			cost_rates: None,
 			cost_status: None,
@@ -1949,6 +1966,11 @@ pub struct ExecutorSerde {
 	#[serde(rename = "llmRequest", skip_serializing_if = "Option::is_none")]
 	pub llm_request: Option<serde_json::Value>,
 
+	/// `llmDelta` contains the text of a single streamed response delta. This is only present while
+	/// evaluating an LLM `streamTransformation`.
+	#[serde(rename = "llmDelta", skip_serializing_if = "Option::is_none")]
+	pub llm_delta: Option<String>,
+
 	/// `source` contains attributes about the source of the request.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub source: Option<SourceContext>,
@@ -2058,6 +2080,7 @@ impl ExecutorSerde {
 		}
 		exec.proxy = ExtensionOrDirect::Direct(self.proxy.as_ref());
 		exec.llm_request = self.llm_request.as_ref();
+		exec.llm_delta = self.llm_delta.as_deref();
 
 		// Set all the ExtensionOrDirect fields
 		exec.source = ExtensionOrDirect::Direct(self.source.as_ref());
@@ -2178,6 +2201,7 @@ pub fn full_example_executor() -> ExecutorSerde {
 		llm_request: Some(json!({
 			"model": "provider/model"
 		})),
+		llm_delta: Some("Hello".to_string()),
 		llm: Some(LLMContext {
 			streaming: false,
 			request_model: "gpt-4".into(),
@@ -2196,6 +2220,7 @@ pub fn full_example_executor() -> ExecutorSerde {
 			reasoning_tokens: Some(30),
 			total_tokens: Some(150),
 			service_tier: Some("default".into()),
+			system_fingerprint: None,
 			first_token: None,
 			time_to_first_token: Some(chrono::Duration::milliseconds(123).into()),
 			time_per_output_token: Some(chrono::Duration::milliseconds(7).into()),
@@ -2236,9 +2261,11 @@ pub fn full_example_executor() -> ExecutorSerde {
 					"isError": false,
 				})),
 				error: None,
+				description: None,
 			}),
 			prompt: None,
 			resource: None,
+			client: None,
 		}),
 		backend: Some(BackendContext {
 			name: "my-backend".into(),
@@ -84,6 +84,7 @@ fn build_test_request() -> crate::http::Request {
 		output_audio_tokens: None,
 		total_tokens: Some(150),
 		service_tier: None,
+		system_fingerprint: None,
 		first_token: None,
 		time_to_first_token: Some(chrono::Duration::milliseconds(123).into()),
 		time_per_output_token: Some(chrono::Duration::milliseconds(7).into()),
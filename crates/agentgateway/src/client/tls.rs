@@ -4,7 +4,7 @@ use tokio_rustls::TlsConnector;
 use tracing::debug;
 
 use crate::http::backendtls::VersionedBackendTLS;
-use crate::transport::stream::Socket;
+use crate::transport::stream::{Socket, UpstreamTLSInfo};
 use crate::types::agent::Target;
 
 pub async fn handshake(
@@ -31,11 +31,12 @@ pub async fn handshake(
 			alpn=?cfg.config.alpn_protocols.iter().map(|bytes| String::from_utf8_lossy(bytes.as_slice())).collect_vec(),
 			"connecting tls");
 
-	let (ext, counter, tcp) = tcp.into_parts();
+	let (mut ext, counter, tcp) = tcp.into_parts();
 	let tls = TlsConnector::from(cfg.config.clone())
 		.connect(server_name, Box::new(tcp))
 		.await
 		.map_err(crate::http::Error::new)?;
+	ext.insert(UpstreamTLSInfo::from_connection(tls.get_ref().1));
 	let socket = Socket::from_tls(ext, counter, tls.into()).map_err(crate::http::Error::new)?;
 	Ok(socket)
 }
@@ -384,6 +384,8 @@ pub fn parse_config(
 		num_worker_threads: parse_worker_threads(raw.worker_threads)
 			.ctx("invalid WORKER_THREADS/config.workerThreads")?,
 		termination_min_deadline,
+		tokenizer_preload_timeout: parse_duration("TOKENIZER_PRELOAD_TIMEOUT")?
+			.or(raw.tokenizer_preload_timeout),
 		threading_mode,
 		backend: raw.backend,
 		admin_runtime_handle: None,
@@ -505,14 +507,14 @@ pub fn parse_config(
 		},
 		logging: telemetry::log::Config {
 			filter: raw
-					.logging
-					.as_ref()
-					.and_then(|l| l.filter.as_ref())
-					.map(|expression| {
-						cel::Expression::new_strict(expression).ctx("invalid config.logging.filter")
-					})
-					.transpose()?
-					.map(Arc::new),
+				.logging
+				.as_ref()
+				.and_then(|l| l.filter.as_ref())
+				.map(|expression| {
+					cel::Expression::new_strict(expression).ctx("invalid config.logging.filter")
+				})
+				.transpose()?
+				.map(Arc::new),
 			level: match raw.logging.as_ref().and_then(|l| l.level.as_ref()) {
 				None => "".to_string(),
 				Some(RawLoggingLevel::Single(level)) => level.to_string(),
@@ -524,14 +526,23 @@ pub fn parse_config(
 				.and_then(|l| l.format.clone())
 				.unwrap_or_default(),
 			database: database.clone(),
-				fields: logging_fields(raw.logging.as_ref().and_then(|f| f.fields.clone()))
-					.ctx("invalid config.logging.fields")?,
-				database_fields: if database.is_some() {
-					database_logging_fields(raw.standard_attributes.as_ref())
-						.ctx("invalid config.standardAttributes")?
-				} else {
-					Default::default()
-				},
+			payload_export: raw
+				.logging
+				.as_ref()
+				.and_then(|l| l.payload_export.clone()),
+			llm_summary: raw
+				.logging
+				.as_ref()
+				.and_then(|l| l.llm_summary)
+				.unwrap_or_default(),
+			fields: logging_fields(raw.logging.as_ref().and_then(|f| f.fields.clone()))
+				.ctx("invalid config.logging.fields")?,
+			database_fields: if database.is_some() {
+				database_logging_fields(raw.standard_attributes.as_ref())
+					.ctx("invalid config.standardAttributes")?
+			} else {
+				Default::default()
+			},
 		},
 		dns: client::Config {
 			resolver_cfg,
@@ -558,6 +569,7 @@ pub fn parse_config(
 		},
 		database,
 		config_store,
+		error_verbosity: raw.error_verbosity.unwrap_or_default(),
 		session_encoder,
 		oidc_cookie_encoder,
 			hbone: Arc::new(agent_hbone::Config {
@@ -125,6 +125,10 @@ impl APIKeyHash {
 		}
 		Ok(APIKeyHash(digest.to_ascii_lowercase()))
 	}
+
+	pub(crate) fn as_str(&self) -> &str {
+		&self.0
+	}
 }
 
 fn deser_key_hash<'de, D>(deserializer: D) -> Result<String, D::Error>
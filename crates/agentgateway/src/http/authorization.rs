@@ -31,6 +31,33 @@ impl HTTPAuthorizationSet {
 		}
 		Ok(())
 	}
+
+	/// Like `apply`, but a matching `reject` rule denies the request with the custom response it
+	/// produced rather than the default authorization failure.
+	pub fn apply_policy(
+		&self,
+		req: &http::Request,
+	) -> Result<http::PolicyResponse, crate::proxy::ProxyResponse> {
+		let exec = cel::Executor::new_request(req);
+		if let Some(rejection) = self.0.rejection(&exec) {
+			return match rejection {
+				Ok(resp) => Ok(http::PolicyResponse {
+					direct_response: Some(resp),
+					response_headers: None,
+				}),
+				Err(e) => {
+					tracing::warn!("invalid authorization rejection: {e}");
+					Err(crate::proxy::ProxyResponse::from(
+						ProxyError::AuthorizationFailed,
+					))
+				},
+			};
+		}
+		self
+			.apply(req)
+			.map_err(|_| crate::proxy::ProxyResponse::from(ProxyError::AuthorizationFailed))?;
+		Ok(http::PolicyResponse::default())
+	}
 }
 
 impl crate::store::RequestPolicyTrait for HTTPAuthorizationSet {
@@ -40,10 +67,7 @@ impl crate::store::RequestPolicyTrait for HTTPAuthorizationSet {
 		_log: &mut crate::telemetry::log::RequestLog,
 		req: &mut http::Request,
 	) -> Result<http::PolicyResponse, crate::proxy::ProxyResponse> {
-		self
-			.apply(req)
-			.map_err(|_| crate::proxy::ProxyResponse::from(ProxyError::AuthorizationFailed))?;
-		Ok(http::PolicyResponse::default())
+		self.apply_policy(req)
 	}
 
 	fn expressions(&self) -> impl Iterator<Item = &cel::Expression> {
@@ -58,10 +82,7 @@ impl crate::store::BackendPolicyTrait for HTTPAuthorizationSet {
 		_log: &mut Option<&mut crate::telemetry::log::RequestLog>,
 		req: &mut http::Request,
 	) -> Result<http::PolicyResponse, crate::proxy::ProxyResponse> {
-		self
-			.apply(req)
-			.map_err(|_| crate::proxy::ProxyResponse::from(ProxyError::AuthorizationFailed))?;
-		Ok(http::PolicyResponse::default())
+		self.apply_policy(req)
 	}
 
 	fn expressions(&self) -> impl Iterator<Item = &cel::Expression> {
@@ -109,6 +130,7 @@ pub struct PolicySet {
 	allow: Vec<Arc<cel::Expression>>,
 	deny: Vec<Arc<cel::Expression>>,
 	require: Vec<Arc<cel::Expression>>,
+	reject: Vec<Arc<cel::Expression>>,
 }
 
 #[derive(Clone, Debug)]
@@ -136,6 +158,9 @@ enum RuleTypeSerde {
 	Deny(String),
 	/// Require this CEL expression to be true.
 	Require(String),
+	/// Deny the request with a custom response when this CEL expression evaluates to a map of
+	/// `status`, `body` and `headers`. A `null` or `false` result does not reject the request.
+	Reject(String),
 }
 
 impl PolicySet {
@@ -148,6 +173,7 @@ impl PolicySet {
 			allow,
 			deny,
 			require,
+			reject: vec![],
 		}
 	}
 }
@@ -155,7 +181,8 @@ impl PolicySet {
 pub fn se_policies<S: Serializer>(t: &PolicySet, serializer: S) -> Result<S::Ok, S::Error> {
 	let len = usize::from(!t.allow.is_empty())
 		+ usize::from(!t.deny.is_empty())
-		+ usize::from(!t.require.is_empty());
+		+ usize::from(!t.require.is_empty())
+		+ usize::from(!t.reject.is_empty());
 	let mut m = serializer.serialize_map(Some(len))?;
 	if !t.allow.is_empty() {
 		m.serialize_entry("allow", &t.allow)?;
@@ -166,6 +193,9 @@ pub fn se_policies<S: Serializer>(t: &PolicySet, serializer: S) -> Result<S::Ok,
 	if !t.require.is_empty() {
 		m.serialize_entry("require", &t.require)?;
 	}
+	if !t.reject.is_empty() {
+		m.serialize_entry("reject", &t.reject)?;
+	}
 	m.end()
 }
 
@@ -174,11 +204,7 @@ where
 	D: Deserializer<'de>,
 {
 	let raw = Vec::<RuleSerde>::deserialize(deserializer)?;
-	let mut res = PolicySet {
-		allow: vec![],
-		deny: vec![],
-		require: vec![],
-	};
+	let mut res = PolicySet::default();
 	for r in raw {
 		match r {
 			RuleSerde::Object {
@@ -203,6 +229,13 @@ where
 					.map(Arc::new)
 					.map_err(|e| serde::de::Error::custom(e.to_string()))?,
 			),
+			RuleSerde::Object {
+				rule: RuleTypeSerde::Reject(reject),
+			} => res.reject.push(
+				cel::Expression::new_strict(reject)
+					.map(Arc::new)
+					.map_err(|e| serde::de::Error::custom(e.to_string()))?,
+			),
 		};
 	}
 	Ok(res)
@@ -244,6 +277,7 @@ impl RuleSets {
 				.iter()
 				.chain(rule_set.rules.deny.iter())
 				.chain(rule_set.rules.require.iter())
+				.chain(rule_set.rules.reject.iter())
 				.map(|rule| rule.as_ref())
 		})
 	}
@@ -257,8 +291,11 @@ impl RuleSets {
 		#[allow(clippy::if_same_then_else)] // This is intentional to make things explicit.
 		let allowed = if !has_rules {
 			true
-		// If there are any DENY, deny
-		} else if rule_sets.iter().any(|r| r.denies(exec)) {
+		// If there are any DENY or REJECT, deny
+		} else if rule_sets
+			.iter()
+			.any(|r| r.denies(exec) || r.rejection(exec).is_some())
+		{
 			false
 		// All REQUIRE policies must match when present.
 		} else if rule_sets.iter().any(|r| !r.all_requires_match(exec)) {
@@ -298,6 +335,13 @@ impl RuleSets {
 						mode: AuthorizationRuleMode::Require,
 					});
 				}
+				for rule in &rule_set.rules.reject {
+					rules.push(AuthorizationRuleResult {
+						name: rule.original_expression.clone(),
+						matched: rejection_value(exec, rule).is_some(),
+						mode: AuthorizationRuleMode::Reject,
+					});
+				}
 			}
 
 			trace.authorization_result(
@@ -313,6 +357,11 @@ impl RuleSets {
 		allowed
 	}
 
+	/// The custom response from the first `reject` rule that matches, if any.
+	pub fn rejection(&self, exec: &Executor) -> Option<anyhow::Result<http::Response>> {
+		self.0.iter().find_map(|r| r.rejection(exec))
+	}
+
 	pub fn is_empty(&self) -> bool {
 		self.0.is_empty()
 	}
@@ -324,7 +373,10 @@ impl RuleSet {
 	}
 
 	pub fn has_rules(&self) -> bool {
-		!self.rules.allow.is_empty() || !self.rules.deny.is_empty() || !self.rules.require.is_empty()
+		!self.rules.allow.is_empty()
+			|| !self.rules.deny.is_empty()
+			|| !self.rules.require.is_empty()
+			|| !self.rules.reject.is_empty()
 	}
 
 	pub fn has_allow_rules(&self) -> bool {
@@ -364,6 +416,64 @@ impl RuleSet {
 			.iter()
 			.all(|rule| exec.eval_bool(rule.as_ref()))
 	}
+
+	pub fn rejection(&self, exec: &cel::Executor) -> Option<anyhow::Result<http::Response>> {
+		self
+			.rules
+			.reject
+			.iter()
+			.find_map(|rule| rejection_value(exec, rule))
+			.map(rejection_response)
+	}
+}
+
+/// Evaluates a `reject` rule. Evaluation errors, `null` and `false` do not reject.
+fn rejection_value(exec: &cel::Executor, rule: &cel::Expression) -> Option<serde_json::Value> {
+	match exec.eval(rule).ok()?.json().ok()? {
+		serde_json::Value::Null | serde_json::Value::Bool(false) => None,
+		v => Some(v),
+	}
+}
+
+fn rejection_response(v: serde_json::Value) -> anyhow::Result<http::Response> {
+	let serde_json::Value::Object(mut v) = v else {
+		anyhow::bail!("reject expression must evaluate to a map, got {v}");
+	};
+	let status = match v.remove("status") {
+		None => ::http::StatusCode::FORBIDDEN,
+		Some(s) => s
+			.as_u64()
+			.and_then(|s| u16::try_from(s).ok())
+			.and_then(|s| ::http::StatusCode::from_u16(s).ok())
+			.ok_or_else(|| anyhow::anyhow!("invalid rejection status {s}"))?,
+	};
+	let mut builder = ::http::Response::builder().status(status);
+	if let Some(headers) = v.remove("headers") {
+		let serde_json::Value::Object(headers) = headers else {
+			anyhow::bail!("rejection headers must be a map, got {headers}");
+		};
+		for (k, hv) in headers {
+			let hv = match hv {
+				serde_json::Value::String(s) => s,
+				other => other.to_string(),
+			};
+			builder = builder.header(k, hv);
+		}
+	}
+	let body = match v.remove("body") {
+		None | Some(serde_json::Value::Null) => http::Body::empty(),
+		Some(serde_json::Value::String(s)) => http::Body::from(s),
+		Some(json) => {
+			if !builder
+				.headers_ref()
+				.is_some_and(|h| h.contains_key(::http::header::CONTENT_TYPE))
+			{
+				builder = builder.header(::http::header::CONTENT_TYPE, "application/json");
+			}
+			http::Body::from(serde_json::to_vec(&json)?)
+		},
+	};
+	Ok(builder.body(body)?)
 }
 
 #[cfg(any(test, feature = "internal_benches"))]
@@ -49,6 +49,16 @@ fn create_require_policy_set(policies: Vec<&str>) -> PolicySet {
 	policy_set
 }
 
+fn create_reject_policy_set(policies: Vec<&str>) -> PolicySet {
+	let mut policy_set = PolicySet::default();
+	for p in policies.into_iter() {
+		policy_set
+			.reject
+			.push(Arc::new(cel::Expression::new_strict(p).unwrap()));
+	}
+	policy_set
+}
+
 #[test]
 fn test_rbac_reject_exact_match() {
 	let policies = vec![r#"mcp.tool.name == "increment" && jwt.user == "admin""#];
@@ -218,6 +228,43 @@ fn test_network_authorization_deny_takes_precedence() {
 	assert_matches!(network_authz.apply(&source), Err(_));
 }
 
+#[tokio::test]
+async fn test_reject_rule_returns_custom_response() {
+	let reject = RuleSet::new(create_reject_policy_set(vec![
+		r#"request.headers["x-tier"] == "free" ? {"status": 429, "body": {"error": "quota exceeded"}, "headers": {"retry-after": "30"}} : null"#,
+	]));
+	let authz = HTTPAuthorizationSet::new(vec![reject].into());
+	let request = |tier: &str| {
+		::http::Request::builder()
+			.uri("http://example.com/")
+			.header("x-tier", tier)
+			.body(Body::empty())
+			.unwrap()
+	};
+
+	let resp = authz
+		.apply_policy(&request("free"))
+		.unwrap()
+		.direct_response
+		.expect("reject rule should produce a direct response");
+	assert_eq!(resp.status(), ::http::StatusCode::TOO_MANY_REQUESTS);
+	assert_eq!(resp.headers()["retry-after"], "30");
+	assert_eq!(
+		resp.headers()[::http::header::CONTENT_TYPE],
+		"application/json"
+	);
+	let body = http::read_body_with_limit(resp.into_body(), 1024)
+		.await
+		.unwrap();
+	assert_eq!(
+		serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+		json!({"error": "quota exceeded"})
+	);
+
+	let allowed = authz.apply_policy(&request("paid")).unwrap();
+	assert!(allowed.direct_response.is_none());
+}
+
 #[test]
 fn test_stacked_deny_policies() {
 	// Two deny-only RuleSets: one denies "increment", another denies "decrement"
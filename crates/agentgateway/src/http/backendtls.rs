@@ -8,7 +8,7 @@ use rustls::ClientConfig;
 use rustls_pki_types::pem::PemObject;
 use rustls_pki_types::{CertificateDer, ServerName};
 use serde::Serializer;
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::serdes::{schema_de, schema_ser};
 use crate::transport::tls;
@@ -315,6 +315,17 @@ impl LocalBackendTLS {
 			),
 			None => None,
 		};
+		if self.insecure {
+			warn!(
+				hostname = self.hostname.as_deref(),
+				"backend TLS certificate verification is disabled; connections are vulnerable to interception"
+			);
+		} else if self.insecure_host {
+			warn!(
+				hostname = self.hostname.as_deref(),
+				"backend TLS hostname verification is disabled"
+			);
+		}
 
 		ResolvedBackendTLS {
 			cert,
@@ -108,6 +108,19 @@ fn detect_encoding(ce: &ContentEncoding) -> EncodingDecision {
 	}
 }
 
+/// Returns whether every coding listed in an `Accept-Encoding` value can be decoded by
+/// [`decompress_body`], so a response to a request carrying it can always be inspected.
+/// Quality parameters are ignored; the `*` wildcard is rejected as it admits any coding.
+pub fn is_decodable_accept_encoding(value: &str) -> bool {
+	value.split(',').all(|token| {
+		let coding = token.split(';').next().unwrap_or_default().trim();
+		coding.is_empty()
+			|| ["identity", GZIP, DEFLATE, BR, ZSTD]
+				.iter()
+				.any(|supported| coding.eq_ignore_ascii_case(supported))
+	})
+}
+
 /// Decompresses an HTTP body stream, returning a new body that yields decompressed chunks.
 ///
 /// Use this for streaming responses (SSE, large files) where you can't buffer the entire body.
@@ -277,6 +290,14 @@ mod tests {
 		assert!(matches!(result, Err(Error::UnsupportedEncoding)));
 	}
 
+	#[test]
+	fn test_decodable_accept_encoding() {
+		assert!(is_decodable_accept_encoding("identity"));
+		assert!(is_decodable_accept_encoding("gzip;q=1.0, BR, zstd;q=0.5"));
+		assert!(!is_decodable_accept_encoding("gzip, compress"));
+		assert!(!is_decodable_accept_encoding("*"));
+	}
+
 	#[tokio::test]
 	async fn test_identity_passthrough() {
 		let body = Body::from("hello");
@@ -0,0 +1,103 @@
+use ::http::{HeaderMap, Method, StatusCode};
+
+use crate::http::{WellKnownContentTypes, classify_content_type};
+use crate::*;
+
+#[apply(schema!)]
+#[derive(Copy, PartialEq, Eq)]
+pub enum ContentType {
+	/// `application/json`
+	Json,
+	/// `text/event-stream`
+	Sse,
+}
+
+impl ContentType {
+	fn matches(&self, headers: &HeaderMap) -> bool {
+		matches!(
+			(self, classify_content_type(headers)),
+			(ContentType::Json, WellKnownContentTypes::Json)
+				| (ContentType::Sse, WellKnownContentTypes::Sse)
+		)
+	}
+}
+
+#[apply(schema!)]
+pub struct ContentTypeEnforcement {
+	/// Content types accepted on requests. Other requests, including those without a content type,
+	/// are rejected with `415`. GET, HEAD and OPTIONS requests are not checked.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub request: Vec<ContentType>,
+	/// Content types accepted on successful upstream responses. Other responses are replaced with a
+	/// `502`. Error and `204` responses are not checked.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub response: Vec<ContentType>,
+}
+
+impl ContentTypeEnforcement {
+	pub fn apply_to_request(
+		&self,
+		req: &crate::http::Request,
+	) -> Result<(), crate::proxy::ProxyResponse> {
+		if self.request.is_empty()
+			|| matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS)
+			|| self.request.iter().any(|ct| ct.matches(req.headers()))
+		{
+			return Ok(());
+		}
+		debug!(
+			content_type = ?req.headers().get(::http::header::CONTENT_TYPE),
+			"rejecting request content type"
+		);
+		Err(direct_response(StatusCode::UNSUPPORTED_MEDIA_TYPE))
+	}
+
+	pub fn apply_to_response(
+		&self,
+		resp: &crate::http::Response,
+	) -> Result<(), crate::proxy::ProxyResponse> {
+		if self.response.is_empty()
+			|| !resp.status().is_success()
+			|| resp.status() == StatusCode::NO_CONTENT
+			|| self.response.iter().any(|ct| ct.matches(resp.headers()))
+		{
+			return Ok(());
+		}
+		warn!(
+			content_type = ?resp.headers().get(::http::header::CONTENT_TYPE),
+			"rejecting response content type"
+		);
+		Err(direct_response(StatusCode::BAD_GATEWAY))
+	}
+}
+
+fn direct_response(status: StatusCode) -> crate::proxy::ProxyResponse {
+	let resp = ::http::Response::builder()
+		.status(status)
+		.body(crate::http::Body::empty())
+		.expect("static response builds");
+	crate::proxy::ProxyResponse::DirectResponse(Box::new(resp))
+}
+
+impl crate::store::RequestPolicyTrait for ContentTypeEnforcement {
+	async fn apply(
+		&self,
+		_client: &crate::proxy::httpproxy::PolicyClient,
+		_log: &mut crate::telemetry::log::RequestLog,
+		req: &mut crate::http::Request,
+	) -> Result<crate::http::PolicyResponse, crate::proxy::ProxyResponse> {
+		self.apply_to_request(req)?;
+		Ok(Default::default())
+	}
+}
+
+impl crate::store::ResponsePolicyTrait for ContentTypeEnforcement {
+	async fn apply(
+		&self,
+		_log: &mut crate::telemetry::log::RequestLog,
+		res: &mut crate::http::Response,
+	) -> Result<crate::http::PolicyResponse, crate::proxy::ProxyResponse> {
+		self.apply_to_response(res)?;
+		Ok(Default::default())
+	}
+}
@@ -419,7 +419,8 @@ fn host_matches(pattern: &str, host: &str) -> bool {
 	wildcard_match(pattern, host)
 }
 
-fn wildcard_match(pattern: &str, value: &str) -> bool {
+/// Matches `value` against `pattern`, where `*` matches any run of characters.
+pub(crate) fn wildcard_match(pattern: &str, value: &str) -> bool {
 	let pattern = pattern.as_bytes();
 	let value = value.as_bytes();
 	let (mut pattern_idx, mut value_idx) = (0usize, 0usize);
@@ -260,7 +260,7 @@ mod body_modes {
 			mock,
 			ext_proc::FailureMode::FailClosed,
 			ExtProcMock::new(BufferedBodyReplacementWithoutContentLengthExtProc::default),
-			"{}",
+			r#"{"config":{"errorVerbosity":"verbose"}}"#,
 			Some(processing_options),
 		)
 		.await;
@@ -597,7 +597,7 @@ mod body_modes {
 			mock,
 			ext_proc::FailureMode::FailClosed,
 			ExtProcMock::new(BufferedBodyReplacementWithoutContentLengthExtProc::default),
-			"{}",
+			r#"{"config":{"errorVerbosity":"verbose"}}"#,
 			Some(processing_options),
 		)
 		.await;
@@ -1671,7 +1671,7 @@ mod immediate_and_failure {
 		let res = send_request_body(io, Method::POST, "http://lo", b"request").await;
 		assert_eq!(res.status(), 500);
 		let body = read_body_raw(res.into_body()).await;
-		assert!(body.as_ref().starts_with(b"ext_proc failed:"));
+		assert_eq!(body.as_ref(), b"ext_proc failed");
 	}
 
 	#[tokio::test]
@@ -1756,7 +1756,7 @@ mod immediate_and_failure {
 		let res = send_request_body(io, Method::POST, "http://lo", b"request").await;
 		assert_eq!(res.status(), 500);
 		let body = read_body_raw(res.into_body()).await;
-		assert!(body.as_ref().starts_with(b"ext_proc failed:"));
+		assert_eq!(body.as_ref(), b"ext_proc failed");
 	}
 
 	#[tokio::test]
@@ -651,6 +651,8 @@ fn make_min_req_log() -> crate::telemetry::log::RequestLog {
 		level: "info".to_string(),
 		format: crate::LoggingFormat::Text,
 		database: None,
+		payload_export: None,
+		llm_summary: false,
 	};
 	let cel = log::CelLogging::new(log_cfg, MetricsConfig::default());
 	let mut prom = Registry::default();
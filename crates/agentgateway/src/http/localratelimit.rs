@@ -26,6 +26,7 @@ impl<'de> serde::Deserialize<'de> for RateLimit {
 }
 
 #[apply(schema!)]
+#[derive(Eq, PartialEq)]
 pub struct RateLimitSpec {
 	/// Maximum number of tokens that can accumulate in the local bucket.
 	#[serde(default)]
@@ -110,8 +111,7 @@ impl RateLimit {
 				Err(ProxyError::RateLimitExceeded {
 					limit: self.ratelimit.max_tokens(),
 					remaining: avail,
-					reset_seconds: (self.ratelimit.next_refill() - clocksource::precise::Instant::now())
-						.as_secs(),
+					reset_seconds: self.ratelimit.peek().1.as_secs(),
 				})
 			}
 		}
@@ -125,6 +125,38 @@ impl RateLimit {
 	pub fn amend_tokens(&self, tokens_to_remove: i64) {
 		self.ratelimit.amend_tokens(tokens_to_remove);
 	}
+
+	/// Carries the bucket level and refill schedule of `old`, the limit this one replaces, over to
+	/// this limit so a config reload does not hand out a fresh bucket. Only limits with an
+	/// identical spec are resumed; returns whether this one was.
+	pub fn resume_from(&self, old: &RateLimit) -> bool {
+		if self.spec != old.spec {
+			return false;
+		}
+		self.ratelimit.resume_from(&old.ratelimit);
+		true
+	}
+
+	/// Snapshot of the bucket, including any refill that is due and any prior `amend_tokens`.
+	/// This does not modify the bucket.
+	pub fn status(&self) -> RateLimitStatus {
+		let (remaining, reset) = self.ratelimit.peek();
+		RateLimitStatus {
+			limit_type: self.spec.limit_type.clone(),
+			limit: self.ratelimit.max_tokens(),
+			remaining,
+			reset_seconds: reset.as_secs(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RateLimitStatus {
+	#[serde(rename = "type")]
+	pub limit_type: RateLimitType,
+	pub limit: u64,
+	pub remaining: u64,
+	pub reset_seconds: u64,
 }
 
 impl crate::store::RequestPolicyTrait for Vec<RateLimit> {
@@ -210,6 +242,31 @@ mod ratelimit {
 			self.refill_at.load(Ordering::Relaxed)
 		}
 
+		/// Returns the tokens that would be available now and the time until the following refill,
+		/// accounting for any refill that is due without applying it.
+		pub fn peek(&self) -> (u64, core::time::Duration) {
+			let time = Instant::now();
+			let available = self.available.load(Ordering::Acquire);
+			let refill_at = self.next_refill();
+			if time < refill_at {
+				return (
+					available,
+					core::time::Duration::from_nanos((refill_at - time).as_nanos()),
+				);
+			}
+			let parameters = self.parameters;
+			let interval = parameters.refill_interval.as_nanos();
+			let intervals = (time - refill_at).as_nanos() / interval + 1;
+			let next_refill = refill_at + Duration::from_nanos(intervals.saturating_mul(interval));
+			let available = available
+				.saturating_add(intervals.saturating_mul(parameters.refill_amount))
+				.min(parameters.capacity);
+			(
+				available,
+				core::time::Duration::from_nanos((next_refill - time).as_nanos()),
+			)
+		}
+
 		/// Returns the number of tokens that have been dropped due to bucket
 		/// overflowing.
 		#[allow(dead_code)]
@@ -238,6 +295,18 @@ mod ratelimit {
 				});
 		}
 
+		/// Copy the available tokens and next refill time from `old`, applying any refill that is
+		/// due on `old` first.
+		pub fn resume_from(&self, old: &Ratelimiter) {
+			let _ = old.refill(Instant::now());
+			self
+				.available
+				.store(old.available.load(Ordering::Acquire), Ordering::Release);
+			self
+				.refill_at
+				.store(old.refill_at.load(Ordering::Acquire), Ordering::Release);
+		}
+
 		/// Internal function to refill the token bucket. Called as part of
 		/// `try_wait()`
 		fn refill(&self, time: Instant) -> Result<(), core::time::Duration> {
@@ -757,5 +826,27 @@ mod ratelimit {
 			rl.amend_tokens(2);
 			assert_eq!(rl.available(), available_after_refill - 2);
 		}
+
+		// peek reports a due refill without applying it, and never an elapsed reset time
+		#[test]
+		pub fn peek_does_not_refill() {
+			let rl = Ratelimiter::builder(5, Duration::from_secs(3600))
+				.max_tokens(10)
+				.initial_available(2)
+				.build()
+				.unwrap();
+
+			let (available, reset) = rl.peek();
+			assert_eq!(available, 2);
+			assert!(reset <= Duration::from_secs(3600));
+
+			force_refill_due(&rl, ClockDuration::from_nanos(1));
+			let refill_at = rl.next_refill();
+			let (available, reset) = rl.peek();
+			assert_eq!(available, 7);
+			assert!(reset > Duration::ZERO);
+			assert_eq!(rl.available(), 2);
+			assert_eq!(rl.next_refill(), refill_at);
+		}
 	}
 }
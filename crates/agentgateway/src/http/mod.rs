@@ -5,6 +5,7 @@ pub mod timeout;
 pub mod buffer;
 pub mod bufferbody;
 mod buflist;
+pub mod contenttype;
 pub mod cors;
 pub mod delay;
 pub mod jwt;
@@ -238,6 +239,8 @@ pub use ::http::uri::{Authority, Scheme};
 pub use ::http::{
 	HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri, header, status, uri,
 };
+use agent_core::strng;
+use agent_core::strng::Strng;
 use axum_core::BoxError;
 use bytes::Bytes;
 use cel::Value;
@@ -726,6 +729,23 @@ pub fn get_host_with_port(req: &Request) -> Result<&str, ProxyError> {
 	Ok(host)
 }
 
+/// caller_identity returns the authenticated caller of a request, if any. The JWT subject is
+/// preferred, then the basic auth username, then a hash of the API key.
+pub fn caller_identity(extensions: &::http::Extensions) -> Option<Strng> {
+	if let Some(claims) = extensions.get::<jwt::Claims>()
+		&& let Some(serde_json::Value::String(sub)) = claims.inner.get("sub")
+	{
+		return Some(strng::format!("jwt:{sub}"));
+	}
+	if let Some(claims) = extensions.get::<basicauth::Claims>() {
+		return Some(strng::format!("basic:{}", claims.username));
+	}
+	if let Some(claims) = extensions.get::<apikey::Claims>() {
+		return Some(strng::format!("apikey:{}", claims.key.sha256().as_str()));
+	}
+	None
+}
+
 pub async fn read_req_body(req: Request) -> Result<Bytes, axum_core::Error> {
 	let lim = buffer_limit(&req);
 	read_body_with_limit(req.into_body(), lim).await
@@ -24,9 +24,12 @@ pub fn retry_after(status: StatusCode, h: &HeaderMap) -> Option<std::time::Durat
 	}
 }
 
-/// Some APIs may return rate limit information via response headers.
-/// There is no single standard for this, so we must check a few common implementations.
-fn process_rate_limit_headers(h: &HeaderMap, now: SystemTime) -> Option<std::time::Duration> {
+/// Returns the delay requested by `retry-after` or `retry-after-ms`, regardless of status.
+pub fn retry_after_header(h: &HeaderMap) -> Option<std::time::Duration> {
+	retry_after_headers(h, SystemTime::now())
+}
+
+fn retry_after_headers(h: &HeaderMap, now: SystemTime) -> Option<std::time::Duration> {
 	// `Retry-After`: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Retry-After
 	// Value may be in seconds, or an HTTP date.
 	// This is the only standardized header we can use.
@@ -46,6 +49,15 @@ fn process_rate_limit_headers(h: &HeaderMap, now: SystemTime) -> Option<std::tim
 	{
 		return Some(std::time::Duration::from_millis(ms));
 	}
+	None
+}
+
+/// Some APIs may return rate limit information via response headers.
+/// There is no single standard for this, so we must check a few common implementations.
+fn process_rate_limit_headers(h: &HeaderMap, now: SystemTime) -> Option<std::time::Duration> {
+	if let Some(d) = retry_after_headers(h, now) {
+		return Some(d);
+	}
 
 	// x-ratelimit-reset: commonly used.
 	// Typically this is a unix epoch timestamp OR number of seconds. Rarely it is number of milliseconds.
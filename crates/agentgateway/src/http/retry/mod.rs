@@ -24,6 +24,16 @@ pub struct Policy {
 	)]
 	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
 	pub backoff: Option<Duration>,
+	/// Total time allowed for the initial attempt plus all retries (including model fallbacks).
+	/// Once it is spent, in-flight attempts are cancelled and no further attempts are made; the
+	/// request fails with a 504 even if attempts remain.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub budget: Option<Duration>,
 	/// HTTP response status codes that should be retried.
 	#[serde(serialize_with = "ser_display_iter", deserialize_with = "de_codes")]
 	#[cfg_attr(feature = "schema", schemars(with = "Vec<std::num::NonZeroU16>"))]
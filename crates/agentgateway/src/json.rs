@@ -87,3 +87,38 @@ pub fn convert<I: Serialize, O: DeserializeOwned>(input: &I) -> Result<O, serde_
 	let o = serde_json::from_value::<O>(v)?;
 	Ok(o)
 }
+
+/// Default for [`exceeds_depth`]; matches the recursion limit serde_json enforces on its own.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// exceeds_depth reports whether arrays and objects in `bytes` nest deeper than `max`.
+/// This only scans brackets, so it is cheap enough to run before parsing; it does not validate
+/// the JSON.
+pub fn exceeds_depth(bytes: &[u8], max: usize) -> bool {
+	let mut depth = 0usize;
+	let mut in_string = false;
+	let mut escaped = false;
+	for b in bytes {
+		if in_string {
+			match b {
+				_ if escaped => escaped = false,
+				b'\\' => escaped = true,
+				b'"' => in_string = false,
+				_ => {},
+			}
+			continue;
+		}
+		match b {
+			b'"' => in_string = true,
+			b'[' | b'{' => {
+				depth += 1;
+				if depth > max {
+					return true;
+				}
+			},
+			b']' | b'}' => depth = depth.saturating_sub(1),
+			_ => {},
+		}
+	}
+	false
+}
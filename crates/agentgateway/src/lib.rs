@@ -222,6 +222,12 @@ pub struct RawConfig {
 	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
 	connection_min_termination_deadline: Option<Duration>,
 
+	/// Eagerly load LLM tokenizers at startup, holding readiness until they are loaded or this timeout
+	/// elapses. By default, tokenizers are loaded in the background once a tokenizing backend is configured.
+	#[serde(default, with = "serde_dur_option")]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	tokenizer_preload_timeout: Option<Duration>,
+
 	/// Number of worker threads for the async runtime. Accepts a number or a string such as "auto".
 	worker_threads: Option<StringOrInt>,
 
@@ -231,6 +237,9 @@ pub struct RawConfig {
 	logging: Option<RawLogging>,
 	/// Metrics configuration, including metric removal and custom fields.
 	metrics: Option<RawMetrics>,
+	/// How much detail about internal failures is returned to clients in error responses. Defaults to
+	/// `terse`; the full error is always recorded in the request log.
+	error_verbosity: Option<ErrorVerbosity>,
 
 	/// Configuration for upstream connections, including keepalives, timeouts, and pooling.
 	#[serde(default)]
@@ -406,6 +415,11 @@ pub struct RawLogging {
 	format: Option<LoggingFormat>,
 	/// Log-store database configuration; enables request logging to a database backend.
 	database: Option<telemetry::log_store::Config>,
+	/// Asynchronously export captured LLM prompts and completions to an external sink.
+	payload_export: Option<telemetry::payload_export::Config>,
+	/// Emit a single `llm_summary` log line at the end of each LLM request, with its model,
+	/// provider, token usage, estimated cost, latency, time to first token, and status.
+	llm_summary: Option<bool>,
 }
 
 #[apply(schema_de!)]
@@ -425,6 +439,16 @@ pub enum ConfigStoreMode {
 	Hybrid,
 }
 
+#[apply(schema!)]
+#[derive(Default, Eq, PartialEq, Copy)]
+pub enum ErrorVerbosity {
+	/// Return a generic message for each error, omitting upstream and internal causes.
+	#[default]
+	Terse,
+	/// Return the full error, including upstream and internal causes.
+	Verbose,
+}
+
 #[apply(schema_de!)]
 #[serde(untagged)]
 pub enum RawLoggingLevel {
@@ -599,6 +623,9 @@ pub struct Config {
 	pub termination_max_deadline: Duration,
 	#[serde(with = "serde_dur")]
 	pub termination_min_deadline: Duration,
+	/// If set, tokenizers are preloaded at startup and readiness waits up to this long for them.
+	#[serde(with = "serde_dur_option")]
+	pub tokenizer_preload_timeout: Option<Duration>,
 	/// Specify the number of worker threads the Tokio Runtime will use.
 	pub num_worker_threads: usize,
 	pub admin_addr: Address,
@@ -614,6 +641,8 @@ pub struct Config {
 	pub tracing: Option<trc::DeprecatedConfig>,
 	pub metrics: crate::telemetry::log::MetricsConfig,
 	pub logging: crate::telemetry::log::Config,
+	/// How much error detail is returned to clients.
+	pub error_verbosity: ErrorVerbosity,
 	pub database: Option<telemetry::log_store::Config>,
 	pub config_store: ConfigStoreConfig,
 
@@ -775,6 +804,8 @@ pub struct ProxyInputs {
 	pub admin: Option<management::admin::AdminService>,
 	pub mcp_state: mcp::App,
 	pub ca: Option<Arc<CaClient>>,
+	/// LLM calls in flight that identical requests can share.
+	pub llm_in_flight: llm::dedup::InFlightCalls,
 }
 
 impl ProxyInputs {
@@ -801,6 +832,7 @@ impl ProxyInputs {
 			admin: None,
 			mcp_state,
 			ca,
+			llm_in_flight: Default::default(),
 		}
 	}
 }
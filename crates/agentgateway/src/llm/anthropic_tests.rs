@@ -33,7 +33,10 @@ fn make_bearer_request_with_explicit_auth(token: &str) -> crate::http::Request {
 
 #[test]
 fn set_required_fields_oauth_token() {
-	let provider = AIProvider::Anthropic(anthropic::Provider { model: None });
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
 	let mut req = make_bearer_request(&format!("{OAUTH_TOKEN_PREFIX}01234567890abcdef"));
 
 	provider
@@ -50,7 +53,10 @@ fn set_required_fields_oauth_token() {
 
 #[test]
 fn set_required_fields_oauth_token_strips_api_key() {
-	let provider = AIProvider::Anthropic(anthropic::Provider { model: None });
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
 	let mut req = make_bearer_request_with_api_key(
 		&format!("{OAUTH_TOKEN_PREFIX}01234567890abcdef"),
 		"some-stale-key",
@@ -66,9 +72,30 @@ fn set_required_fields_oauth_token_strips_api_key() {
 	assert!(!req.headers().contains_key("x-api-key"));
 }
 
+#[test]
+fn set_required_fields_anthropic_version() {
+	let version = |api_version: Option<&str>| {
+		let provider = AIProvider::Anthropic(anthropic::Provider {
+			model: None,
+			api_version: api_version.map(Into::into),
+		});
+		let mut req = make_bearer_request("sk-ant-REDACTED");
+		provider
+			.set_required_fields(&mut req, RouteType::Messages, None)
+			.unwrap();
+		req.headers()["anthropic-version"].clone()
+	};
+
+	assert_eq!(version(None), "2023-06-01");
+	assert_eq!(version(Some("2025-01-01")), "2025-01-01");
+}
+
 #[test]
 fn set_required_fields_api_key_token() {
-	let provider = AIProvider::Anthropic(anthropic::Provider { model: None });
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
 	let mut req = make_bearer_request("sk-ant-REDACTED");
 
 	provider
@@ -89,7 +116,10 @@ fn set_required_fields_api_key_token() {
 fn set_required_fields_explicit_authorization_preserved() {
 	// When backend auth location is explicitly set to Authorization header,
 	// Anthropic provider must NOT rewrite it to x-api-key (e.g. Databricks).
-	let provider = AIProvider::Anthropic(anthropic::Provider { model: None });
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
 	let mut req = make_bearer_request_with_explicit_auth("eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9");
 
 	provider
@@ -124,7 +154,10 @@ fn set_required_fields_explicit_authorization_preserved() {
 fn set_required_fields_default_auth_still_rewrites() {
 	// When backend auth location was NOT explicitly set (defaulted),
 	// non-OAuth tokens must still be rewritten to x-api-key.
-	let provider = AIProvider::Anthropic(anthropic::Provider { model: None });
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
 	let mut req = make_bearer_request("sk-ant-REDACTED");
 
 	// Simulate default (non-explicit) auth location
@@ -151,7 +184,10 @@ fn set_required_fields_default_auth_still_rewrites() {
 fn set_required_fields_explicit_non_authorization_location_preserved() {
 	// If user explicitly configures any location, even a non-Authorization header,
 	// Anthropic provider should not rewrite Authorization (explicit always wins).
-	let provider = AIProvider::Anthropic(anthropic::Provider { model: None });
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
 	let mut req = make_bearer_request("sk-ant-REDACTED");
 
 	req
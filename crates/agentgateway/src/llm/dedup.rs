@@ -0,0 +1,261 @@
+//! Single-flight deduplication of identical LLM requests.
+//!
+//! When many clients send the same deterministic request at once, only the first is sent to the
+//! provider; the others wait for it and receive a copy of its response. Requests are only shared
+//! between the same caller and credentials. Only successful responses that fit in the response
+//! buffer limit are shared. If the shared call fails, each waiting request makes its own call.
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use ::http::{HeaderMap, StatusCode, Version};
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use tokio::sync::OnceCell;
+
+use crate::http::{Body, Response};
+use crate::llm::LLMRequest;
+use crate::proxy::ProxyError;
+
+type Key = [u8; 32];
+type Flight = Arc<OnceCell<Option<SharedResponse>>>;
+
+/// The in-flight deduplicated calls, by request key.
+#[derive(Debug, Default, Clone)]
+pub struct InFlightCalls(Arc<Mutex<HashMap<Key, Flight>>>);
+
+#[derive(Debug, Clone)]
+struct SharedResponse {
+	status: StatusCode,
+	version: Version,
+	headers: HeaderMap,
+	body: Bytes,
+}
+
+impl SharedResponse {
+	fn to_response(&self) -> Response {
+		let mut resp = Response::new(Body::from(self.body.clone()));
+		*resp.status_mut() = self.status;
+		*resp.version_mut() = self.version;
+		*resp.headers_mut() = self.headers.clone();
+		resp
+	}
+}
+
+/// Whether a request always produces the same response, so a response can be shared with an
+/// identical request.
+pub fn is_deterministic(req: &LLMRequest) -> bool {
+	!req.streaming && req.params.temperature == Some(0.0)
+}
+
+/// Headers carrying provider credentials. A response is never shared across credentials, since
+/// the caller of one key must not see a response paid for, or authorized by, another.
+const CREDENTIAL_HEADERS: [&str; 4] = ["authorization", "x-api-key", "api-key", "x-goog-api-key"];
+
+/// The key identifying identical requests to a provider from the same caller.
+pub fn request_key(
+	provider: &str,
+	caller: Option<&str>,
+	headers: &HeaderMap,
+	uri: &::http::Uri,
+	body: &[u8],
+) -> Key {
+	let mut digest = Sha256::new();
+	let mut field = |part: &[u8]| {
+		digest.update((part.len() as u64).to_be_bytes());
+		digest.update(part);
+	};
+	field(provider.as_bytes());
+	field(caller.unwrap_or_default().as_bytes());
+	for name in CREDENTIAL_HEADERS {
+		for value in headers.get_all(name) {
+			field(name.as_bytes());
+			field(value.as_bytes());
+		}
+	}
+	field(uri.to_string().as_bytes());
+	digest.update(body);
+	digest.finalize().into()
+}
+
+impl InFlightCalls {
+	/// call runs `make_call`, unless an identical call is already in flight, in which case its
+	/// response is shared. Response bodies larger than the response buffer limit are not shared.
+	pub async fn call<F, Fut>(&self, key: Key, make_call: F) -> Result<Response, ProxyError>
+	where
+		F: FnOnce() -> Fut,
+		Fut: Future<Output = Result<Response, ProxyError>>,
+	{
+		let flight = self
+			.0
+			.lock()
+			.expect("mutex poisoned")
+			.entry(key)
+			.or_default()
+			.clone();
+		let mut make_call = Some(make_call);
+		let mut own = None;
+		let (make_call_ref, own_ref) = (&mut make_call, &mut own);
+		let shared = flight
+			.get_or_init(move || async move {
+				let make_call = make_call_ref.take().expect("call is made at most once");
+				let (result, shared) = share_response(make_call().await).await;
+				*own_ref = Some(result);
+				shared
+			})
+			.await
+			.clone();
+		{
+			let mut flights = self.0.lock().expect("mutex poisoned");
+			if flights.get(&key).is_some_and(|f| Arc::ptr_eq(f, &flight)) {
+				flights.remove(&key);
+			}
+		}
+		if let Some(own) = own {
+			return own;
+		}
+		match (shared, make_call) {
+			(Some(shared), _) => Ok(shared.to_response()),
+			(None, Some(make_call)) => make_call().await,
+			(None, None) => unreachable!("the call was made but its result was not recorded"),
+		}
+	}
+}
+
+/// Buffers a successful response so it can be shared, returning the caller's copy alongside.
+/// A response too large to buffer is returned to the caller unshared.
+async fn share_response(
+	resp: Result<Response, ProxyError>,
+) -> (Result<Response, ProxyError>, Option<SharedResponse>) {
+	let mut resp = match resp {
+		Ok(resp) if resp.status().is_success() => resp,
+		other => return (other, None),
+	};
+	let body = match crate::http::inspect_response_body(&mut resp).await {
+		Ok(crate::http::BodyInspection::Complete(body)) => body,
+		_ => return (Ok(resp), None),
+	};
+	let shared = SharedResponse {
+		status: resp.status(),
+		version: resp.version(),
+		headers: resp.headers().clone(),
+		body,
+	};
+	(Ok(resp), Some(shared))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	use http_body_util::BodyExt;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn concurrent_identical_requests_share_one_call() {
+		const REQUESTS: usize = 8;
+		let calls = InFlightCalls::default();
+		let upstream_calls = Arc::new(AtomicUsize::new(0));
+		let key = request_key(
+			"openai",
+			None,
+			&HeaderMap::new(),
+			&"/v1/chat/completions".parse().unwrap(),
+			br#"{"model":"gpt-4o","temperature":0}"#,
+		);
+		let barrier = Arc::new(tokio::sync::Barrier::new(REQUESTS));
+
+		let tasks = (0..REQUESTS).map(|_| {
+			let calls = calls.clone();
+			let upstream_calls = upstream_calls.clone();
+			let barrier = barrier.clone();
+			tokio::spawn(async move {
+				barrier.wait().await;
+				let resp = calls
+					.call(key, || async move {
+						let n = upstream_calls.fetch_add(1, Ordering::SeqCst);
+						// Hold the call open so every request joins it.
+						tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+						Ok(Response::new(Body::from(format!("response {n}"))))
+					})
+					.await
+					.unwrap();
+				resp.into_body().collect().await.unwrap().to_bytes()
+			})
+		});
+		let bodies = futures::future::join_all(tasks).await;
+
+		assert_eq!(upstream_calls.load(Ordering::SeqCst), 1);
+		for body in bodies {
+			assert_eq!(body.unwrap(), Bytes::from_static(b"response 0"));
+		}
+		assert!(calls.0.lock().unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn failed_call_is_not_shared() {
+		let calls = InFlightCalls::default();
+		let key = request_key(
+			"openai",
+			None,
+			&HeaderMap::new(),
+			&"/".parse().unwrap(),
+			b"{}",
+		);
+		let resp = calls
+			.call(key, || async {
+				let mut resp = Response::new(Body::empty());
+				*resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+				Ok(resp)
+			})
+			.await
+			.unwrap();
+		assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+		let resp = calls
+			.call(key, || async { Ok(Response::new(Body::empty())) })
+			.await
+			.unwrap();
+		assert_eq!(resp.status(), StatusCode::OK);
+	}
+
+	#[tokio::test]
+	async fn oversized_response_is_returned_unshared() {
+		let calls = InFlightCalls::default();
+		let key = request_key(
+			"openai",
+			None,
+			&HeaderMap::new(),
+			&"/".parse().unwrap(),
+			b"{}",
+		);
+		let resp = calls
+			.call(key, || async {
+				let mut resp = Response::new(Body::from("larger than the limit"));
+				resp.extensions_mut().insert(crate::http::BufferLimit(4));
+				Ok(resp)
+			})
+			.await
+			.unwrap();
+		assert_eq!(resp.status(), StatusCode::OK);
+		let body = resp.into_body().collect().await.unwrap().to_bytes();
+		assert_eq!(body, Bytes::from_static(b"larger than the limit"));
+	}
+
+	#[test]
+	fn keys_are_scoped_to_the_caller() {
+		let uri = "/v1/chat/completions".parse().unwrap();
+		let body = br#"{"model":"gpt-4o","temperature":0}"#;
+		let key = |caller: Option<&str>, auth: Option<&'static str>| {
+			let mut headers = HeaderMap::new();
+			if let Some(auth) = auth {
+				headers.insert(::http::header::AUTHORIZATION, auth.parse().unwrap());
+			}
+			request_key("openai", caller, &headers, &uri, body)
+		};
+		assert_eq!(key(Some("jwt:alice"), None), key(Some("jwt:alice"), None));
+		assert_ne!(key(Some("jwt:alice"), None), key(Some("jwt:bob"), None));
+		assert_ne!(key(None, Some("Bearer a")), key(None, Some("Bearer b")));
+	}
+}
@@ -1,22 +1,29 @@
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use ::http::request::Parts;
 use ::http::uri::{Authority, PathAndQuery};
 use ::http::{HeaderMap, HeaderName, HeaderValue, header};
 use agent_core::prelude::Strng;
+use agent_core::readiness::BlockReady;
 use agent_core::strng;
-pub use agent_llm::tokenizer::{num_tokens_from_messages, preload_tokenizers};
+pub use agent_llm::tokenizer::{
+	DEFAULT_IMAGE_TOKENS, has_local_tokenizer, num_tokens_from_messages, num_tokens_from_non_text,
+	preload_tokenizers,
+};
+use agent_llm::tool_call_id::ToolCallIdFormat;
 pub use agent_llm::{
-	AIError, CacheTokenConvention, ChatFormat, InputFormat, LLMInfo, LLMRequest, LLMRequestParams,
-	LLMResponse, PromptCachingConfig, Provider, ProviderState, RequestType, ResponseType, RouteType,
-	SimpleChatCompletionMessage, anthropic, conversion, copilot, custom, gemini,
-	logged_response_parsing, openai, types,
+	AIError, CacheTokenConvention, ChatFormat, GuardModification, InputFormat, LLMInfo, LLMRequest,
+	LLMRequestParams, LLMResponse, PromptCachingConfig, Provider, ProviderState, RequestType,
+	ResponseType, RouteType, SimpleChatCompletionMessage, anthropic, conversion, copilot, custom,
+	gemini, logged_response_parsing, mistral, openai, types,
 };
 use axum_extra::headers::authorization::Bearer;
 use headers::{ContentEncoding, HeaderMapExt};
 pub use policy::Policy;
 use rand::RngExt;
+use rand::distr::Distribution;
+use rand::distr::weighted::WeightedIndex;
 use serde::de::DeserializeOwned;
 
 use crate::http::auth::{
@@ -28,15 +35,18 @@ use crate::proxy::httpproxy::PolicyClient;
 use crate::store::{BackendPolicies, LLMResponsePolicies};
 use crate::telemetry::log::{AsyncLog, RequestLog};
 use crate::types::agent::{BackendTrafficPolicy, SimpleBackendReference, Target};
-use crate::types::loadbalancer::{ActiveHandle, EndpointWithInfo};
+use crate::types::loadbalancer::{ActiveHandle, EndpointWithInfo, Sampler, SkipReason};
 use crate::*;
 pub mod model_router;
 pub use agent_llm::{azure, bedrock, vertex};
 
 pub mod cost;
+pub mod dedup;
 pub mod policy;
+pub mod retry;
 
 use policy::streaming_guardrails::GuardedSseBody;
+use policy::streaming_transform::TransformedSseBody;
 
 use crate::cel::{Executor, LLMContext, RequestSnapshot};
 use crate::proxy::dtrace;
@@ -50,6 +60,180 @@ mod anthropic_tests;
 #[cfg(test)]
 mod tests;
 
+/// Loads tokenizers ahead of the first tokenizing request, holding `ready` until they are loaded or
+/// `timeout` elapses.
+pub async fn preload_tokenizers_for_readiness(ready: BlockReady, timeout: std::time::Duration) {
+	preload_with_timeout(ready, timeout, preload_tokenizers).await
+}
+
+async fn preload_with_timeout(
+	ready: BlockReady,
+	timeout: std::time::Duration,
+	load: impl FnOnce() + Send + 'static,
+) {
+	let t0 = std::time::Instant::now();
+	match tokio::time::timeout(timeout, tokio::task::spawn_blocking(load)).await {
+		Ok(Ok(())) => info!("tokenizers preloaded in {}ms", t0.elapsed().as_millis()),
+		Ok(Err(e)) => warn!("tokenizer preload failed: {e}"),
+		Err(_) => warn!("tokenizers not loaded within {timeout:?}, marking ready anyway"),
+	}
+	drop(ready);
+}
+
+/// Builds an OpenAI-style `invalid_request_error` response for a rejected request parameter.
+fn invalid_param_response(param: &str, message: &str) -> Response {
+	let body = serde_json::json!({
+		"error": {
+			"message": message,
+			"type": "invalid_request_error",
+			"param": param,
+		}
+	});
+	::http::Response::builder()
+		.status(::http::StatusCode::BAD_REQUEST)
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("static request should succeed")
+}
+
+fn prompt_too_large_response(chars: u64, limit: u64) -> Response {
+	let body = serde_json::json!({
+		"error": {
+			"message": format!("prompt has {chars} characters, exceeding the maximum of {limit}"),
+			"type": "invalid_request_error",
+		}
+	});
+	::http::Response::builder()
+		.status(::http::StatusCode::PAYLOAD_TOO_LARGE)
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("static request should succeed")
+}
+
+/// Builds the `403` returned for a model the `allowedModels`/`deniedModels` policy rejects, in the
+/// error shape of the client's API.
+fn model_not_allowed_response(model: &str, input_format: InputFormat) -> Response {
+	let message = format!("model {model} is not allowed");
+	let body = match input_format {
+		InputFormat::Messages | InputFormat::CountTokens => serde_json::json!({
+			"type": "error",
+			"error": {
+				"type": "permission_error",
+				"message": message,
+			}
+		}),
+		_ => serde_json::json!({
+			"error": {
+				"message": message,
+				"type": "invalid_request_error",
+				"param": "model",
+				"code": "model_not_allowed",
+			}
+		}),
+	};
+	::http::Response::builder()
+		.status(::http::StatusCode::FORBIDDEN)
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("static request should succeed")
+}
+
+/// Rejects pathologically nested bodies before they are parsed.
+fn check_json_depth(policies: Option<&Policy>, bytes: &[u8]) -> Result<(), AIError> {
+	let limit = policies
+		.and_then(|p| p.max_json_depth)
+		.unwrap_or(crate::json::DEFAULT_MAX_DEPTH);
+	if crate::json::exceeds_depth(bytes, limit) {
+		return Err(AIError::RequestTooDeep(limit));
+	}
+	Ok(())
+}
+
+fn request_too_deep_response(limit: usize) -> Response {
+	let body = serde_json::json!({
+		"error": {
+			"message": format!("request JSON is nested deeper than the maximum of {limit}"),
+			"type": "invalid_request_error",
+			"code": "request_too_deep",
+		}
+	});
+	::http::Response::builder()
+		.status(::http::StatusCode::BAD_REQUEST)
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("static request should succeed")
+}
+
+/// Builds the `400` returned when a request cannot be translated for the provider, naming the
+/// input format and provider and listing the formats the provider does accept.
+fn unsupported_conversion_response(
+	route_type: RouteType,
+	provider: &AIProvider,
+	detail: &str,
+) -> Response {
+	let supported = provider.supported_formats(None);
+	let suggestion = if supported.is_empty() {
+		format!(
+			"provider {} does not accept any LLM formats",
+			provider.provider()
+		)
+	} else {
+		format!(
+			"send this request to a route of a supported format: {}",
+			supported
+				.iter()
+				.map(|f| f.route_type().as_str())
+				.collect::<Vec<_>>()
+				.join(", ")
+		)
+	};
+	let body = serde_json::json!({
+		"error": {
+			"message": format!(
+				"{} requests cannot be sent to provider {}: {detail}",
+				route_type.as_str(),
+				provider.provider()
+			),
+			"type": "invalid_request_error",
+			"code": "unsupported_conversion",
+			"input_format": route_type,
+			"provider": provider.provider().as_str(),
+			"supported_formats": supported,
+			"suggestion": suggestion,
+		}
+	});
+	::http::Response::builder()
+		.status(::http::StatusCode::BAD_REQUEST)
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("static request should succeed")
+}
+
+fn empty_choices_response() -> Response {
+	let body = serde_json::json!({
+		"error": {
+			"message": "The model returned a response with no choices",
+			"type": "server_error",
+			"code": "empty_choices",
+		}
+	});
+	let mut resp = ::http::Response::builder()
+		.status(::http::StatusCode::BAD_GATEWAY)
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(body.to_string()))
+		.expect("static request should succeed");
+	resp.extensions_mut().insert(EmptyChoicesResponse);
+	resp
+}
+
+fn is_event_stream(resp: &Response) -> bool {
+	resp
+		.headers()
+		.get(header::CONTENT_TYPE)
+		.and_then(|v| v.to_str().ok())
+		.is_some_and(|ct| ct.starts_with("text/event-stream"))
+}
+
 fn normalize_sse_response_headers(mut resp: Response) -> Response {
 	resp.headers_mut().insert(
 		header::CONTENT_TYPE,
@@ -67,15 +251,61 @@ pub struct AIBackend {
 
 impl AIBackend {
 	pub fn select_provider(&self) -> Option<(Arc<NamedAIProvider>, ActiveHandle)> {
+		self.select_provider_for_size(None)
+	}
+
+	/// Whether any provider sets a `requestSize` range, so selection needs the request body size.
+	pub fn routes_by_size(&self) -> bool {
+		self
+			.providers
+			.iter()
+			.index()
+			.values()
+			.any(|ep| ep.endpoint.request_size.is_some())
+	}
+
+	/// Selects a provider for a request body of `size` bytes. Providers with a `requestSize` range
+	/// that excludes the size are skipped, so large batches can go to a high-throughput provider and
+	/// small ones to a low-latency provider. The remaining providers are sampled by weight, as in
+	/// `select_provider`. The ranges are ignored when the size is unknown or no provider accepts it.
+	pub fn select_provider_for_size(
+		&self,
+		size: Option<u64>,
+	) -> Option<(Arc<NamedAIProvider>, ActiveHandle)> {
 		let iter = self.providers.iter();
 		let index = iter.index();
 		if index.is_empty() {
 			return None;
 		}
+		let candidates = size_candidates(index.values(), size);
 		// Intentionally allow `rand::seq::index::sample` so we can pick the same element twice
 		// This avoids starvation where the worst endpoint gets 0 traffic
-		let a = rand::rng().random_range(0..index.len());
-		let b = rand::rng().random_range(0..index.len());
+		let mut rng = rand::rng();
+		let (a, b) = match (iter.sampler(), &candidates) {
+			(Some(Sampler::Weighted(dist)), None) => (dist.sample(&mut rng), dist.sample(&mut rng)),
+			// Drained means every healthy provider has weight 0; they are the fallbacks, so use them.
+			(Some(Sampler::Uniform | Sampler::Drained) | None, None) => (
+				rng.random_range(0..index.len()),
+				rng.random_range(0..index.len()),
+			),
+			(sampler, Some(candidates)) => {
+				// The cached distribution covers every provider, so weight the candidates separately.
+				// If they all have weight 0, they are the fallbacks, so sample them uniformly.
+				let dist = matches!(sampler, Some(Sampler::Weighted(_)))
+					.then(|| WeightedIndex::new(candidates.iter().map(|i| index[*i].capacity as u64)).ok())
+					.flatten();
+				match dist {
+					Some(dist) => (
+						candidates[dist.sample(&mut rng)],
+						candidates[dist.sample(&mut rng)],
+					),
+					None => (
+						candidates[rng.random_range(0..candidates.len())],
+						candidates[rng.random_range(0..candidates.len())],
+					),
+				}
+			},
+		};
 		let best = [a, b]
 			.into_iter()
 			.map(|idx| {
@@ -85,9 +315,127 @@ impl AIBackend {
 			})
 			.max_by(|(_, a), (_, b)| a.score().total_cmp(&b.score()));
 		let (ep, ep_info) = best?;
+		if tracing::enabled!(tracing::Level::DEBUG) {
+			let mut skipped = self.providers.skipped();
+			if let Some(candidates) = &candidates {
+				skipped.extend(
+					index
+						.keys()
+						.enumerate()
+						.filter(|(i, _)| !candidates.contains(i))
+						.map(|(_, k)| (k.clone(), SkipReason::RequestSize)),
+				);
+			}
+			if !skipped.is_empty() {
+				debug!(
+					selected = %ep.name,
+					skipped = %skipped
+						.iter()
+						.map(|(name, reason)| format!("{name}={}", reason.as_str()))
+						.collect::<Vec<_>>()
+						.join(","),
+					"providers skipped during selection"
+				);
+			}
+		}
 		let handle = self.providers.start_request(ep.name.clone(), ep_info);
 		Some((ep, handle))
 	}
+
+	/// Selects the provider for an affinity key, such as a conversation id. Weighted rendezvous
+	/// hashing over the healthy providers keeps a key on the same provider, sends each provider a
+	/// share of keys proportional to its weight, and an evicted provider only moves its own keys.
+	/// As in `select_provider_for_size`, providers whose `requestSize` range excludes `size` are
+	/// skipped first.
+	pub fn select_provider_with_affinity(
+		&self,
+		key: &str,
+		size: Option<u64>,
+	) -> Option<(Arc<NamedAIProvider>, ActiveHandle)> {
+		let iter = self.providers.iter();
+		let index = iter.index();
+		let candidates = size_candidates(index.values(), size);
+		let is_candidate = |i: &usize| candidates.as_ref().is_none_or(|c| c.contains(i));
+		// If every candidate has weight 0, they are the fallbacks, so weigh them equally.
+		let weighted = matches!(iter.sampler(), Some(Sampler::Weighted(_)))
+			&& index
+				.values()
+				.enumerate()
+				.any(|(i, ep)| is_candidate(&i) && ep.capacity > 0);
+		let score = |name: &str, ep: &EndpointWithInfo<NamedAIProvider>| {
+			affinity_score(key, name, if weighted { ep.capacity } else { 1 })
+		};
+		let (_, EndpointWithInfo { endpoint, info, .. }) = index
+			.iter()
+			.enumerate()
+			.filter(|(i, _)| is_candidate(i))
+			.map(|(_, entry)| entry)
+			.max_by(|(a_name, a), (b_name, b)| score(a_name, a).total_cmp(&score(b_name, b)))?;
+		let handle = self.providers.start_request(endpoint.name.clone(), info);
+		Some((endpoint.clone(), handle))
+	}
+}
+
+/// Indices of the providers whose `requestSize` range accepts `size`, if that excludes some but
+/// not all of them.
+fn size_candidates<'a>(
+	providers: impl ExactSizeIterator<Item = &'a EndpointWithInfo<NamedAIProvider>>,
+	size: Option<u64>,
+) -> Option<Vec<usize>> {
+	let size = size?;
+	let total = providers.len();
+	let candidates = providers
+		.enumerate()
+		.filter(|(_, ep)| {
+			ep.endpoint
+				.request_size
+				.as_ref()
+				.is_none_or(|r| r.contains(size))
+		})
+		.map(|(i, _)| i)
+		.collect::<Vec<_>>();
+	(!candidates.is_empty() && candidates.len() < total).then_some(candidates)
+}
+
+/// Inclusive range of request body sizes, in bytes.
+#[apply(schema!)]
+pub struct RequestSizeRange {
+	/// Smallest request body, in bytes, sent to this provider.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub min_bytes: Option<u64>,
+	/// Largest request body, in bytes, sent to this provider.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_bytes: Option<u64>,
+}
+
+impl RequestSizeRange {
+	pub fn contains(&self, size: u64) -> bool {
+		self.min_bytes.is_none_or(|min| size >= min) && self.max_bytes.is_none_or(|max| size <= max)
+	}
+}
+
+/// Weighted rendezvous score of `provider` for `key`: `weight / -ln(u)` for a uniform `u` derived
+/// from the key and provider, so the highest score lands on each provider in proportion to its
+/// weight. A weight of 0 never wins against a positive weight.
+fn affinity_score(key: &str, provider: &str, weight: u32) -> f64 {
+	if weight == 0 {
+		return 0.0;
+	}
+	// The top 53 bits fill an f64 mantissa; the half offset keeps `u` inside (0, 1).
+	let u = ((affinity_hash(key, provider) >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+	weight as f64 / -u.ln()
+}
+
+fn affinity_hash(key: &str, provider: &str) -> u64 {
+	use sha2::{Digest, Sha256};
+	// std's hashers may change between Rust releases, which would move every pinned key on
+	// upgrade or split it across mixed-version gateways. SHA-256 output is fixed.
+	let mut h = Sha256::new();
+	h.update((key.len() as u64).to_le_bytes());
+	h.update(key);
+	h.update(provider);
+	let digest = h.finalize();
+	u64::from_le_bytes(digest[..8].try_into().expect("SHA-256 digest is 32 bytes"))
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -98,6 +446,8 @@ pub struct NamedAIProvider {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub provider_backend: Option<SimpleBackendReference>,
 	pub host_override: Option<Target>,
+	/// Upstream path, replacing the provider default. May contain `{model}`, `{region}`,
+	/// `{project}` and `{version}` placeholders, resolved per request.
 	pub path_override: Option<Strng>,
 	pub path_prefix: Option<Strng>,
 	/// Whether to tokenize on the request flow. This enables us to do more accurate rate limits,
@@ -105,10 +455,76 @@ pub struct NamedAIProvider {
 	/// This comes with the cost of an expensive operation.
 	#[serde(default)]
 	pub tokenize: bool,
+	/// User-Agent sent to the provider. Defaults to `agentgateway/<version>`.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		serialize_with = "ser_string_or_bytes_option"
+	)]
+	pub user_agent: Option<HeaderValue>,
+	/// Accept-Encoding sent to the provider, replacing the client's. Limited to codings the
+	/// response decompression path supports.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub accept_encoding: Option<Strng>,
+	/// Retries of failed calls to this provider.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub retry: Option<retry::Retry>,
+	/// Maximum size of a request body buffered for this provider, overriding the listener's
+	/// buffer limit. Defaults to 2 MiB.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_request_bytes: Option<usize>,
+	/// Maximum size of a response body buffered from this provider, overriding the listener's
+	/// buffer limit. Defaults to 2 MiB.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_response_bytes: Option<usize>,
+	/// Relative share of traffic sent to this provider within its group. A weight of 0 is only
+	/// used when every other provider in the group is unhealthy.
+	pub weight: u32,
+	/// Request body sizes this provider is selected for. Unset accepts any size.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub request_size: Option<RequestSizeRange>,
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub inline_policies: Vec<BackendTrafficPolicy>,
 }
 
+static DEFAULT_USER_AGENT: LazyLock<HeaderValue> = LazyLock::new(|| {
+	HeaderValue::from_str(&format!(
+		"agentgateway/{}",
+		agent_core::version::BuildInfo::new().version
+	))
+	.unwrap_or_else(|_| HeaderValue::from_static("agentgateway"))
+});
+
+impl NamedAIProvider {
+	/// Resolve whether to tokenize the request, preferring the route-level override if one is set.
+	pub fn should_tokenize(&self, policy: Option<&Policy>) -> bool {
+		policy.and_then(|p| p.tokenize).unwrap_or(self.tokenize)
+	}
+
+	/// Apply the provider's request body limit, if set, in place of the listener's.
+	pub fn apply_request_body_limit(&self, req: &mut Request) {
+		if let Some(limit) = self.max_request_bytes {
+			req.extensions_mut().insert(http::BufferLimit::new(limit));
+		}
+	}
+
+	/// Set the limit the provider's response body is buffered with on the upstream request, which
+	/// the response inherits: the provider's response body limit, or else the listener's.
+	pub fn apply_response_body_limit(&self, req: &mut Request, listener_limit: usize) {
+		let limit = self.max_response_bytes.unwrap_or(listener_limit);
+		req.extensions_mut().insert(http::BufferLimit::new(limit));
+	}
+
+	/// Set the User-Agent sent to the provider, replacing the one sent by the client.
+	pub fn set_user_agent(&self, req: &mut Request) {
+		let user_agent = self
+			.user_agent
+			.clone()
+			.unwrap_or_else(|| DEFAULT_USER_AGENT.clone());
+		req.headers_mut().insert(header::USER_AGENT, user_agent);
+	}
+}
+
 #[apply(schema!)]
 pub enum AIProvider {
 	OpenAI(openai::Provider),
@@ -118,6 +534,7 @@ pub enum AIProvider {
 	Bedrock(BedrockProvider),
 	Azure(AzureProvider),
 	Copilot(copilot::Provider),
+	Mistral(mistral::Provider),
 	Custom(custom::Provider),
 }
 
@@ -240,7 +657,7 @@ fn cache_convention_for(
 			Some(Messages | AnthropicTokenCount) => InputExcludesCache,
 			_ => InputIncludesCache,
 		},
-		_ => InputIncludesCache, // openai, azure, gemini, copilot/vertex non-anthropic
+		_ => InputIncludesCache, // openai, azure, gemini, mistral, copilot/vertex non-anthropic
 	}
 }
 
@@ -289,6 +706,8 @@ struct ChatStreamContext {
 	model: String,
 	include_completion_in_log: bool,
 	tool_name_map: Option<conversion::bedrock::BedrockToolNameMap>,
+	include_usage: bool,
+	estimate_missing_usage: bool,
 }
 
 /// Ordered chat conversion table.
@@ -318,7 +737,7 @@ const CHAT_TRANSLATIONS: &[ChatTranslation] = {
 		// Responses
 		chat(InputFormat::Responses, ChatFormat::OpenAICompletions),
 		chat(InputFormat::Responses, ChatFormat::BedrockConverse),
-		// Missing: Responses -> Messages
+		chat(InputFormat::Responses, ChatFormat::AnthropicMessages),
 	]
 };
 
@@ -345,9 +764,7 @@ fn render_anthropic_messages(req: types::ChatRequest<'_>) -> Result<Vec<u8>, AIE
 	match req {
 		types::ChatRequest::Completions(req) => conversion::messages::from_completions::translate(req),
 		types::ChatRequest::Messages(req) => serde_json::to_vec(req).map_err(AIError::RequestMarshal),
-		types::ChatRequest::Responses(_) => Err(AIError::UnsupportedConversion(strng::literal!(
-			"responses to messages"
-		))),
+		types::ChatRequest::Responses(req) => conversion::messages::from_responses::translate(req),
 	}
 }
 
@@ -360,6 +777,14 @@ fn render_bedrock_converse(
 			"expected bedrock provider"
 		)));
 	};
+	// Streaming completions requests without stream_options had include_usage set for us, so only an
+	// explicit `false` from the client turns the usage chunk off.
+	let include_usage = match &req {
+		types::ChatRequest::Completions(req) => {
+			req.stream_options.as_ref().is_none_or(|o| o.include_usage)
+		},
+		_ => true,
+	};
 	let bedrock = match req {
 		types::ChatRequest::Completions(req) => conversion::bedrock::from_completions::translate(
 			req,
@@ -377,11 +802,12 @@ fn render_bedrock_converse(
 			ctx.prompt_caching,
 		),
 	}?;
-	let provider_state = if bedrock.tool_name_map.is_empty() {
+	let provider_state = if bedrock.tool_name_map.is_empty() && include_usage {
 		None
 	} else {
 		Some(ProviderState::Bedrock {
 			tool_names: Arc::new(bedrock.tool_name_map),
+			include_usage,
 		})
 	};
 	Ok(RenderedChatRequest {
@@ -391,6 +817,21 @@ fn render_bedrock_converse(
 }
 
 impl ChatTranslation {
+	/// Whether chat completions are streamed through from an OpenAI-compatible provider untranslated.
+	fn is_completions_passthrough(&self) -> bool {
+		self.input == InputFormat::Completions && self.output == ChatFormat::OpenAICompletions
+	}
+
+	/// Whether the provider response is returned to the client in its original format.
+	fn is_passthrough(&self) -> bool {
+		matches!(
+			(self.input, self.output),
+			(InputFormat::Completions, ChatFormat::OpenAICompletions)
+				| (InputFormat::Messages, ChatFormat::AnthropicMessages)
+				| (InputFormat::Responses, ChatFormat::OpenAIResponses)
+		)
+	}
+
 	fn provider_format(&self) -> custom::ProviderFormat {
 		match self.output {
 			ChatFormat::OpenAICompletions => custom::ProviderFormat::Completions,
@@ -416,18 +857,39 @@ impl ChatTranslation {
 		let body = match self.output {
 			ChatFormat::OpenAICompletions => render_openai_completions(req),
 			ChatFormat::OpenAIResponses => render_openai_responses(req),
-			ChatFormat::AnthropicMessages if matches!(ctx.provider, AIProvider::Vertex(_)) => {
-				vertex::prepare_anthropic_message_body(render_anthropic_messages(req)?)
+			ChatFormat::AnthropicMessages => match ctx.provider {
+				AIProvider::Vertex(p) => p.prepare_anthropic_message_body(render_anthropic_messages(req)?),
+				_ => render_anthropic_messages(req),
+			},
+			ChatFormat::BedrockConverse => {
+				let rendered = render_bedrock_converse(req, ctx)?;
+				self.validate_rendered_request(&rendered.body)?;
+				return Ok(rendered);
 			},
-			ChatFormat::AnthropicMessages => render_anthropic_messages(req),
-			ChatFormat::BedrockConverse => return render_bedrock_converse(req, ctx),
 		}?;
+		self.validate_rendered_request(&body)?;
 		Ok(RenderedChatRequest {
 			body,
 			provider_state: None,
 		})
 	}
 
+	/// With the `upstream-validation` feature, rejects rendered bodies that do not match the
+	/// provider request contract, so translation drift fails integration tests instead of reaching
+	/// the provider.
+	#[cfg(feature = "upstream-validation")]
+	fn validate_rendered_request(&self, body: &[u8]) -> Result<(), AIError> {
+		agent_llm::upstream_schema::validate(self.output, body).map_err(|e| {
+			tracing::error!(output = ?self.output, "translated request failed validation: {e}");
+			AIError::InvalidUpstreamRequest(strng::new(e.to_string()))
+		})
+	}
+
+	#[cfg(not(feature = "upstream-validation"))]
+	fn validate_rendered_request(&self, _body: &[u8]) -> Result<(), AIError> {
+		Ok(())
+	}
+
 	fn render_response(
 		&self,
 		bytes: &Bytes,
@@ -461,6 +923,9 @@ impl ChatTranslation {
 				InputFormat::Completions => {
 					conversion::messages::from_completions::translate_response(bytes)
 				},
+				InputFormat::Responses => {
+					conversion::messages::from_responses::translate_response(bytes, ctx.model)
+				},
 				_ => Err(AIError::UnsupportedConversion(strng::format!(
 					"from {:?} to {:?}",
 					self.output,
@@ -498,6 +963,7 @@ impl ChatTranslation {
 				InputFormat::Completions => conversion::completions::passthrough_stream(
 					ctx.logger,
 					ctx.include_completion_in_log,
+					ctx.estimate_missing_usage,
 					resp,
 				),
 				InputFormat::Messages => resp.map(|b| {
@@ -533,6 +999,9 @@ impl ChatTranslation {
 				InputFormat::Completions => resp.map(|b| {
 					conversion::messages::from_completions::translate_stream(b, ctx.buffer_limit, ctx.logger)
 				}),
+				InputFormat::Responses => resp.map(|b| {
+					conversion::messages::from_responses::translate_stream(b, ctx.buffer_limit, ctx.logger)
+				}),
 				_ => resp,
 			},
 
@@ -548,6 +1017,7 @@ impl ChatTranslation {
 							&ctx.model,
 							&msg,
 							tool_name_map,
+							ctx.include_usage,
 						)
 					})
 				},
@@ -636,6 +1106,7 @@ impl ChatTranslation {
 					InputFormat::Completions => {
 						conversion::messages::from_completions::translate_error(bytes)
 					},
+					InputFormat::Responses => conversion::messages::from_responses::translate_error(bytes),
 					_ => unsupported(),
 				},
 				ChatErrorFormat::OpenAI => match self.input {
@@ -683,6 +1154,7 @@ enum PreparedRequest {
 		response: Response,
 		guardrail: &'static str,
 	},
+	Rejected(Response),
 }
 
 struct BufferedResponse {
@@ -701,6 +1173,7 @@ impl AIProvider {
 			AIProvider::Bedrock(_p) => bedrock::Provider::NAME,
 			AIProvider::Azure(_p) => azure::Provider::NAME,
 			AIProvider::Copilot(_p) => copilot::Provider::NAME,
+			AIProvider::Mistral(_p) => mistral::Provider::NAME,
 			AIProvider::Custom(p) => p
 				.provider_override
 				.clone()
@@ -711,6 +1184,7 @@ impl AIProvider {
 		match self {
 			AIProvider::OpenAI(_) | AIProvider::Copilot(_) => Some(openai::DEFAULT_BASE_PATH),
 			AIProvider::Anthropic(_) => Some(anthropic::DEFAULT_BASE_PATH),
+			AIProvider::Mistral(_) => Some(mistral::DEFAULT_BASE_PATH),
 			_ => None,
 		}
 	}
@@ -724,14 +1198,34 @@ impl AIProvider {
 			AIProvider::Bedrock(p) => p.model.clone(),
 			AIProvider::Azure(p) => p.model.clone(),
 			AIProvider::Copilot(p) => p.model.clone(),
+			AIProvider::Mistral(p) => p.model.clone(),
 			AIProvider::Custom(p) => p.model.clone(),
 		}
 	}
 
+	/// Tool-call id format the provider enforces, if it rejects ids issued by other providers.
+	pub fn tool_call_id_format(&self) -> Option<ToolCallIdFormat> {
+		match self {
+			AIProvider::Bedrock(_) => Some(ToolCallIdFormat::Bedrock),
+			AIProvider::Mistral(_) => Some(ToolCallIdFormat::Mistral),
+			AIProvider::Custom(p) if p.provider_override.as_deref() == Some("mistral") => {
+				Some(ToolCallIdFormat::Mistral)
+			},
+			_ => None,
+		}
+	}
+
 	pub fn supported_formats(&self, request_model: Option<&str>) -> Vec<custom::ProviderFormat> {
 		use custom::ProviderFormat::*;
 		match self {
-			AIProvider::OpenAI(_) => vec![Completions, Responses, Embeddings, Realtime, Rerank],
+			AIProvider::OpenAI(_) => vec![
+				Completions,
+				Responses,
+				Embeddings,
+				Realtime,
+				Rerank,
+				TextCompletions,
+			],
 			AIProvider::Copilot(_) => {
 				if copilot::Provider::is_anthropic_model(request_model) {
 					vec![Messages]
@@ -740,7 +1234,7 @@ impl AIProvider {
 				}
 			},
 			AIProvider::Azure(p) => {
-				let mut formats = vec![Completions, Responses, Embeddings, Rerank];
+				let mut formats = vec![Completions, Responses, Embeddings, Rerank, TextCompletions];
 				if matches!(p.resource_type, azure::AzureResourceType::Foundry)
 					&& p.is_anthropic_model(request_model)
 				{
@@ -748,7 +1242,7 @@ impl AIProvider {
 				}
 				formats
 			},
-			AIProvider::Gemini(_) => vec![Completions, Embeddings],
+			AIProvider::Gemini(_) | AIProvider::Mistral(_) => vec![Completions, Embeddings],
 			AIProvider::Anthropic(_) => vec![Messages, AnthropicTokenCount],
 			AIProvider::Bedrock(p) => {
 				let mut formats = vec![Completions, Messages, Responses, Embeddings, Rerank];
@@ -788,7 +1282,7 @@ impl AIProvider {
 			},
 			AIProvider::Azure(_) => vec![ChatFormat::OpenAIResponses, ChatFormat::OpenAICompletions],
 
-			AIProvider::Gemini(_) => vec![ChatFormat::OpenAICompletions],
+			AIProvider::Gemini(_) | AIProvider::Mistral(_) => vec![ChatFormat::OpenAICompletions],
 			AIProvider::Anthropic(_) => vec![ChatFormat::AnthropicMessages],
 			AIProvider::Bedrock(_) => vec![ChatFormat::BedrockConverse],
 
@@ -866,6 +1360,7 @@ impl AIProvider {
 			InputFormat::Realtime => Realtime,
 			InputFormat::CountTokens => AnthropicTokenCount,
 			InputFormat::Rerank => Rerank,
+			InputFormat::TextCompletions => TextCompletions,
 			InputFormat::Detect
 			| InputFormat::Completions
 			| InputFormat::Messages
@@ -887,7 +1382,10 @@ impl AIProvider {
 			..Default::default()
 		};
 		Some(match self {
-			AIProvider::OpenAI(_) | AIProvider::Gemini(_) | AIProvider::Anthropic(_) => btls,
+			AIProvider::OpenAI(_)
+			| AIProvider::Gemini(_)
+			| AIProvider::Mistral(_)
+			| AIProvider::Anthropic(_) => btls,
 			AIProvider::Copilot(_) => BackendPolicies {
 				backend_auth: Some(BackendAuth::new(BackendAuthKind::Copilot)),
 				..btls
@@ -927,6 +1425,7 @@ impl AIProvider {
 			AIProvider::OpenAI(_) => Target::Hostname(openai::DEFAULT_HOST, 443),
 			AIProvider::Copilot(_) => Target::Hostname(copilot::DEFAULT_HOST, 443),
 			AIProvider::Gemini(_) => Target::Hostname(gemini::DEFAULT_HOST, 443),
+			AIProvider::Mistral(_) => Target::Hostname(mistral::DEFAULT_HOST, 443),
 			AIProvider::Anthropic(_) => Target::Hostname(anthropic::DEFAULT_HOST, 443),
 			AIProvider::Vertex(p) => Target::Hostname(p.get_host(route_type), 443),
 			AIProvider::Bedrock(p) => Target::Hostname(p.get_host(route_type), 443),
@@ -943,10 +1442,12 @@ impl AIProvider {
 		path_override: Option<&str>,
 		path_prefix: Option<&str>,
 		has_host_override: bool,
+		accept_encoding: Option<&str>,
 	) -> anyhow::Result<()> {
 		if let Some(path_override) = path_override {
+			let path = self.render_path_template(path_override, llm_request)?;
 			http::modify_req_uri(req, |uri| {
-				uri.path_and_query = Some(PathAndQuery::from_str(path_override)?);
+				uri.path_and_query = Some(PathAndQuery::from_str(&path)?);
 				Ok(())
 			})?;
 		} else {
@@ -956,9 +1457,73 @@ impl AIProvider {
 			self.set_default_authority(req, route_type)?;
 		}
 		self.set_required_fields(req, route_type, llm_request)?;
+		if let Some(accept_encoding) = accept_encoding {
+			req.headers_mut().insert(
+				header::ACCEPT_ENCODING,
+				HeaderValue::from_str(accept_encoding)?,
+			);
+		}
 		Ok(())
 	}
 
+	/// Resolves `{placeholder}` segments in a path override. Supported placeholders are `{model}`
+	/// (the provider's model override, else the request model), `{region}` (Vertex, Bedrock),
+	/// `{project}` (Vertex project ID, Azure Foundry project name) and `{version}` (Azure API
+	/// version). A placeholder without a value for this provider or request is an error. Values are
+	/// percent-encoded as a single path segment, and `.` or `..` values are rejected.
+	fn render_path_template(
+		&self,
+		template: &str,
+		llm_request: Option<&LLMRequest>,
+	) -> anyhow::Result<String> {
+		if !template.contains('{') {
+			return Ok(template.to_string());
+		}
+		let mut out = String::with_capacity(template.len());
+		let mut rest = template;
+		while let Some(start) = rest.find('{') {
+			out.push_str(&rest[..start]);
+			let end = rest[start..]
+				.find('}')
+				.map(|e| start + e)
+				.ok_or_else(|| anyhow::anyhow!("unterminated placeholder in path template {template}"))?;
+			let name = &rest[start + 1..end];
+			let value = match name {
+				"model" => self
+					.override_model()
+					.or_else(|| llm_request.map(|r| r.request_model.clone())),
+				"region" => match self {
+					AIProvider::Vertex(p) => p.region.clone(),
+					AIProvider::Bedrock(p) => Some(p.region.clone()),
+					_ => None,
+				},
+				"project" => match self {
+					AIProvider::Vertex(p) => Some(p.project_id.clone()),
+					AIProvider::Azure(p) => p.project_name.clone(),
+					_ => None,
+				},
+				"version" => match self {
+					AIProvider::Azure(p) => p.api_version.clone(),
+					_ => None,
+				},
+				_ => anyhow::bail!("unknown placeholder {{{name}}} in path template {template}"),
+			};
+			let value = value.ok_or_else(|| {
+				anyhow::anyhow!(
+					"placeholder {{{name}}} in path template {template} has no value for provider {}",
+					self.provider()
+				)
+			})?;
+			if value == "." || value == ".." {
+				anyhow::bail!("placeholder {{{name}}} in path template {template} resolved to {value}");
+			}
+			out.push_str(&agent_llm::encode_path_segment(&value));
+			rest = &rest[end + 1..];
+		}
+		out.push_str(rest);
+		Ok(out)
+	}
+
 	fn set_path_and_query(uri: &mut http::uri::Parts, path: &str) -> anyhow::Result<()> {
 		let query = uri.path_and_query.as_ref().and_then(|p| p.query());
 		if let Some(query) = query {
@@ -1066,6 +1631,20 @@ impl AIProvider {
 				})?;
 				Ok(())
 			}),
+			AIProvider::Mistral(_) => http::modify_req(req, |req| {
+				http::modify_uri(req, |uri| {
+					let path = format!(
+						"{}{}",
+						path_prefix.map_or(mistral::DEFAULT_BASE_PATH, |prefix| {
+							prefix.trim_end_matches('/')
+						}),
+						mistral::path_suffix(route_type)
+					);
+					Self::set_path_and_query(uri, &path)?;
+					Ok(())
+				})?;
+				Ok(())
+			}),
 			AIProvider::Gemini(_) => http::modify_req(req, |req| {
 				http::modify_uri(req, |uri| {
 					let path = Self::with_path_prefix(gemini::path(route_type), path_prefix);
@@ -1150,6 +1729,7 @@ impl AIProvider {
 			AIProvider::Copilot(_) => Authority::from_static(copilot::DEFAULT_HOST_STR),
 			AIProvider::Anthropic(_) => Authority::from_static(anthropic::DEFAULT_HOST_STR),
 			AIProvider::Gemini(_) => Authority::from_static(gemini::DEFAULT_HOST_STR),
+			AIProvider::Mistral(_) => Authority::from_static(mistral::DEFAULT_HOST_STR),
 			AIProvider::Vertex(provider) => Authority::from_str(&provider.get_host(route_type))?,
 			AIProvider::Azure(provider) => Authority::from_str(&provider.get_host())?,
 			AIProvider::Custom(_) => return Ok(()),
@@ -1183,7 +1763,7 @@ impl AIProvider {
 		llm_request: Option<&LLMRequest>,
 	) -> anyhow::Result<()> {
 		match self {
-			AIProvider::Anthropic(_) => {
+			AIProvider::Anthropic(p) => {
 				http::modify_req(req, |req| {
 					if let Some(authz) = req.headers.typed_get::<headers::Authorization<Bearer>>() {
 						// Check whether the backend auth location was explicitly configured by the user.
@@ -1206,10 +1786,9 @@ impl AIProvider {
 							req.headers.insert("x-api-key", api_key);
 						}
 					}
-					// https://docs.anthropic.com/en/api/versioning
 					req
 						.headers
-						.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+						.insert("anthropic-version", HeaderValue::from_str(p.api_version())?);
 					Ok(())
 				})
 			},
@@ -1224,9 +1803,10 @@ impl AIProvider {
 						RouteType::Messages | RouteType::AnthropicTokenCount
 					) {
 					http::modify_req(req, |req| {
-						req
-							.headers
-							.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+						req.headers.insert(
+							"anthropic-version",
+							HeaderValue::from_static(anthropic::DEFAULT_API_VERSION),
+						);
 						Ok(())
 					})
 				} else {
@@ -1259,6 +1839,74 @@ impl AIProvider {
 		}
 	}
 
+	/// Converts an error from processing a request into a proxy response. Requests that cannot be
+	/// translated for this provider get a structured `400` and are counted by input format and
+	/// provider; other errors are processing failures.
+	pub fn request_error(
+		&self,
+		route_type: RouteType,
+		err: AIError,
+		metrics: &crate::telemetry::metrics::Metrics,
+	) -> crate::proxy::ProxyResponse {
+		let detail = match err {
+			AIError::UnsupportedConversion(detail) => detail,
+			AIError::RequestTooDeep(limit) => {
+				return crate::proxy::ProxyResponse::DirectResponse(Box::new(request_too_deep_response(
+					limit,
+				)));
+			},
+			err => return crate::proxy::ProxyError::Processing(err.into()).into(),
+		};
+		debug!(
+			"unsupported conversion from {} to {}: {detail}",
+			route_type.as_str(),
+			self.provider()
+		);
+		metrics
+			.llm_unsupported_conversions
+			.get_or_create(&crate::telemetry::metrics::UnsupportedConversionLabels {
+				input_format: strng::new(route_type.as_str()).into(),
+				provider: self.provider().into(),
+			})
+			.inc();
+		crate::proxy::ProxyResponse::DirectResponse(Box::new(unsupported_conversion_response(
+			route_type, self, &detail,
+		)))
+	}
+
+	/// Response headers the provider returns its own request id in, in order of preference.
+	fn upstream_request_id_headers(&self) -> &'static [&'static str] {
+		match self {
+			AIProvider::Anthropic(_) => &["request-id"],
+			AIProvider::Bedrock(_) => &["x-amzn-requestid"],
+			AIProvider::Azure(_) => &["apim-request-id", "x-request-id"],
+			AIProvider::Custom(_) => &["x-request-id", "request-id"],
+			AIProvider::OpenAI(_)
+			| AIProvider::Gemini(_)
+			| AIProvider::Vertex(_)
+			| AIProvider::Mistral(_)
+			| AIProvider::Copilot(_) => &["x-request-id"],
+		}
+	}
+
+	/// Whether the provider accepts the `developer` role in chat completions messages. Providers
+	/// translated to another format (Anthropic, Bedrock) handle the role in conversion.
+	fn supports_developer_role(&self) -> bool {
+		matches!(
+			self,
+			AIProvider::OpenAI(_) | AIProvider::Azure(_) | AIProvider::Copilot(_)
+		)
+	}
+
+	/// Whether the provider accepts OpenAI's `logit_bias` in chat completions. Custom providers
+	/// are OpenAI-compatible servers, so the parameter is passed through.
+	fn supports_logit_bias(&self) -> bool {
+		matches!(
+			self,
+			AIProvider::OpenAI(_) | AIProvider::Azure(_) | AIProvider::Custom(_)
+		)
+	}
+
 	pub async fn process_completions_request(
 		&self,
 		backend_info: &crate::http::auth::BackendInfo,
@@ -1270,7 +1918,46 @@ impl AIProvider {
 		let (parts, mut req) = self
 			.read_body_and_default_model::<types::completions::Request>(policies, req, log)
 			.await?;
-		self.apply_model_alias(policies, &mut req);
+		self.apply_model_alias(policies, &parts, &mut req);
+		if let Some(rejected) =
+			self.check_request_model_allowed(policies, InputFormat::Completions, &mut req)
+		{
+			return Ok(rejected);
+		}
+		if req.has_deprecated_functions_with_tools() {
+			match policies
+				.and_then(|p| p.deprecated_functions)
+				.unwrap_or_default()
+			{
+				policy::DeprecatedFunctions::Drop => {
+					warn!("request sets both `tools` and deprecated `functions`; dropping `functions`");
+					req.drop_deprecated_functions();
+				},
+				policy::DeprecatedFunctions::Error => {
+					return Ok(RequestResult::Rejected(invalid_param_response(
+						"functions",
+						"`functions` is deprecated and cannot be combined with `tools`",
+					)));
+				},
+			}
+		}
+		if req.has_logit_bias() && !self.supports_logit_bias() {
+			match policies
+				.and_then(|p| p.unsupported_logit_bias)
+				.unwrap_or_default()
+			{
+				policy::UnsupportedLogitBias::Drop => {
+					warn!(provider = %self.provider(), "provider does not support `logit_bias`; dropping it");
+					req.drop_logit_bias();
+				},
+				policy::UnsupportedLogitBias::Error => {
+					return Ok(RequestResult::Rejected(invalid_param_response(
+						"logit_bias",
+						"`logit_bias` is not supported by this provider",
+					)));
+				},
+			}
+		}
 
 		// If a user doesn't request usage, we will not get token information which we need
 		// We always set it.
@@ -1278,7 +1965,11 @@ impl AIProvider {
 		// Notably, this adds a final SSE event.
 		// We could actually go remove that on the response, but it would mean we cannot do passthrough-parsing,
 		// so unless we have a compelling use case for it, for now we keep it.
-		if req.stream.unwrap_or_default() && req.stream_options.is_none() {
+		// Mistral always reports usage on the final chunk and does not accept `stream_options`.
+		if req.stream.unwrap_or_default()
+			&& req.stream_options.is_none()
+			&& !matches!(self, AIProvider::Mistral(_))
+		{
 			req.stream_options = Some(types::completions::StreamOptions {
 				include_usage: true,
 				rest: Default::default(),
@@ -1290,6 +1981,9 @@ impl AIProvider {
 		) {
 			req.normalize_openai_token_limit();
 		}
+		if !self.supports_developer_role() && req.map_developer_role_to_system() {
+			debug!("provider does not support the `developer` role; sending as `system`");
+		}
 		self
 			.process_chat_request(
 				backend_info,
@@ -1315,7 +2009,12 @@ impl AIProvider {
 		let (parts, mut req) = self
 			.read_body_and_default_model::<types::messages::Request>(policies, req, log)
 			.await?;
-		self.apply_model_alias(policies, &mut req);
+		self.apply_model_alias(policies, &parts, &mut req);
+		if let Some(rejected) =
+			self.check_request_model_allowed(policies, InputFormat::Messages, &mut req)
+		{
+			return Ok(rejected);
+		}
 
 		self
 			.process_chat_request(
@@ -1342,7 +2041,12 @@ impl AIProvider {
 		let (parts, mut req) = self
 			.read_body_and_default_model::<types::embeddings::Request>(policies, req, log)
 			.await?;
-		self.apply_model_alias(policies, &mut req);
+		self.apply_model_alias(policies, &parts, &mut req);
+		if let Some(rejected) =
+			self.check_request_model_allowed(policies, InputFormat::Embeddings, &mut req)
+		{
+			return Ok(rejected);
+		}
 
 		self
 			.process_non_chat_request(
@@ -1369,7 +2073,12 @@ impl AIProvider {
 		let (parts, mut req) = self
 			.read_body_and_default_model::<types::rerank::Request>(policies, req, log)
 			.await?;
-		self.apply_model_alias(policies, &mut req);
+		self.apply_model_alias(policies, &parts, &mut req);
+		if let Some(rejected) =
+			self.check_request_model_allowed(policies, InputFormat::Rerank, &mut req)
+		{
+			return Ok(rejected);
+		}
 
 		self
 			.process_non_chat_request(
@@ -1385,6 +2094,40 @@ impl AIProvider {
 			.await
 	}
 
+	pub async fn process_text_completions_request(
+		&self,
+		backend_info: &crate::http::auth::BackendInfo,
+		policies: Option<&Policy>,
+		req: Request,
+		tokenize: bool,
+		log: &mut Option<&mut RequestLog>,
+	) -> Result<RequestResult, AIError> {
+		let (parts, mut req) = self
+			.read_body_and_default_model::<types::text_completions::Request>(policies, req, log)
+			.await?;
+		self.apply_model_alias(policies, &parts, &mut req);
+		if let Some(rejected) =
+			self.check_request_model_allowed(policies, InputFormat::TextCompletions, &mut req)
+		{
+			return Ok(rejected);
+		}
+
+		// Only OpenAI-compatible providers accept legacy text completions, so the body is
+		// forwarded as-is.
+		self
+			.process_non_chat_request(
+				backend_info,
+				policies,
+				InputFormat::TextCompletions,
+				req,
+				parts,
+				tokenize,
+				log,
+				|_, req, _, _| serde_json::to_vec(req).map_err(AIError::RequestMarshal),
+			)
+			.await
+	}
+
 	pub async fn process_responses_request(
 		&self,
 		backend_info: &crate::http::auth::BackendInfo,
@@ -1396,7 +2139,12 @@ impl AIProvider {
 		let (mut parts, mut req) = self
 			.read_body_and_default_model::<types::responses::Request>(policies, req, log)
 			.await?;
-		self.apply_model_alias(policies, &mut req);
+		self.apply_model_alias(policies, &parts, &mut req);
+		if let Some(rejected) =
+			self.check_request_model_allowed(policies, InputFormat::Responses, &mut req)
+		{
+			return Ok(rejected);
+		}
 
 		// Strip client-specific headers that cause AWS signature mismatches for Bedrock
 		if matches!(self, AIProvider::Bedrock(_)) {
@@ -1428,7 +2176,12 @@ impl AIProvider {
 		let (parts, mut req) = self
 			.read_body_and_default_model::<types::count_tokens::Request>(policies, req, log)
 			.await?;
-		self.apply_model_alias(policies, &mut req);
+		self.apply_model_alias(policies, &parts, &mut req);
+		if let Some(rejected) =
+			self.check_request_model_allowed(policies, InputFormat::CountTokens, &mut req)
+		{
+			return Ok(rejected);
+		}
 
 		// Some Anthropic-compatible clients (e.g. Claude Code) always call
 		// `/v1/messages/count_tokens`. For providers/models without a native
@@ -1441,7 +2194,11 @@ impl AIProvider {
 		if use_local {
 			let messages = req.get_messages();
 			let model = req.model.as_deref().unwrap_or_default();
-			let count = num_tokens_from_messages(model, &messages)?;
+			let image_tokens = policies
+				.and_then(|p| p.image_tokens)
+				.unwrap_or(DEFAULT_IMAGE_TOKENS);
+			let count = num_tokens_from_messages(model, &messages)?
+				+ num_tokens_from_non_text(model, &req.get_non_text_input(), image_tokens)?;
 			let body = serde_json::to_vec(&types::count_tokens::Response {
 				input_tokens: count,
 			})
@@ -1492,6 +2249,7 @@ impl AIProvider {
 		};
 
 		let req = if is_json {
+			check_json_depth(policies, &bytes)?;
 			if let Some(p) = policies
 				&& p.has_request_body_mutations()
 			{
@@ -1533,9 +2291,11 @@ impl AIProvider {
 			AIProvider::Anthropic(_) | AIProvider::Custom(_) => {
 				serde_json::to_vec(req).map_err(AIError::RequestMarshal)
 			},
-			AIProvider::Bedrock(_) => {
-				conversion::bedrock::from_anthropic_token_count::translate(req, headers)
-			},
+			AIProvider::Bedrock(p) => conversion::bedrock::from_anthropic_token_count::translate(
+				req,
+				headers,
+				p.anthropic_version.as_deref(),
+			),
 			AIProvider::Vertex(provider) => {
 				let body = serde_json::to_vec(req).map_err(AIError::RequestMarshal)?;
 				provider.prepare_anthropic_count_tokens_body(body)
@@ -1562,6 +2322,7 @@ impl AIProvider {
 			| AIProvider::Copilot(_)
 			| AIProvider::Azure(_)
 			| AIProvider::Gemini(_)
+			| AIProvider::Mistral(_)
 			| AIProvider::Anthropic(_) => serde_json::to_vec(req).map_err(AIError::RequestMarshal),
 			AIProvider::Vertex(_) => conversion::vertex::from_embeddings::translate(req),
 			AIProvider::Bedrock(p) => conversion::bedrock::from_embeddings::translate(req, p),
@@ -1575,13 +2336,19 @@ impl AIProvider {
 			| AIProvider::Copilot(_)
 			| AIProvider::Azure(_)
 			| AIProvider::Gemini(_)
+			| AIProvider::Mistral(_)
 			| AIProvider::Anthropic(_) => serde_json::to_vec(req).map_err(AIError::RequestMarshal),
 			AIProvider::Vertex(p) => conversion::vertex::from_rerank::translate(req, p),
 			AIProvider::Bedrock(p) => conversion::bedrock::from_rerank::translate(req, p),
 		}
 	}
 
-	fn apply_model_alias(&self, policies: Option<&Policy>, req: &mut impl RequestType) {
+	fn apply_model_alias(
+		&self,
+		policies: Option<&Policy>,
+		parts: &Parts,
+		req: &mut impl RequestType,
+	) {
 		if let Some(p) = policies {
 			// Apply model alias resolution
 			if req.supports_model()
@@ -1591,6 +2358,49 @@ impl AIProvider {
 				*model = aliased.to_string();
 			}
 		}
+		// A retry after the requested model was unavailable; the substitute wins over any alias.
+		if let Some(ModelFallback(fallback)) = parts.extensions.get::<ModelFallback>()
+			&& req.supports_model()
+			&& let Some(model) = req.model()
+		{
+			*model = fallback.to_string();
+		}
+	}
+
+	/// Rejects the request if its model, after alias resolution, is not allowed by the policy.
+	fn check_request_model_allowed(
+		&self,
+		policies: Option<&Policy>,
+		input_format: InputFormat,
+		req: &mut impl RequestType,
+	) -> Option<RequestResult> {
+		if !req.supports_model() {
+			return None;
+		}
+		let model = req.model().clone();
+		self.check_model_allowed(policies, input_format, model.as_deref())
+	}
+
+	/// Rejects `model` if it is not allowed by the policy. A request without a model is checked as
+	/// the provider's default model; with neither, only a policy without `allowedModels` admits it.
+	fn check_model_allowed(
+		&self,
+		policies: Option<&Policy>,
+		input_format: InputFormat,
+		model: Option<&str>,
+	) -> Option<RequestResult> {
+		let p = policies?;
+		let default_model = self.override_model();
+		let model = model.filter(|m| !m.is_empty()).or(default_model.as_deref());
+		let allowed = match model {
+			Some(model) => p.is_model_allowed(model),
+			None => p.allowed_models.is_none(),
+		};
+		(!allowed).then(|| {
+			let model = model.unwrap_or_default();
+			debug!(%model, "model rejected by policy");
+			RequestResult::Rejected(model_not_allowed_response(model, input_format))
+		})
 	}
 
 	#[allow(clippy::too_many_arguments)]
@@ -1605,9 +2415,62 @@ impl AIProvider {
 		tokenize: bool,
 		log: &mut Option<&mut RequestLog>,
 	) -> Result<PreparedRequest, AIError> {
+		// Counting characters is cheap; reject abusive prompts before guardrails and tokenization.
+		// Only chat and text completion formats carry a text prompt.
+		if let Some(limit) = policies.and_then(|p| p.max_prompt_chars)
+			&& original_format.supports_prompt_guard()
+		{
+			let chars = req
+				.get_messages()
+				.iter()
+				.map(|m| m.content.chars().count() as u64)
+				.sum::<u64>();
+			if chars > limit {
+				return Ok(PreparedRequest::Rejected(prompt_too_large_response(
+					chars, limit,
+				)));
+			}
+		}
+		match policies.and_then(|p| p.streaming_requests) {
+			Some(policy::StreamingRequests::Downgrade) => {
+				if req.disable_streaming() {
+					parts.extensions.insert(StreamDowngraded);
+				}
+			},
+			Some(policy::StreamingRequests::Reject) if req.disable_streaming() => {
+				return Ok(PreparedRequest::Rejected(invalid_param_response(
+					"stream",
+					"streaming is not supported on this route",
+				)));
+			},
+			_ => {},
+		}
 		if let Some(p) = policies {
-			p.apply_prompt_enrichment(req);
-
+			p.apply_default_params(req);
+			if let Some(cap) = p.max_output_tokens_cap
+				&& req.cap_max_output_tokens(cap)
+			{
+				parts.extensions.insert(OutputTokensCapped(cap));
+			}
+			p.apply_prompt_enrichment(
+				req,
+				log.as_ref().and_then(|l| l.request_snapshot.as_deref()),
+			);
+			if p.normalize_tool_call_ids.unwrap_or_default()
+				&& let Some(format) = self.tool_call_id_format()
+			{
+				req.normalize_tool_call_ids(format);
+			}
+
+			if let Some(mirror) = &p.audit_mirror
+				&& original_format.supports_prompt_guard()
+			{
+				mirror.mirror(
+					PolicyClient::new(backend_info.inputs.clone()),
+					req.model().as_deref().map(strng::new),
+					req.get_messages(),
+				);
+			}
 			if original_format.supports_prompt_guard() {
 				let http_headers = &parts.headers;
 				let claims = parts.extensions.get::<Claims>().cloned();
@@ -1619,6 +2482,14 @@ impl AIProvider {
 						warn!("failed to call prompt guard webhook: {e}");
 						AIError::PromptWebhookError
 					})? {
+					record_prompt_guard_rejection(
+						&backend_info.inputs.metrics,
+						crate::telemetry::metrics::GuardrailPhase::Request,
+						guardrail,
+						&response,
+						self.provider(),
+						backend_info.target.name(),
+					);
 					return Ok(PreparedRequest::GuardrailRejected {
 						response,
 						guardrail,
@@ -1627,10 +2498,77 @@ impl AIProvider {
 			}
 		}
 
-		let mut llm_info = req.to_llm_request(self.provider(), tokenize)?;
+		// The external tokenizer counts chat messages; other formats fall back to local counting.
+		let external_tokenizer = policies
+			.and_then(|p| p.external_tokenizer.as_ref())
+			.filter(|_| tokenize && original_format.is_chat());
+		let image_tokens = policies
+			.and_then(|p| p.image_tokens)
+			.unwrap_or(DEFAULT_IMAGE_TOKENS);
+		let lenient =
+			policies.and_then(|p| p.tokenize_failure) == Some(policy::TokenizeFailure::Lenient);
+		let local_tokenize = tokenize && external_tokenizer.is_none();
+		let mut llm_info = match req.to_llm_request(self.provider(), local_tokenize) {
+			// Only the token count can fail differently with tokenization on; anything else fails
+			// the retry as well.
+			Err(e) if local_tokenize && lenient => {
+				let llm_info = req.to_llm_request(self.provider(), false)?;
+				skip_token_count(&llm_info.request_model, &e);
+				llm_info
+			},
+			r => r?,
+		};
+		if external_tokenizer.is_none()
+			&& let Some(tokens) = llm_info.input_tokens
+		{
+			match num_tokens_from_non_text(
+				&llm_info.request_model,
+				&req.get_non_text_input(),
+				image_tokens,
+			) {
+				Ok(n) => llm_info.input_tokens = Some(tokens + n),
+				Err(e) if lenient => {
+					skip_token_count(&llm_info.request_model, &e);
+					llm_info.input_tokens = None;
+				},
+				Err(e) => return Err(e),
+			}
+		}
+		if let Some(tokenizer) = external_tokenizer {
+			let model = llm_info.request_model.clone();
+			let messages = req.get_messages();
+			llm_info.input_tokens = if has_local_tokenizer(&model) {
+				let count = num_tokens_from_messages(&model, &messages).and_then(|n| {
+					num_tokens_from_non_text(&model, &req.get_non_text_input(), image_tokens).map(|m| n + m)
+				});
+				match count {
+					Ok(n) => Some(n),
+					Err(e) if lenient => {
+						skip_token_count(&model, &e);
+						None
+					},
+					Err(e) => return Err(e),
+				}
+			} else {
+				let client = PolicyClient::new(backend_info.inputs.clone());
+				tokenizer
+					.count_tokens(&client, &model, &messages)
+					.await
+					.inspect_err(|e| warn!(%model, "failed to count tokens with the external tokenizer: {e}"))
+					.ok()
+			};
+		}
 		if original_format == InputFormat::Detect {
 			types::detect::amend_request_info(&mut llm_info, parts.uri.path());
 		}
+		if let Some((param, message)) = policies
+			.and_then(|p| p.param_limits.as_ref())
+			.and_then(|l| l.validate(&llm_info.params))
+		{
+			return Ok(PreparedRequest::Rejected(invalid_param_response(
+				param, &message,
+			)));
+		}
 		llm_info.cache_convention =
 			cache_convention_for(self, provider_format, &llm_info.request_model);
 		if let Some(log) = log
@@ -1689,6 +2627,7 @@ impl AIProvider {
 					guardrail,
 				});
 			},
+			PreparedRequest::Rejected(response) => return Ok(RequestResult::Rejected(response)),
 		};
 
 		let rendered = chat_translation.render_request(
@@ -1766,7 +2705,18 @@ impl AIProvider {
 					guardrail,
 				});
 			},
+			PreparedRequest::Rejected(response) => return Ok(RequestResult::Rejected(response)),
 		};
+		// Passthrough requests are not parsed, so their model is only known once the request is
+		// prepared.
+		if original_format == InputFormat::Detect
+			&& let Some(rejected) = self.check_model_allowed(
+				policies,
+				original_format,
+				Some(llm_info.request_model.as_str()),
+			) {
+			return Ok(rejected);
+		}
 		let request_model = llm_info.request_model.as_str();
 		let body = render(self, &req, &parts, request_model)?;
 		parts.headers.remove(header::CONTENT_LENGTH);
@@ -1781,6 +2731,16 @@ impl AIProvider {
 	}
 
 	#[allow(clippy::too_many_arguments)]
+	#[tracing::instrument(
+		level = "debug",
+		skip_all,
+		fields(
+			provider = %self.provider(),
+			model = %req.request_model,
+			input_format = ?req.input_format,
+			streaming = req.streaming,
+		)
+	)]
 	pub async fn process_response(
 		&self,
 		client: PolicyClient,
@@ -1792,6 +2752,13 @@ impl AIProvider {
 		model_catalog: Option<&Arc<cost::ModelCatalog>>,
 		resp: Response,
 	) -> Result<Response, AIError> {
+		let mut req = req;
+		// Passthrough requests only guess streaming from the request, so an SSE response to a request
+		// without a stream flag is still parsed as a stream to pick up usage from its final events.
+		if !req.streaming && req.input_format == InputFormat::Detect && is_event_stream(&resp) {
+			req.streaming = true;
+			log.non_atomic_mutate(|l| l.request.streaming = true);
+		}
 		// Non-success responses are plain JSON, not event-stream data.
 		// Only enter the streaming path for successful responses; errors
 		// fall through to the buffered path where process_error translates them.
@@ -1809,6 +2776,28 @@ impl AIProvider {
 		}
 		let model_catalog = model_catalog.map(Arc::as_ref);
 
+		let mut resp = resp;
+		if rate_limit.stream_oversized_responses
+			&& rate_limit.prompt_guard.is_empty()
+			&& resp.status().is_success()
+			&& self.is_passthrough_response(&req)
+			&& let Ok(http::BodyInspection::Partial(_)) = http::inspect_response_body(&mut resp).await
+		{
+			warn!(
+				model = %req.request_model,
+				"response exceeds the buffer limit; streaming it through without parsing usage"
+			);
+			let (parts, body) = resp.into_parts();
+			return Ok(Self::finalize_response(
+				parts,
+				body,
+				req,
+				LLMResponse::default(),
+				model_catalog,
+				&log,
+			));
+		}
+
 		let buffered = Self::buffer_response(resp).await?;
 
 		match req.input_format {
@@ -1821,6 +2810,9 @@ impl AIProvider {
 			InputFormat::Rerank => {
 				self.process_rerank_buffered_response(req, buffered, model_catalog, &log)
 			},
+			InputFormat::TextCompletions => {
+				self.process_text_completions_buffered_response(req, buffered, model_catalog, &log)
+			},
 			_ => {
 				self
 					.process_chat_or_detect_buffered_response(
@@ -1861,26 +2853,65 @@ impl AIProvider {
 			(LLMResponse::default(), body)
 		} else {
 			let mut resp = self.translate_chat_or_detect_response(&req, &bytes)?;
+			if rate_limit.empty_choices.is_some() && resp.has_no_choices() {
+				warn!(model = %req.request_model, "provider returned a response with no choices");
+				// The provider still reports usage for the call, so account for it as usual.
+				let llm_resp = resp.to_llm_response(include_completion_in_log);
+				let (parts, body) = empty_choices_response().into_parts();
+				return Ok(Self::finalize_chat_response(
+					&client,
+					parts,
+					body,
+					req,
+					llm_resp,
+					rate_limit,
+					req_snapshot.as_deref(),
+					model_catalog,
+					&log,
+				));
+			}
 			let prompt_guard_headers =
 				response_prompt_guard_headers(&parts.headers, rate_limit.request_traceparent.as_ref());
 
 			// Apply response prompt guard
-			if let Some(dr) = Policy::apply_response_prompt_guard(
+			let mut guard_modifications = Vec::new();
+			if let Some((dr, guard)) = Policy::apply_response_prompt_guard(
 				&client,
 				resp.as_mut(),
 				&prompt_guard_headers,
 				&rate_limit.prompt_guard,
 				req_snapshot.as_deref(),
+				&mut guard_modifications,
 			)
 			.await
 			.map_err(|e| {
 				warn!("failed to apply response prompt guard: {e}");
 				AIError::PromptWebhookError
 			})? {
+				record_prompt_guard_rejection(
+					&client.inputs.metrics,
+					crate::telemetry::metrics::GuardrailPhase::Response,
+					guard,
+					&dr,
+					req.provider.clone(),
+					rate_limit.backend.clone(),
+				);
 				return Ok(dr);
 			}
+			if rate_limit.guard_modifications_header
+				&& !guard_modifications.is_empty()
+				&& let Ok(value) = HeaderValue::from_str(
+					&guard_modifications
+						.iter()
+						.map(ToString::to_string)
+						.collect::<Vec<_>>()
+						.join(", "),
+				) {
+				parts.headers.insert(GUARD_MODIFICATIONS_HEADER, value);
+			}
 
-			let llm_resp = resp.to_llm_response(include_completion_in_log);
+			let mut llm_resp = resp.to_llm_response(include_completion_in_log);
+			llm_resp.guard_modifications = guard_modifications;
 			let body = resp.serialize().map_err(AIError::ResponseParsing)?;
 			(llm_resp, Bytes::copy_from_slice(&body))
 		};
@@ -1898,6 +2929,33 @@ impl AIProvider {
 			Body::from(body)
 		};
 		parts.headers.remove(header::CONTENT_LENGTH);
+		Ok(Self::finalize_chat_response(
+			&client,
+			parts,
+			body,
+			req,
+			llm_resp,
+			rate_limit,
+			req_snapshot.as_deref(),
+			model_catalog,
+			&log,
+		))
+	}
+
+	/// Like `finalize_response`, but also records the input token divergence and amends the rate
+	/// limits with the usage of the response.
+	#[allow(clippy::too_many_arguments)]
+	fn finalize_chat_response(
+		client: &PolicyClient,
+		mut parts: ::http::response::Parts,
+		body: Body,
+		req: LLMRequest,
+		llm_resp: LLMResponse,
+		rate_limit: LLMResponsePolicies,
+		req_snapshot: Option<&RequestSnapshot>,
+		model_catalog: Option<&cost::ModelCatalog>,
+		log: &AsyncLog<llm::LLMInfo>,
+	) -> Response {
 		let llm_info = LLMInfo::new(req, llm_resp);
 		parts
 			.extensions
@@ -1907,14 +2965,26 @@ impl AIProvider {
 			));
 		let resp = Response::from_parts(parts, body);
 
+		record_input_token_divergence(&client.inputs.metrics, &llm_info);
 		if !rate_limit.local_rate_limit.is_empty() || rate_limit.remote_rate_limit.is_some() {
-			let exec = cel::Executor::new_response(req_snapshot.as_deref(), &resp);
+			let exec = cel::Executor::new_response(req_snapshot, &resp);
 			// In the initial request, we subtracted the approximate request tokens.
 			// Now we should have the real request tokens and the response tokens
 			amend_tokens(rate_limit, &llm_info, exec);
 		}
 		log.store(Some(llm_info));
-		Ok(resp)
+		resp
+	}
+
+	/// Whether a successful response for `req` is forwarded without translation.
+	fn is_passthrough_response(&self, req: &LLMRequest) -> bool {
+		match req.input_format {
+			InputFormat::Detect | InputFormat::TextCompletions => true,
+			InputFormat::Completions | InputFormat::Messages | InputFormat::Responses => self
+				.chat_translation(req.input_format, Some(req.request_model.as_str()))
+				.is_ok_and(|t| t.is_passthrough()),
+			_ => false,
+		}
 	}
 
 	async fn buffer_response(resp: Response) -> Result<BufferedResponse, AIError> {
@@ -2074,6 +3144,41 @@ impl AIProvider {
 		))
 	}
 
+	fn process_text_completions_buffered_response(
+		&self,
+		req: LLMRequest,
+		buffered: BufferedResponse,
+		model_catalog: Option<&cost::ModelCatalog>,
+		log: &AsyncLog<llm::LLMInfo>,
+	) -> Result<Response, AIError> {
+		let BufferedResponse {
+			mut parts, bytes, ..
+		} = buffered;
+		parts.headers.remove(header::CONTENT_LENGTH);
+		if !parts.status.is_success() {
+			let body = self.process_error(&req, parts.status, &bytes)?;
+			return Ok(Self::finalize_response(
+				parts,
+				body.into(),
+				req,
+				LLMResponse::default(),
+				model_catalog,
+				log,
+			));
+		}
+		// Text completions are only served by OpenAI-compatible providers; the body is returned as-is.
+		let resp: types::text_completions::Response =
+			serde_json::from_slice(&bytes).map_err(logged_response_parsing(&bytes))?;
+		Ok(Self::finalize_response(
+			parts,
+			bytes.into(),
+			req,
+			resp.to_llm_response(false),
+			model_catalog,
+			log,
+		))
+	}
+
 	fn process_embeddings_response(
 		&self,
 		req: &LLMRequest,
@@ -2174,6 +3279,7 @@ impl AIProvider {
 		let model = req.request_model.clone();
 		let input_format = req.input_format;
 		let bedrock_tool_name_map = bedrock_tool_name_map(&req).cloned();
+		let include_usage = bedrock_include_usage(&req);
 		let chat_translation = if input_format.is_chat() {
 			Some(self.chat_translation(input_format, Some(&model))?)
 		} else {
@@ -2237,7 +3343,26 @@ impl AIProvider {
 			vec![]
 		};
 
-		let logger = AmendOnDrop::new(log, response_policies, req_snapshot, model_catalog).into_llm();
+		let usage_trailers = response_policies.usage_trailers.then(|| log.clone());
+		let synthesize_done = response_policies.synthesize_stream_done
+			&& chat_translation.is_some_and(|t| t.is_completions_passthrough());
+		let reset_log = log.clone();
+		let timing_log = log.clone();
+		let guard_log = log.clone();
+		let estimate_missing_usage = response_policies.estimate_missing_stream_usage;
+		let stream_transformation = response_policies
+			.stream_transformation
+			.clone()
+			.filter(|_| input_format.is_chat())
+			.map(|t| (t, req_snapshot.clone()));
+		let logger = AmendOnDrop::new(
+			log,
+			response_policies,
+			req_snapshot,
+			model_catalog,
+			Some(client.inputs.metrics.clone()),
+		)
+		.into_llm();
 		let stream_format = match self {
 			AIProvider::Bedrock(_) => "awsEventStream",
 			_ => "sseJson",
@@ -2261,6 +3386,8 @@ impl AIProvider {
 					model: model.to_string(),
 					include_completion_in_log,
 					tool_name_map: bedrock_tool_name_map,
+					include_usage,
+					estimate_missing_usage,
 				},
 			)
 		} else {
@@ -2268,7 +3395,9 @@ impl AIProvider {
 				(AIProvider::Bedrock(_), InputFormat::Detect) => {
 					types::detect::passthrough_aws_stream(logger, resp)
 				},
-				(_, InputFormat::Detect) => types::detect::passthrough_stream(logger, resp),
+				(_, InputFormat::Detect | InputFormat::TextCompletions) => {
+					types::detect::passthrough_stream(logger, resp)
+				},
 				(_, InputFormat::Realtime) => {
 					return Err(AIError::UnsupportedConversion(strng::literal!(
 						"realtime does not use streaming codepath"
@@ -2282,11 +3411,35 @@ impl AIProvider {
 			}
 		};
 
-		if !evaluators.is_empty() {
+		// Terminate before guardrails and transformations so they see a complete stream.
+		let translated = if synthesize_done {
+			translated.map(StreamDoneBody::wrap)
+		} else {
+			translated
+		};
+		let translated = if !evaluators.is_empty() {
 			// `logger` is owned by the translated body; pass None to avoid double-logging.
-			return Ok(translated.map(|b| GuardedSseBody::new(b, evaluators, buffer, None)));
-		}
-		Ok(translated)
+			translated.map(|b| GuardedSseBody::new(b, evaluators, buffer, None, Some(guard_log)))
+		} else {
+			translated
+		};
+		// Transform after guardrails so they evaluate what the model actually produced.
+		let translated = match stream_transformation {
+			Some((t, snapshot)) => translated.map(|b| TransformedSseBody::new(b, t, snapshot, buffer)),
+			None => translated,
+		};
+		// Detect paths pass upstream bytes through untouched, so there is no format to normalize into.
+		let translated = if input_format.is_chat() {
+			translated.map(|b| StreamResetBody::wrap(b, reset_log, input_format))
+		} else {
+			translated
+		};
+		let metrics = client.inputs.metrics.clone();
+		let translated = translated.map(|b| StreamTimingBody::wrap(b, timing_log, metrics));
+		Ok(match usage_trailers {
+			Some(log) => translated.map(|b| UsageTrailersBody::wrap(b, log)),
+			None => translated,
+		})
 	}
 
 	async fn read_body_and_default_model<T: RequestType + DeserializeOwned>(
@@ -2316,6 +3469,7 @@ impl AIProvider {
 			parts.headers.remove(header::CONTENT_ENCODING);
 			parts.headers.remove(header::TRANSFER_ENCODING);
 		}
+		check_json_depth(policies, &bytes)?;
 
 		if self.override_model().is_none()
 			&& types::detect::extract_model_from_path(parts.uri.path()).is_none()
@@ -2398,7 +3552,10 @@ impl AIProvider {
 		match (self, req.input_format) {
 			(AIProvider::Custom(_), InputFormat::Embeddings) => Ok(bytes.clone()),
 			(
-				AIProvider::OpenAI(_) | AIProvider::Copilot(_) | AIProvider::Azure(_),
+				AIProvider::OpenAI(_)
+				| AIProvider::Copilot(_)
+				| AIProvider::Azure(_)
+				| AIProvider::Mistral(_),
 				InputFormat::Embeddings,
 			) => {
 				// Passthrough; nothing needed
@@ -2430,6 +3587,13 @@ impl AIProvider {
 			(AIProvider::Vertex(_), InputFormat::Rerank) => {
 				conversion::vertex::from_rerank::translate_error(bytes)
 			},
+			(
+				AIProvider::OpenAI(_) | AIProvider::Azure(_) | AIProvider::Custom(_),
+				InputFormat::TextCompletions,
+			) => {
+				// Passthrough; nothing needed
+				Ok(bytes.clone())
+			},
 			(_, InputFormat::Realtime) => Err(AIError::UnsupportedConversion(strng::literal!(
 				"realtime does not use this codepath"
 			))),
@@ -2442,11 +3606,20 @@ impl AIProvider {
 
 fn bedrock_tool_name_map(req: &LLMRequest) -> Option<&conversion::bedrock::BedrockToolNameMap> {
 	match &req.provider_state {
-		Some(ProviderState::Bedrock { tool_names }) => Some(tool_names.as_ref()),
+		Some(ProviderState::Bedrock { tool_names, .. }) if !tool_names.is_empty() => {
+			Some(tool_names.as_ref())
+		},
 		_ => None,
 	}
 }
 
+fn bedrock_include_usage(req: &LLMRequest) -> bool {
+	match &req.provider_state {
+		Some(ProviderState::Bedrock { include_usage, .. }) => *include_usage,
+		_ => true,
+	}
+}
+
 fn map_compression_error(e: http::compression::Error, headers: &::http::HeaderMap) -> AIError {
 	match e {
 		http::compression::Error::UnsupportedEncoding => AIError::UnsupportedEncoding(strng::new(
@@ -2472,6 +3645,43 @@ fn response_prompt_guard_headers(
 	headers
 }
 
+fn record_prompt_guard_rejection(
+	metrics: &crate::telemetry::metrics::Metrics,
+	phase: crate::telemetry::metrics::GuardrailPhase,
+	guard: &'static str,
+	response: &Response,
+	provider: Strng,
+	backend: Strng,
+) {
+	metrics
+		.prompt_guard_rejections
+		.get_or_create(&crate::telemetry::metrics::GuardrailRejectionLabels {
+			phase,
+			guard: strng::new(guard).into(),
+			code: Some(response.status().as_u16()).into(),
+			provider: provider.into(),
+			backend: backend.into(),
+		})
+		.inc();
+}
+
+/// Records how far the estimated input tokens were from what the provider reported, so tokenizer
+/// accuracy can be validated per model.
+fn record_input_token_divergence(metrics: &crate::telemetry::metrics::Metrics, llm_resp: &LLMInfo) {
+	let (Some(estimated), Some(reported)) = (
+		llm_resp.request.input_tokens,
+		llm_resp.response.input_tokens,
+	) else {
+		return;
+	};
+	metrics
+		.gen_ai_input_token_estimate_divergence
+		.get_or_create(&crate::telemetry::metrics::GenAITokenEstimateLabels {
+			gen_ai_request_model: llm_resp.request.request_model.clone().into(),
+		})
+		.observe(((reported as i64) - (estimated as i64)) as f64);
+}
+
 fn amend_tokens(rate_limit: store::LLMResponsePolicies, llm_resp: &LLMInfo, exec: Executor) {
 	let input_mismatch = match (
 		llm_resp.request.input_tokens,
@@ -2495,11 +3705,96 @@ fn amend_tokens(rate_limit: store::LLMResponsePolicies, llm_resp: &LLMInfo, exec
 	}
 }
 
+/// Request extension carrying the substitute model for a retry after the provider reported the
+/// requested model as unavailable. See `Policy::model_fallbacks`.
+#[derive(Debug, Clone)]
+pub struct ModelFallback(pub Strng);
+
+/// Response extension marking a structured error that replaced a response with no choices, so
+/// the proxy can retry it when the `emptyChoices` policy asks for that.
+#[derive(Debug, Clone, Copy)]
+pub struct EmptyChoicesResponse;
+
+/// Response header reporting the output token cap applied to the request.
+pub const MAX_OUTPUT_TOKENS_CAP_HEADER: HeaderName =
+	HeaderName::from_static("x-max-output-tokens-cap");
+
+/// Request extension recording that `Policy::streaming_requests` turned a streaming request into
+/// a non-streaming one.
+#[derive(Debug, Clone, Copy)]
+pub struct StreamDowngraded;
+
+/// Response header marking a response that was not streamed although the client asked for it.
+pub const STREAM_DOWNGRADED_HEADER: HeaderName = HeaderName::from_static("x-stream-downgraded");
+
+/// Response header echoing the provider's own id for the request, whichever header the provider
+/// returned it in.
+pub const UPSTREAM_REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-upstream-request-id");
+
+/// Response header carrying the provider's model deprecation notice.
+pub const MODEL_DEPRECATION_HEADER: HeaderName = HeaderName::from_static("x-model-deprecation");
+
+/// Response header listing the changes response guards made to the response.
+pub const GUARD_MODIFICATIONS_HEADER: HeaderName =
+	HeaderName::from_static("x-guardrail-modifications");
+
+/// Returns the provider's own id for the request from its provider-specific response header. With
+/// `echo`, the id is also set on the response as `x-upstream-request-id`.
+pub fn capture_upstream_request_id(
+	provider: &AIProvider,
+	resp: &mut Response,
+	echo: bool,
+) -> Option<Strng> {
+	let id = provider
+		.upstream_request_id_headers()
+		.iter()
+		.find_map(|name| resp.headers().get(*name))?
+		.clone();
+	let captured = id.to_str().ok().map(strng::new);
+	if echo {
+		resp.headers_mut().insert(UPSTREAM_REQUEST_ID_HEADER, id);
+	}
+	captured
+}
+
+/// Returns the provider's model deprecation notice, built from its `deprecation` (RFC 9745) and
+/// `sunset` (RFC 8594) response headers. With `surface`, the notice is also set on the response as
+/// `x-model-deprecation`.
+pub fn capture_model_deprecation(resp: &mut Response, surface: bool) -> Option<String> {
+	let headers = resp.headers();
+	let value = |name: &HeaderName| headers.get(name).and_then(|v| v.to_str().ok());
+	let notice = match (value(&DEPRECATION), value(&SUNSET)) {
+		(None, None) => return None,
+		(Some(d), None) => format!("deprecation={d}"),
+		(None, Some(s)) => format!("sunset={s}"),
+		(Some(d), Some(s)) => format!("deprecation={d}; sunset={s}"),
+	};
+	if surface && let Ok(v) = HeaderValue::from_str(&notice) {
+		resp.headers_mut().insert(MODEL_DEPRECATION_HEADER, v);
+	}
+	Some(notice)
+}
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Logs a local token count that failed with a lenient `tokenizeFailure`; the request continues
+/// without an input token estimate.
+fn skip_token_count(model: &str, e: &AIError) {
+	warn!(%model, "failed to count input tokens, continuing without an estimate: {e}");
+}
+
+/// Request extension recording that `Policy::max_output_tokens_cap` lowered (or filled in) the
+/// request's output token limit.
+#[derive(Debug, Clone, Copy)]
+pub struct OutputTokensCapped(pub u64);
+
 pub struct AmendOnDrop {
 	log: AsyncLog<llm::LLMInfo>,
 	pol: Option<LLMResponsePolicies>,
 	req: Option<Arc<RequestSnapshot>>,
 	catalog: Option<Arc<cost::ModelCatalog>>,
+	metrics: Option<Arc<crate::telemetry::metrics::Metrics>>,
 }
 
 impl AmendOnDrop {
@@ -2508,21 +3803,29 @@ impl AmendOnDrop {
 		pol: LLMResponsePolicies,
 		req: Option<Arc<RequestSnapshot>>,
 		catalog: Option<Arc<cost::ModelCatalog>>,
+		metrics: Option<Arc<crate::telemetry::metrics::Metrics>>,
 	) -> Self {
 		Self {
 			log,
 			pol: Some(pol),
 			req,
 			catalog,
+			metrics,
 		}
 	}
 	pub fn non_atomic_mutate(&self, f: impl FnOnce(&mut llm::LLMInfo)) {
 		self.log.non_atomic_mutate(f);
 	}
 	pub fn report_usage(&mut self) {
-		if let Some(pol) = self.pol.take()
-			&& (!pol.local_rate_limit.is_empty() || pol.remote_rate_limit.is_some())
-		{
+		let Some(pol) = self.pol.take() else {
+			return;
+		};
+		if let Some(metrics) = &self.metrics {
+			self
+				.log
+				.non_atomic_mutate(|r| record_input_token_divergence(metrics, r));
+		}
+		if !pol.local_rate_limit.is_empty() || pol.remote_rate_limit.is_some() {
 			self.log.non_atomic_mutate(|r| {
 				let ctx = LLMContext::from_llm_info(r.clone(), self.catalog.as_deref());
 				let exec = cel::Executor::new_llm_rate_limit_streaming(self.req.as_deref(), &ctx);
@@ -2551,3 +3854,385 @@ impl Drop for AmendOnDrop {
 		self.report_usage();
 	}
 }
+
+pin_project_lite::pin_project! {
+	// Appends the streamed token usage as `x-usage-*` trailers once the wrapped body ends.
+	struct UsageTrailersBody {
+		#[pin]
+		inner: Body,
+		log: AsyncLog<llm::LLMInfo>,
+		done: bool,
+	}
+}
+
+impl UsageTrailersBody {
+	fn wrap(inner: Body, log: AsyncLog<llm::LLMInfo>) -> Body {
+		Body::new(Self {
+			inner,
+			log,
+			done: false,
+		})
+	}
+}
+
+fn insert_usage_trailers(log: &AsyncLog<llm::LLMInfo>, trailers: &mut HeaderMap) {
+	let Some(info) = log.load_clone() else {
+		return;
+	};
+	let resp = &info.response;
+	let total = resp
+		.total_tokens
+		.or_else(|| Some(resp.input_tokens? + resp.output_tokens?));
+	for (name, value) in [
+		("x-usage-input-tokens", resp.input_tokens),
+		("x-usage-output-tokens", resp.output_tokens),
+		("x-usage-total-tokens", total),
+	] {
+		if let Some(value) = value {
+			trailers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+		}
+	}
+}
+
+impl http_body::Body for UsageTrailersBody {
+	type Data = Bytes;
+	type Error = crate::http::Error;
+
+	fn poll_frame(
+		self: std::pin::Pin<&mut Self>,
+		cx: &mut std::task::Context<'_>,
+	) -> std::task::Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+		let this = self.project();
+		if *this.done {
+			return std::task::Poll::Ready(None);
+		}
+		let frame = match std::task::ready!(this.inner.poll_frame(cx)) {
+			// Upstream trailers are kept, with usage merged in.
+			Some(Ok(frame)) if frame.is_trailers() => {
+				let mut trailers = frame.into_trailers().expect("checked is_trailers");
+				insert_usage_trailers(this.log, &mut trailers);
+				*this.done = true;
+				Some(Ok(http_body::Frame::trailers(trailers)))
+			},
+			None => {
+				let mut trailers = HeaderMap::new();
+				insert_usage_trailers(this.log, &mut trailers);
+				*this.done = true;
+				(!trailers.is_empty()).then(|| Ok(http_body::Frame::trailers(trailers)))
+			},
+			other => other,
+		};
+		std::task::Poll::Ready(frame)
+	}
+
+	fn is_end_stream(&self) -> bool {
+		self.done
+	}
+}
+
+pin_project_lite::pin_project! {
+	// Counts the chunks of a streamed response and the longest gap between them.
+	struct StreamTimingBody {
+		#[pin]
+		inner: Body,
+		timing: StreamTiming,
+	}
+}
+
+impl StreamTimingBody {
+	fn wrap(
+		inner: Body,
+		log: AsyncLog<llm::LLMInfo>,
+		metrics: Arc<crate::telemetry::metrics::Metrics>,
+	) -> Body {
+		Body::new(Self {
+			inner,
+			timing: StreamTiming {
+				log,
+				metrics,
+				chunks: 0,
+				last_chunk: None,
+				max_gap: Duration::ZERO,
+			},
+		})
+	}
+}
+
+/// Reports on drop, so streams the client abandons are still summarized.
+struct StreamTiming {
+	log: AsyncLog<llm::LLMInfo>,
+	metrics: Arc<crate::telemetry::metrics::Metrics>,
+	chunks: u64,
+	last_chunk: Option<Instant>,
+	max_gap: Duration,
+}
+
+impl StreamTiming {
+	fn record_chunk(&mut self) {
+		let now = Instant::now();
+		if let Some(last) = self.last_chunk {
+			self.max_gap = self.max_gap.max(now.saturating_duration_since(last));
+		}
+		self.last_chunk = Some(now);
+		self.chunks += 1;
+	}
+}
+
+impl Drop for StreamTiming {
+	fn drop(&mut self) {
+		self.log.non_atomic_mutate(|r| {
+			debug!(
+				provider = %r.request.provider,
+				model = %r.request.request_model,
+				chunks = self.chunks,
+				max_gap_ms = self.max_gap.as_millis() as u64,
+				"llm stream complete"
+			);
+			// A gap needs two chunks; a single chunk says nothing about stalls.
+			if self.chunks > 1 {
+				self
+					.metrics
+					.llm_stream_max_chunk_gap
+					.get_or_create(&crate::telemetry::metrics::LLMModelLabels {
+						provider: r.request.provider.clone().into(),
+						model: r.request.request_model.clone().into(),
+					})
+					.observe(self.max_gap.as_secs_f64());
+			}
+		});
+	}
+}
+
+impl http_body::Body for StreamTimingBody {
+	type Data = Bytes;
+	type Error = crate::http::Error;
+
+	fn poll_frame(
+		self: std::pin::Pin<&mut Self>,
+		cx: &mut std::task::Context<'_>,
+	) -> std::task::Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+		let this = self.project();
+		let frame = std::task::ready!(this.inner.poll_frame(cx));
+		if let Some(Ok(frame)) = &frame
+			&& frame.data_ref().is_some_and(|d| !d.is_empty())
+		{
+			this.timing.record_chunk();
+		}
+		std::task::Poll::Ready(frame)
+	}
+
+	fn is_end_stream(&self) -> bool {
+		self.inner.is_end_stream()
+	}
+
+	fn size_hint(&self) -> http_body::SizeHint {
+		self.inner.size_hint()
+	}
+}
+
+pin_project_lite::pin_project! {
+	// Appends `data: [DONE]` to an OpenAI-format stream that reaches EOF without it.
+	struct StreamDoneBody {
+		#[pin]
+		inner: Body,
+		// The last bytes seen, enough to recognize a trailing `data: [DONE]` split across frames.
+		tail: Vec<u8>,
+		done: bool,
+	}
+}
+
+impl StreamDoneBody {
+	const DONE_EVENT: &'static [u8] = b"data: [DONE]";
+
+	fn wrap(inner: Body) -> Body {
+		Body::new(Self {
+			inner,
+			tail: Vec::new(),
+			done: false,
+		})
+	}
+}
+
+impl http_body::Body for StreamDoneBody {
+	type Data = Bytes;
+	type Error = crate::http::Error;
+
+	fn poll_frame(
+		self: std::pin::Pin<&mut Self>,
+		cx: &mut std::task::Context<'_>,
+	) -> std::task::Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+		let this = self.project();
+		if *this.done {
+			return std::task::Poll::Ready(None);
+		}
+		let frame = match std::task::ready!(this.inner.poll_frame(cx)) {
+			Some(Ok(frame)) => {
+				if let Some(data) = frame.data_ref()
+					&& !data.is_empty()
+				{
+					this.tail.extend_from_slice(data);
+					let keep = Self::DONE_EVENT.len() + 8;
+					if this.tail.len() > keep {
+						this.tail.drain(..this.tail.len() - keep);
+					}
+				}
+				Some(Ok(frame))
+			},
+			None => {
+				*this.done = true;
+				let tail = this.tail.trim_ascii_end();
+				if tail.ends_with(Self::DONE_EVENT) {
+					None
+				} else {
+					debug!("upstream stream ended without [DONE]; appending it");
+					// Finish a final event that is missing its blank line before starting ours.
+					let sep: &[u8] = if tail.is_empty() || this.tail.ends_with(b"\n\n") {
+						b""
+					} else {
+						b"\n\n"
+					};
+					let done = [sep, Self::DONE_EVENT, b"\n\n"].concat();
+					Some(Ok(http_body::Frame::data(Bytes::from(done))))
+				}
+			},
+			other => other,
+		};
+		std::task::Poll::Ready(frame)
+	}
+
+	fn is_end_stream(&self) -> bool {
+		self.done
+	}
+}
+
+/// Error type recorded on the LLM log when the upstream resets the connection mid-stream.
+pub const CONNECTION_RESET_ERROR_TYPE: &str = "connection_reset";
+
+pin_project_lite::pin_project! {
+	// Replaces an upstream connection reset with a normalized error event and the input format's
+	// terminal marker, so clients see a well-formed end of stream rather than an abrupt cut.
+	struct StreamResetBody {
+		#[pin]
+		inner: Body,
+		log: AsyncLog<llm::LLMInfo>,
+		input_format: InputFormat,
+		done: bool,
+	}
+}
+
+impl StreamResetBody {
+	fn wrap(inner: Body, log: AsyncLog<llm::LLMInfo>, input_format: InputFormat) -> Body {
+		Body::new(Self {
+			inner,
+			log,
+			input_format,
+			done: false,
+		})
+	}
+}
+
+fn is_connection_reset(err: &crate::http::Error) -> bool {
+	let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+	while let Some(e) = source {
+		if let Some(io) = e.downcast_ref::<std::io::Error>()
+			&& matches!(
+				io.kind(),
+				std::io::ErrorKind::ConnectionReset | std::io::ErrorKind::ConnectionAborted
+			) {
+			return true;
+		}
+		if let Some(h) = e.downcast_ref::<hyper::Error>()
+			&& h.is_incomplete_message()
+		{
+			return true;
+		}
+		source = e.source();
+	}
+	false
+}
+
+fn stream_reset_events(input_format: InputFormat) -> Bytes {
+	use agent_llm::parse::encode_sse_event;
+	const MESSAGE: &str = "upstream connection reset mid-stream";
+	let json = |v: serde_json::Value| Bytes::from(v.to_string());
+	match input_format {
+		InputFormat::Messages => [
+			encode_sse_event(
+				"error",
+				json(serde_json::json!({
+					"type": "error",
+					"error": {"type": "api_error", "message": MESSAGE},
+				})),
+			),
+			encode_sse_event(
+				"message_stop",
+				json(serde_json::json!({"type": "message_stop"})),
+			),
+		]
+		.concat()
+		.into(),
+		// The Responses API has no `[DONE]` sentinel; its `error` event is terminal.
+		InputFormat::Responses => encode_sse_event(
+			"error",
+			json(serde_json::json!({
+				"type": "error",
+				"code": CONNECTION_RESET_ERROR_TYPE,
+				"message": MESSAGE,
+				"param": null,
+			})),
+		),
+		_ => [
+			encode_sse_event(
+				"",
+				json(serde_json::json!({
+					"error": {
+						"type": CONNECTION_RESET_ERROR_TYPE,
+						"code": CONNECTION_RESET_ERROR_TYPE,
+						"message": MESSAGE,
+					},
+				})),
+			),
+			encode_sse_event("", Bytes::from_static(b"[DONE]")),
+		]
+		.concat()
+		.into(),
+	}
+}
+
+impl http_body::Body for StreamResetBody {
+	type Data = Bytes;
+	type Error = crate::http::Error;
+
+	fn poll_frame(
+		self: std::pin::Pin<&mut Self>,
+		cx: &mut std::task::Context<'_>,
+	) -> std::task::Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+		let this = self.project();
+		if *this.done {
+			return std::task::Poll::Ready(None);
+		}
+		let frame = match std::task::ready!(this.inner.poll_frame(cx)) {
+			Some(Err(e)) if is_connection_reset(&e) => {
+				warn!("upstream connection reset mid-stream: {e}");
+				// Usage seen so far is already on the log; the translator records it when dropped.
+				this.log.non_atomic_mutate(|info| {
+					info.response.error_type = Some(strng::literal!(CONNECTION_RESET_ERROR_TYPE));
+				});
+				*this.done = true;
+				Some(Ok(http_body::Frame::data(stream_reset_events(
+					*this.input_format,
+				))))
+			},
+			None => {
+				*this.done = true;
+				None
+			},
+			other => other,
+		};
+		std::task::Poll::Ready(frame)
+	}
+
+	fn is_end_stream(&self) -> bool {
+		self.done
+	}
+}
@@ -5,7 +5,6 @@ use agent_core::strng;
 use bytes::Bytes;
 use futures_util::stream;
 use headers::{ContentEncoding, HeaderMapExt};
-use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
 use rand::seq::IndexedRandom;
 use serde_json::Value;
 
@@ -510,7 +509,7 @@ fn rewrite_path_model(path: &str, target: &str) -> Option<String> {
 		let (_, suffix) = after_publisher.split_once(':')?;
 		return Some(format!(
 			"{prefix}/publishers/{publisher}/models/{}:{suffix}",
-			encode_model_path_segment(target)
+			agent_llm::encode_path_segment(target)
 		));
 	}
 	for suffix in [
@@ -524,18 +523,13 @@ fn rewrite_path_model(path: &str, target: &str) -> Option<String> {
 		{
 			return Some(format!(
 				"{prefix}/model/{}{suffix}",
-				encode_model_path_segment(target)
+				agent_llm::encode_path_segment(target)
 			));
 		}
 	}
 	None
 }
 
-fn encode_model_path_segment(model: &str) -> String {
-	const MODEL_SEGMENT: &AsciiSet = &CONTROLS.add(b'/').add(b'%');
-	utf8_percent_encode(model, MODEL_SEGMENT).to_string()
-}
-
 fn multipart_boundary(req: &Request) -> Option<String> {
 	req
 		.headers()
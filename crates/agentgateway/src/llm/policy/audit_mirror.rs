@@ -0,0 +1,151 @@
+//! Asynchronous mirroring of LLM requests to an audit webhook.
+//!
+//! The webhook receives `POST /audit` with the model and the normalized messages, after the
+//! configured redaction rules are applied. Delivery never holds up the request: copies are sent in
+//! the background, and dropped once `maxPending` deliveries are outstanding.
+use ::http::HeaderValue;
+use ::http::header::CONTENT_TYPE;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::llm::SimpleChatCompletionMessage;
+use crate::llm::policy::{DEFAULT_TIMEOUT, RegexRules, with_timeout};
+use crate::proxy::httpproxy::PolicyClient;
+use crate::telemetry::metrics::{OutboundCallKind, OutboundCallSubtype};
+use crate::types::agent::SimpleBackendReference;
+use crate::*;
+
+const AUDIT_PATH: &str = "/audit";
+const DEFAULT_MAX_PENDING: usize = 1000;
+
+#[apply(schema!)]
+pub struct AuditMirror {
+	/// Backend that receives a copy of each request.
+	pub target: SimpleBackendReference,
+	/// Rules applied to message text before it is mirrored. Matches are masked, or with the
+	/// `reject` action the whole message is withheld.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub redact: Option<RegexRules>,
+	/// Maximum number of copies waiting for delivery across all requests using this policy. Requests
+	/// arriving while the limit is reached are not mirrored. Defaults to 1000.
+	#[serde(default)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<usize>"))]
+	pub max_pending: PendingLimit,
+}
+
+/// Caps the number of outstanding audit deliveries. Clones share the same limit.
+#[derive(Clone)]
+pub struct PendingLimit {
+	limit: usize,
+	semaphore: Arc<Semaphore>,
+}
+
+impl PendingLimit {
+	pub fn new(limit: usize) -> Self {
+		Self {
+			limit,
+			semaphore: Arc::new(Semaphore::new(limit)),
+		}
+	}
+
+	fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+		self.semaphore.clone().try_acquire_owned().ok()
+	}
+}
+
+impl Default for PendingLimit {
+	fn default() -> Self {
+		Self::new(DEFAULT_MAX_PENDING)
+	}
+}
+
+impl std::fmt::Debug for PendingLimit {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("PendingLimit").field(&self.limit).finish()
+	}
+}
+
+impl Serialize for PendingLimit {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.limit.serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for PendingLimit {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let limit = usize::deserialize(deserializer)?;
+		if limit == 0 {
+			return Err(serde::de::Error::custom(
+				"maxPending must be greater than 0",
+			));
+		}
+		Ok(Self::new(limit))
+	}
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct AuditRequest {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	model: Option<Strng>,
+	messages: Vec<SimpleChatCompletionMessage>,
+}
+
+impl AuditMirror {
+	/// mirror queues a redacted copy of the request for delivery. This never waits: when `maxPending`
+	/// deliveries are outstanding the copy is dropped.
+	pub fn mirror(
+		&self,
+		client: PolicyClient,
+		model: Option<Strng>,
+		messages: Vec<SimpleChatCompletionMessage>,
+	) {
+		let Some(permit) = self.max_pending.try_acquire() else {
+			debug!("audit mirror has too many pending deliveries, dropping request copy");
+			return;
+		};
+		let redact = self.redact.clone();
+		let target = self.target.clone();
+		tokio::spawn(async move {
+			let _permit = permit;
+			let messages = match &redact {
+				Some(rules) => messages
+					.into_iter()
+					.map(|m| match rules.redact(&m.content) {
+						Some(content) => SimpleChatCompletionMessage {
+							role: m.role,
+							content: content.into(),
+						},
+						None => m,
+					})
+					.collect(),
+				None => messages,
+			};
+			if let Err(e) = deliver(&client, &target, AuditRequest { model, messages }).await {
+				warn!("failed to mirror request to audit webhook: {e}");
+			}
+		});
+	}
+}
+
+async fn deliver(
+	client: &PolicyClient,
+	target: &SimpleBackendReference,
+	body: AuditRequest,
+) -> anyhow::Result<()> {
+	let body = serde_json::to_vec(&body)?;
+	let req = ::http::Request::builder()
+		.uri(AUDIT_PATH)
+		.method(http::Method::POST)
+		.header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+		.body(crate::http::Body::from(body))?;
+	let req = with_timeout(req, DEFAULT_TIMEOUT);
+	let res = client
+		.with_outbound(OutboundCallKind::Mirror, OutboundCallSubtype::Llm)
+		.call_reference(req, target)
+		.await?;
+	if !res.status().is_success() {
+		anyhow::bail!("audit webhook returned status {}", res.status());
+	}
+	Ok(())
+}
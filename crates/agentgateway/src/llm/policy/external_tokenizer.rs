@@ -0,0 +1,111 @@
+//! Token counting through an external service, for models without a known local tokenizer.
+//!
+//! The service receives `POST /count_tokens` with the model and the normalized messages, and
+//! responds with `{"input_tokens": <count>}`. Counts are cached by model and messages.
+use ::http::HeaderValue;
+use ::http::header::CONTENT_TYPE;
+use quick_cache::sync::Cache;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::llm::SimpleChatCompletionMessage;
+use crate::llm::policy::{DEFAULT_TIMEOUT, with_timeout};
+use crate::proxy::httpproxy::PolicyClient;
+use crate::telemetry::metrics::{OutboundCallKind, OutboundCallSubtype};
+use crate::types::agent::SimpleBackendReference;
+use crate::*;
+
+const COUNT_TOKENS_PATH: &str = "/count_tokens";
+const CACHE_ENTRIES: usize = 4096;
+
+#[apply(schema!)]
+pub struct ExternalTokenizer {
+	/// Backend that counts tokens for models without a known local tokenizer.
+	pub target: SimpleBackendReference,
+	/// How long to wait for the token count. Defaults to 10s.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub timeout: Option<Duration>,
+	#[serde(skip)]
+	pub cache: TokenCountCache,
+}
+
+/// Token counts by model and messages. Clones share the same cache.
+#[derive(Clone)]
+pub struct TokenCountCache(Arc<Cache<[u8; 32], u64>>);
+
+impl Default for TokenCountCache {
+	fn default() -> Self {
+		Self(Arc::new(Cache::new(CACHE_ENTRIES)))
+	}
+}
+
+impl std::fmt::Debug for TokenCountCache {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("TokenCountCache")
+			.field(&self.0.len())
+			.finish()
+	}
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+struct CountTokensRequest<'a> {
+	model: &'a str,
+	messages: &'a [SimpleChatCompletionMessage],
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct CountTokensResponse {
+	input_tokens: u64,
+}
+
+fn cache_key(model: &str, messages: &[SimpleChatCompletionMessage]) -> [u8; 32] {
+	let mut digest = Sha256::new();
+	for part in std::iter::once(model).chain(
+		messages
+			.iter()
+			.flat_map(|m| [m.role.as_str(), m.content.as_str()]),
+	) {
+		digest.update((part.len() as u64).to_be_bytes());
+		digest.update(part);
+	}
+	digest.finalize().into()
+}
+
+impl ExternalTokenizer {
+	/// count_tokens returns the input tokens of `messages` for `model`, as counted by the service.
+	pub async fn count_tokens(
+		&self,
+		client: &PolicyClient,
+		model: &str,
+		messages: &[SimpleChatCompletionMessage],
+	) -> anyhow::Result<u64> {
+		let key = cache_key(model, messages);
+		if let Some(count) = self.cache.0.get(&key) {
+			return Ok(count);
+		}
+		let body = serde_json::to_vec(&CountTokensRequest { model, messages })?;
+		let req = ::http::Request::builder()
+			.uri(COUNT_TOKENS_PATH)
+			.method(http::Method::POST)
+			.header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+			.body(crate::http::Body::from(body))?;
+		let req = with_timeout(req, self.timeout.unwrap_or(DEFAULT_TIMEOUT));
+		let res = client
+			.with_outbound(OutboundCallKind::Policy, OutboundCallSubtype::Tokenizer)
+			.call_reference(req, &self.target)
+			.await?;
+		if !res.status().is_success() {
+			anyhow::bail!("token counting service returned status {}", res.status());
+		}
+		let parsed: CountTokensResponse = json::from_response_body(res).await?;
+		self.cache.0.insert(key, parsed.input_tokens);
+		Ok(parsed.input_tokens)
+	}
+}
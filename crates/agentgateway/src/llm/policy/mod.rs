@@ -9,27 +9,34 @@ use crate::http::filters::{BackendRequestTimeout, HeaderModifier};
 use crate::http::jwt::Claims;
 use crate::http::{HeaderOrPseudo, Response, StatusCode, auth};
 use crate::llm::policy::webhook::{MaskActionBody, RequestAction, ResponseAction};
-use crate::llm::{AIError, RequestType, ResponseType};
+use crate::llm::{AIError, LLMRequestParams, RequestType, ResponseType};
 use crate::proxy::httpproxy::PolicyClient;
 use crate::telemetry::log::RequestLog;
 use crate::types::agent::{BackendTrafficPolicy, HeaderMatch, SimpleBackendReference};
 use crate::*;
 
-fn with_default_timeout(mut req: crate::http::Request) -> crate::http::Request {
-	req
-		.extensions_mut()
-		.insert(BackendRequestTimeout(Duration::from_secs(10)));
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn with_default_timeout(req: crate::http::Request) -> crate::http::Request {
+	with_timeout(req, DEFAULT_TIMEOUT)
+}
+
+fn with_timeout(mut req: crate::http::Request, timeout: Duration) -> crate::http::Request {
+	req.extensions_mut().insert(BackendRequestTimeout(timeout));
 	req
 }
 
 pub mod webhook;
 
+pub mod audit_mirror;
 mod azure_content_safety;
 mod bedrock_guardrails;
+pub mod external_tokenizer;
 mod google_model_armor;
 mod moderation;
 mod pii;
 pub mod streaming_guardrails;
+pub mod streaming_transform;
 #[cfg(test)]
 #[path = "tests.rs"]
 mod tests;
@@ -152,6 +159,296 @@ pub struct Policy {
 		schemars(with = "std::collections::HashMap<String, crate::llm::RouteType>")
 	)]
 	pub routes: SortedRoutes,
+	/// Overrides the provider `tokenize` setting for requests matching this policy.
+	/// If unset, the provider setting is used.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tokenize: Option<bool>,
+	/// Service used to count input tokens, when tokenization is enabled, for models without a
+	/// known local tokenizer. If unset, such models are counted locally with an approximate
+	/// tokenizer, or rejected if their tokenizer is not supported.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub external_tokenizer: Option<external_tokenizer::ExternalTokenizer>,
+	/// How a failure to count input tokens locally, such as for a model whose tokenizer is not
+	/// supported, is handled. Defaults to `strict`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tokenize_failure: Option<TokenizeFailure>,
+	/// Default sampling parameters applied only when the client did not set them.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub default_params: Option<DefaultParams>,
+	/// Upper bounds on client-provided request parameters; requests exceeding them are rejected.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub param_limits: Option<ParamLimits>,
+	/// Hard cap on output tokens sent to the provider. A larger or missing client
+	/// `max_tokens` / `max_output_tokens` is replaced with the cap, and the response carries an
+	/// `x-max-output-tokens-cap` header.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_output_tokens_cap: Option<u64>,
+	/// Send token usage as `x-usage-*` trailers on streaming responses to HTTP/2 (or newer) clients.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub usage_trailers: Option<bool>,
+	/// Rewrite tool-call ids in the conversation history into the format the provider accepts
+	/// (Mistral, Bedrock). The mapping is deterministic, so ids stay stable across turns.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub normalize_tool_call_ids: Option<bool>,
+	/// Substitute models keyed by requested model. When the provider reports the requested model
+	/// as unavailable (`404`), the request is retried once with the substitute.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	pub model_fallbacks: HashMap<Strng, Strng>,
+	/// Rewrite the text of each streamed response delta with a CEL expression.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub stream_transformation: Option<StreamTransformation>,
+	/// How to handle a successful chat completions response with an empty `choices` list.
+	/// By default it is passed through unchanged.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub empty_choices: Option<EmptyChoices>,
+	/// How to handle a chat completions request that sets the deprecated `functions` alongside
+	/// `tools`. By default `functions` is dropped in favor of `tools` and a warning is logged.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub deprecated_functions: Option<DeprecatedFunctions>,
+	/// How to handle a chat completions request that sets `logit_bias` for a provider other than
+	/// OpenAI or Azure, which do not support it. By default it is dropped and a warning is logged.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub unsupported_logit_bias: Option<UnsupportedLogitBias>,
+	/// Append a terminal `data: [DONE]` event to passthrough chat completions streams that end
+	/// without one. Some OpenAI-compatible providers omit it, leaving clients waiting for it.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub synthesize_stream_done: Option<bool>,
+	/// Backoff advertised to clients when the provider responds `429` without saying when to retry.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub default_retry_after: Option<DefaultRetryAfter>,
+	/// Maximum number of characters across the client's prompt messages. Larger requests are
+	/// rejected with a `413` before any guardrail or tokenization work is done.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_prompt_chars: Option<u64>,
+	/// Maximum nesting depth of arrays and objects in the request body. Deeper requests are
+	/// rejected with a `400` before they are parsed. Defaults to 128.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_json_depth: Option<usize>,
+	/// Request header, such as a conversation id, whose value pins requests to one provider of
+	/// the backend. Requests with the same value go to the same provider while it stays healthy,
+	/// which maximizes provider prompt cache hits.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub provider_affinity_header: Option<Strng>,
+	/// Marks the route as non-streaming and sets how a request with `stream: true` is handled.
+	/// By default such requests are streamed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub streaming_requests: Option<StreamingRequests>,
+	/// Echo the provider's own request id (such as OpenAI's `x-request-id` or Bedrock's
+	/// `x-amzn-requestid`) to the client as `x-upstream-request-id`. The id is always recorded in
+	/// the request log.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub echo_upstream_request_id: Option<bool>,
+	/// Forward a non-streaming response that exceeds the response buffer limit to the client as-is,
+	/// instead of failing the request. Usage cannot be parsed from such responses, so they are not
+	/// counted towards token rate limits or cost. Only applies where the response would not be
+	/// translated and no response prompt guard is configured.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub stream_oversized_responses: Option<bool>,
+	/// Share one provider call between identical deterministic requests (non-streaming, with a
+	/// temperature of 0) that are in flight at the same time. Each requester receives a copy of the
+	/// response.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub deduplicate_requests: Option<bool>,
+	/// Report the changes response guards made (guard, action and affected fields, never the
+	/// original values) to the client in an `x-guardrail-modifications` header. The changes are
+	/// always recorded in the request log. Streamed responses are masked after their headers are
+	/// sent, so their changes are only logged.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub guard_modifications_header: Option<bool>,
+	/// Tokens counted for each image in the request when `tokenize` estimates input tokens
+	/// locally. Defaults to 85, the cost of a low-detail OpenAI image.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub image_tokens: Option<u64>,
+	/// Models clients may request, checked after alias resolution. Entries may use `*` wildcards,
+	/// such as `gpt-4*`. Other models are rejected with a `403`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub allowed_models: Option<Vec<Strng>>,
+	/// Models clients may not request, checked after alias resolution. Entries may use `*`
+	/// wildcards. Takes precedence over `allowedModels`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub denied_models: Option<Vec<Strng>>,
+	/// Send a redacted copy of each request to an audit webhook. Copies are delivered in the
+	/// background and never delay or fail the request.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub audit_mirror: Option<audit_mirror::AuditMirror>,
+	/// Estimate token usage that a streaming chat completions response does not fully report, as
+	/// happens with some providers for tool calls. Missing input tokens fall back to the request
+	/// estimate and missing output tokens are approximated from the streamed output. Such requests
+	/// are logged with `gen_ai.usage.estimated`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub estimate_missing_stream_usage: Option<bool>,
+	/// Pass the provider's model deprecation notice (its `deprecation` and `sunset` response
+	/// headers) to the client as `x-model-deprecation`. Notices are always logged as a warning.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub model_deprecation_header: Option<bool>,
+}
+
+#[apply(schema!)]
+#[derive(Copy, Default, PartialEq, Eq)]
+pub enum TokenizeFailure {
+	/// Fail the request.
+	#[default]
+	Strict,
+	/// Log the error and send the request without an input token estimate.
+	Lenient,
+}
+
+#[apply(schema!)]
+#[derive(Copy, PartialEq, Eq)]
+pub enum StreamingRequests {
+	/// Stream the response as requested.
+	Honor,
+	/// Send the request without streaming and return the complete response, with an
+	/// `x-stream-downgraded: true` header.
+	Downgrade,
+	/// Reject the request with a `400` error.
+	Reject,
+}
+
+#[apply(schema!)]
+#[derive(Copy, PartialEq, Eq)]
+pub struct DefaultRetryAfter {
+	/// Delay sent as `retry-after-ms` on `429` responses that carry no `retry-after` or rate limit
+	/// reset header.
+	#[serde(with = "serde_dur")]
+	#[cfg_attr(feature = "schema", schemars(with = "String"))]
+	pub backoff: Duration,
+	/// Also evict the provider endpoint for the backoff, as if the provider had sent it.
+	/// By default the injected header is only seen by clients.
+	#[serde(default)]
+	pub outlier_detection: bool,
+}
+
+/// Marks a response whose `retry-after-ms` was injected from [DefaultRetryAfter] and must not
+/// drive outlier detection.
+#[derive(Clone, Copy, Debug)]
+pub struct DefaultedRetryAfter;
+
+impl DefaultRetryAfter {
+	pub fn apply(&self, resp: &mut Response) {
+		if resp.status() != StatusCode::TOO_MANY_REQUESTS
+			|| http::outlierdetection::retry_after(resp.status(), resp.headers()).is_some()
+		{
+			return;
+		}
+		let ms = u64::try_from(self.backoff.as_millis()).unwrap_or(u64::MAX);
+		resp.headers_mut().insert(
+			http::x_headers::RETRY_AFTER_MS,
+			::http::HeaderValue::from(ms),
+		);
+		if !self.outlier_detection {
+			resp.extensions_mut().insert(DefaultedRetryAfter);
+		}
+	}
+}
+
+#[apply(schema!)]
+#[derive(Copy, PartialEq, Eq)]
+pub enum EmptyChoices {
+	/// Replace the response with a `502` structured error.
+	Error,
+	/// Retry the request once; if the retry also has no choices, return a `502` structured error.
+	Retry,
+}
+
+#[apply(schema!)]
+#[derive(Copy, Default, PartialEq, Eq)]
+pub enum DeprecatedFunctions {
+	/// Drop `functions` (and `function_call`) and send only `tools`.
+	#[default]
+	Drop,
+	/// Reject the request with a `400` error.
+	Error,
+}
+
+#[apply(schema!)]
+#[derive(Copy, Default, PartialEq, Eq)]
+pub enum UnsupportedLogitBias {
+	/// Drop `logit_bias` and send the rest of the request.
+	#[default]
+	Drop,
+	/// Reject the request with a `400` error.
+	Error,
+}
+
+#[apply(schema!)]
+pub struct StreamTransformation {
+	/// Expression evaluated against each streamed text delta, available as `llmDelta`.
+	/// It must return a string, which is sent in place of the delta.
+	pub expression: Arc<cel::Expression>,
+	/// Maximum bytes of a trailing partial word held back and joined with the next delta.
+	/// Defaults to 256.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_holdback: Option<usize>,
+}
+
+#[apply(schema!)]
+#[derive(Default)]
+pub struct DefaultParams {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub temperature: Option<f64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub top_p: Option<f64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub frequency_penalty: Option<f64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub presence_penalty: Option<f64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub seed: Option<i64>,
+	/// Output token limit (`max_tokens`, `max_completion_tokens`, or `max_output_tokens`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_tokens: Option<u64>,
+}
+
+impl DefaultParams {
+	fn to_request_params(&self) -> LLMRequestParams {
+		LLMRequestParams {
+			temperature: self.temperature,
+			top_p: self.top_p,
+			frequency_penalty: self.frequency_penalty,
+			presence_penalty: self.presence_penalty,
+			seed: self.seed,
+			max_tokens: self.max_tokens,
+			..Default::default()
+		}
+	}
+}
+
+#[apply(schema!)]
+#[derive(Default)]
+pub struct ParamLimits {
+	/// Maximum allowed output token limit (`max_tokens`, `max_completion_tokens`, or `max_output_tokens`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_tokens: Option<u64>,
+	/// Maximum allowed `temperature`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_temperature: Option<f64>,
+	/// Maximum allowed `top_p`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_top_p: Option<f64>,
+}
+
+impl ParamLimits {
+	/// Returns the name of the first parameter exceeding its bound, along with a description.
+	pub fn validate(&self, params: &LLMRequestParams) -> Option<(&'static str, String)> {
+		fn check<T: PartialOrd + std::fmt::Display>(
+			name: &'static str,
+			value: Option<T>,
+			limit: Option<T>,
+		) -> Option<(&'static str, String)> {
+			let (value, limit) = (value?, limit?);
+			if value <= limit {
+				return None;
+			}
+			Some((
+				name,
+				format!("{name} {value} exceeds the maximum of {limit}"),
+			))
+		}
+		check("max_tokens", params.max_tokens, self.max_tokens)
+			.or_else(|| check("temperature", params.temperature, self.max_temperature))
+			.or_else(|| check("top_p", params.top_p, self.max_top_p))
+	}
 }
 
 fn webhook_header_expressions(g: &PromptGuard) -> impl Iterator<Item = &cel::Expression> {
@@ -182,6 +479,13 @@ impl crate::store::HasExpressions for Policy {
 					.iter()
 					.flat_map(webhook_header_expressions),
 			)
+			.chain(self.prompts.iter().filter_map(|p| p.bypass.as_deref()))
+			.chain(
+				self
+					.stream_transformation
+					.iter()
+					.map(|t| t.expression.as_ref()),
+			)
 	}
 }
 
@@ -233,6 +537,21 @@ pub struct PromptEnrichment {
 	/// Messages prepended to the beginning of each chat request.
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub prepend: Vec<crate::llm::SimpleChatCompletionMessage>,
+	/// CEL expression evaluated against the original request; when it is `true`, enrichment is
+	/// skipped. Combine a request header with an authentication check (for example
+	/// `request.headers["x-skip-enrichment"] == "true" && jwt.sub == "calibration"`) so only
+	/// authorized callers can bypass it. Evaluation errors apply enrichment.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub bypass: Option<Arc<cel::Expression>>,
+}
+
+impl PromptEnrichment {
+	fn bypassed(&self, original: Option<&cel::RequestSnapshot>) -> bool {
+		self
+			.bypass
+			.as_ref()
+			.is_some_and(|expr| cel::Executor::new_request_snapshot(original).eval_bool(expr))
+	}
 }
 
 #[apply(schema!)]
@@ -292,7 +611,8 @@ enum GuardrailOutcome {
 /// `GuardedSseBody` future.
 #[async_trait::async_trait]
 pub trait StreamingEvaluator: Send {
-	/// Evaluate a text window. Returns `Some(Blocked)` if the content should be blocked.
+	/// Evaluate a text window. Returns `Some(Blocked)` if the content should be blocked, or
+	/// `Some(Masked)` with the rewritten window if parts of it should be masked.
 	async fn evaluate(&mut self, window: &str) -> anyhow::Result<Option<StreamingGuardrailOutcome>>;
 
 	/// Returns the failure mode to apply when `evaluate` returns an error.
@@ -300,12 +620,19 @@ pub trait StreamingEvaluator: Send {
 	fn failure_mode(&self) -> FailureMode {
 		FailureMode::FailOpen
 	}
+
+	/// Kind of guard this evaluates, as recorded in guard modifications.
+	fn name(&self) -> &'static str {
+		"unknown"
+	}
 }
 
 /// Outcome returned by a `StreamingEvaluator`.
 pub enum StreamingGuardrailOutcome {
 	/// Content was blocked; include the rejection body to encode for the stream.
 	Blocked(Bytes),
+	/// Content was masked; include the masked window text.
+	Masked(String),
 }
 
 struct TextResponse {
@@ -522,13 +849,7 @@ impl PromptGuard {
 				let body = rejected.into_body().collect().await?.to_bytes();
 				Ok(Some(StreamingGuardrailOutcome::Blocked(body)))
 			},
-			GuardrailOutcome::Masked => {
-				debug_assert!(
-					false,
-					"streaming response guard unexpectedly returned Masked; streaming masking is not supported"
-				);
-				Ok(None)
-			},
+			GuardrailOutcome::Masked => Ok(Some(StreamingGuardrailOutcome::Masked(resp.content))),
 			GuardrailOutcome::None => Ok(None),
 			GuardrailOutcome::FailOpen => Ok(None),
 		}
@@ -536,6 +857,17 @@ impl PromptGuard {
 }
 
 impl Policy {
+	/// Rejects default params that exceed the policy's own limits. Limits are checked after
+	/// defaults are filled in, so such a default would fail every request that leaves it unset.
+	pub fn validate_default_params(&self) -> anyhow::Result<()> {
+		if let (Some(defaults), Some(limits)) = (&self.default_params, &self.param_limits)
+			&& let Some((_, message)) = limits.validate(&defaults.to_request_params())
+		{
+			anyhow::bail!("defaultParams exceed paramLimits: {message}");
+		}
+		Ok(())
+	}
+
 	/// Returns `true` if any prompt guard has response guards that require streaming evaluation.
 	pub fn has_streaming_response_guards(&self) -> bool {
 		self
@@ -580,6 +912,30 @@ impl Policy {
 		);
 	}
 
+	/// Returns the substitute for `model` if the provider response shows it is unavailable.
+	pub fn model_fallback(&self, model: &str, status: ::http::StatusCode) -> Option<&Strng> {
+		if status != ::http::StatusCode::NOT_FOUND {
+			return None;
+		}
+		self
+			.model_fallbacks
+			.get(model)
+			.filter(|sub| sub.as_str() != model)
+	}
+
+	/// Whether `model` passes `allowed_models` and `denied_models`.
+	pub fn is_model_allowed(&self, model: &str) -> bool {
+		let matches = |patterns: &[Strng]| {
+			patterns
+				.iter()
+				.any(|p| crate::http::cors::wildcard_match(p, model))
+		};
+		if self.denied_models.as_deref().is_some_and(matches) {
+			return false;
+		}
+		self.allowed_models.as_deref().is_none_or(matches)
+	}
+
 	pub fn resolve_model_alias(&self, model: &str) -> Option<&Strng> {
 		// Fast path: exact match in HashMap (O(1))
 		if let Some(target) = self.model_aliases.get(model) {
@@ -602,8 +958,22 @@ impl Policy {
 		None
 	}
 
-	pub fn apply_prompt_enrichment(&self, chat: &mut dyn RequestType) {
+	pub fn apply_default_params(&self, chat: &mut dyn RequestType) {
+		if let Some(defaults) = &self.default_params {
+			chat.apply_default_params(&defaults.to_request_params());
+		}
+	}
+
+	pub fn apply_prompt_enrichment(
+		&self,
+		chat: &mut dyn RequestType,
+		original: Option<&cel::RequestSnapshot>,
+	) {
 		if let Some(prompts) = &self.prompts {
+			if prompts.bypassed(original) {
+				tracing::debug!("prompt enrichment bypassed for request");
+				return;
+			}
 			if !prompts.prepend.is_empty() {
 				chat.prepend_prompts(prompts.prepend.clone());
 			}
@@ -1304,8 +1674,10 @@ impl Policy {
 		http_headers: &HeaderMap,
 		guards: &Vec<ResponseGuard>,
 		original: Option<&cel::RequestSnapshot>,
-	) -> anyhow::Result<Option<Response>> {
-		for g in guards {
+		modifications: &mut Vec<crate::llm::GuardModification>,
+	) -> anyhow::Result<Option<(Response, &'static str)>> {
+		for (index, g) in guards.iter().enumerate() {
+			let before = resp.to_webhook_choices();
 			match Self::apply_single_response_guard(g, resp, http_headers, client, original).await? {
 				GuardrailOutcome::Rejected(res) => {
 					Self::record_guardrail_trip(
@@ -1313,13 +1685,21 @@ impl Policy {
 						crate::telemetry::metrics::GuardrailPhase::Response,
 						crate::telemetry::metrics::GuardrailAction::Reject,
 					);
-					return Ok(Some(res));
+					return Ok(Some((res, g.kind.name())));
 				},
 				GuardrailOutcome::Masked => {
+					let action = crate::telemetry::metrics::GuardrailAction::Mask;
 					Self::record_guardrail_trip(
 						client,
 						crate::telemetry::metrics::GuardrailPhase::Response,
-						crate::telemetry::metrics::GuardrailAction::Mask,
+						action,
+					);
+					record_guard_modification(
+						modifications,
+						index,
+						g.kind.name(),
+						action,
+						changed_choice_fields(&before, &resp.to_webhook_choices()),
 					);
 				},
 				GuardrailOutcome::None => {
@@ -1418,6 +1798,62 @@ pub enum RequestGuardKind {
 	AzureContentSafety(AzureContentSafety),
 }
 
+/// Records that the response guard at `index` changed `fields`, merging them into an earlier
+/// record for the same guard and action. Streamed responses are guarded window by window, so one
+/// guard can modify a response many times.
+pub(crate) fn record_guard_modification(
+	modifications: &mut Vec<crate::llm::GuardModification>,
+	index: usize,
+	guard: &str,
+	action: crate::telemetry::metrics::GuardrailAction,
+	fields: Vec<Strng>,
+) {
+	let action = action.as_str();
+	match modifications
+		.iter_mut()
+		.find(|m| m.index == index && m.action.as_str() == action)
+	{
+		Some(existing) => {
+			for field in fields {
+				if !existing.fields.contains(&field) {
+					existing.fields.push(field);
+				}
+			}
+		},
+		None => modifications.push(crate::llm::GuardModification {
+			index,
+			guard: strng::new(guard),
+			action: strng::new(action),
+			fields,
+		}),
+	}
+}
+
+/// Names of the response fields that differ between two snapshots of the response choices.
+fn changed_choice_fields(
+	before: &[webhook::ResponseChoice],
+	after: &[webhook::ResponseChoice],
+) -> Vec<Strng> {
+	(0..before.len().max(after.len()))
+		.filter(|&i| {
+			before.get(i).map(|c| &c.message.content) != after.get(i).map(|c| &c.message.content)
+		})
+		.map(|i| strng::format!("choices[{i}].message.content"))
+		.collect()
+}
+
+impl ResponseGuardKind {
+	fn name(&self) -> &'static str {
+		match self {
+			ResponseGuardKind::Regex(_) => "regex",
+			ResponseGuardKind::Webhook(_) => "webhook",
+			ResponseGuardKind::BedrockGuardrails(_) => "bedrockGuardrails",
+			ResponseGuardKind::GoogleModelArmor(_) => "googleModelArmor",
+			ResponseGuardKind::AzureContentSafety(_) => "azureContentSafety",
+		}
+	}
+}
+
 impl RequestGuardKind {
 	fn name(&self) -> &'static str {
 		match self {
@@ -1457,6 +1893,18 @@ pub enum RegexRule {
 	},
 }
 
+impl RegexRules {
+	/// Applies the rules to content leaving the gateway outside the request path, such as exported
+	/// prompt records. Returns `None` when nothing matched; the `reject` action withholds the whole
+	/// content.
+	pub fn redact(&self, content: &str) -> Option<String> {
+		match Policy::apply_prompt_guard_regex(content, self)? {
+			RegexResult::Mask(masked) => Some(masked),
+			RegexResult::Reject => Some("<redacted>".to_string()),
+		}
+	}
+}
+
 impl RequestRejection {
 	pub fn as_response(&self) -> Response {
 		let mut response = ::http::response::Builder::new()
@@ -1542,6 +1990,20 @@ pub struct Webhook {
 	/// Defaults to `failClosed`.
 	#[serde(default, skip_serializing_if = "crate::serdes::is_default")]
 	pub failure_mode: FailureMode,
+	/// Maximum number of concurrent calls to the webhook across all requests using this policy.
+	/// Calls past the limit wait in arrival order. Unlimited if unset.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<usize>"))]
+	pub max_concurrency: Option<webhook::ConcurrencyLimit>,
+	/// How long to wait for the webhook to respond before treating the call as failed.
+	/// Defaults to 10s.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub timeout: Option<Duration>,
 }
 
 #[apply(schema!)]
@@ -13,6 +13,11 @@
 //!    **Block** → the held (never-forwarded) frames are discarded and a synthetic
 //!    SSE error event is emitted. Content flushed by earlier passing windows
 //!    cannot be retracted — an accepted accuracy/latency tradeoff.
+//!    **Mask** → the text deltas of the held frames are rewritten so the client
+//!    receives the masked batch, then they are flushed. If the mask reaches into
+//!    the overlap tail, which was already sent, the stream is blocked instead.
+//!
+//! Frames are held at SSE event boundaries so held events can be rewritten whole.
 //!
 //! This is not 100% accurate: a guard that needs full-response context, or a
 //! pattern spanning more than the overlap window, can be missed.
@@ -24,6 +29,8 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use ::http::HeaderMap;
+use agent_core::strng;
+use agent_core::strng::Strng;
 use bytes::Bytes;
 use http_body::Frame;
 use pin_project_lite::pin_project;
@@ -31,10 +38,13 @@ use tokio_sse_codec::{Event, Frame as SseFrame, SseDecoder};
 use tokio_util::codec::Decoder;
 use tracing::warn;
 
+use super::streaming_transform::{encode, parse_event, text_field, text_slot};
 use super::{
 	FailureMode, ResponseGuard, ResponseGuardKind, StreamingEvaluator, StreamingGuardrailOutcome,
+	record_guard_modification,
 };
 use crate::cel::RequestSnapshot;
+use crate::llm::LLMInfo;
 use crate::llm::policy::PromptGuard;
 use crate::proxy::httpproxy::PolicyClient;
 
@@ -59,22 +69,40 @@ pub fn tail_chars(s: &str, max_bytes: usize) -> &str {
 	&s[start..]
 }
 
-/// Run all evaluators against a window. Returns the rejection body if any evaluator blocked.
-pub async fn evaluate_window(
+/// Combined outcome of all evaluators for one window.
+pub enum WindowOutcome {
+	Pass,
+	/// The fully masked window, and the positions of the evaluators that masked it.
+	Masked(String, Vec<usize>),
+	Blocked(Bytes),
+}
+
+/// Run all evaluators against a window. Each evaluator sees the window as masked by the ones
+/// before it.
+pub async fn evaluate_window_with_masking(
 	evaluators: &mut [Box<dyn StreamingEvaluator>],
 	window: &str,
-) -> Option<Bytes> {
-	for ev in evaluators.iter_mut() {
-		match ev.evaluate(window).await {
+) -> WindowOutcome {
+	let mut masked: Option<String> = None;
+	let mut masked_by = Vec::new();
+	for (index, ev) in evaluators.iter_mut().enumerate() {
+		match ev.evaluate(masked.as_deref().unwrap_or(window)).await {
 			Ok(Some(StreamingGuardrailOutcome::Blocked(body))) => {
 				tracing::debug!("streaming guardrail blocked response window");
-				return Some(body);
+				return WindowOutcome::Blocked(body);
+			},
+			Ok(Some(StreamingGuardrailOutcome::Masked(text))) => {
+				tracing::debug!("streaming guardrail masked response window");
+				masked = Some(text);
+				masked_by.push(index);
 			},
 			Ok(None) => {},
 			Err(e) => match ev.failure_mode() {
 				FailureMode::FailClosed => {
 					warn!("streaming guardrail error, failing closed: {e}");
-					return Some(Bytes::from_static(b"Content blocked by guardrail policy"));
+					return WindowOutcome::Blocked(Bytes::from_static(
+						b"Content blocked by guardrail policy",
+					));
 				},
 				FailureMode::FailOpen => {
 					warn!("streaming guardrail error, failing open: {e}");
@@ -82,9 +110,67 @@ pub async fn evaluate_window(
 			},
 		}
 	}
+	match masked {
+		Some(text) => WindowOutcome::Masked(text, masked_by),
+		None => WindowOutcome::Pass,
+	}
+}
+
+/// Run all evaluators against a window. Returns the rejection body if any evaluator blocked.
+/// Masks are not applied, for callers that cannot rewrite the held content.
+pub async fn evaluate_window(
+	evaluators: &mut [Box<dyn StreamingEvaluator>],
+	window: &str,
+) -> Option<Bytes> {
+	match evaluate_window_with_masking(evaluators, window).await {
+		WindowOutcome::Blocked(body) => Some(body),
+		WindowOutcome::Pass | WindowOutcome::Masked(..) => None,
+	}
+}
+
+/// Length of the first complete SSE event in `buf`, including the blank line that ends it. Lines
+/// may end in `\n` or `\r\n`.
+fn event_len(buf: &[u8]) -> Option<usize> {
+	let mut line_start = 0;
+	for (i, b) in buf.iter().enumerate() {
+		if *b != b'\n' {
+			continue;
+		}
+		if matches!(&buf[line_start..i], b"" | b"\r") {
+			return Some(i + 1);
+		}
+		line_start = i + 1;
+	}
 	None
 }
 
+/// Rewrite the text deltas of `frames` to `masked`. The first text event carries all of it and
+/// the others are emptied, so the client receives the masked batch exactly once. Returns the names
+/// of the rewritten fields.
+fn mask_held_frames(frames: &mut [Bytes], masked: &str) -> Vec<Strng> {
+	let mut masked = Some(masked);
+	let mut fields = Vec::new();
+	for frame in frames.iter_mut() {
+		let (name, data) = parse_event(frame);
+		let Some(mut json) = data
+			.filter(|d| d != "[DONE]")
+			.and_then(|d| serde_json::from_str::<serde_json::Value>(&d).ok())
+		else {
+			continue;
+		};
+		let Some(slot) = text_slot(&mut json) else {
+			continue;
+		};
+		*slot = serde_json::Value::String(masked.take().unwrap_or_default().to_string());
+		let field = text_field(&json);
+		if !fields.iter().any(|f| f.as_str() == field) {
+			fields.push(strng::new(field));
+		}
+		*frame = encode(&name, &json);
+	}
+	fields
+}
+
 // ---------------------------------------------------------------------------
 // Factory
 // ---------------------------------------------------------------------------
@@ -120,6 +206,10 @@ impl StreamingEvaluator for ResponseGuardEvaluator {
 		}
 	}
 
+	fn name(&self) -> &'static str {
+		self.guard.kind.name()
+	}
+
 	async fn evaluate(&mut self, window: &str) -> anyhow::Result<Option<StreamingGuardrailOutcome>> {
 		PromptGuard::evaluate_streaming_response_window(
 			&self.guard,
@@ -152,15 +242,20 @@ fn guardrail_blocked_sse_bytes(body: Bytes) -> Bytes {
 }
 
 type EvalFuture =
-	Pin<Box<dyn Future<Output = (Vec<Box<dyn StreamingEvaluator>>, Option<Bytes>)> + Send + 'static>>;
+	Pin<Box<dyn Future<Output = (Vec<Box<dyn StreamingEvaluator>>, WindowOutcome)> + Send + 'static>>;
 
 /// Internal state machine for `GuardedSseBody`.
 enum GuardedBodyState {
 	/// Reading from upstream, holding frames until the eval threshold is reached.
 	Buffering,
 	/// Evaluating the current window asynchronously. `eof` records whether the
-	/// upstream is already exhausted (this is the final evaluation).
-	Evaluating { fut: EvalFuture, eof: bool },
+	/// upstream is already exhausted (this is the final evaluation). `overlap` is
+	/// the already-sent text the window starts with.
+	Evaluating {
+		fut: EvalFuture,
+		eof: bool,
+		overlap: String,
+	},
 	/// Yield held frames in order, then return to `Buffering` (or `Done` if `eof`).
 	Flushing { queue: VecDeque<Bytes>, eof: bool },
 	/// Send the synthetic error event then close.
@@ -179,6 +274,8 @@ pin_project! {
 		buffer_limit: usize,
 		held_frames: Vec<Bytes>,
 		held_bytes: usize,
+		// Raw bytes of a not yet terminated event, held until it completes.
+		partial: bytes::BytesMut,
 		pending_text: String,
 		overlap_tail: String,
 		sse_decoder: SseDecoder<Bytes>,
@@ -187,6 +284,8 @@ pin_project! {
 		// Owns the rate-limit logger; dropped only when this body is fully consumed,
 		// so telemetry is recorded at the correct time.
 		logger: Option<crate::llm::AmendOnDrop>,
+		// Where applied masks are recorded.
+		log: Option<crate::telemetry::log::AsyncLog<LLMInfo>>,
 	}
 }
 
@@ -197,6 +296,7 @@ impl GuardedSseBody {
 	/// * `evaluators` – one evaluator per configured response guard.
 	/// * `buffer_limit` – max bytes of held frames; reaching it forces an evaluation.
 	/// * `logger` – rate-limit logger that must outlive the streaming response.
+	/// * `log` – request log that applied masks are recorded in.
 	// We do actually return Self; just wrapped in an http_body::Body. The annotation silences a false positive from clippy about that.
 	#[allow(clippy::new_ret_no_self)]
 	pub fn new(
@@ -204,12 +304,14 @@ impl GuardedSseBody {
 		evaluators: Vec<Box<dyn StreamingEvaluator>>,
 		buffer_limit: usize,
 		logger: Option<crate::llm::AmendOnDrop>,
+		log: Option<crate::telemetry::log::AsyncLog<LLMInfo>>,
 	) -> crate::http::Body {
 		Self::with_threshold(
 			inner,
 			evaluators,
 			buffer_limit,
 			logger,
+			log,
 			DEFAULT_EVAL_THRESHOLD,
 		)
 	}
@@ -220,6 +322,7 @@ impl GuardedSseBody {
 		evaluators: Vec<Box<dyn StreamingEvaluator>>,
 		buffer_limit: usize,
 		logger: Option<crate::llm::AmendOnDrop>,
+		log: Option<crate::telemetry::log::AsyncLog<LLMInfo>>,
 		eval_threshold: usize,
 	) -> crate::http::Body {
 		crate::http::Body::new(Self {
@@ -229,12 +332,14 @@ impl GuardedSseBody {
 			buffer_limit,
 			held_frames: Vec::new(),
 			held_bytes: 0,
+			partial: bytes::BytesMut::new(),
 			pending_text: String::new(),
 			overlap_tail: String::new(),
 			sse_decoder: SseDecoder::with_max_size(buffer_limit),
 			decode_buffer: bytes::BytesMut::new(),
 			state: GuardedBodyState::Buffering,
 			logger,
+			log,
 		})
 	}
 
@@ -318,17 +423,50 @@ impl http_body::Body for GuardedSseBody {
 				// -----------------------------------------------------------------
 				// Evaluating: poll the guardrail future for the current window.
 				// -----------------------------------------------------------------
-				GuardedBodyState::Evaluating { fut, eof } => match fut.as_mut().poll(cx) {
+				GuardedBodyState::Evaluating { fut, eof, overlap } => match fut.as_mut().poll(cx) {
 					Poll::Pending => return Poll::Pending,
-					Poll::Ready((evaluators, blocked_body)) => {
+					Poll::Ready((evaluators, outcome)) => {
 						*this.evaluators = evaluators;
-						if let Some(body) = blocked_body {
+						let outcome = match outcome {
+							WindowOutcome::Masked(window, masked_by) => {
+								match window.strip_prefix(overlap.as_str()) {
+									Some(batch) => {
+										let fields = mask_held_frames(this.held_frames, batch);
+										if let Some(log) = this.log.as_ref() {
+											let evaluators = &*this.evaluators;
+											log.non_atomic_mutate(|l| {
+												for &index in &masked_by {
+													record_guard_modification(
+														&mut l.response.guard_modifications,
+														index,
+														evaluators[index].name(),
+														crate::telemetry::metrics::GuardrailAction::Mask,
+														fields.clone(),
+													);
+												}
+											});
+										}
+										// Later windows must overlap with what the client actually received.
+										if !*eof {
+											*this.overlap_tail = tail_chars(&window, OVERLAP_BYTES).to_string();
+										}
+										WindowOutcome::Pass
+									},
+									// The mask reaches into text that was already sent and cannot be retracted.
+									None => WindowOutcome::Blocked(Bytes::from_static(
+										b"Content blocked by guardrail policy",
+									)),
+								}
+							},
+							outcome => outcome,
+						};
+						if let WindowOutcome::Blocked(body) = outcome {
 							this.held_frames.clear();
 							*this.held_bytes = 0;
 							*this.state = GuardedBodyState::Blocked(body);
 						} else {
 							let queue: VecDeque<Bytes> = this.held_frames.drain(..).collect();
-							*this.held_bytes = 0;
+							*this.held_bytes = this.partial.len();
 							*this.state = GuardedBodyState::Flushing { queue, eof: *eof };
 						}
 					},
@@ -347,7 +485,14 @@ impl http_body::Body for GuardedSseBody {
 
 							let raw = data.clone();
 							*this.held_bytes += raw.len();
-							this.held_frames.push(raw.clone());
+							this.partial.extend_from_slice(&raw);
+							while let Some(len) = event_len(this.partial) {
+								this.held_frames.push(this.partial.split_to(len).freeze());
+							}
+							if this.partial.len() >= *this.buffer_limit {
+								// An event this large cannot be rewritten; hold it as-is.
+								this.held_frames.push(this.partial.split().freeze());
+							}
 
 							this.decode_buffer.extend_from_slice(&raw);
 							loop {
@@ -376,19 +521,26 @@ impl http_body::Body for GuardedSseBody {
 								// In that case, flush the buffer as-is without evaluation, to avoid stalling on unprocessable content.
 								if this.pending_text.is_empty() {
 									let queue: VecDeque<Bytes> = this.held_frames.drain(..).collect();
-									*this.held_bytes = 0;
+									*this.held_bytes = this.partial.len();
 									*this.state = GuardedBodyState::Flushing { queue, eof: false };
 									continue;
 								}
 								let batch = std::mem::take(this.pending_text);
 								let window = format!("{}{}", this.overlap_tail, batch);
-								*this.overlap_tail = tail_chars(&window, OVERLAP_BYTES).to_string();
+								let overlap = std::mem::replace(
+									this.overlap_tail,
+									tail_chars(&window, OVERLAP_BYTES).to_string(),
+								);
 								let mut evaluators = std::mem::take(this.evaluators);
 								let fut: EvalFuture = Box::pin(async move {
-									let blocked_body = evaluate_window(&mut evaluators, &window).await;
-									(evaluators, blocked_body)
+									let outcome = evaluate_window_with_masking(&mut evaluators, &window).await;
+									(evaluators, outcome)
 								});
-								*this.state = GuardedBodyState::Evaluating { fut, eof: false };
+								*this.state = GuardedBodyState::Evaluating {
+									fut,
+									eof: false,
+									overlap,
+								};
 							}
 						},
 						Poll::Ready(None) => {
@@ -408,6 +560,9 @@ impl http_body::Body for GuardedSseBody {
 								}
 							}
 
+							if !this.partial.is_empty() {
+								this.held_frames.push(this.partial.split().freeze());
+							}
 							if this.pending_text.is_empty() {
 								let queue: VecDeque<Bytes> = this.held_frames.drain(..).collect();
 								*this.held_bytes = 0;
@@ -417,13 +572,17 @@ impl http_body::Body for GuardedSseBody {
 
 							let batch = std::mem::take(this.pending_text);
 							let window = format!("{}{}", this.overlap_tail, batch);
-							this.overlap_tail.clear();
+							let overlap = std::mem::take(this.overlap_tail);
 							let mut evaluators = std::mem::take(this.evaluators);
 							let fut: EvalFuture = Box::pin(async move {
-								let blocked_body = evaluate_window(&mut evaluators, &window).await;
-								(evaluators, blocked_body)
+								let outcome = evaluate_window_with_masking(&mut evaluators, &window).await;
+								(evaluators, outcome)
 							});
-							*this.state = GuardedBodyState::Evaluating { fut, eof: true };
+							*this.state = GuardedBodyState::Evaluating {
+								fut,
+								eof: true,
+								overlap,
+							};
 						},
 					}
 				},
@@ -483,6 +642,31 @@ mod tests {
 		}
 	}
 
+	struct MaskEvaluator {
+		pattern: regex::Regex,
+	}
+
+	#[async_trait::async_trait]
+	impl StreamingEvaluator for MaskEvaluator {
+		async fn evaluate(
+			&mut self,
+			window: &str,
+		) -> anyhow::Result<Option<StreamingGuardrailOutcome>> {
+			if !self.pattern.is_match(window) {
+				return Ok(None);
+			}
+			Ok(Some(StreamingGuardrailOutcome::Masked(
+				self.pattern.replace_all(window, "<masked>").into_owned(),
+			)))
+		}
+	}
+
+	fn mask_evaluator(pattern: &str) -> MaskEvaluator {
+		MaskEvaluator {
+			pattern: regex::Regex::new(pattern).unwrap(),
+		}
+	}
+
 	struct ErrorEvaluator {
 		mode: crate::llm::policy::FailureMode,
 	}
@@ -530,7 +714,7 @@ mod tests {
 		let done = sse_bytes("[DONE]");
 		let body = make_body(vec![chunk.clone(), done.clone()]);
 
-		let guarded = GuardedSseBody::new(body, vec![Box::new(PassEvaluator)], 1024 * 1024, None);
+		let guarded = GuardedSseBody::new(body, vec![Box::new(PassEvaluator)], 1024 * 1024, None, None);
 
 		let bytes = guarded.collect().await.unwrap().to_bytes();
 		assert!(bytes.starts_with(&chunk));
@@ -542,7 +726,13 @@ mod tests {
 		let done = sse_bytes("[DONE]");
 		let body = make_body(vec![chunk, done]);
 
-		let guarded = GuardedSseBody::new(body, vec![Box::new(BlockEvaluator)], 1024 * 1024, None);
+		let guarded = GuardedSseBody::new(
+			body,
+			vec![Box::new(BlockEvaluator)],
+			1024 * 1024,
+			None,
+			None,
+		);
 
 		let bytes = guarded.collect().await.unwrap().to_bytes();
 		assert!(contains(&bytes, b"guardrail_blocked"));
@@ -566,6 +756,7 @@ mod tests {
 			vec![Box::new(pattern_evaluator("SSN"))],
 			1024 * 1024,
 			None,
+			None,
 		);
 
 		let bytes = guarded.collect().await.unwrap().to_bytes();
@@ -584,6 +775,7 @@ mod tests {
 			vec![Box::new(pattern_evaluator("SSN"))],
 			1024 * 1024,
 			None,
+			None,
 		);
 
 		let bytes = guarded.collect().await.unwrap().to_bytes();
@@ -603,6 +795,7 @@ mod tests {
 			vec![Box::new(pattern_evaluator("credit card"))],
 			1024 * 1024,
 			None,
+			None,
 		);
 
 		let bytes = guarded.collect().await.unwrap().to_bytes();
@@ -622,6 +815,7 @@ mod tests {
 			vec![Box::new(pattern_evaluator("forbidden"))],
 			1024 * 1024,
 			None,
+			None,
 			4,
 		);
 
@@ -643,6 +837,7 @@ mod tests {
 			vec![Box::new(pattern_evaluator("credit card"))],
 			1024 * 1024,
 			None,
+			None,
 			4,
 		);
 
@@ -651,6 +846,159 @@ mod tests {
 		assert!(!contains(&bytes, b"card number"));
 	}
 
+	#[tokio::test]
+	async fn test_mask_mid_stream() {
+		let chunk1 = delta_bytes("this part is fine. ");
+		let chunk2 = delta_bytes("my ssn is 123-45-6789");
+		let done = sse_bytes("[DONE]");
+		// Split an event across frames; it is still rewritten whole.
+		let body = make_body(vec![
+			chunk1,
+			chunk2.slice(..10),
+			chunk2.slice(10..),
+			delta_bytes(" thanks"),
+			done,
+		]);
+
+		let log = crate::telemetry::log::AsyncLog::default();
+		log.store(Some(LLMInfo::new(
+			crate::llm::LLMRequest {
+				input_tokens: None,
+				input_format: crate::llm::InputFormat::Completions,
+				cache_convention: crate::llm::CacheTokenConvention::pending(),
+				request_model: "test-model".into(),
+				provider: "test-provider".into(),
+				streaming: true,
+				params: Default::default(),
+				prompt: None,
+				provider_state: None,
+			},
+			Default::default(),
+		)));
+		let guarded = GuardedSseBody::with_threshold(
+			body,
+			vec![Box::new(mask_evaluator(r"\d{3}-\d{2}-\d{4}"))],
+			1024 * 1024,
+			None,
+			Some(log.clone()),
+			4,
+		);
+
+		let bytes = guarded.collect().await.unwrap().to_bytes();
+		let text = String::from_utf8_lossy(&bytes);
+		assert!(text.contains("this part is fine. "), "{text}");
+		assert!(text.contains("my ssn is <masked>"), "{text}");
+		assert!(!text.contains("6789"), "{text}");
+		// The masked text stays in the overlap of the next window without blocking it.
+		assert!(text.contains(" thanks"), "{text}");
+		assert!(!text.contains("guardrail_blocked"), "{text}");
+		assert!(text.ends_with("data: [DONE]\n\n"), "{text}");
+
+		let info = log.take().expect("llm info should be logged");
+		assert_eq!(
+			info.response.guard_modifications,
+			vec![crate::llm::GuardModification {
+				index: 0,
+				guard: strng::new("unknown"),
+				action: strng::new("mask"),
+				fields: vec![strng::new("choices[0].delta.content")],
+			}]
+		);
+	}
+
+	#[tokio::test]
+	async fn test_mask_mid_stream_crlf() {
+		let crlf = |b: Bytes| Bytes::from(String::from_utf8_lossy(&b).replace('\n', "\r\n"));
+		let chunk1 = crlf(delta_bytes("this part is fine. "));
+		let chunk2 = crlf(delta_bytes("my ssn is 123-45-6789"));
+		let body = make_body(vec![
+			chunk1,
+			chunk2.slice(..10),
+			chunk2.slice(10..),
+			crlf(delta_bytes(" thanks")),
+			crlf(sse_bytes("[DONE]")),
+		]);
+
+		let guarded = GuardedSseBody::with_threshold(
+			body,
+			vec![Box::new(mask_evaluator(r"\d{3}-\d{2}-\d{4}"))],
+			1024 * 1024,
+			None,
+			None,
+			4,
+		);
+
+		let bytes = guarded.collect().await.unwrap().to_bytes();
+		let text = String::from_utf8_lossy(&bytes);
+		assert!(text.contains("this part is fine. "), "{text}");
+		assert!(text.contains("my ssn is <masked>"), "{text}");
+		assert!(!text.contains("6789"), "{text}");
+		assert!(text.contains(" thanks"), "{text}");
+		assert!(!text.contains("guardrail_blocked"), "{text}");
+	}
+
+	#[test]
+	fn test_event_len_line_endings() {
+		assert_eq!(event_len(b"data: a\n\ndata: b"), Some(9));
+		assert_eq!(event_len(b"data: a\r\n\r\ndata: b"), Some(11));
+		assert_eq!(event_len(b"data: a\r\n\ndata: b"), Some(10));
+		assert_eq!(event_len(b"data: a\r\ndata: b\r\n"), None);
+	}
+
+	#[tokio::test]
+	async fn test_mask_of_sent_text_blocks() {
+		let chunk1 = delta_bytes("my credit");
+		let chunk2 = delta_bytes(" card number");
+		let done = sse_bytes("[DONE]");
+		let body = make_body(vec![chunk1, chunk2, done]);
+
+		let guarded = GuardedSseBody::with_threshold(
+			body,
+			vec![Box::new(mask_evaluator("credit card"))],
+			1024 * 1024,
+			None,
+			None,
+			4,
+		);
+
+		// "my credit" was already sent, so the mask can only be enforced by ending the stream.
+		let bytes = guarded.collect().await.unwrap().to_bytes();
+		assert!(contains(&bytes, b"guardrail_blocked"));
+		assert!(!contains(&bytes, b"card number"));
+	}
+
+	#[tokio::test]
+	async fn test_regex_mask_guard_on_stream() {
+		use crate::llm::policy::{Action, RegexRule, RegexRules, RequestRejection};
+
+		let guard = ResponseGuard {
+			rejection: RequestRejection::default(),
+			kind: ResponseGuardKind::Regex(RegexRules {
+				action: Action::Mask,
+				rules: vec![RegexRule::Regex {
+					pattern: regex::Regex::new("secret-[a-z]+").unwrap(),
+				}],
+			}),
+		};
+		let evaluator = make_evaluator(
+			&guard,
+			crate::test_helpers::policy_client(),
+			HeaderMap::new(),
+			None,
+		);
+		let body = make_body(vec![
+			delta_bytes("the key is secret-abc"),
+			sse_bytes("[DONE]"),
+		]);
+
+		let guarded = GuardedSseBody::new(body, vec![evaluator], 1024 * 1024, None, None);
+
+		let bytes = guarded.collect().await.unwrap().to_bytes();
+		let text = String::from_utf8_lossy(&bytes);
+		assert!(text.contains("the key is <masked>"), "{text}");
+		assert!(!text.contains("secret-abc"), "{text}");
+	}
+
 	#[test]
 	fn test_tail_chars_respects_utf8_boundaries() {
 		let s = "héllo wörld";
@@ -0,0 +1,594 @@
+//! Streaming response transformation: `TransformedSseBody`.
+//!
+//! Unlike `transformations`, which rewrite the buffered request body, a
+//! [`StreamTransformation`] rewrites the text of each streamed response delta as it
+//! passes through. The delta is bound to `llmDelta` and the expression's string result
+//! is sent in its place.
+//!
+//! Contract for the expression:
+//!
+//! * `llmDelta` is always a complete UTF-8 string. A multibyte sequence is never split
+//!   across two evaluations.
+//! * Text is released up to the last whitespace seen so far; the trailing partial word
+//!   (at most `maxHoldback` bytes) is held back and joined with the next delta. A token
+//!   without whitespace that spans two upstream chunks is therefore seen whole, while a
+//!   pattern containing whitespace may still be split across evaluations.
+//! * Text is held back separately for each choice (or content block), so interleaved
+//!   choices never see each other's text.
+//! * Held text is flushed before any non-text event and at the end of the stream, in an
+//!   event of its own. Responses events after it are renumbered so `sequence_number`
+//!   stays unique and increasing.
+//! * If the expression fails or does not return a string, the delta is sent unchanged.
+//!
+//! The body wraps the gateway's translated SSE output, so every event is `\n\n`
+//! terminated regardless of the upstream wire format.
+
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use bytes::{Bytes, BytesMut};
+use http_body::Frame;
+use pin_project_lite::pin_project;
+use tracing::debug;
+
+use super::StreamTransformation;
+use super::streaming_guardrails::tail_chars;
+use crate::cel::{self, RequestSnapshot};
+
+/// Bytes of a trailing partial word held back when `maxHoldback` is unset.
+pub const DEFAULT_MAX_HOLDBACK: usize = 256;
+
+pin_project! {
+	// An `http_body::Body` wrapper that applies a CEL expression to each streamed text delta.
+	pub struct TransformedSseBody {
+		#[pin]
+		inner: crate::http::Body,
+		state: TransformState,
+		done: bool,
+	}
+}
+
+impl TransformedSseBody {
+	/// Wrap `inner` so each text delta is rewritten by `transformation`.
+	///
+	/// * `req` – request snapshot exposed to the expression as `request`.
+	/// * `buffer_limit` – max bytes buffered while waiting for an event terminator.
+	// We do actually return Self; just wrapped in an http_body::Body. The annotation silences a false positive from clippy about that.
+	#[allow(clippy::new_ret_no_self)]
+	pub fn new(
+		inner: crate::http::Body,
+		transformation: StreamTransformation,
+		req: Option<Arc<RequestSnapshot>>,
+		buffer_limit: usize,
+	) -> crate::http::Body {
+		crate::http::Body::new(Self {
+			inner,
+			state: TransformState {
+				transformation,
+				req,
+				buffer_limit,
+				pending: BytesMut::new(),
+				carries: BTreeMap::new(),
+				last_sequence_number: None,
+				sequence_shift: 0,
+			},
+			done: false,
+		})
+	}
+}
+
+/// Position of a text delta within its stream: the choice (or output, or content block)
+/// index, and the content index within a Responses output item.
+type SlotKey = (u64, u64);
+
+/// Trailing partial word held back for the next delta of one slot.
+struct Carry {
+	text: String,
+	// Event name and payload used as the envelope when the text is flushed.
+	name: String,
+	envelope: serde_json::Value,
+}
+
+struct TransformState {
+	transformation: StreamTransformation,
+	req: Option<Arc<RequestSnapshot>>,
+	buffer_limit: usize,
+	// Raw bytes of a not yet terminated event.
+	pending: BytesMut,
+	carries: BTreeMap<SlotKey, Carry>,
+	// Last Responses `sequence_number` sent downstream, and how far upstream numbers are
+	// shifted by the flush events we inserted.
+	last_sequence_number: Option<u64>,
+	sequence_shift: u64,
+}
+
+impl TransformState {
+	fn push(&mut self, data: &[u8]) -> Bytes {
+		self.pending.extend_from_slice(data);
+		let mut out = BytesMut::new();
+		while let Some(pos) = self.pending.windows(2).position(|w| w == b"\n\n") {
+			let event = self.pending.split_to(pos + 2).freeze();
+			out.extend_from_slice(&self.event(event));
+		}
+		if self.pending.len() > self.buffer_limit {
+			// An event this large is not something we can rewrite; pass it through untouched.
+			out.extend_from_slice(&self.flush_carries());
+			out.extend_from_slice(&self.pending.split());
+		}
+		out.freeze()
+	}
+
+	fn finish(&mut self) -> Bytes {
+		let mut out = BytesMut::new();
+		out.extend_from_slice(&self.flush_carries());
+		out.extend_from_slice(&self.pending.split());
+		out.freeze()
+	}
+
+	fn event(&mut self, raw: Bytes) -> Bytes {
+		let (name, data) = parse_event(&raw);
+		let Some(mut json) = data
+			.filter(|d| d != "[DONE]")
+			.and_then(|d| serde_json::from_str::<serde_json::Value>(&d).ok())
+		else {
+			return self.passthrough(raw, None);
+		};
+		if text_slots(&mut json).is_empty() {
+			return self.passthrough(raw, Some((&name, json)));
+		}
+
+		let max_holdback = self.max_holdback();
+		let mut held = Vec::new();
+		for (key, slot, is_final) in text_slots(&mut json) {
+			let mut released = self
+				.carries
+				.remove(&key)
+				.map(|c| c.text)
+				.unwrap_or_default();
+			released.push_str(slot.as_str().unwrap_or_default());
+			if !is_final {
+				let split = release_point(&released, max_holdback);
+				let text = released.split_off(split);
+				if !text.is_empty() {
+					held.push((key, text));
+				}
+			}
+			*slot = serde_json::Value::String(self.transform(released));
+		}
+		self.renumber(&mut json);
+		for (key, text) in held {
+			let envelope = envelope(&json, key);
+			self.carries.insert(
+				key,
+				Carry {
+					text,
+					name: name.clone(),
+					envelope,
+				},
+			);
+		}
+		encode(&name, &json)
+	}
+
+	/// Send a non-text event, after any held text. `json` is the parsed payload, which is
+	/// re-encoded only when its sequence number has to change.
+	fn passthrough(&mut self, raw: Bytes, json: Option<(&str, serde_json::Value)>) -> Bytes {
+		let mut out = BytesMut::from(self.flush_carries().as_ref());
+		if let Some((name, mut json)) = json
+			&& self.renumber(&mut json)
+		{
+			out.extend_from_slice(&encode(name, &json));
+		} else {
+			out.extend_from_slice(&raw);
+		}
+		out.freeze()
+	}
+
+	/// Send all held text, one event per slot.
+	fn flush_carries(&mut self) -> Bytes {
+		let mut out = BytesMut::new();
+		for (_, carry) in std::mem::take(&mut self.carries) {
+			let Carry {
+				text,
+				name,
+				envelope: mut json,
+			} = carry;
+			let transformed = self.transform(text);
+			if let Some((_, slot, _)) = text_slots(&mut json).into_iter().next() {
+				*slot = serde_json::Value::String(transformed);
+			}
+			if json.get("sequence_number").is_some() {
+				let n = self.last_sequence_number.map_or(0, |n| n + 1);
+				json["sequence_number"] = n.into();
+				self.last_sequence_number = Some(n);
+				self.sequence_shift += 1;
+			}
+			out.extend_from_slice(&encode(&name, &json));
+		}
+		out.freeze()
+	}
+
+	/// Shift an upstream Responses `sequence_number` past the events we inserted. Returns
+	/// whether the number changed.
+	fn renumber(&mut self, json: &mut serde_json::Value) -> bool {
+		let Some(n) = json.get("sequence_number").and_then(|n| n.as_u64()) else {
+			return false;
+		};
+		let shifted = n + self.sequence_shift;
+		self.last_sequence_number = Some(shifted);
+		if self.sequence_shift == 0 {
+			return false;
+		}
+		json["sequence_number"] = shifted.into();
+		true
+	}
+
+	fn transform(&self, text: String) -> String {
+		if text.is_empty() {
+			return text;
+		}
+		let exec = cel::Executor::new_llm_delta(self.req.as_deref(), &text);
+		let result = match exec
+			.eval(&self.transformation.expression)
+			.map_err(|e| e.to_string())
+			.and_then(|v| v.json().map_err(|e| e.to_string()))
+		{
+			Ok(serde_json::Value::String(s)) => Some(s),
+			Ok(other) => {
+				debug!("stream transformation returned non-string value {other}, passing delta through");
+				None
+			},
+			Err(e) => {
+				debug!("stream transformation failed, passing delta through: {e}");
+				None
+			},
+		};
+		result.unwrap_or(text)
+	}
+
+	fn max_holdback(&self) -> usize {
+		self
+			.transformation
+			.max_holdback
+			.unwrap_or(DEFAULT_MAX_HOLDBACK)
+	}
+}
+
+/// Split a raw SSE event into its `event:` name and joined `data:` payload.
+pub(super) fn parse_event(raw: &[u8]) -> (String, Option<String>) {
+	let text = String::from_utf8_lossy(raw);
+	let mut name = String::new();
+	let mut data: Option<String> = None;
+	for line in text.lines() {
+		if let Some(v) = line.strip_prefix("event:") {
+			name = v.strip_prefix(' ').unwrap_or(v).to_string();
+		} else if let Some(v) = line.strip_prefix("data:") {
+			let v = v.strip_prefix(' ').unwrap_or(v);
+			match &mut data {
+				Some(d) => {
+					d.push('\n');
+					d.push_str(v);
+				},
+				None => data = Some(v.to_string()),
+			}
+		}
+	}
+	(name, data)
+}
+
+pub(super) fn encode(name: &str, json: &serde_json::Value) -> Bytes {
+	let data = serde_json::to_vec(json).unwrap_or_default();
+	agent_llm::parse::encode_sse_event(name, Bytes::from(data))
+}
+
+/// Name of the field `text_slot` locates, for reporting which fields a guard changed.
+pub(super) fn text_field(v: &serde_json::Value) -> &'static str {
+	if v.get("type").and_then(|t| t.as_str()) == Some("response.output_text.delta") {
+		"delta"
+	} else if v.get("choices").is_some() {
+		"choices[0].delta.content"
+	} else {
+		"delta.text"
+	}
+}
+
+/// Locate the text delta in a Responses, Completions, or Messages stream event.
+pub(super) fn text_slot(v: &mut serde_json::Value) -> Option<&mut serde_json::Value> {
+	// OpenAI responses: response.output_text.delta
+	if v.get("type").and_then(|t| t.as_str()) == Some("response.output_text.delta") {
+		return v.get_mut("delta").filter(|d| d.is_string());
+	}
+	// OpenAI completions: choices[0].delta.content
+	if v.get("choices").is_some() {
+		return v
+			.pointer_mut("/choices/0/delta/content")
+			.filter(|c| c.is_string());
+	}
+	// Anthropic messages: delta.text
+	v.pointer_mut("/delta/text").filter(|t| t.is_string())
+}
+
+/// Every text delta in a Responses, Completions, or Messages stream event, with its slot
+/// and whether it is the last one for that slot. A completions chunk carrying a finish
+/// reason is the last one for its choice.
+fn text_slots(v: &mut serde_json::Value) -> Vec<(SlotKey, &mut serde_json::Value, bool)> {
+	let index = |v: &serde_json::Value, field: &str| v.get(field).and_then(|i| i.as_u64());
+	if v.get("type").and_then(|t| t.as_str()) == Some("response.output_text.delta") {
+		let key = (
+			index(v, "output_index").unwrap_or_default(),
+			index(v, "content_index").unwrap_or_default(),
+		);
+		return v
+			.get_mut("delta")
+			.filter(|d| d.is_string())
+			.map(|d| (key, d, false))
+			.into_iter()
+			.collect();
+	}
+	if v.get("choices").is_some() {
+		let Some(choices) = v.get_mut("choices").and_then(|c| c.as_array_mut()) else {
+			return Vec::new();
+		};
+		return choices
+			.iter_mut()
+			.enumerate()
+			.filter_map(|(i, choice)| {
+				let key = (index(choice, "index").unwrap_or(i as u64), 0);
+				let is_final = choice.get("finish_reason").is_some_and(|f| !f.is_null());
+				choice
+					.pointer_mut("/delta/content")
+					.filter(|c| c.is_string())
+					.map(|c| (key, c, is_final))
+			})
+			.collect();
+	}
+	let key = (index(v, "index").unwrap_or_default(), 0);
+	v.pointer_mut("/delta/text")
+		.filter(|t| t.is_string())
+		.map(|t| (key, t, false))
+		.into_iter()
+		.collect()
+}
+
+/// The payload for flushing text held for `key`, built from the event it was held from.
+/// A completions chunk keeps only that choice, with nothing but its text, so no other
+/// field (a tool call, a role, usage) is repeated.
+fn envelope(json: &serde_json::Value, key: SlotKey) -> serde_json::Value {
+	let mut envelope = json.clone();
+	if let Some(obj) = envelope.as_object_mut()
+		&& obj.contains_key("choices")
+	{
+		obj.remove("usage");
+		obj.insert(
+			"choices".to_string(),
+			serde_json::json!([{
+				"index": key.0,
+				"delta": {"content": ""},
+				"finish_reason": null,
+			}]),
+		);
+	}
+	envelope
+}
+
+/// Byte offset up to which `s` can be released: just past the last whitespace, while
+/// holding back at most `max_holdback` bytes. Always a char boundary.
+fn release_point(s: &str, max_holdback: usize) -> usize {
+	let after_space = s
+		.char_indices()
+		.rev()
+		.find(|(_, c)| c.is_whitespace())
+		.map(|(i, c)| i + c.len_utf8())
+		.unwrap_or(0);
+	let held = tail_chars(&s[after_space..], max_holdback);
+	s.len() - held.len()
+}
+
+impl http_body::Body for TransformedSseBody {
+	type Data = Bytes;
+	type Error = crate::http::Error;
+
+	fn poll_frame(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+	) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+		let mut this = self.project();
+		if *this.done {
+			return Poll::Ready(None);
+		}
+		loop {
+			match ready!(this.inner.as_mut().poll_frame(cx)) {
+				Some(Ok(frame)) => match frame.into_data() {
+					Ok(data) => {
+						let out = this.state.push(&data);
+						if !out.is_empty() {
+							return Poll::Ready(Some(Ok(Frame::data(out))));
+						}
+					},
+					Err(frame) => return Poll::Ready(Some(Ok(frame))),
+				},
+				Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+				None => {
+					*this.done = true;
+					let out = this.state.finish();
+					return if out.is_empty() {
+						Poll::Ready(None)
+					} else {
+						Poll::Ready(Some(Ok(Frame::data(out))))
+					};
+				},
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use http_body_util::BodyExt as _;
+	use serde_json::json;
+
+	use super::*;
+
+	fn sse_bytes(content: &str) -> Bytes {
+		Bytes::from(format!("data: {}\n\n", content))
+	}
+
+	fn delta_bytes(text: &str) -> Bytes {
+		sse_bytes(&format!(
+			"{{\"choices\":[{{\"delta\":{{\"content\":\"{}\"}}}}]}}",
+			text
+		))
+	}
+
+	fn make_body(chunks: Vec<Bytes>) -> crate::http::Body {
+		use std::convert::Infallible;
+
+		use futures_util::stream;
+		let stream = stream::iter(chunks.into_iter().map(Ok::<Bytes, Infallible>));
+		crate::http::Body::from_stream(stream)
+	}
+
+	fn transformation(expr: &str) -> StreamTransformation {
+		StreamTransformation {
+			expression: Arc::new(cel::Expression::new_strict(expr).unwrap()),
+			max_holdback: None,
+		}
+	}
+
+	/// Concatenate the text deltas of a completions stream.
+	fn collect_text(bytes: &[u8]) -> String {
+		String::from_utf8_lossy(bytes)
+			.split("\n\n")
+			.filter_map(|e| e.strip_prefix("data: "))
+			.filter(|d| *d != "[DONE]")
+			.filter_map(|d| serde_json::from_str::<serde_json::Value>(d).ok())
+			.filter_map(|v| {
+				v.pointer("/choices/0/delta/content")
+					.and_then(|c| c.as_str())
+					.map(str::to_string)
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn test_masks_token_spanning_chunks() {
+		let body = make_body(vec![
+			delta_bytes("your key is sk-ab"),
+			delta_bytes("c123 keep it safe"),
+			sse_bytes("[DONE]"),
+		]);
+		let transformed = TransformedSseBody::new(
+			body,
+			transformation(r#"llmDelta.regexReplace("sk-[a-z0-9]+", "****")"#),
+			None,
+			1024 * 1024,
+		);
+
+		let bytes = transformed.collect().await.unwrap().to_bytes();
+		assert_eq!(collect_text(&bytes), "your key is **** keep it safe");
+		assert!(bytes.ends_with(b"data: [DONE]\n\n"));
+	}
+
+	#[tokio::test]
+	async fn test_multibyte_and_failure_pass_through() {
+		let body = make_body(vec![delta_bytes("héllo wö"), delta_bytes("rld")]);
+		let transformed =
+			TransformedSseBody::new(body, transformation("fail('nope')"), None, 1024 * 1024);
+
+		let bytes = transformed.collect().await.unwrap().to_bytes();
+		assert_eq!(collect_text(&bytes), "héllo wörld");
+	}
+
+	#[tokio::test]
+	async fn test_holds_text_per_choice() {
+		let choice = |index: u64, text: &str| {
+			sse_bytes(
+				&json!({"choices": [{"index": index, "delta": {"content": text}, "finish_reason": null}]})
+					.to_string(),
+			)
+		};
+		let body = make_body(vec![
+			choice(0, "hello wor"),
+			choice(1, "good mor"),
+			choice(0, "ld"),
+			choice(1, "ning"),
+			sse_bytes("[DONE]"),
+		]);
+		let transformed = TransformedSseBody::new(
+			body,
+			transformation(r#"llmDelta.regexReplace("world|morning", "****")"#),
+			None,
+			1024 * 1024,
+		);
+
+		let bytes = transformed.collect().await.unwrap().to_bytes();
+		let mut texts = [String::new(), String::new()];
+		for event in String::from_utf8_lossy(&bytes).split("\n\n") {
+			let Some(v) = event
+				.strip_prefix("data: ")
+				.and_then(|d| serde_json::from_str::<serde_json::Value>(d).ok())
+			else {
+				continue;
+			};
+			for c in v["choices"].as_array().unwrap() {
+				texts[c["index"].as_u64().unwrap() as usize] += c["delta"]["content"].as_str().unwrap();
+			}
+		}
+		assert_eq!(texts, ["hello ****", "good ****"]);
+	}
+
+	#[tokio::test]
+	async fn test_flushed_responses_event_gets_new_sequence_number() {
+		let event = |v: serde_json::Value| {
+			Bytes::from(format!(
+				"event: {}\ndata: {}\n\n",
+				v["type"].as_str().unwrap(),
+				v
+			))
+		};
+		let body = make_body(vec![
+			event(json!({
+				"type": "response.output_text.delta",
+				"sequence_number": 0,
+				"output_index": 0,
+				"content_index": 0,
+				"delta": "hello wor",
+			})),
+			event(json!({
+				"type": "response.output_text.done",
+				"sequence_number": 1,
+				"output_index": 0,
+				"content_index": 0,
+				"text": "hello wor",
+			})),
+		]);
+		let transformed = TransformedSseBody::new(body, transformation("llmDelta"), None, 1024 * 1024);
+
+		let bytes = transformed.collect().await.unwrap().to_bytes();
+		let events: Vec<serde_json::Value> = String::from_utf8_lossy(&bytes)
+			.split("\n\n")
+			.filter_map(|e| e.lines().find_map(|l| l.strip_prefix("data: ")))
+			.map(|d| serde_json::from_str(d).unwrap())
+			.collect();
+		let numbers: Vec<_> = events
+			.iter()
+			.map(|e| e["sequence_number"].clone())
+			.collect();
+		assert_eq!(numbers, [json!(0), json!(1), json!(2)]);
+		let deltas: Vec<_> = events.iter().map(|e| e["delta"].clone()).collect();
+		assert_eq!(
+			deltas,
+			[json!("hello "), json!("wor"), serde_json::Value::Null]
+		);
+	}
+
+	#[test]
+	fn test_release_point_respects_holdback() {
+		assert_eq!(release_point("hello wor", 256), 6);
+		assert_eq!(release_point("abcdef", 2), 4);
+		// Never split inside a multibyte char.
+		assert_eq!(release_point("aé", 1), 3);
+	}
+}
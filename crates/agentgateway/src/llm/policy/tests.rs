@@ -19,6 +19,8 @@ async fn webhook_fail_open_emits_single_metric() {
 				headers: Default::default(),
 				forward_header_matches: vec![],
 				failure_mode: FailureMode::FailOpen,
+				max_concurrency: None,
+				timeout: None,
 			}),
 		}],
 		response: vec![],
@@ -56,6 +58,82 @@ async fn webhook_fail_open_emits_single_metric() {
 	);
 }
 
+/// Calls a webhook guard whose backend responds with `response`, returning the outcome and the
+/// (possibly masked) request text.
+async fn call_webhook_guard(
+	response: wiremock::ResponseTemplate,
+	failure_mode: FailureMode,
+	timeout: Option<Duration>,
+) -> (anyhow::Result<GuardrailOutcome>, String) {
+	use wiremock::matchers::{method, path};
+	use wiremock::{Mock, MockServer};
+
+	use crate::types::agent::{SimpleBackendReference, Target};
+
+	let mock = MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/request"))
+		.respond_with(response)
+		.mount(&mock)
+		.await;
+	let guard = RequestGuard {
+		rejection: Default::default(),
+		kind: RequestGuardKind::Webhook(Webhook {
+			target: SimpleBackendReference::InlineBackend(Target::Address(*mock.address())),
+			headers: Default::default(),
+			forward_header_matches: vec![],
+			failure_mode,
+			max_concurrency: None,
+			timeout,
+		}),
+	};
+	let mut req = TextRequest {
+		content: "hello world".to_string(),
+	};
+	let client = crate::test_helpers::policy_client();
+	let outcome =
+		Policy::apply_single_request_guard(&guard, &mut req, &HeaderMap::new(), &client, None, None)
+			.await;
+	(outcome, req.content)
+}
+
+#[tokio::test]
+async fn webhook_server_error_fails_open() {
+	// A 5xx is a failure even when the body would parse as a valid action.
+	let error = || {
+		wiremock::ResponseTemplate::new(500).set_body_json(serde_json::json!({
+			"action": {"body": {"messages": []}, "reason": "masked"}
+		}))
+	};
+
+	let (outcome, content) = call_webhook_guard(error(), FailureMode::FailOpen, None).await;
+	assert!(matches!(outcome, Ok(GuardrailOutcome::FailOpen)));
+	assert_eq!(content, "hello world");
+
+	let (outcome, _) = call_webhook_guard(error(), FailureMode::FailClosed, None).await;
+	let err = outcome.err().expect("FailClosed must reject a 5xx");
+	assert!(err.to_string().contains("500"), "{err}");
+}
+
+#[tokio::test]
+async fn webhook_timeout_fails_open() {
+	let slow = || {
+		wiremock::ResponseTemplate::new(200)
+			.set_body_json(serde_json::json!({"action": {"reason": "ok"}}))
+			.set_delay(Duration::from_secs(5))
+	};
+	let timeout = Some(Duration::from_millis(100));
+
+	let start = std::time::Instant::now();
+	let (outcome, content) = call_webhook_guard(slow(), FailureMode::FailOpen, timeout).await;
+	assert!(start.elapsed() < Duration::from_secs(5));
+	assert!(matches!(outcome, Ok(GuardrailOutcome::FailOpen)));
+	assert_eq!(content, "hello world");
+
+	let (outcome, _) = call_webhook_guard(slow(), FailureMode::FailClosed, timeout).await;
+	assert!(outcome.is_err());
+}
+
 #[test]
 fn test_get_webhook_forward_headers() {
 	let mut headers = HeaderMap::new();
@@ -1523,3 +1601,66 @@ fn test_google_model_armor_implicit_auth_used_when_no_user_credentials() {
 		resolved.backend_auth
 	);
 }
+
+#[test]
+fn test_prompt_enrichment_bypass() {
+	use secrecy::SecretString;
+	use serde_json::json;
+
+	let policy: Policy = serde_json::from_value(json!({
+		"prompts": {
+			"prepend": [{"role": "system", "content": "Follow the org style guide."}],
+			"bypass": "request.headers[\"x-skip-enrichment\"] == \"true\" && jwt.sub == \"calibration\""
+		}
+	}))
+	.unwrap();
+
+	let snapshot = |skip: bool, sub: &str| {
+		let mut req = ::http::Request::builder()
+			.uri("http://example.com/v1/chat/completions")
+			.header("x-skip-enrichment", skip.to_string())
+			.body(crate::http::Body::empty())
+			.unwrap();
+		req.extensions_mut().insert(Claims {
+			inner: serde_json::from_value(json!({"sub": sub})).unwrap(),
+			jwt: SecretString::from("token"),
+		});
+		cel::snapshot_request(&mut req, false)
+	};
+	let enrich = |original: &cel::RequestSnapshot| {
+		let mut req: crate::llm::types::completions::Request = serde_json::from_value(json!({
+			"model": "gpt-4o",
+			"messages": [{"role": "user", "content": "calibrate"}]
+		}))
+		.unwrap();
+		policy.apply_prompt_enrichment(&mut req, Some(original));
+		req.messages.len()
+	};
+
+	// Authorized caller asking for the bypass: enrichment is skipped.
+	assert_eq!(enrich(&snapshot(true, "calibration")), 1);
+	// Header without the authorized identity, or no header at all: enrichment applies.
+	assert_eq!(enrich(&snapshot(true, "someone-else")), 2);
+	assert_eq!(enrich(&snapshot(false, "calibration")), 2);
+}
+
+#[test]
+fn test_default_params_must_respect_param_limits() {
+	use serde_json::json;
+
+	let policy = |max_tokens: u64| -> Policy {
+		serde_json::from_value(json!({
+			"defaultParams": {"maxTokens": max_tokens, "temperature": 0.2},
+			"paramLimits": {"maxTokens": 4096, "maxTemperature": 1.0}
+		}))
+		.unwrap()
+	};
+	policy(1024).validate_default_params().unwrap();
+	let err = policy(8192).validate_default_params().unwrap_err();
+	assert!(
+		err
+			.to_string()
+			.contains("max_tokens 8192 exceeds the maximum of 4096"),
+		"{err}"
+	);
+}
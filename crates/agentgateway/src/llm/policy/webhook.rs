@@ -1,11 +1,13 @@
 use ::http::header::CONTENT_TYPE;
 use ::http::{HeaderMap, HeaderValue, header};
 pub use agent_llm::webhook::{Message, ResponseChoice};
-use serde::{Deserialize, Serialize};
+use prometheus_client::metrics::gauge::Gauge;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 use crate::cel::RequestSnapshot;
 use crate::http::{HeaderOrPseudoValue, RequestOrResponse};
-use crate::llm::policy::{Webhook, with_default_timeout};
+use crate::llm::policy::{DEFAULT_TIMEOUT, Webhook, with_timeout};
 use crate::proxy::httpproxy::PolicyClient;
 use crate::telemetry::metrics::{OutboundCallKind, OutboundCallSubtype};
 use crate::*;
@@ -121,6 +123,90 @@ pub enum MaskActionBody {
 	ResponseChoices(ResponseChoices),
 }
 
+/// Caps the number of concurrent calls to a webhook. Calls past the limit wait, in arrival
+/// order, for an earlier call to complete. Clones share the same limit.
+#[derive(Clone)]
+pub struct ConcurrencyLimit {
+	limit: usize,
+	semaphore: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimit {
+	pub fn new(limit: usize) -> Self {
+		Self {
+			limit,
+			semaphore: Arc::new(Semaphore::new(limit)),
+		}
+	}
+
+	pub fn limit(&self) -> usize {
+		self.limit
+	}
+
+	async fn acquire(&self) -> OwnedSemaphorePermit {
+		self
+			.semaphore
+			.clone()
+			.acquire_owned()
+			.await
+			.expect("semaphore is never closed")
+	}
+}
+
+impl std::fmt::Debug for ConcurrencyLimit {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("ConcurrencyLimit")
+			.field(&self.limit)
+			.finish()
+	}
+}
+
+impl Serialize for ConcurrencyLimit {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.limit.serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for ConcurrencyLimit {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let limit = usize::deserialize(deserializer)?;
+		if limit == 0 {
+			return Err(serde::de::Error::custom(
+				"maxConcurrency must be greater than 0",
+			));
+		}
+		Ok(Self::new(limit))
+	}
+}
+
+/// Held for the duration of a webhook call: counts the call in the in-flight gauge and
+/// owns its concurrency permit, releasing both on drop.
+struct InFlight {
+	gauge: Gauge,
+	_permit: Option<OwnedSemaphorePermit>,
+}
+
+impl InFlight {
+	async fn begin(client: &PolicyClient, webhook: &Webhook) -> Self {
+		let permit = match &webhook.max_concurrency {
+			Some(limit) => Some(limit.acquire().await),
+			None => None,
+		};
+		let gauge = client.inputs.metrics.guardrail_webhook_in_flight.clone();
+		gauge.inc();
+		Self {
+			gauge,
+			_permit: permit,
+		}
+	}
+}
+
+impl Drop for InFlight {
+	fn drop(&mut self) {
+		self.gauge.dec();
+	}
+}
+
 fn build_request_for_request(
 	webhook: &Webhook,
 	original: Option<&RequestSnapshot>,
@@ -206,6 +292,15 @@ fn apply_header_expressions(
 	}
 }
 
+/// An error status means the webhook could not evaluate the guardrail, even if it sent a
+/// parseable body.
+fn check_status(res: &crate::http::Response) -> anyhow::Result<()> {
+	if !res.status().is_success() {
+		anyhow::bail!("webhook returned status {}", res.status());
+	}
+	Ok(())
+}
+
 pub async fn send_request(
 	client: &PolicyClient,
 	webhook: &Webhook,
@@ -213,18 +308,18 @@ pub async fn send_request(
 	http_headers: &HeaderMap,
 	messages: Vec<Message>,
 ) -> anyhow::Result<GuardrailsPromptResponse> {
-	let whr = with_default_timeout(build_request_for_request(
-		webhook,
-		original,
-		http_headers,
-		messages,
-	)?);
+	let whr = with_timeout(
+		build_request_for_request(webhook, original, http_headers, messages)?,
+		webhook.timeout.unwrap_or(DEFAULT_TIMEOUT),
+	);
+	let _in_flight = InFlight::begin(client, webhook).await;
 	let res = Box::pin(
 		client
 			.with_outbound(OutboundCallKind::Policy, OutboundCallSubtype::Guardrail)
 			.call_reference(whr, &webhook.target),
 	)
 	.await?;
+	check_status(&res)?;
 	let parsed = json::from_response_body(res).await?;
 	Ok(parsed)
 }
@@ -236,16 +331,16 @@ pub async fn send_response(
 	http_headers: &HeaderMap,
 	choices: Vec<ResponseChoice>,
 ) -> anyhow::Result<GuardrailsResponseResponse> {
-	let whr = with_default_timeout(build_request_for_response(
-		webhook,
-		original,
-		http_headers,
-		choices,
-	)?);
+	let whr = with_timeout(
+		build_request_for_response(webhook, original, http_headers, choices)?,
+		webhook.timeout.unwrap_or(DEFAULT_TIMEOUT),
+	);
+	let _in_flight = InFlight::begin(client, webhook).await;
 	let res = client
 		.with_outbound(OutboundCallKind::Policy, OutboundCallSubtype::Guardrail)
 		.call_reference(whr, &webhook.target)
 		.await?;
+	check_status(&res)?;
 	let parsed = json::from_response_body(res).await?;
 	Ok(parsed)
 }
@@ -268,6 +363,8 @@ mod tests {
 			headers,
 			forward_header_matches: vec![],
 			failure_mode: FailureMode::FailClosed,
+			max_concurrency: None,
+			timeout: None,
 		}
 	}
 
@@ -400,4 +497,51 @@ mod tests {
 		// ...but context-dependent ones are skipped.
 		assert!(req.headers().get("x-user").is_none());
 	}
+
+	#[tokio::test]
+	async fn concurrent_calls_are_bounded_by_limit() {
+		use std::time::Duration;
+
+		use wiremock::matchers::{method, path};
+		use wiremock::{Mock, MockServer, ResponseTemplate};
+
+		use crate::types::agent::Target;
+
+		let mock = MockServer::start().await;
+		Mock::given(method("POST"))
+			.and(path("/request"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.set_body_json(serde_json::json!({"action": {"reason": "ok"}}))
+					.set_delay(Duration::from_millis(300)),
+			)
+			.mount(&mock)
+			.await;
+
+		let mut wh = webhook(vec![]);
+		wh.target = SimpleBackendReference::InlineBackend(Target::Address(*mock.address()));
+		wh.max_concurrency = Some(ConcurrencyLimit::new(2));
+		let client = crate::test_helpers::policy_client();
+		let in_flight = client.inputs.metrics.guardrail_webhook_in_flight.clone();
+
+		let calls: Vec<_> = (0..5)
+			.map(|_| {
+				let (client, wh) = (client.clone(), wh.clone());
+				tokio::spawn(
+					async move { send_request(&client, &wh, None, &HeaderMap::new(), vec![]).await },
+				)
+			})
+			.collect();
+
+		// Only the first two calls reach the webhook; the rest wait for a permit.
+		tokio::time::sleep(Duration::from_millis(150)).await;
+		assert_eq!(mock.received_requests().await.unwrap().len(), 2);
+		assert_eq!(in_flight.get(), 2);
+
+		for call in calls {
+			call.await.unwrap().unwrap();
+		}
+		assert_eq!(mock.received_requests().await.unwrap().len(), 5);
+		assert_eq!(in_flight.get(), 0);
+	}
 }
@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use ::http::{HeaderMap, StatusCode};
+
+use crate::*;
+
+const DEFAULT_BASE_BACKOFF: Duration = Duration::from_millis(500);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Retries of failed calls to an LLM provider.
+///
+/// Only conditions that are safe to repeat are retried: `5xx` responses, and `429` responses that
+/// say when to retry via `retry-after`, `retry-after-ms` or one of the `x-ratelimit-reset` headers.
+/// The decision is made on the response
+/// head, before any of the body is sent to the client, so a response that has started streaming
+/// is never retried.
+#[apply(schema!)]
+pub struct Retry {
+	/// Maximum number of retries after the first attempt.
+	pub max_retries: u8,
+	/// Base delay of the jittered exponential backoff between attempts. Defaults to 500ms.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub base_backoff: Option<Duration>,
+	/// Maximum delay between attempts. Defaults to 30s. A `retry-after` longer than this is not
+	/// retried.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub max_backoff: Option<Duration>,
+}
+
+impl Retry {
+	/// retry_delay returns how long to wait before retrying a response, or None if it must not be
+	/// retried. `retries` is the number of retries already made.
+	pub fn retry_delay(
+		&self,
+		retries: u8,
+		status: StatusCode,
+		headers: &HeaderMap,
+	) -> Option<Duration> {
+		if retries >= self.max_retries {
+			return None;
+		}
+		let retry_after = if status == StatusCode::TOO_MANY_REQUESTS {
+			// Without a hint, a rate limited request is likely to be rate limited again.
+			Some(http::outlierdetection::retry_after(status, headers)?)
+		} else if status.is_server_error() {
+			http::outlierdetection::retry_after_header(headers)
+		} else {
+			return None;
+		};
+		let max = self.max_backoff.unwrap_or(DEFAULT_MAX_BACKOFF);
+		match retry_after {
+			Some(d) if d > max => None,
+			Some(d) => Some(d),
+			None => Some(self.backoff(retries, max)),
+		}
+	}
+
+	// Exponential backoff with full jitter.
+	fn backoff(&self, retries: u8, max: Duration) -> Duration {
+		let base = self.base_backoff.unwrap_or(DEFAULT_BASE_BACKOFF);
+		let ceiling = base.saturating_mul(1 << retries.min(16)).min(max);
+		ceiling.mul_f64(rand::random_range(0.0..=1.0))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn retry(max_retries: u8) -> Retry {
+		Retry {
+			max_retries,
+			base_backoff: Some(Duration::from_millis(100)),
+			max_backoff: Some(Duration::from_secs(5)),
+		}
+	}
+
+	fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+		pairs
+			.iter()
+			.map(|(k, v)| {
+				(
+					::http::HeaderName::from_static(k),
+					::http::HeaderValue::from_static(v),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn retries_server_errors_with_bounded_backoff() {
+		let r = retry(2);
+		for retries in 0..2 {
+			let d = r
+				.retry_delay(retries, StatusCode::SERVICE_UNAVAILABLE, &HeaderMap::new())
+				.unwrap();
+			assert!(d <= Duration::from_millis(100 << retries), "{d:?}");
+		}
+		assert_eq!(
+			r.retry_delay(2, StatusCode::SERVICE_UNAVAILABLE, &HeaderMap::new()),
+			None
+		);
+	}
+
+	#[test]
+	fn honors_retry_after() {
+		let r = retry(1);
+		assert_eq!(
+			r.retry_delay(
+				0,
+				StatusCode::TOO_MANY_REQUESTS,
+				&headers(&[("retry-after-ms", "250")])
+			),
+			Some(Duration::from_millis(250))
+		);
+		assert_eq!(
+			r.retry_delay(
+				0,
+				StatusCode::SERVICE_UNAVAILABLE,
+				&headers(&[("retry-after", "2")])
+			),
+			Some(Duration::from_secs(2))
+		);
+		// Longer than maxBackoff
+		assert_eq!(
+			r.retry_delay(
+				0,
+				StatusCode::TOO_MANY_REQUESTS,
+				&headers(&[("retry-after", "60")])
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn honors_rate_limit_reset_on_429() {
+		let r = retry(1);
+		assert_eq!(
+			r.retry_delay(
+				0,
+				StatusCode::TOO_MANY_REQUESTS,
+				&headers(&[("x-ratelimit-reset", "2")])
+			),
+			Some(Duration::from_secs(2))
+		);
+		assert_eq!(
+			r.retry_delay(
+				0,
+				StatusCode::TOO_MANY_REQUESTS,
+				&headers(&[("x-ratelimit-reset-requests", "1s")])
+			),
+			Some(Duration::from_secs(1))
+		);
+		// Only a 429 carries rate limit reset semantics.
+		assert!(
+			r.retry_delay(
+				0,
+				StatusCode::SERVICE_UNAVAILABLE,
+				&headers(&[("x-ratelimit-reset", "20")])
+			)
+			.unwrap()
+				<= Duration::from_millis(100)
+		);
+	}
+
+	#[test]
+	fn does_not_retry_unsafe_conditions() {
+		let r = retry(3);
+		assert_eq!(
+			r.retry_delay(0, StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new()),
+			None
+		);
+		assert_eq!(
+			r.retry_delay(0, StatusCode::BAD_REQUEST, &HeaderMap::new()),
+			None
+		);
+		assert_eq!(r.retry_delay(0, StatusCode::OK, &HeaderMap::new()), None);
+	}
+}
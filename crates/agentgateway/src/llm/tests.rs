@@ -50,6 +50,7 @@ fn streaming_amend_on_drop_updates_local_rate_limit() {
 		},
 		None,
 		None,
+		None,
 	);
 	amend.report_usage();
 
@@ -65,6 +66,48 @@ fn streaming_amend_on_drop_updates_local_rate_limit() {
 	);
 }
 
+#[test]
+fn streaming_amend_on_drop_records_input_token_divergence() {
+	let mut registry = prometheus_client::registry::Registry::default();
+	let metrics = Arc::new(crate::telemetry::metrics::Metrics::new(
+		&mut registry,
+		Default::default(),
+	));
+	let log = AsyncLog::default();
+	log.store(Some(LLMInfo {
+		request: llm_request_with_tokens(Some(5)),
+		response: LLMResponse {
+			input_tokens: Some(9),
+			output_tokens: Some(4),
+			..Default::default()
+		},
+	}));
+
+	let mut amend = AmendOnDrop::new(
+		log,
+		LLMResponsePolicies::default(),
+		None,
+		None,
+		Some(metrics),
+	);
+	amend.report_usage();
+
+	let mut encoded = String::new();
+	prometheus_client::encoding::text::encode(&mut encoded, &registry).unwrap();
+	assert!(
+		encoded.contains(
+			"gen_ai_input_token_estimate_divergence_sum{gen_ai_request_model=\"test-model\"} 4.0"
+		),
+		"got:\n{encoded}"
+	);
+	assert!(
+		encoded.contains(
+			"gen_ai_input_token_estimate_divergence_count{gen_ai_request_model=\"test-model\"} 1"
+		),
+		"got:\n{encoded}"
+	);
+}
+
 fn test_root() -> &'static Path {
 	Path::new("../llm/src/tests")
 }
@@ -236,137 +279,807 @@ async fn openai_provider_normalizes_max_tokens_after_model_alias() {
 	assert_eq!(llm_request.params.max_tokens, Some(1024));
 }
 
+#[test]
+fn provider_affinity_is_sticky_per_key() {
+	let provider = |name: &str| NamedAIProvider {
+		name: name.into(),
+		provider: AIProvider::OpenAI(openai::Provider { model: None }),
+		provider_backend: None,
+		host_override: None,
+		path_override: None,
+		path_prefix: None,
+		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: 1,
+		request_size: None,
+		inline_policies: vec![],
+	};
+	let backend = AIBackend {
+		providers: crate::types::loadbalancer::EndpointSet::new(vec![
+			["a", "b", "c", "d"]
+				.into_iter()
+				.map(|n| (strng::new(n), provider(n)))
+				.collect(),
+		]),
+	};
+
+	let mut picked = std::collections::HashSet::new();
+	for conversation in 0..32 {
+		let key = format!("conversation-{conversation}");
+		let (first, _) = backend.select_provider_with_affinity(&key, None).unwrap();
+		for _ in 0..10 {
+			let (p, _) = backend.select_provider_with_affinity(&key, None).unwrap();
+			assert_eq!(p.name, first.name, "{key} moved providers");
+		}
+		picked.insert(first.name.clone());
+	}
+	// Different conversations still spread across providers.
+	assert!(picked.len() > 1);
+}
+
+#[test]
+fn provider_weights_shape_selection() {
+	let provider = |name: &str, weight: u32| NamedAIProvider {
+		name: name.into(),
+		provider: AIProvider::OpenAI(openai::Provider { model: None }),
+		provider_backend: None,
+		host_override: None,
+		path_override: None,
+		path_prefix: None,
+		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight,
+		request_size: None,
+		inline_policies: vec![],
+	};
+	let backend = |providers: &[(&str, u32)]| AIBackend {
+		providers: crate::types::loadbalancer::EndpointSet::new_with_capacity(
+			vec![
+				providers
+					.iter()
+					.map(|(n, w)| (strng::new(n), provider(n, *w)))
+					.collect(),
+			],
+			|p| p.weight,
+		),
+	};
+
+	let weighted = backend(&[("cheap", 9), ("premium", 1), ("fallback", 0)]);
+	let mut counts = std::collections::HashMap::<Strng, usize>::new();
+	const ITERATIONS: usize = 20_000;
+	for _ in 0..ITERATIONS {
+		let (p, _) = weighted.select_provider().unwrap();
+		*counts.entry(p.name.clone()).or_default() += 1;
+	}
+	let share = |name: &str| counts.get(name).copied().unwrap_or_default() as f64 / ITERATIONS as f64;
+	assert!((share("cheap") - 0.9).abs() < 0.02, "{counts:?}");
+	assert!((share("premium") - 0.1).abs() < 0.02, "{counts:?}");
+	assert_eq!(share("fallback"), 0.0, "{counts:?}");
+
+	// With no other healthy provider, weight 0 providers still serve traffic.
+	let fallback_only = backend(&[("fallback", 0)]);
+	let (p, _) = fallback_only.select_provider().unwrap();
+	assert_eq!(p.name, "fallback");
+}
+
+#[test]
+fn provider_request_size_routes_batches() {
+	let provider = |name: &str, request_size: RequestSizeRange| NamedAIProvider {
+		name: name.into(),
+		provider: AIProvider::OpenAI(openai::Provider { model: None }),
+		provider_backend: None,
+		host_override: None,
+		path_override: None,
+		path_prefix: None,
+		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: 1,
+		request_size: Some(request_size),
+		inline_policies: vec![],
+	};
+	let backend = AIBackend {
+		providers: crate::types::loadbalancer::EndpointSet::new(vec![vec![
+			(
+				strng::new("low-latency"),
+				provider(
+					"low-latency",
+					RequestSizeRange {
+						min_bytes: None,
+						max_bytes: Some(4096),
+					},
+				),
+			),
+			(
+				strng::new("high-throughput"),
+				provider(
+					"high-throughput",
+					RequestSizeRange {
+						min_bytes: Some(4097),
+						max_bytes: None,
+					},
+				),
+			),
+		]]),
+	};
+
+	for _ in 0..50 {
+		let (p, _) = backend.select_provider_for_size(Some(1 << 20)).unwrap();
+		assert_eq!(p.name, "high-throughput");
+		let (p, _) = backend.select_provider_for_size(Some(512)).unwrap();
+		assert_eq!(p.name, "low-latency");
+	}
+
+	// Without a known size, every provider is eligible.
+	let picked = (0..200)
+		.filter_map(|_| backend.select_provider_for_size(None))
+		.map(|(p, _)| p.name.clone())
+		.collect::<std::collections::HashSet<_>>();
+	assert_eq!(picked.len(), 2);
+}
+
 #[tokio::test]
-async fn openai_provider_preserves_max_tokens_for_non_gpt_models() {
+async fn provider_request_size_keeps_weights() {
+	agent_core::telemetry::testing::setup_test_logging();
+	let provider = |name: &str, weight: u32, request_size: RequestSizeRange| NamedAIProvider {
+		name: name.into(),
+		provider: AIProvider::OpenAI(openai::Provider { model: None }),
+		provider_backend: None,
+		host_override: None,
+		path_override: None,
+		path_prefix: None,
+		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight,
+		request_size: Some(request_size),
+		inline_policies: vec![],
+	};
+	let small = RequestSizeRange {
+		min_bytes: None,
+		max_bytes: Some(4096),
+	};
+	let large = RequestSizeRange {
+		min_bytes: Some(4097),
+		max_bytes: None,
+	};
+	let backend = AIBackend {
+		providers: crate::types::loadbalancer::EndpointSet::new_with_capacity(
+			vec![
+				[
+					("size-small", 5, small),
+					("size-cheap", 9, large.clone()),
+					("size-premium", 1, large),
+				]
+				.into_iter()
+				.map(|(n, w, r)| (strng::new(n), provider(n, w, r)))
+				.collect(),
+			],
+			|p| p.weight,
+		),
+	};
+
+	let mut counts = std::collections::HashMap::<Strng, usize>::new();
+	const ITERATIONS: usize = 20_000;
+	for _ in 0..ITERATIONS {
+		let (p, _) = backend.select_provider_for_size(Some(1 << 20)).unwrap();
+		*counts.entry(p.name.clone()).or_default() += 1;
+	}
+	let share = |name: &str| counts.get(name).copied().unwrap_or_default() as f64 / ITERATIONS as f64;
+	assert!((share("size-cheap") - 0.9).abs() < 0.02, "{counts:?}");
+	assert!((share("size-premium") - 0.1).abs() < 0.02, "{counts:?}");
+	assert_eq!(share("size-small"), 0.0, "{counts:?}");
+
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("message", "providers skipped during selection"),
+		("skipped", "size-small=request_size"),
+	])
+	.await
+	.unwrap();
+	assert!(log["selected"] != "size-small", "{log}");
+}
+
+#[test]
+fn provider_affinity_respects_request_size_and_weights() {
+	let provider = |name: &str, weight: u32, request_size: RequestSizeRange| NamedAIProvider {
+		name: name.into(),
+		provider: AIProvider::OpenAI(openai::Provider { model: None }),
+		provider_backend: None,
+		host_override: None,
+		path_override: None,
+		path_prefix: None,
+		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight,
+		request_size: Some(request_size),
+		inline_policies: vec![],
+	};
+	let small = RequestSizeRange {
+		min_bytes: None,
+		max_bytes: Some(4096),
+	};
+	let large = RequestSizeRange {
+		min_bytes: Some(4097),
+		max_bytes: None,
+	};
+	let backend = AIBackend {
+		providers: crate::types::loadbalancer::EndpointSet::new_with_capacity(
+			vec![
+				[
+					("affinity-small", 5, small),
+					("affinity-cheap", 9, large.clone()),
+					("affinity-premium", 1, large.clone()),
+					("affinity-drained", 0, large),
+				]
+				.into_iter()
+				.map(|(n, w, r)| (strng::new(n), provider(n, w, r)))
+				.collect(),
+			],
+			|p| p.weight,
+		),
+	};
+
+	let mut counts = std::collections::HashMap::<Strng, usize>::new();
+	const KEYS: usize = 5_000;
+	for conversation in 0..KEYS {
+		let key = format!("conversation-{conversation}");
+		let (first, _) = backend
+			.select_provider_with_affinity(&key, Some(1 << 20))
+			.unwrap();
+		let (again, _) = backend
+			.select_provider_with_affinity(&key, Some(1 << 20))
+			.unwrap();
+		assert_eq!(again.name, first.name, "{key} moved providers");
+		*counts.entry(first.name.clone()).or_default() += 1;
+
+		let (p, _) = backend
+			.select_provider_with_affinity(&key, Some(512))
+			.unwrap();
+		assert_eq!(p.name, "affinity-small");
+	}
+	// Keys are spread over the providers that accept the size, in proportion to their weight.
+	let share = |name: &str| counts.get(name).copied().unwrap_or_default() as f64 / KEYS as f64;
+	assert!((share("affinity-cheap") - 0.9).abs() < 0.03, "{counts:?}");
+	assert!((share("affinity-premium") - 0.1).abs() < 0.03, "{counts:?}");
+	assert_eq!(share("affinity-small"), 0.0, "{counts:?}");
+	assert_eq!(share("affinity-drained"), 0.0, "{counts:?}");
+}
+
+#[tokio::test]
+async fn provider_selection_logs_skipped_unhealthy_providers() {
+	use crate::types::loadbalancer::SkipReason;
+	agent_core::telemetry::testing::setup_test_logging();
+	let provider = |name: &str| NamedAIProvider {
+		name: name.into(),
+		provider: AIProvider::OpenAI(openai::Provider { model: None }),
+		provider_backend: None,
+		host_override: None,
+		path_override: None,
+		path_prefix: None,
+		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: 1,
+		request_size: None,
+		inline_policies: vec![],
+	};
+	let backend = AIBackend {
+		providers: crate::types::loadbalancer::EndpointSet::new(vec![
+			["skip-healthy", "skip-unhealthy"]
+				.into_iter()
+				.map(|n| (strng::new(n), provider(n)))
+				.collect(),
+		]),
+	};
+
+	backend.providers.evict(
+		strng::new("skip-unhealthy"),
+		std::time::Instant::now() + std::time::Duration::from_secs(60),
+	);
+	// Ejection is applied asynchronously by the eviction worker.
+	for _ in 0..100 {
+		if !backend.providers.skipped().is_empty() {
+			break;
+		}
+		tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+	}
+	assert_eq!(
+		backend.providers.skipped(),
+		vec![(strng::new("skip-unhealthy"), SkipReason::Ejected)]
+	);
+
+	let (p, _) = backend.select_provider().unwrap();
+	assert_eq!(p.name, "skip-healthy");
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("message", "providers skipped during selection"),
+		("selected", "skip-healthy"),
+	])
+	.await
+	.unwrap();
+	assert_eq!(log["skipped"], "skip-unhealthy=ejected");
+}
+
+#[tokio::test]
+async fn route_tokenize_override_enables_tokenization() {
 	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
 	use crate::test_helpers::proxymock::setup_proxy_test;
 	use crate::types::agent::BackendTarget;
 
-	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let named = NamedAIProvider {
+		name: "default".into(),
+		provider: AIProvider::OpenAI(openai::Provider { model: None }),
+		provider_backend: None,
+		host_override: None,
+		path_override: None,
+		path_prefix: None,
+		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: 1,
+		request_size: None,
+		inline_policies: vec![],
+	};
 	let inputs = setup_proxy_test("{}").unwrap().pi;
 	let backend_info = BackendInfo {
 		target: BackendTarget::Invalid,
-		call_target: Target::from(("localhost", 11434)),
+		call_target: Target::from(("api.openai.com", 443)),
 		inputs,
 	};
+	let policy = Policy {
+		tokenize: Some(true),
+		..Default::default()
+	};
+	assert!(!named.should_tokenize(None));
+	assert!(named.should_tokenize(Some(&policy)));
+
 	let req = ::http::Request::builder()
 		.uri("/v1/chat/completions")
 		.header(::http::header::CONTENT_TYPE, "application/json")
 		.body(Body::from(
 			br#"{
-				"model": "llama3.1",
-				"max_tokens": 1024,
+				"model": "gpt-4o",
 				"messages": [{"role": "user", "content": "hello"}]
 			}"#
 				.to_vec(),
 		))
 		.unwrap();
 
-	let RequestResult::Success {
-		request: forwarded,
-		llm_request,
-		..
-	} = provider
-		.process_completions_request(&backend_info, None, req, false, &mut None)
+	let RequestResult::Success { llm_request, .. } = named
+		.provider
+		.process_completions_request(
+			&backend_info,
+			Some(&policy),
+			req,
+			named.should_tokenize(Some(&policy)),
+			&mut None,
+		)
 		.await
-		.expect("OpenAI-compatible completions request should process")
+		.expect("OpenAI completions request should process")
 	else {
 		panic!("expected forwarded request");
 	};
 
-	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
-	let forwarded_json: Value =
-		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
-
-	assert_eq!(forwarded_json["max_tokens"], json!(1024));
-	assert!(forwarded_json.get("max_completion_tokens").is_none());
-	assert_eq!(llm_request.params.max_tokens, Some(1024));
+	assert!(llm_request.input_tokens.is_some());
 }
 
 #[tokio::test]
-async fn count_tokens_resolves_model_alias_once_for_upstream_request() {
+async fn unknown_model_uses_external_tokenizer() {
+	use wiremock::matchers::{body_partial_json, method, path};
+	use wiremock::{Mock, MockServer, ResponseTemplate};
+
 	use crate::http::auth::BackendInfo;
 	use crate::llm::policy::Policy;
+	use crate::llm::policy::external_tokenizer::ExternalTokenizer;
 	use crate::test_helpers::proxymock::setup_proxy_test;
-	use crate::types::agent::BackendTarget;
+	use crate::types::agent::{BackendTarget, SimpleBackendReference};
+
+	let counter = MockServer::start().await;
+	Mock::given(method("POST"))
+		.and(path("/count_tokens"))
+		.and(body_partial_json(json!({"model": "acme-large"})))
+		.respond_with(ResponseTemplate::new(200).set_body_json(json!({"input_tokens": 42})))
+		.expect(1)
+		.mount(&counter)
+		.await;
 
-	let provider = AIProvider::Anthropic(anthropic::Provider { model: None });
 	let inputs = setup_proxy_test("{}").unwrap().pi;
 	let backend_info = BackendInfo {
 		target: BackendTarget::Invalid,
-		call_target: Target::from(("api.anthropic.com", 443)),
+		call_target: Target::from(("api.openai.com", 443)),
 		inputs,
 	};
 	let policy = Policy {
-		model_aliases: std::collections::HashMap::from([
-			(strng::new("short-name"), strng::new("middle-name")),
-			(strng::new("middle-name"), strng::new("final-name")),
-		]),
+		external_tokenizer: Some(ExternalTokenizer {
+			target: SimpleBackendReference::InlineBackend(Target::Address(*counter.address())),
+			timeout: None,
+			cache: Default::default(),
+		}),
 		..Default::default()
 	};
+	let input_tokens = async |model: &str| {
+		let req = ::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(
+				serde_json::to_vec(&json!({
+					"model": model,
+					"messages": [{"role": "user", "content": "hello"}]
+				}))
+				.unwrap(),
+			))
+			.unwrap();
+		let RequestResult::Success { llm_request, .. } =
+			AIProvider::OpenAI(openai::Provider { model: None })
+				.process_completions_request(&backend_info, Some(&policy), req, true, &mut None)
+				.await
+				.expect("completions request should process")
+		else {
+			panic!("expected forwarded request");
+		};
+		llm_request.input_tokens
+	};
+
+	assert_eq!(input_tokens("acme-large").await, Some(42));
+	// Repeated prompts are served from the cache.
+	assert_eq!(input_tokens("acme-large").await, Some(42));
+	// Models with a local tokenizer are still counted locally.
+	assert_ne!(input_tokens("gpt-4o").await, Some(42));
+
+	// Embeddings have no messages to send to the external tokenizer, so it is skipped.
 	let req = ::http::Request::builder()
-		.uri("/v1/messages/count_tokens")
+		.uri("/v1/embeddings")
 		.header(::http::header::CONTENT_TYPE, "application/json")
 		.body(Body::from(
-			br#"{
-				"model": "short-name",
-				"messages": [{"role": "user", "content": "hello"}]
-			}"#
-				.to_vec(),
+			serde_json::to_vec(&json!({
+				"model": "text-embedding-3-small",
+				"input": "hello"
+			}))
+			.unwrap(),
 		))
 		.unwrap();
-
-	let RequestResult::Success {
-		request: forwarded,
-		llm_request,
-		..
-	} = provider
-		.process_count_tokens_request(&backend_info, req, Some(&policy), &mut None)
-		.await
-		.expect("count_tokens request should process")
+	let RequestResult::Success { llm_request, .. } =
+		AIProvider::OpenAI(openai::Provider { model: None })
+			.process_embeddings_request(&backend_info, Some(&policy), req, true, &mut None)
+			.await
+			.expect("embeddings request should process")
 	else {
 		panic!("expected forwarded request");
 	};
+	assert_eq!(llm_request.input_tokens, None);
+}
 
-	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
-	let forwarded_json: Value =
-		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+#[tokio::test]
+async fn audit_mirror_delivers_redacted_copy_without_blocking() {
+	use std::time::{Duration, Instant};
 
-	assert_eq!(forwarded_json["model"], json!("middle-name"));
-	assert_eq!(llm_request.request_model, "middle-name");
+	use wiremock::matchers::{method, path};
+	use wiremock::{Mock, MockServer, ResponseTemplate};
+
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::audit_mirror::{AuditMirror, PendingLimit};
+	use crate::llm::policy::{Action, Builtin, Policy, RegexRule, RegexRules};
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::{BackendTarget, SimpleBackendReference};
+
+	let audit = MockServer::start().await;
+	// A slow webhook must not slow down the request.
+	Mock::given(method("POST"))
+		.and(path("/audit"))
+		.respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+		.mount(&audit)
+		.await;
+
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs: setup_proxy_test("{}").unwrap().pi,
+	};
+	let policy = Policy {
+		audit_mirror: Some(AuditMirror {
+			target: SimpleBackendReference::InlineBackend(Target::Address(*audit.address())),
+			redact: Some(RegexRules {
+				action: Action::Mask,
+				rules: vec![RegexRule::Builtin {
+					builtin: Builtin::Email,
+				}],
+			}),
+			max_pending: PendingLimit::new(1),
+		}),
+		..Default::default()
+	};
+	let send = async || {
+		let req = ::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(
+				serde_json::to_vec(&json!({
+					"model": "gpt-4o",
+					"messages": [{"role": "user", "content": "hi, I am jane@example.com"}]
+				}))
+				.unwrap(),
+			))
+			.unwrap();
+		AIProvider::OpenAI(openai::Provider { model: None })
+			.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
+			.await
+			.expect("completions request should process")
+	};
+
+	let t0 = Instant::now();
+	assert!(matches!(send().await, RequestResult::Success { .. }));
+	// The first copy is still pending, so this one is dropped rather than queued.
+	assert!(matches!(send().await, RequestResult::Success { .. }));
+	assert!(t0.elapsed() < Duration::from_millis(500));
+
+	let deadline = Instant::now() + Duration::from_secs(5);
+	let received = loop {
+		let received = audit.received_requests().await.unwrap();
+		if !received.is_empty() || Instant::now() > deadline {
+			break received;
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+	};
+	tokio::time::sleep(Duration::from_millis(600)).await;
+	assert_eq!(audit.received_requests().await.unwrap().len(), 1);
+
+	let body: Value = serde_json::from_slice(&received[0].body).unwrap();
+	assert_eq!(body["model"], "gpt-4o");
+	assert_eq!(body["messages"][0]["role"], "user");
+	let content = body["messages"][0]["content"].as_str().unwrap();
+	assert!(!content.contains("jane@example.com"), "{content}");
 }
 
 #[tokio::test]
-async fn count_tokens_uses_native_endpoint_after_model_alias() {
+async fn model_allow_and_deny_lists() {
 	use crate::http::auth::BackendInfo;
 	use crate::llm::policy::Policy;
 	use crate::test_helpers::proxymock::setup_proxy_test;
 	use crate::types::agent::BackendTarget;
 
-	let provider = AIProvider::Vertex(vertex::Provider {
-		model: None,
-		region: None,
-		project_id: strng::new("test-project"),
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs: setup_proxy_test("{}").unwrap().pi,
+	};
+	let policy = Policy {
+		allowed_models: Some(vec![strng::new("gpt-4*"), strng::new("o3")]),
+		denied_models: Some(vec![strng::new("gpt-4-32k*")]),
+		model_aliases: [(strng::new("big"), strng::new("gpt-4-32k-0613"))]
+			.into_iter()
+			.collect(),
+		..Default::default()
+	};
+	let body = |model: &str| {
+		Body::from(
+			serde_json::to_vec(&json!({
+				"model": model,
+				"messages": [{"role": "user", "content": "hello"}],
+				"max_tokens": 16
+			}))
+			.unwrap(),
+		)
+	};
+	let completions = async |model: &str| {
+		let req = ::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(body(model))
+			.unwrap();
+		AIProvider::OpenAI(openai::Provider { model: None })
+			.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
+			.await
+			.expect("completions request should process")
+	};
+	let rejected = async |result: RequestResult| -> Value {
+		let RequestResult::Rejected(resp) = result else {
+			panic!("expected rejected request");
+		};
+		assert_eq!(resp.status(), ::http::StatusCode::FORBIDDEN);
+		serde_json::from_slice(&resp.collect().await.unwrap().to_bytes()).unwrap()
+	};
+
+	// Exact and glob allow entries.
+	assert!(matches!(
+		completions("o3").await,
+		RequestResult::Success { .. }
+	));
+	assert!(matches!(
+		completions("gpt-4o-mini").await,
+		RequestResult::Success { .. }
+	));
+	// Not on the allow list.
+	let err = rejected(completions("claude-sonnet-4").await).await;
+	assert_eq!(err["error"]["code"], "model_not_allowed");
+	assert_eq!(
+		err["error"]["message"],
+		"model claude-sonnet-4 is not allowed"
+	);
+	// The deny list wins over a matching allow glob.
+	rejected(completions("gpt-4-32k").await).await;
+	// An alias is checked by the model it resolves to.
+	let err = rejected(completions("big").await).await;
+	assert_eq!(
+		err["error"]["message"],
+		"model gpt-4-32k-0613 is not allowed"
+	);
+
+	// Anthropic clients get an Anthropic-shaped error.
+	let req = ::http::Request::builder()
+		.uri("/v1/messages")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(body("claude-sonnet-4"))
+		.unwrap();
+	let err = rejected(
+		AIProvider::Anthropic(anthropic::Provider {
+			model: None,
+			api_version: None,
+		})
+		.process_messages_request(&backend_info, Some(&policy), req, false, &mut None)
+		.await
+		.expect("messages request should process"),
+	)
+	.await;
+	assert_eq!(err["type"], "error");
+	assert_eq!(err["error"]["type"], "permission_error");
+
+	// Passthrough requests are checked too, with a missing model resolved to the provider's model.
+	let detect = async |provider_model: Option<&str>, body: Value| {
+		let req = ::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(serde_json::to_vec(&body).unwrap()))
+			.unwrap();
+		AIProvider::OpenAI(openai::Provider {
+			model: provider_model.map(strng::new),
+		})
+		.process_detect_request(&backend_info, Some(&policy), req, &mut None)
+		.await
+		.expect("passthrough request should process")
+	};
+	rejected(detect(None, json!({"model": "claude-sonnet-4"})).await).await;
+	assert!(matches!(
+		detect(None, json!({"model": "gpt-4o"})).await,
+		RequestResult::Success { .. }
+	));
+	assert!(matches!(
+		detect(Some("gpt-4o"), json!({"input": "hello"})).await,
+		RequestResult::Success { .. }
+	));
+	rejected(detect(Some("gpt-4-32k"), json!({"input": "hello"})).await).await;
+	// Without any model, only a policy without an allow list admits the request.
+	rejected(detect(None, json!({"input": "hello"})).await).await;
+}
+
+#[tokio::test]
+async fn local_token_count_includes_images_and_tool_calls() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs: setup_proxy_test("{}").unwrap().pi,
+	};
+	let input_tokens = async |body: Value, policy: Option<&Policy>| {
+		let req = ::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(serde_json::to_vec(&body).unwrap()))
+			.unwrap();
+		let RequestResult::Success { llm_request, .. } =
+			AIProvider::OpenAI(openai::Provider { model: None })
+				.process_completions_request(&backend_info, policy, req, true, &mut None)
+				.await
+				.expect("completions request should process")
+		else {
+			panic!("expected forwarded request");
+		};
+		llm_request.input_tokens.expect("tokens should be counted")
+	};
+
+	let text_only = input_tokens(
+		json!({
+			"model": "gpt-4o",
+			"messages": [{"role": "user", "content": [{"type": "text", "text": "what is this?"}]}]
+		}),
+		None,
+	)
+	.await;
+
+	let image = json!({
+		"model": "gpt-4o",
+		"messages": [{"role": "user", "content": [
+			{"type": "text", "text": "what is this?"},
+			{"type": "image_url", "image_url": {"url": "https://example.com/cat.png"}}
+		]}]
 	});
+	assert_eq!(
+		input_tokens(image.clone(), None).await,
+		text_only + DEFAULT_IMAGE_TOKENS
+	);
+	let policy = Policy {
+		image_tokens: Some(1000),
+		..Default::default()
+	};
+	assert_eq!(input_tokens(image, Some(&policy)).await, text_only + 1000);
+
+	let tool_call = input_tokens(
+		json!({
+			"model": "gpt-4o",
+			"messages": [
+				{"role": "user", "content": [{"type": "text", "text": "what is this?"}]},
+				{"role": "assistant", "tool_calls": [{
+					"id": "call_1",
+					"type": "function",
+					"function": {"name": "lookup_weather", "arguments": "{\"city\":\"Amsterdam\"}"}
+				}]}
+			],
+			"tools": [{"type": "function", "function": {"name": "lookup_weather", "parameters": {"type": "object"}}}]
+		}),
+		None,
+	)
+	.await;
+	// One extra message adds 4 tokens of overhead; the call and tool definition add more.
+	assert!(tool_call > text_only + 4 + 10, "{tool_call} vs {text_only}");
+}
+
+#[tokio::test]
+async fn default_params_fill_only_unset_fields() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::{DefaultParams, Policy};
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
 	let inputs = setup_proxy_test("{}").unwrap().pi;
 	let backend_info = BackendInfo {
 		target: BackendTarget::Invalid,
-		call_target: Target::from(("us-central1-aiplatform.googleapis.com", 443)),
+		call_target: Target::from(("api.openai.com", 443)),
 		inputs,
 	};
 	let policy = Policy {
-		model_aliases: std::collections::HashMap::from([(
-			strng::new("short-name"),
-			strng::new("claude-3-5-sonnet"),
-		)]),
+		default_params: Some(DefaultParams {
+			temperature: Some(0.25),
+			top_p: Some(0.75),
+			max_tokens: Some(128),
+			..Default::default()
+		}),
 		..Default::default()
 	};
 	let req = ::http::Request::builder()
-		.uri("/v1/messages/count_tokens")
+		.uri("/v1/chat/completions")
 		.header(::http::header::CONTENT_TYPE, "application/json")
 		.body(Body::from(
 			br#"{
-				"model": "short-name",
+				"model": "gpt-4o",
+				"top_p": 0.5,
 				"messages": [{"role": "user", "content": "hello"}]
 			}"#
 				.to_vec(),
@@ -376,12 +1089,11 @@ async fn count_tokens_uses_native_endpoint_after_model_alias() {
 	let RequestResult::Success {
 		request: forwarded,
 		llm_request,
-		upstream_route_type,
 		..
 	} = provider
-		.process_count_tokens_request(&backend_info, req, Some(&policy), &mut None)
+		.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
 		.await
-		.expect("count_tokens request should process")
+		.expect("OpenAI completions request should process")
 	else {
 		panic!("expected forwarded request");
 	};
@@ -390,75 +1102,78 @@ async fn count_tokens_uses_native_endpoint_after_model_alias() {
 	let forwarded_json: Value =
 		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
 
-	assert_eq!(upstream_route_type, RouteType::AnthropicTokenCount);
-	assert_eq!(forwarded_json["model"], json!("claude-3-5-sonnet"));
-	assert_eq!(llm_request.request_model, "claude-3-5-sonnet");
+	assert_eq!(forwarded_json["temperature"], json!(0.25));
+	assert_eq!(forwarded_json["top_p"], json!(0.5));
+	assert_eq!(llm_request.params.temperature, Some(0.25));
+	assert_eq!(llm_request.params.top_p, Some(0.5));
+	assert_eq!(llm_request.params.max_tokens, Some(128));
 }
 
 #[tokio::test]
-async fn vertex_anthropic_messages_prepares_vertex_body() {
+async fn param_limits_reject_out_of_bounds_max_tokens() {
 	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::{ParamLimits, Policy};
 	use crate::test_helpers::proxymock::setup_proxy_test;
 	use crate::types::agent::BackendTarget;
 
-	let provider = AIProvider::Vertex(vertex::Provider {
-		model: None,
-		region: Some(strng::new("us-central1")),
-		project_id: strng::new("test-project"),
-	});
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
 	let inputs = setup_proxy_test("{}").unwrap().pi;
 	let backend_info = BackendInfo {
 		target: BackendTarget::Invalid,
-		call_target: Target::from(("us-central1-aiplatform.googleapis.com", 443)),
+		call_target: Target::from(("api.openai.com", 443)),
 		inputs,
 	};
+	let policy = Policy {
+		param_limits: Some(ParamLimits {
+			max_tokens: Some(100_000),
+			..Default::default()
+		}),
+		..Default::default()
+	};
 	let req = ::http::Request::builder()
-		.uri("/v1/messages")
+		.uri("/v1/chat/completions")
 		.header(::http::header::CONTENT_TYPE, "application/json")
 		.body(Body::from(
 			br#"{
-				"model": "claude-haiku-4-5-20251001",
-				"max_tokens": 64,
-				"messages": [{"role": "user", "content": "say hi"}]
+				"model": "gpt-4o",
+				"max_tokens": 200000,
+				"messages": [{"role": "user", "content": "hello"}]
 			}"#
 				.to_vec(),
 		))
 		.unwrap();
 
-	let RequestResult::Success {
-		request: forwarded,
-		upstream_route_type,
-		..
-	} = provider
-		.process_messages_request(&backend_info, None, req, false, &mut None)
+	let RequestResult::Rejected(resp) = provider
+		.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
 		.await
-		.expect("Vertex Anthropic messages request should process")
+		.expect("OpenAI completions request should process")
 	else {
-		panic!("expected forwarded request");
+		panic!("expected request to be rejected");
 	};
 
-	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
-	let forwarded_json: Value =
-		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
-
-	assert_eq!(upstream_route_type, RouteType::Messages);
-	assert!(forwarded_json.get("model").is_none());
+	assert_eq!(resp.status(), ::http::StatusCode::BAD_REQUEST);
+	let body = resp.into_body().collect().await.unwrap().to_bytes();
+	let body: Value = serde_json::from_slice(&body).expect("rejection should be JSON");
 	assert_eq!(
-		forwarded_json["anthropic_version"],
-		json!("vertex-2023-10-16")
+		body,
+		json!({
+			"error": {
+				"message": "max_tokens 200000 exceeds the maximum of 100000",
+				"type": "invalid_request_error",
+				"param": "max_tokens",
+			}
+		})
 	);
 }
 
 #[tokio::test]
-async fn provider_model_is_set_before_llm_transformations() {
+async fn max_prompt_chars_rejects_before_tokenization() {
 	use crate::http::auth::BackendInfo;
 	use crate::llm::policy::Policy;
 	use crate::test_helpers::proxymock::setup_proxy_test;
 	use crate::types::agent::BackendTarget;
 
-	let provider = AIProvider::OpenAI(openai::Provider {
-		model: Some("gcp/failover-model".into()),
-	});
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
 	let inputs = setup_proxy_test("{}").unwrap().pi;
 	let backend_info = BackendInfo {
 		target: BackendTarget::Invalid,
@@ -466,52 +1181,59 @@ async fn provider_model_is_set_before_llm_transformations() {
 		inputs,
 	};
 	let policy = Policy {
-		transformations: Some(
-			[(
-				"model".to_string(),
-				std::sync::Arc::new(
-					crate::cel::Expression::new_strict(r#"llmRequest.model.stripPrefix("gcp/")"#).unwrap(),
-				),
-			)]
-			.into_iter()
-			.collect(),
-		),
+		max_prompt_chars: Some(10),
 		..Default::default()
 	};
-	let req = ::http::Request::builder()
-		.uri("/v1/chat/completions")
-		.header(::http::header::CONTENT_TYPE, "application/json")
-		.body(Body::from(
-			br#"{
-				"model": "public-model",
-				"messages": [{"role": "user", "content": "hello"}]
-			}"#
-				.to_vec(),
-		))
-		.unwrap();
+	let req = |content: &str| {
+		::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(
+				serde_json::to_vec(&json!({
+					"model": "gpt-4o",
+					"messages": [
+						{"role": "system", "content": "hi"},
+						{"role": "user", "content": content}
+					]
+				}))
+				.unwrap(),
+			))
+			.unwrap()
+	};
 
-	let RequestResult::Success {
-		request: forwarded,
-		llm_request,
-		..
-	} = provider
-		.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
+	// Rejected requests never produce an LLM request, so tokenization did not run.
+	let RequestResult::Rejected(resp) = provider
+		.process_completions_request(
+			&backend_info,
+			Some(&policy),
+			req("hello world"),
+			true,
+			&mut None,
+		)
 		.await
 		.expect("OpenAI completions request should process")
 	else {
-		panic!("expected forwarded request");
+		panic!("expected request to be rejected");
 	};
+	assert_eq!(resp.status(), ::http::StatusCode::PAYLOAD_TOO_LARGE);
+	let body = resp.into_body().collect().await.unwrap().to_bytes();
+	let body: Value = serde_json::from_slice(&body).expect("rejection should be JSON");
+	assert_eq!(
+		body["error"]["message"],
+		"prompt has 13 characters, exceeding the maximum of 10"
+	);
 
-	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
-	let forwarded_json: Value =
-		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
-
-	assert_eq!(forwarded_json["model"], json!("failover-model"));
-	assert_eq!(llm_request.request_model, "failover-model");
+	let RequestResult::Success { .. } = provider
+		.process_completions_request(&backend_info, Some(&policy), req("hello"), false, &mut None)
+		.await
+		.expect("OpenAI completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
 }
 
 #[tokio::test]
-async fn llm_transformations_can_set_missing_model() {
+async fn max_prompt_chars_ignores_embeddings() {
 	use crate::http::auth::BackendInfo;
 	use crate::llm::policy::Policy;
 	use crate::test_helpers::proxymock::setup_proxy_test;
@@ -525,442 +1247,2118 @@ async fn llm_transformations_can_set_missing_model() {
 		inputs,
 	};
 	let policy = Policy {
-		transformations: Some(
-			[(
-				"model".to_string(),
-				std::sync::Arc::new(crate::cel::Expression::new_strict(r#""transformed-model""#).unwrap()),
-			)]
-			.into_iter()
-			.collect(),
-		),
+		max_prompt_chars: Some(1),
 		..Default::default()
 	};
 	let req = ::http::Request::builder()
-		.uri("/v1/chat/completions")
+		.uri("/v1/embeddings")
 		.header(::http::header::CONTENT_TYPE, "application/json")
 		.body(Body::from(
-			br#"{
-				"messages": [{"role": "user", "content": "hello"}]
-			}"#
-				.to_vec(),
+			serde_json::to_vec(&json!({
+				"model": "text-embedding-3-small",
+				"input": "hello world"
+			}))
+			.unwrap(),
 		))
 		.unwrap();
 
-	let RequestResult::Success {
-		request: forwarded,
-		llm_request,
-		..
-	} = provider
-		.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
+	// Embeddings have no messages, so the prompt limit does not apply.
+	let RequestResult::Success { .. } = provider
+		.process_embeddings_request(&backend_info, Some(&policy), req, false, &mut None)
 		.await
-		.expect("OpenAI completions request should process")
+		.expect("OpenAI embeddings request should process")
 	else {
 		panic!("expected forwarded request");
 	};
-
-	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
-	let forwarded_json: Value =
-		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
-
-	assert_eq!(forwarded_json["model"], json!("transformed-model"));
-	assert_eq!(llm_request.request_model, "transformed-model");
 }
 
 #[tokio::test]
-async fn copilot_anthropic_model_uses_messages_route() {
+async fn max_prompt_chars_applies_to_text_completions() {
 	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
 	use crate::test_helpers::proxymock::setup_proxy_test;
 	use crate::types::agent::BackendTarget;
 
-	let provider = AIProvider::Copilot(copilot::Provider { model: None });
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
 	let inputs = setup_proxy_test("{}").unwrap().pi;
 	let backend_info = BackendInfo {
 		target: BackendTarget::Invalid,
-		call_target: Target::from(("api.githubcopilot.com", 443)),
+		call_target: Target::from(("api.openai.com", 443)),
 		inputs,
 	};
-	let req = ::http::Request::builder()
-		.uri("/v1/messages")
-		.header(::http::header::CONTENT_TYPE, "application/json")
-		.body(Body::from(
-			br#"{
-				"model": "claude-sonnet-4",
-				"max_tokens": 64,
-				"messages": [{"role": "user", "content": "say hi"}]
-			}"#
-				.to_vec(),
-		))
-		.unwrap();
+	let policy = Policy {
+		max_prompt_chars: Some(10),
+		..Default::default()
+	};
+	let req = |prompt: Value| {
+		::http::Request::builder()
+			.uri("/v1/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(
+				serde_json::to_vec(&json!({
+					"model": "gpt-3.5-turbo-instruct",
+					"prompt": prompt,
+				}))
+				.unwrap(),
+			))
+			.unwrap()
+	};
 
-	let RequestResult::Success {
-		request: forwarded,
-		llm_request,
-		upstream_route_type,
-	} = provider
-		.process_messages_request(&backend_info, None, req, false, &mut None)
+	// Every text prompt in a batch counts; token arrays carry no text.
+	let RequestResult::Rejected(resp) = provider
+		.process_text_completions_request(
+			&backend_info,
+			Some(&policy),
+			req(json!(["hello", "world!", [1, 2, 3]])),
+			false,
+			&mut None,
+		)
 		.await
-		.expect("Copilot Anthropic messages request should process")
+		.expect("OpenAI text completions request should process")
 	else {
-		panic!("expected forwarded request");
+		panic!("expected request to be rejected");
 	};
-
-	assert_eq!(upstream_route_type, RouteType::Messages);
+	assert_eq!(resp.status(), ::http::StatusCode::PAYLOAD_TOO_LARGE);
+	let body = resp.into_body().collect().await.unwrap().to_bytes();
+	let body: Value = serde_json::from_slice(&body).expect("rejection should be JSON");
 	assert_eq!(
-		llm_request.cache_convention,
-		CacheTokenConvention::InputExcludesCache
+		body["error"]["message"],
+		"prompt has 11 characters, exceeding the maximum of 10"
 	);
 
-	let mut setup_req =
-		crate::http::tests_common::request("https://example.com/v1/messages", http::Method::POST, &[]);
-	provider
-		.setup_request(
-			&mut setup_req,
-			upstream_route_type,
-			Some(&llm_request),
-			None,
-			None,
+	let RequestResult::Success { .. } = provider
+		.process_text_completions_request(
+			&backend_info,
+			Some(&policy),
+			req(json!("hello")),
 			false,
+			&mut None,
 		)
-		.expect("setup_request should succeed");
-	assert_eq!(setup_req.uri().path(), "/v1/messages");
-
-	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
-	let forwarded_json: Value =
-		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
-	assert_eq!(forwarded_json["model"], json!("claude-sonnet-4"));
-	assert_eq!(forwarded_json["max_tokens"], json!(64));
+		.await
+		.expect("OpenAI text completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
 }
 
-#[test]
-fn openai_token_limit_normalization_keeps_explicit_max_completion_tokens() {
-	let mut request: types::completions::Request = serde_json::from_value(json!({
-		"model": "gpt-5.4",
-		"max_tokens": 1024,
-		"max_completion_tokens": 2048,
-		"messages": [{"role": "user", "content": "hello"}]
-	}))
-	.expect("valid completions request");
+#[tokio::test]
+async fn deeply_nested_json_rejected_before_parsing() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
 
-	request.normalize_openai_token_limit();
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs: inputs.clone(),
+	};
+	let req = |metadata: String| {
+		::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(format!(
+				r#"{{"model":"gpt-4o","messages":[{{"role":"user","content":"hi"}}],"metadata":{metadata}}}"#
+			)))
+			.unwrap()
+	};
+	let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
 
-	assert_eq!(request.max_tokens, None);
-	assert_eq!(request.max_completion_tokens, Some(2048));
-}
+	// Far beyond any real request; rejected by the default limit without recursing into it.
+	let Err(err) = provider
+		.process_completions_request(&backend_info, None, req(nested(100_000)), false, &mut None)
+		.await
+	else {
+		panic!("expected deeply nested request to be rejected");
+	};
+	assert!(matches!(err, AIError::RequestTooDeep(128)), "{err}");
+	let crate::proxy::ProxyResponse::DirectResponse(resp) =
+		provider.request_error(RouteType::Completions, err, &inputs.metrics)
+	else {
+		panic!("expected a direct response");
+	};
+	assert_eq!(resp.status(), ::http::StatusCode::BAD_REQUEST);
+	let body = resp.into_body().collect().await.unwrap().to_bytes();
+	let body: Value = serde_json::from_slice(&body).expect("rejection should be JSON");
+	assert_eq!(body["error"]["code"], "request_too_deep");
 
-#[test]
-fn test_adaptive_thinking_without_effort_maps_to_high_reasoning_effort() {
-	let request: types::messages::Request = serde_json::from_value(json!({
-		"model": "claude-opus-4-6",
-		"max_tokens": 256,
-		"thinking": {
-			"type": "adaptive"
-		},
-		"messages": [
-			{
-				"role": "user",
-				"content": "Give one concise insight."
-			}
-		]
-	}))
-	.expect("valid messages request");
+	// Brackets inside strings do not count towards the depth.
+	let policy = Policy {
+		max_json_depth: Some(4),
+		..Default::default()
+	};
+	let RequestResult::Success { .. } = provider
+		.process_completions_request(
+			&backend_info,
+			Some(&policy),
+			req(r#"{"a":"[[[[[{{{{{"}"#.to_string()),
+			false,
+			&mut None,
+		)
+		.await
+		.expect("shallow request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+	let Err(err) = provider
+		.process_completions_request(
+			&backend_info,
+			Some(&policy),
+			req(nested(4)),
+			false,
+			&mut None,
+		)
+		.await
+	else {
+		panic!("expected request nested past the policy limit to be rejected");
+	};
+	assert!(matches!(err, AIError::RequestTooDeep(4)), "{err}");
+}
 
-	let translated = conversion::completions::from_messages::translate(&request)
-		.expect("messages->completions translation");
-	let translated: Value =
-		serde_json::from_slice(&translated).expect("translated request should be valid json");
+#[tokio::test]
+async fn developer_role_normalized_per_provider() {
+	use crate::http::auth::BackendInfo;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
 
-	assert_eq!(translated.get("reasoning_effort"), Some(&json!("high")));
-}
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("example.com", 443)),
+		inputs,
+	};
+	let req = || {
+		::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(
+				serde_json::to_vec(&json!({
+					"model": "some-model",
+					"messages": [
+						{"role": "developer", "content": "be brief"},
+						{"role": "user", "content": "hello"}
+					]
+				}))
+				.unwrap(),
+			))
+			.unwrap()
+	};
+	let forward = async |provider: &AIProvider| -> Value {
+		let RequestResult::Success { request, .. } = provider
+			.process_completions_request(&backend_info, None, req(), false, &mut None)
+			.await
+			.expect("completions request should process")
+		else {
+			panic!("expected forwarded request");
+		};
+		let body = request.collect().await.unwrap().to_bytes();
+		serde_json::from_slice(&body).expect("forwarded request should be JSON")
+	};
 
-#[test]
+	// Providers with the `developer` role receive it unchanged.
+	for provider in [
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		AIProvider::Copilot(copilot::Provider { model: None }),
+	] {
+		let body = forward(&provider).await;
+		assert_eq!(
+			body["messages"][0]["role"],
+			"developer",
+			"{}",
+			provider.provider()
+		);
+	}
+	// OpenAI-compatible providers without it receive a `system` message instead.
+	for provider in [
+		AIProvider::Gemini(gemini::Provider { model: None }),
+		custom_provider(custom::ProviderFormat::Completions),
+	] {
+		let body = forward(&provider).await;
+		assert_eq!(
+			body["messages"][0]["role"],
+			"system",
+			"{}",
+			provider.provider()
+		);
+		assert_eq!(body["messages"][0]["content"], "be brief");
+		assert_eq!(body["messages"][1]["role"], "user");
+	}
+	// Translated providers carry it as the system prompt.
+	let body = forward(&AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	}))
+	.await;
+	assert_eq!(body["system"], "be brief");
+	assert_eq!(body["messages"][0]["role"], "user");
+}
+
+#[tokio::test]
+async fn logit_bias_preserved_only_for_supporting_providers() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::{Policy, UnsupportedLogitBias};
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("example.com", 443)),
+		inputs,
+	};
+	let req = || {
+		::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(
+				serde_json::to_vec(&json!({
+					"model": "some-model",
+					"messages": [{"role": "user", "content": "hello"}],
+					"logit_bias": {"50256": -100}
+				}))
+				.unwrap(),
+			))
+			.unwrap()
+	};
+	let process = async |provider: &AIProvider, policy: Option<&Policy>| {
+		provider
+			.process_completions_request(&backend_info, policy, req(), false, &mut None)
+			.await
+			.expect("completions request should process")
+	};
+	let forward = async |provider: &AIProvider| -> Value {
+		let RequestResult::Success { request, .. } = process(provider, None).await else {
+			panic!("expected forwarded request");
+		};
+		let body = request.collect().await.unwrap().to_bytes();
+		serde_json::from_slice(&body).expect("forwarded request should be JSON")
+	};
+
+	let body = forward(&AIProvider::OpenAI(openai::Provider { model: None })).await;
+	assert_eq!(body["logit_bias"], json!({"50256": -100}));
+	let body = forward(&custom_provider(custom::ProviderFormat::Completions)).await;
+	assert_eq!(body["logit_bias"], json!({"50256": -100}));
+
+	let anthropic = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
+	let body = forward(&anthropic).await;
+	assert!(body.get("logit_bias").is_none(), "{body}");
+	let log = agent_core::telemetry::testing::eventually_find(&[(
+		"message",
+		"provider does not support `logit_bias`; dropping it",
+	)])
+	.await
+	.unwrap();
+	assert_eq!(log["provider"], "anthropic");
+
+	let policy = Policy {
+		unsupported_logit_bias: Some(UnsupportedLogitBias::Error),
+		..Default::default()
+	};
+	let RequestResult::Rejected(resp) = process(&anthropic, Some(&policy)).await else {
+		panic!("expected rejected request");
+	};
+	assert_eq!(resp.status(), ::http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn mistral_completions_round_trip() {
+	use crate::http::auth::BackendInfo;
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::Mistral(mistral::Provider { model: None });
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from((mistral::DEFAULT_HOST_STR, 443)),
+		inputs: inputs.clone(),
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/chat/completions")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			serde_json::to_vec(&json!({
+				"model": "mistral-small-latest",
+				"messages": [{"role": "user", "content": "hello"}],
+				"max_tokens": 32,
+				"logit_bias": {"50256": -100}
+			}))
+			.unwrap(),
+		))
+		.unwrap();
+	let RequestResult::Success {
+		mut request,
+		llm_request,
+		..
+	} = provider
+		.process_completions_request(&backend_info, None, req, false, &mut None)
+		.await
+		.expect("completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+	provider
+		.setup_request(
+			&mut request,
+			RouteType::Completions,
+			Some(&llm_request),
+			None,
+			None,
+			false,
+			None,
+		)
+		.expect("setup_request should succeed");
+	assert_eq!(request.uri().path(), "/v1/chat/completions");
+	let body: Value =
+		serde_json::from_slice(&request.into_body().collect().await.unwrap().to_bytes()).unwrap();
+	assert!(body.get("logit_bias").is_none(), "{body}");
+	assert_eq!(body["max_tokens"], 32);
+	assert_eq!(body["messages"][0]["content"], "hello");
+
+	let upstream = json!({
+		"id": "cmpl-1",
+		"object": "chat.completion",
+		"created": 1,
+		"model": "mistral-small-latest",
+		"choices": [{"index": 0, "message": {"role": "assistant", "content": "hi"}, "finish_reason": "stop"}],
+		"usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+	});
+	let mut resp = Response::new(Body::from(serde_json::to_vec(&upstream).unwrap()));
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"application/json".parse().unwrap(),
+	);
+	let log = AsyncLog::default();
+	let result = provider
+		.process_response(
+			PolicyClient::new(inputs),
+			llm_request,
+			LLMResponsePolicies::default(),
+			None,
+			log.clone(),
+			false,
+			None,
+			resp,
+		)
+		.await
+		.expect("response should process");
+	let result: Value = serde_json::from_slice(&result.collect().await.unwrap().to_bytes()).unwrap();
+	assert_eq!(result["choices"][0]["message"]["content"], "hi");
+
+	let info = log.take().expect("llm info should be logged");
+	assert_eq!(info.request.provider.as_str(), "mistral");
+	assert_eq!(info.response.input_tokens, Some(5));
+	assert_eq!(info.response.output_tokens, Some(2));
+}
+
+#[tokio::test]
+async fn streaming_requests_on_non_streaming_route() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::{Policy, StreamingRequests};
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs,
+	};
+	let policy = |mode| Policy {
+		streaming_requests: Some(mode),
+		..Default::default()
+	};
+	let req = || {
+		::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.body(Body::from(
+				br#"{
+					"model": "gpt-4o",
+					"stream": true,
+					"stream_options": {"include_usage": true},
+					"messages": [{"role": "user", "content": "hello"}]
+				}"#
+					.to_vec(),
+			))
+			.unwrap()
+	};
+
+	let RequestResult::Success {
+		request: forwarded,
+		llm_request,
+		..
+	} = provider
+		.process_completions_request(
+			&backend_info,
+			Some(&policy(StreamingRequests::Downgrade)),
+			req(),
+			false,
+			&mut None,
+		)
+		.await
+		.expect("OpenAI completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+	assert!(!llm_request.streaming);
+	assert!(forwarded.extensions().get::<StreamDowngraded>().is_some());
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+	assert!(forwarded_json.get("stream").is_none());
+	assert!(forwarded_json.get("stream_options").is_none());
+
+	let RequestResult::Rejected(resp) = provider
+		.process_completions_request(
+			&backend_info,
+			Some(&policy(StreamingRequests::Reject)),
+			req(),
+			false,
+			&mut None,
+		)
+		.await
+		.expect("OpenAI completions request should process")
+	else {
+		panic!("expected request to be rejected");
+	};
+	assert_eq!(resp.status(), ::http::StatusCode::BAD_REQUEST);
+
+	let RequestResult::Success { llm_request, .. } = provider
+		.process_completions_request(
+			&backend_info,
+			Some(&policy(StreamingRequests::Honor)),
+			req(),
+			false,
+			&mut None,
+		)
+		.await
+		.expect("OpenAI completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+	assert!(llm_request.streaming);
+}
+
+#[tokio::test]
+async fn openai_provider_preserves_max_tokens_for_non_gpt_models() {
+	use crate::http::auth::BackendInfo;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("localhost", 11434)),
+		inputs,
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/chat/completions")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "llama3.1",
+				"max_tokens": 1024,
+				"messages": [{"role": "user", "content": "hello"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::Success {
+		request: forwarded,
+		llm_request,
+		..
+	} = provider
+		.process_completions_request(&backend_info, None, req, false, &mut None)
+		.await
+		.expect("OpenAI-compatible completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+
+	assert_eq!(forwarded_json["max_tokens"], json!(1024));
+	assert!(forwarded_json.get("max_completion_tokens").is_none());
+	assert_eq!(llm_request.params.max_tokens, Some(1024));
+}
+
+#[tokio::test]
+async fn count_tokens_resolves_model_alias_once_for_upstream_request() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.anthropic.com", 443)),
+		inputs,
+	};
+	let policy = Policy {
+		model_aliases: std::collections::HashMap::from([
+			(strng::new("short-name"), strng::new("middle-name")),
+			(strng::new("middle-name"), strng::new("final-name")),
+		]),
+		..Default::default()
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/messages/count_tokens")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "short-name",
+				"messages": [{"role": "user", "content": "hello"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::Success {
+		request: forwarded,
+		llm_request,
+		..
+	} = provider
+		.process_count_tokens_request(&backend_info, req, Some(&policy), &mut None)
+		.await
+		.expect("count_tokens request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+
+	assert_eq!(forwarded_json["model"], json!("middle-name"));
+	assert_eq!(llm_request.request_model, "middle-name");
+}
+
+#[tokio::test]
+async fn count_tokens_uses_native_endpoint_after_model_alias() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::Vertex(vertex::Provider {
+		model: None,
+		region: None,
+		project_id: strng::new("test-project"),
+		anthropic_version: None,
+	});
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("us-central1-aiplatform.googleapis.com", 443)),
+		inputs,
+	};
+	let policy = Policy {
+		model_aliases: std::collections::HashMap::from([(
+			strng::new("short-name"),
+			strng::new("claude-3-5-sonnet"),
+		)]),
+		..Default::default()
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/messages/count_tokens")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "short-name",
+				"messages": [{"role": "user", "content": "hello"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::Success {
+		request: forwarded,
+		llm_request,
+		upstream_route_type,
+		..
+	} = provider
+		.process_count_tokens_request(&backend_info, req, Some(&policy), &mut None)
+		.await
+		.expect("count_tokens request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+
+	assert_eq!(upstream_route_type, RouteType::AnthropicTokenCount);
+	assert_eq!(forwarded_json["model"], json!("claude-3-5-sonnet"));
+	assert_eq!(llm_request.request_model, "claude-3-5-sonnet");
+}
+
+#[tokio::test]
+async fn vertex_anthropic_messages_prepares_vertex_body() {
+	use crate::http::auth::BackendInfo;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::Vertex(vertex::Provider {
+		model: None,
+		region: Some(strng::new("us-central1")),
+		project_id: strng::new("test-project"),
+		anthropic_version: None,
+	});
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("us-central1-aiplatform.googleapis.com", 443)),
+		inputs,
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/messages")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "claude-haiku-4-5-20251001",
+				"max_tokens": 64,
+				"messages": [{"role": "user", "content": "say hi"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::Success {
+		request: forwarded,
+		upstream_route_type,
+		..
+	} = provider
+		.process_messages_request(&backend_info, None, req, false, &mut None)
+		.await
+		.expect("Vertex Anthropic messages request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+
+	assert_eq!(upstream_route_type, RouteType::Messages);
+	assert!(forwarded_json.get("model").is_none());
+	assert_eq!(
+		forwarded_json["anthropic_version"],
+		json!("vertex-2023-10-16")
+	);
+}
+
+#[tokio::test]
+async fn provider_model_is_set_before_llm_transformations() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::OpenAI(openai::Provider {
+		model: Some("gcp/failover-model".into()),
+	});
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs,
+	};
+	let policy = Policy {
+		transformations: Some(
+			[(
+				"model".to_string(),
+				std::sync::Arc::new(
+					crate::cel::Expression::new_strict(r#"llmRequest.model.stripPrefix("gcp/")"#).unwrap(),
+				),
+			)]
+			.into_iter()
+			.collect(),
+		),
+		..Default::default()
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/chat/completions")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "public-model",
+				"messages": [{"role": "user", "content": "hello"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::Success {
+		request: forwarded,
+		llm_request,
+		..
+	} = provider
+		.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
+		.await
+		.expect("OpenAI completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+
+	assert_eq!(forwarded_json["model"], json!("failover-model"));
+	assert_eq!(llm_request.request_model, "failover-model");
+}
+
+#[tokio::test]
+async fn llm_transformations_can_set_missing_model() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::Policy;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs,
+	};
+	let policy = Policy {
+		transformations: Some(
+			[(
+				"model".to_string(),
+				std::sync::Arc::new(crate::cel::Expression::new_strict(r#""transformed-model""#).unwrap()),
+			)]
+			.into_iter()
+			.collect(),
+		),
+		..Default::default()
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/chat/completions")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"messages": [{"role": "user", "content": "hello"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::Success {
+		request: forwarded,
+		llm_request,
+		..
+	} = provider
+		.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
+		.await
+		.expect("OpenAI completions request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+
+	assert_eq!(forwarded_json["model"], json!("transformed-model"));
+	assert_eq!(llm_request.request_model, "transformed-model");
+}
+
+#[tokio::test]
+async fn copilot_anthropic_model_uses_messages_route() {
+	use crate::http::auth::BackendInfo;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::Copilot(copilot::Provider { model: None });
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.githubcopilot.com", 443)),
+		inputs,
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/messages")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "claude-sonnet-4",
+				"max_tokens": 64,
+				"messages": [{"role": "user", "content": "say hi"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::Success {
+		request: forwarded,
+		llm_request,
+		upstream_route_type,
+	} = provider
+		.process_messages_request(&backend_info, None, req, false, &mut None)
+		.await
+		.expect("Copilot Anthropic messages request should process")
+	else {
+		panic!("expected forwarded request");
+	};
+
+	assert_eq!(upstream_route_type, RouteType::Messages);
+	assert_eq!(
+		llm_request.cache_convention,
+		CacheTokenConvention::InputExcludesCache
+	);
+
+	let mut setup_req =
+		crate::http::tests_common::request("https://example.com/v1/messages", http::Method::POST, &[]);
+	provider
+		.setup_request(
+			&mut setup_req,
+			upstream_route_type,
+			Some(&llm_request),
+			None,
+			None,
+			false,
+			None,
+		)
+		.expect("setup_request should succeed");
+	assert_eq!(setup_req.uri().path(), "/v1/messages");
+
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+	assert_eq!(forwarded_json["model"], json!("claude-sonnet-4"));
+	assert_eq!(forwarded_json["max_tokens"], json!(64));
+}
+
+#[test]
+fn openai_token_limit_normalization_keeps_explicit_max_completion_tokens() {
+	let mut request: types::completions::Request = serde_json::from_value(json!({
+		"model": "gpt-5.4",
+		"max_tokens": 1024,
+		"max_completion_tokens": 2048,
+		"messages": [{"role": "user", "content": "hello"}]
+	}))
+	.expect("valid completions request");
+
+	request.normalize_openai_token_limit();
+
+	assert_eq!(request.max_tokens, None);
+	assert_eq!(request.max_completion_tokens, Some(2048));
+}
+
+#[test]
+fn test_adaptive_thinking_without_effort_maps_to_high_reasoning_effort() {
+	let request: types::messages::Request = serde_json::from_value(json!({
+		"model": "claude-opus-4-6",
+		"max_tokens": 256,
+		"thinking": {
+			"type": "adaptive"
+		},
+		"messages": [
+			{
+				"role": "user",
+				"content": "Give one concise insight."
+			}
+		]
+	}))
+	.expect("valid messages request");
+
+	let translated = conversion::completions::from_messages::translate(&request)
+		.expect("messages->completions translation");
+	let translated: Value =
+		serde_json::from_slice(&translated).expect("translated request should be valid json");
+
+	assert_eq!(translated.get("reasoning_effort"), Some(&json!("high")));
+}
+
+#[test]
 fn test_completions_reasoning_effort_maps_to_enabled_thinking_budget() {
 	let request: types::completions::Request = serde_json::from_value(json!({
 		"model": "claude-opus-4-6",
 		"messages": [
 			{ "role": "user", "content": "Give one concise insight." }
 		],
-		"reasoning_effort": "minimal"
+		"reasoning_effort": "minimal"
+	}))
+	.expect("valid completions request");
+
+	let translated = conversion::messages::from_completions::translate(&request)
+		.expect("completions->messages translation");
+	let translated: Value =
+		serde_json::from_slice(&translated).expect("translated request should be valid json");
+
+	assert_eq!(
+		translated["thinking"],
+		json!({
+			"type": "enabled",
+			"budget_tokens": 1024
+		})
+	);
+	assert!(translated.get("output_config").is_none());
+}
+
+#[test]
+fn test_completions_json_schema_response_format_maps_to_anthropic_output_config() {
+	let request: types::completions::Request = serde_json::from_value(json!({
+		"model": "claude-opus-4-6",
+		"messages": [
+			{ "role": "user", "content": "Return one short summary." }
+		],
+		"response_format": {
+			"type": "json_schema",
+			"json_schema": {
+				"name": "summary_schema",
+				"schema": {
+					"type": "object",
+					"properties": { "summary": { "type": "string" } },
+					"required": ["summary"],
+					"additionalProperties": false
+				}
+			}
+		}
+	}))
+	.expect("valid completions request");
+
+	let translated = conversion::messages::from_completions::translate(&request)
+		.expect("completions->messages translation");
+	let translated: Value =
+		serde_json::from_slice(&translated).expect("translated request should be valid json");
+
+	assert_eq!(
+		translated["output_config"]["format"],
+		json!({
+			"type": "json_schema",
+			"schema": {
+				"type": "object",
+				"properties": { "summary": { "type": "string" } },
+				"required": ["summary"],
+				"additionalProperties": false
+			}
+		})
+	);
+}
+
+#[test]
+fn test_messages_output_config_format_maps_to_openai_response_format() {
+	let request: types::messages::Request = serde_json::from_value(json!({
+		"model": "claude-opus-4-6",
+		"max_tokens": 256,
+		"output_config": {
+			"format": {
+				"type": "json_schema",
+				"schema": {
+					"type": "object",
+					"properties": { "answer": { "type": "number" } },
+					"required": ["answer"],
+					"additionalProperties": false
+				}
+			}
+		},
+		"messages": [
+			{
+				"role": "user",
+				"content": "What is 2+2?"
+			}
+		]
+	}))
+	.expect("valid messages request");
+
+	let translated = conversion::completions::from_messages::translate(&request)
+		.expect("messages->completions translation");
+	let translated: Value =
+		serde_json::from_slice(&translated).expect("translated request should be valid json");
+
+	assert_eq!(translated["response_format"]["type"], json!("json_schema"));
+	assert_eq!(
+		translated["response_format"]["json_schema"]["name"],
+		json!("structured_output")
+	);
+	assert_eq!(
+		translated["response_format"]["json_schema"]["schema"],
+		json!({
+			"type": "object",
+			"properties": { "answer": { "type": "number" } },
+			"required": ["answer"],
+			"additionalProperties": false
+		})
+	);
+}
+
+/// Verifies that `process_response` routes a non-success response through
+/// the buffered error path even when the request has `streaming: true`.
+///
+/// Constructs a Bedrock 400 JSON error response and passes it through
+/// `process_response` with a streaming `LLMRequest`. Asserts the returned
+/// body is non-empty, valid JSON, and preserves the original error message.
+#[tokio::test]
+async fn process_response_routes_streaming_error_to_buffered_path() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+
+	let bedrock = AIProvider::bedrock(bedrock::Provider {
+		model: Some(strng::new("anthropic.claude-3-5-sonnet-20241022-v2:0")),
+		region: strng::new("us-west-2"),
+		guardrail_identifier: None,
+		guardrail_version: None,
+		anthropic_version: None,
+	});
+
+	let error_json = r#"{"message":"Expected toolResult blocks at messages.2.content for the following Ids: tooluse_abc123"}"#;
+
+	let req = LLMRequest {
+		input_tokens: None,
+		input_format: InputFormat::Completions,
+		cache_convention: CacheTokenConvention::pending(),
+		request_model: "input-model".into(),
+		provider: Default::default(),
+		streaming: true,
+		params: Default::default(),
+		prompt: None,
+		provider_state: None,
+	};
+
+	let body = Body::from(error_json.as_bytes().to_vec());
+	let mut resp = Response::new(body);
+	*resp.status_mut() = ::http::StatusCode::BAD_REQUEST;
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"application/json".parse().unwrap(),
+	);
+
+	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
+
+	let result = bedrock
+		.process_response(
+			client,
+			req,
+			LLMResponsePolicies::default(),
+			None,
+			AsyncLog::default(),
+			false,
+			None,
+			resp,
+		)
+		.await
+		.expect("process_response should succeed for error responses");
+
+	assert_eq!(result.status(), ::http::StatusCode::BAD_REQUEST);
+
+	let result_body = result.collect().await.unwrap().to_bytes();
+	assert!(
+		!result_body.is_empty(),
+		"error response body must not be empty",
+	);
+
+	let parsed: Value =
+		serde_json::from_slice(&result_body).expect("translated error should be valid JSON");
+
+	let message = parsed
+		.pointer("/error/message")
+		.and_then(|v| v.as_str())
+		.unwrap_or_default();
+	assert!(
+		message.contains("toolResult"),
+		"translated error should preserve the original message, got: {message}",
+	);
+}
+
+#[tokio::test]
+async fn successful_completion_logs_no_errors() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	agent_core::telemetry::testing::setup_test_logging();
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let body = serde_json::to_vec(&json!({
+		"id": "chatcmpl-1",
+		"object": "chat.completion",
+		"model": "no-error-log-model",
+		"choices": [
+			{"index": 0, "message": {"role": "assistant", "content": "hello"}, "finish_reason": "stop"}
+		],
+		"usage": {"prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4},
+	}))
+	.unwrap();
+	let mut req = llm_request_with_tokens(None);
+	req.request_model = "no-error-log-model".into();
+	req.streaming = false;
+	let result = provider
+		.process_response(
+			PolicyClient::new(setup_proxy_test("{}").unwrap().pi),
+			req,
+			LLMResponsePolicies::default(),
+			None,
+			AsyncLog::default(),
+			false,
+			None,
+			Response::new(Body::from(body)),
+		)
+		.await
+		.expect("response should process");
+	assert_eq!(result.status(), ::http::StatusCode::OK);
+	result.collect().await.unwrap();
+
+	let errors = agent_core::telemetry::testing::find(&[("level", "error")])
+		.into_iter()
+		.filter(|log| log["process_response"]["model"] == "no-error-log-model")
+		.collect::<Vec<_>>();
+	assert!(errors.is_empty(), "unexpected error logs: {errors:?}");
+}
+
+#[tokio::test]
+async fn response_guard_mask_is_logged_without_values() {
+	use crate::llm::policy::{
+		RegexRule, RegexRules, RequestRejection, ResponseGuard, ResponseGuardKind,
+	};
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let body = serde_json::to_vec(&json!({
+		"id": "chatcmpl-1",
+		"object": "chat.completion",
+		"model": "gpt-4o",
+		"choices": [
+			{"index": 0, "message": {"role": "assistant", "content": "nothing to see"}, "finish_reason": "stop"},
+			{"index": 1, "message": {"role": "assistant", "content": "ssn is 123-45-6789"}, "finish_reason": "stop"}
+		],
 	}))
-	.expect("valid completions request");
+	.unwrap();
+	let mut req = llm_request_with_tokens(None);
+	req.request_model = "gpt-4o".into();
+	req.streaming = false;
+	let log = AsyncLog::default();
+	let result = provider
+		.process_response(
+			PolicyClient::new(setup_proxy_test("{}").unwrap().pi),
+			req,
+			LLMResponsePolicies {
+				prompt_guard: vec![ResponseGuard {
+					rejection: RequestRejection::default(),
+					kind: ResponseGuardKind::Regex(RegexRules {
+						action: Default::default(),
+						rules: vec![RegexRule::Regex {
+							pattern: regex::Regex::new(r"\d{3}-\d{2}-\d{4}").unwrap(),
+						}],
+					}),
+				}],
+				guard_modifications_header: true,
+				..Default::default()
+			},
+			None,
+			log.clone(),
+			false,
+			None,
+			Response::new(Body::from(body)),
+		)
+		.await
+		.expect("response should process");
+
+	let header = result
+		.headers()
+		.get(GUARD_MODIFICATIONS_HEADER)
+		.expect("modifications header")
+		.to_str()
+		.unwrap()
+		.to_string();
+	assert_eq!(
+		header,
+		"regex;index=0;action=mask;fields=\"choices[1].message.content\""
+	);
+	let result_body = result.collect().await.unwrap().to_bytes();
+	assert!(!String::from_utf8_lossy(&result_body).contains("123-45-6789"));
+
+	let info = log.take().expect("llm info should be logged");
+	assert_eq!(
+		info.response.guard_modifications,
+		vec![GuardModification {
+			index: 0,
+			guard: strng::new("regex"),
+			action: strng::new("mask"),
+			fields: vec![strng::new("choices[1].message.content")],
+		}]
+	);
+	let logged = serde_json::to_string(&info.response).unwrap();
+	assert!(logged.contains("choices[1].message.content"), "{logged}");
+	assert!(!logged.contains("123-45-6789"), "{logged}");
+}
+
+#[tokio::test]
+async fn prompt_guard_rejection_is_counted() {
+	use crate::http::auth::BackendInfo;
+	use crate::llm::policy::{
+		Action, Policy, PromptGuard, RegexRule, RegexRules, RequestGuard, RequestGuardKind,
+		RequestRejection,
+	};
+	use crate::telemetry::metrics::{GuardrailPhase, GuardrailRejectionLabels};
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Backend {
+			name: "openai".into(),
+			namespace: "default".into(),
+			section: None,
+		},
+		call_target: Target::from(("api.openai.com", 443)),
+		inputs: inputs.clone(),
+	};
+	let policy = Policy {
+		prompt_guard: Some(PromptGuard {
+			streaming: Default::default(),
+			request: vec![RequestGuard {
+				rejection: RequestRejection::default(),
+				kind: RequestGuardKind::Regex(RegexRules {
+					action: Action::Reject,
+					rules: vec![RegexRule::Regex {
+						pattern: regex::Regex::new(r"\d{3}-\d{2}-\d{4}").unwrap(),
+					}],
+				}),
+			}],
+			response: vec![],
+		}),
+		..Default::default()
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/chat/completions")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "gpt-4o",
+				"messages": [{"role": "user", "content": "my ssn is 123-45-6789"}]
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
+
+	let RequestResult::GuardrailRejected { response, .. } = provider
+		.process_completions_request(&backend_info, Some(&policy), req, false, &mut None)
+		.await
+		.expect("OpenAI completions request should process")
+	else {
+		panic!("expected request to be rejected by the prompt guard");
+	};
+	assert_eq!(response.status(), ::http::StatusCode::FORBIDDEN);
+
+	let rejections = inputs
+		.metrics
+		.prompt_guard_rejections
+		.get_or_create(&GuardrailRejectionLabels {
+			phase: GuardrailPhase::Request,
+			guard: strng::literal!("regex").into(),
+			code: Some(403).into(),
+			provider: strng::literal!("openai").into(),
+			backend: strng::literal!("default/openai").into(),
+		})
+		.get();
+	assert_eq!(rejections, 1);
+}
+
+#[tokio::test]
+async fn oversized_response_streams_through_when_enabled() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::transport::BufferLimit;
+
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let body = serde_json::to_vec(&json!({
+		"id": "chatcmpl-1",
+		"object": "chat.completion",
+		"model": "gpt-4o",
+		"choices": [{"index": 0, "message": {"role": "assistant", "content": "x".repeat(256)}, "finish_reason": "stop"}],
+	}))
+	.unwrap();
+	let response = || {
+		let mut resp = Response::new(Body::from(body.clone()));
+		resp.extensions_mut().insert(BufferLimit(64));
+		resp
+	};
+	let mut req = llm_request_with_tokens(None);
+	req.request_model = "gpt-4o".into();
+	req.streaming = false;
+	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
+
+	let err = provider
+		.process_response(
+			client.clone(),
+			req.clone(),
+			LLMResponsePolicies::default(),
+			None,
+			AsyncLog::default(),
+			false,
+			None,
+			response(),
+		)
+		.await
+		.expect_err("oversized response should fail by default");
+	assert!(matches!(err, AIError::ResponseTooLarge), "got {err:?}");
+
+	let log = AsyncLog::default();
+	let result = provider
+		.process_response(
+			client,
+			req,
+			LLMResponsePolicies {
+				stream_oversized_responses: true,
+				..Default::default()
+			},
+			None,
+			log.clone(),
+			false,
+			None,
+			response(),
+		)
+		.await
+		.expect("oversized response should stream through");
+	assert_eq!(result.status(), ::http::StatusCode::OK);
+	let result_body = result.collect().await.unwrap().to_bytes();
+	assert_eq!(result_body.as_ref(), body.as_slice());
+	let info = log.take().expect("llm info should still be logged");
+	assert_eq!(info.response.output_tokens, None);
+}
+
+#[test]
+fn openai_completions_error_translates_to_messages_client() {
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let mut req = llm_request_with_tokens(None);
+	req.input_format = InputFormat::Messages;
+	req.request_model = "gpt-4o".into();
+
+	let error = Bytes::from_static(
+		br#"{"error":{"message":"bad request","type":"invalid_request_error","param":null,"code":null}}"#,
+	);
+	let translated = provider
+		.process_error(&req, ::http::StatusCode::BAD_REQUEST, &error)
+		.expect("OpenAI error should translate to messages error");
+	let body: Value = serde_json::from_slice(&translated).expect("translated error should be JSON");
+
+	assert_eq!(body["type"], json!("error"));
+	assert_eq!(body["error"]["type"], json!("invalid_request_error"));
+	assert_eq!(body["error"]["message"], json!("bad request"));
+}
+
+#[test]
+fn custom_messages_error_translates_to_completions_client() {
+	let provider = custom_provider(custom::ProviderFormat::Messages);
+	let mut req = llm_request_with_tokens(None);
+	req.input_format = InputFormat::Completions;
+	req.request_model = "claude-test".into();
+
+	let error = Bytes::from_static(
+		br#"{"type":"error","error":{"type":"invalid_request_error","message":"bad request"}}"#,
+	);
+	let translated = provider
+		.process_error(&req, ::http::StatusCode::BAD_REQUEST, &error)
+		.expect("Anthropic error should translate to completions error");
+	let body: Value = serde_json::from_slice(&translated).expect("translated error should be JSON");
+
+	assert_eq!(body["error"]["type"], json!("invalid_request_error"));
+	assert_eq!(body["error"]["message"], json!("bad request"));
+}
+
+#[test]
+fn foundry_claude_messages_error_uses_anthropic_shape() {
+	let provider = AIProvider::azure(azure::Provider {
+		model: None,
+		resource_name: strng::new("example"),
+		resource_type: azure::AzureResourceType::Foundry,
+		api_version: None,
+		project_name: Some(strng::new("project")),
+	});
+	let mut req = llm_request_with_tokens(None);
+	req.input_format = InputFormat::Messages;
+	req.request_model = "claude-haiku-4-5".into();
+
+	let error = Bytes::from_static(
+		br#"{"type":"error","error":{"type":"invalid_request_error","message":"bad request"}}"#,
+	);
+	let translated = provider
+		.process_error(&req, ::http::StatusCode::BAD_REQUEST, &error)
+		.expect("Foundry Claude messages error should stay Anthropic-shaped");
+	let body: Value = serde_json::from_slice(&translated).expect("translated error should be JSON");
+
+	assert_eq!(body["type"], json!("error"));
+	assert_eq!(body["error"]["type"], json!("invalid_request_error"));
+	assert_eq!(body["error"]["message"], json!("bad request"));
+}
+
+#[test]
+fn bedrock_upstream_request_id_captured_and_echoed() {
+	let bedrock = AIProvider::bedrock(bedrock::Provider {
+		model: None,
+		region: strng::new("us-east-1"),
+		guardrail_identifier: None,
+		guardrail_version: None,
+		anthropic_version: None,
+	});
+	let response = || {
+		let mut resp = Response::new(Body::empty());
+		resp.headers_mut().insert(
+			crate::http::x_headers::X_AMZN_REQUESTID,
+			"bedrock-req-1".parse().unwrap(),
+		);
+		// Not Bedrock's request id header, so it must not be picked up.
+		resp
+			.headers_mut()
+			.insert("x-request-id", "other".parse().unwrap());
+		resp
+	};
+
+	let mut resp = response();
+	assert_eq!(
+		capture_upstream_request_id(&bedrock, &mut resp, true).as_deref(),
+		Some("bedrock-req-1")
+	);
+	crate::http::tests_common::assert_header(&resp, UPSTREAM_REQUEST_ID_HEADER, "bedrock-req-1");
+
+	// Without echo, the id is captured for the log but not exposed to the client.
+	let mut resp = response();
+	assert_eq!(
+		capture_upstream_request_id(&bedrock, &mut resp, false).as_deref(),
+		Some("bedrock-req-1")
+	);
+	assert!(resp.headers().get(UPSTREAM_REQUEST_ID_HEADER).is_none());
+
+	let openai = AIProvider::OpenAI(openai::Provider { model: None });
+	let mut resp = response();
+	assert_eq!(
+		capture_upstream_request_id(&openai, &mut resp, true).as_deref(),
+		Some("other")
+	);
+}
+
+#[test]
+fn model_deprecation_captured_and_surfaced() {
+	let response = || {
+		let mut resp = Response::new(Body::empty());
+		resp
+			.headers_mut()
+			.insert("deprecation", "@1767225600".parse().unwrap());
+		resp
+			.headers_mut()
+			.insert("sunset", "Wed, 01 Jul 2026 00:00:00 GMT".parse().unwrap());
+		resp
+	};
+	let notice = "deprecation=@1767225600; sunset=Wed, 01 Jul 2026 00:00:00 GMT";
+
+	let mut resp = response();
+	assert_eq!(
+		capture_model_deprecation(&mut resp, true).as_deref(),
+		Some(notice)
+	);
+	crate::http::tests_common::assert_header(&resp, MODEL_DEPRECATION_HEADER, notice);
+
+	// Without surfacing, the notice is only returned for logging.
+	let mut resp = response();
+	assert_eq!(
+		capture_model_deprecation(&mut resp, false).as_deref(),
+		Some(notice)
+	);
+	assert!(resp.headers().get(MODEL_DEPRECATION_HEADER).is_none());
+
+	let mut resp = Response::new(Body::empty());
+	assert_eq!(capture_model_deprecation(&mut resp, true), None);
+	assert!(resp.headers().get(MODEL_DEPRECATION_HEADER).is_none());
+}
+
+#[tokio::test]
+async fn process_streaming_bedrock_records_time_to_first_token() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	let bedrock = AIProvider::bedrock(bedrock::Provider {
+		model: Some(strng::new("openai.gpt-oss-120b-1:0")),
+		region: strng::new("us-east-1"),
+		guardrail_identifier: None,
+		guardrail_version: None,
+		anthropic_version: None,
+	});
+
+	let body = Body::from(
+		fs::read(fixture_path("response/bedrock/basic.bin"))
+			.expect("failed to read Bedrock streaming fixture"),
+	);
+	let mut resp = Response::new(body);
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"application/vnd.amazon.eventstream".parse().unwrap(),
+	);
+
+	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
+	let log = AsyncLog::default();
+	log.store(Some(LLMInfo {
+		request: llm_request_with_tokens(None),
+		response: LLMResponse::default(),
+	}));
+	let translated = bedrock
+		.process_streaming(
+			client,
+			llm_request_with_tokens(None),
+			LLMResponsePolicies::default(),
+			None,
+			log.clone(),
+			false,
+			None,
+			resp,
+		)
+		.expect("Bedrock streaming translation should succeed");
+	translated.collect().await.unwrap();
+
+	// The request log derives `gen_ai_server_time_to_first_token` from this instant.
+	let info = log.take().expect("llm info should be logged");
+	assert!(info.response.first_token.is_some());
+}
+
+#[tokio::test]
+async fn process_streaming_records_chunk_count_and_max_gap() {
+	use futures_util::StreamExt;
+
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	agent_core::telemetry::testing::setup_test_logging();
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let chunks = [
+		"data: {\"choices\":[{\"delta\":{\"content\":\"a\"}}]}\n\n",
+		"data: {\"choices\":[{\"delta\":{\"content\":\"b\"}}]}\n\n",
+		"data: [DONE]\n\n",
+	];
+	let frames =
+		futures_util::stream::iter(chunks.into_iter().enumerate()).then(|(i, c)| async move {
+			if i == 2 {
+				tokio::time::sleep(Duration::from_millis(50)).await;
+			}
+			Ok::<_, std::io::Error>(Bytes::from_static(c.as_bytes()))
+		});
+	let mut resp = Response::new(Body::from_stream(frames));
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"text/event-stream".parse().unwrap(),
+	);
+
+	let mut registry = prometheus_client::registry::Registry::default();
+	let metrics = Arc::new(crate::telemetry::metrics::Metrics::new(
+		&mut registry,
+		Default::default(),
+	));
+	let pi = setup_proxy_test("{}").unwrap().pi;
+	let client = PolicyClient::new(Arc::new(crate::ProxyInputs {
+		metrics,
+		..(*pi).clone()
+	}));
+	let req = LLMRequest {
+		input_format: InputFormat::Detect,
+		request_model: "stream-timing-model".into(),
+		..llm_request_with_tokens(None)
+	};
+	let translated = provider
+		.process_streaming(
+			client,
+			req,
+			LLMResponsePolicies::default(),
+			None,
+			AsyncLog::default(),
+			false,
+			None,
+			resp,
+		)
+		.expect("streaming passthrough should succeed");
+	translated.into_body().collect().await.unwrap();
+
+	agent_core::telemetry::testing::eventually_find(&[
+		("message", "llm stream complete"),
+		("model", "stream-timing-model"),
+		("chunks", "3"),
+	])
+	.await
+	.expect("stream summary should be logged");
+
+	let mut encoded = String::new();
+	prometheus_client::encoding::text::encode(&mut encoded, &registry).unwrap();
+	let labels = "{provider=\"test-provider\",model=\"stream-timing-model\"}";
+	assert!(
+		encoded.contains(&format!("llm_stream_max_chunk_gap_seconds_count{labels} 1")),
+		"got:\n{encoded}"
+	);
+	let sum: f64 = encoded
+		.lines()
+		.find_map(|l| l.strip_prefix(&format!("llm_stream_max_chunk_gap_seconds_sum{labels} ")))
+		.expect("gap sum should be recorded")
+		.parse()
+		.unwrap();
+	assert!(sum >= 0.05, "max gap {sum} should cover the stall");
+}
 
-	let translated = conversion::messages::from_completions::translate(&request)
-		.expect("completions->messages translation");
-	let translated: Value =
-		serde_json::from_slice(&translated).expect("translated request should be valid json");
+#[tokio::test]
+async fn process_streaming_bedrock_completions_normalizes_sse_headers_and_done() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	let bedrock = AIProvider::bedrock(bedrock::Provider {
+		model: Some(strng::new("openai.gpt-oss-120b-1:0")),
+		region: strng::new("us-east-1"),
+		guardrail_identifier: None,
+		guardrail_version: None,
+		anthropic_version: None,
+	});
 
-	assert_eq!(
-		translated["thinking"],
-		json!({
-			"type": "enabled",
-			"budget_tokens": 1024
-		})
+	let body = Body::from(
+		fs::read(fixture_path("response/bedrock/basic.bin"))
+			.expect("failed to read Bedrock streaming fixture"),
+	);
+	let mut resp = Response::new(body);
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"application/vnd.amazon.eventstream".parse().unwrap(),
+	);
+	resp.headers_mut().insert(
+		crate::http::x_headers::X_AMZN_REQUESTID,
+		"request_id".parse().unwrap(),
 	);
-	assert!(translated.get("output_config").is_none());
-}
 
-#[test]
-fn test_completions_json_schema_response_format_maps_to_anthropic_output_config() {
-	let request: types::completions::Request = serde_json::from_value(json!({
-		"model": "claude-opus-4-6",
-		"messages": [
-			{ "role": "user", "content": "Return one short summary." }
-		],
-		"response_format": {
-			"type": "json_schema",
-			"json_schema": {
-				"name": "summary_schema",
-				"schema": {
-					"type": "object",
-					"properties": { "summary": { "type": "string" } },
-					"required": ["summary"],
-					"additionalProperties": false
-				}
-			}
-		}
-	}))
-	.expect("valid completions request");
+	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
+	let translated = bedrock
+		.process_streaming(
+			client,
+			LLMRequest {
+				input_tokens: None,
+				input_format: InputFormat::Completions,
+				cache_convention: CacheTokenConvention::pending(),
+				request_model: "input-model".into(),
+				provider: Default::default(),
+				streaming: true,
+				params: Default::default(),
+				prompt: None,
+				provider_state: None,
+			},
+			LLMResponsePolicies::default(),
+			None,
+			AsyncLog::default(),
+			false,
+			None,
+			resp,
+		)
+		.expect("Bedrock streaming translation should succeed");
 
-	let translated = conversion::messages::from_completions::translate(&request)
-		.expect("completions->messages translation");
-	let translated: Value =
-		serde_json::from_slice(&translated).expect("translated request should be valid json");
+	crate::http::tests_common::assert_header(
+		&translated,
+		::http::header::CONTENT_TYPE,
+		"text/event-stream",
+	);
 
-	assert_eq!(
-		translated["output_config"]["format"],
-		json!({
-			"type": "json_schema",
-			"schema": {
-				"type": "object",
-				"properties": { "summary": { "type": "string" } },
-				"required": ["summary"],
-				"additionalProperties": false
-			}
-		})
+	let body = translated.collect().await.unwrap().to_bytes();
+	let text = String::from_utf8(body.to_vec()).expect("stream should be valid UTF-8");
+	assert!(
+		text.ends_with("data: [DONE]\n\n"),
+		"translated Bedrock completions stream must end with [DONE], got:\n{text}",
+	);
+	assert!(
+		!text.contains("event: \n"),
+		"translated Bedrock completions stream must not emit empty event fields:\n{text}",
 	);
 }
 
-#[test]
-fn test_messages_output_config_format_maps_to_openai_response_format() {
-	let request: types::messages::Request = serde_json::from_value(json!({
-		"model": "claude-opus-4-6",
-		"max_tokens": 256,
-		"output_config": {
-			"format": {
-				"type": "json_schema",
-				"schema": {
-					"type": "object",
-					"properties": { "answer": { "type": "number" } },
-					"required": ["answer"],
-					"additionalProperties": false
-				}
-			}
-		},
-		"messages": [
-			{
-				"role": "user",
-				"content": "What is 2+2?"
-			}
-		]
-	}))
-	.expect("valid messages request");
-
-	let translated = conversion::completions::from_messages::translate(&request)
-		.expect("messages->completions translation");
-	let translated: Value =
-		serde_json::from_slice(&translated).expect("translated request should be valid json");
-
-	assert_eq!(translated["response_format"]["type"], json!("json_schema"));
-	assert_eq!(
-		translated["response_format"]["json_schema"]["name"],
-		json!("structured_output")
+#[tokio::test]
+async fn process_streaming_emits_usage_trailers() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let sse = concat!(
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n",
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[],\"usage\":{\"prompt_tokens\":9,\"completion_tokens\":3,\"total_tokens\":12}}\n\n",
+		"data: [DONE]\n\n",
 	);
-	assert_eq!(
-		translated["response_format"]["json_schema"]["schema"],
-		json!({
-			"type": "object",
-			"properties": { "answer": { "type": "number" } },
-			"required": ["answer"],
-			"additionalProperties": false
-		})
+	let mut resp = Response::new(Body::from(sse));
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"text/event-stream".parse().unwrap(),
 	);
+
+	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
+	let translated = provider
+		.process_streaming(
+			client,
+			LLMRequest {
+				input_tokens: None,
+				input_format: InputFormat::Completions,
+				cache_convention: CacheTokenConvention::pending(),
+				request_model: "gpt-4o".into(),
+				provider: Default::default(),
+				streaming: true,
+				params: Default::default(),
+				prompt: None,
+				provider_state: None,
+			},
+			LLMResponsePolicies {
+				usage_trailers: true,
+				..Default::default()
+			},
+			None,
+			AsyncLog::default(),
+			false,
+			None,
+			resp,
+		)
+		.expect("streaming translation should succeed");
+
+	let collected = translated.into_body().collect().await.unwrap();
+	let trailers = collected.trailers().expect("usage trailers").clone();
+	assert_eq!(trailers.get("x-usage-input-tokens").unwrap(), "9");
+	assert_eq!(trailers.get("x-usage-output-tokens").unwrap(), "3");
+	assert_eq!(trailers.get("x-usage-total-tokens").unwrap(), "12");
+	let text = String::from_utf8(collected.to_bytes().to_vec()).unwrap();
+	assert!(text.ends_with("data: [DONE]\n\n"), "got:\n{text}");
 }
 
-/// Verifies that `process_response` routes a non-success response through
-/// the buffered error path even when the request has `streaming: true`.
-///
-/// Constructs a Bedrock 400 JSON error response and passes it through
-/// `process_response` with a streaming `LLMRequest`. Asserts the returned
-/// body is non-empty, valid JSON, and preserves the original error message.
 #[tokio::test]
-async fn process_response_routes_streaming_error_to_buffered_path() {
+async fn anthropic_translates_responses_request_and_response() {
+	use crate::http::auth::BackendInfo;
 	use crate::proxy::httpproxy::PolicyClient;
 	use crate::test_helpers::proxymock::setup_proxy_test;
+	use crate::types::agent::BackendTarget;
 
-	let bedrock = AIProvider::bedrock(bedrock::Provider {
-		model: Some(strng::new("anthropic.claude-3-5-sonnet-20241022-v2:0")),
-		region: strng::new("us-west-2"),
-		guardrail_identifier: None,
-		guardrail_version: None,
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
 	});
+	let inputs = setup_proxy_test("{}").unwrap().pi;
+	let backend_info = BackendInfo {
+		target: BackendTarget::Invalid,
+		call_target: Target::from(("api.anthropic.com", 443)),
+		inputs: inputs.clone(),
+	};
+	let req = ::http::Request::builder()
+		.uri("/v1/responses")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			br#"{
+				"model": "claude-sonnet-4-5",
+				"instructions": "be brief",
+				"input": "say hi",
+				"max_output_tokens": 64
+			}"#
+				.to_vec(),
+		))
+		.unwrap();
 
-	let error_json = r#"{"message":"Expected toolResult blocks at messages.2.content for the following Ids: tooluse_abc123"}"#;
-
-	let req = LLMRequest {
-		input_tokens: None,
-		input_format: InputFormat::Completions,
-		cache_convention: CacheTokenConvention::pending(),
-		request_model: "input-model".into(),
-		provider: Default::default(),
-		streaming: true,
-		params: Default::default(),
-		prompt: None,
-		provider_state: None,
+	let RequestResult::Success {
+		request: forwarded,
+		mut llm_request,
+		upstream_route_type,
+		..
+	} = provider
+		.process_responses_request(&backend_info, None, req, false, &mut None)
+		.await
+		.expect("responses request should translate to messages")
+	else {
+		panic!("expected forwarded request");
 	};
 
-	let body = Body::from(error_json.as_bytes().to_vec());
-	let mut resp = Response::new(body);
-	*resp.status_mut() = ::http::StatusCode::BAD_REQUEST;
+	let forwarded_body = forwarded.collect().await.unwrap().to_bytes();
+	let forwarded_json: Value =
+		serde_json::from_slice(&forwarded_body).expect("forwarded request should be JSON");
+	assert_eq!(upstream_route_type, RouteType::Messages);
+	assert_eq!(forwarded_json["model"], json!("claude-sonnet-4-5"));
+	assert_eq!(forwarded_json["max_tokens"], json!(64));
+	assert!(
+		forwarded_json["system"].to_string().contains("be brief"),
+		"got: {forwarded_json}"
+	);
+	assert_eq!(forwarded_json["messages"][0]["role"], json!("user"));
+
+	let body = serde_json::to_vec(&json!({
+		"id": "msg_1",
+		"type": "message",
+		"role": "assistant",
+		"model": "claude-sonnet-4-5",
+		"content": [{"type": "text", "text": "hi"}],
+		"stop_reason": "end_turn",
+		"stop_sequence": null,
+		"usage": {"input_tokens": 7, "output_tokens": 2}
+	}))
+	.unwrap();
+	llm_request.streaming = false;
+	let result = provider
+		.process_response(
+			PolicyClient::new(inputs),
+			llm_request,
+			LLMResponsePolicies::default(),
+			None,
+			AsyncLog::default(),
+			false,
+			None,
+			Response::new(Body::from(body)),
+		)
+		.await
+		.expect("messages response should translate to responses");
+
+	let result_body = result.collect().await.unwrap().to_bytes();
+	let parsed: Value = serde_json::from_slice(&result_body).unwrap();
+	assert_eq!(parsed["object"], json!("response"));
+	assert_eq!(parsed["status"], json!("completed"));
+	assert_eq!(parsed["output"][0]["content"][0]["text"], json!("hi"));
+	assert_eq!(parsed["usage"]["input_tokens"], json!(7));
+	assert_eq!(parsed["usage"]["output_tokens"], json!(2));
+}
+
+#[tokio::test]
+async fn anthropic_streaming_translates_to_responses_events() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	let provider = AIProvider::Anthropic(anthropic::Provider {
+		model: None,
+		api_version: None,
+	});
+	let sse = concat!(
+		"event: message_start\n",
+		"data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"type\":\"message\",\"role\":\"assistant\",",
+		"\"model\":\"claude-sonnet-4-5\",\"content\":[],\"stop_reason\":null,\"stop_sequence\":null,",
+		"\"usage\":{\"input_tokens\":9,\"output_tokens\":1}}}\n\n",
+		"event: content_block_start\n",
+		"data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+		"event: content_block_delta\n",
+		"data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n",
+		"event: content_block_stop\n",
+		"data: {\"type\":\"content_block_stop\",\"index\":0}\n\n",
+		"event: content_block_start\n",
+		"data: {\"type\":\"content_block_start\",\"index\":1,\"content_block\":{\"type\":\"tool_use\",",
+		"\"id\":\"toolu_1\",\"name\":\"lookup\",\"input\":{}}}\n\n",
+		"event: content_block_delta\n",
+		"data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",",
+		"\"partial_json\":\"{\\\"q\\\":1}\"}}\n\n",
+		"event: content_block_stop\n",
+		"data: {\"type\":\"content_block_stop\",\"index\":1}\n\n",
+		"event: message_delta\n",
+		"data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"tool_use\",\"stop_sequence\":null},",
+		"\"usage\":{\"output_tokens\":3}}\n\n",
+		"event: message_stop\n",
+		"data: {\"type\":\"message_stop\"}\n\n",
+	);
+	let mut resp = Response::new(Body::from(sse));
 	resp.headers_mut().insert(
 		::http::header::CONTENT_TYPE,
-		"application/json".parse().unwrap(),
+		"text/event-stream".parse().unwrap(),
 	);
 
+	let log = AsyncLog::default();
 	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
-
-	let result = bedrock
-		.process_response(
+	let translated = provider
+		.process_streaming(
 			client,
-			req,
+			LLMRequest {
+				input_tokens: None,
+				input_format: InputFormat::Responses,
+				cache_convention: CacheTokenConvention::pending(),
+				request_model: "claude-sonnet-4-5".into(),
+				provider: Default::default(),
+				streaming: true,
+				params: Default::default(),
+				prompt: None,
+				provider_state: None,
+			},
 			LLMResponsePolicies::default(),
 			None,
-			AsyncLog::default(),
+			log.clone(),
 			false,
 			None,
 			resp,
 		)
-		.await
-		.expect("process_response should succeed for error responses");
-
-	assert_eq!(result.status(), ::http::StatusCode::BAD_REQUEST);
-
-	let result_body = result.collect().await.unwrap().to_bytes();
-	assert!(
-		!result_body.is_empty(),
-		"error response body must not be empty",
-	);
-
-	let parsed: Value =
-		serde_json::from_slice(&result_body).expect("translated error should be valid JSON");
-
-	let message = parsed
-		.pointer("/error/message")
-		.and_then(|v| v.as_str())
-		.unwrap_or_default();
-	assert!(
-		message.contains("toolResult"),
-		"translated error should preserve the original message, got: {message}",
-	);
+		.expect("streaming translation should succeed");
+
+	let body = translated.into_body().collect().await.unwrap().to_bytes();
+	let text = String::from_utf8(body.to_vec()).unwrap();
+	let events: Vec<Value> = text
+		.split("\n\n")
+		.filter_map(|e| e.lines().find_map(|l| l.strip_prefix("data: ")))
+		.filter_map(|d| serde_json::from_str(d).ok())
+		.collect();
+	let types: Vec<&str> = events.iter().filter_map(|e| e["type"].as_str()).collect();
+	assert_eq!(types.first(), Some(&"response.created"), "got:\n{text}");
+	assert_eq!(types.last(), Some(&"response.completed"), "got:\n{text}");
+	let delta = events
+		.iter()
+		.find(|e| e["type"] == "response.output_text.delta")
+		.expect("text delta");
+	assert_eq!(delta["delta"], json!("Hi"));
+	let args = events
+		.iter()
+		.find(|e| e["type"] == "response.function_call_arguments.done")
+		.expect("function call arguments");
+	assert_eq!(args["arguments"], json!("{\"q\":1}"));
+	assert_eq!(args["name"], json!("lookup"));
+	let completed = events.last().unwrap();
+	assert_eq!(completed["response"]["usage"]["input_tokens"], json!(9));
+	assert_eq!(completed["response"]["usage"]["output_tokens"], json!(3));
+
+	let info = log.take().expect("llm info should be logged");
+	assert_eq!(info.response.input_tokens, Some(9));
+	assert_eq!(info.response.output_tokens, Some(3));
+	assert!(info.response.first_token.is_some());
 }
 
-#[test]
-fn openai_completions_error_translates_to_messages_client() {
+#[tokio::test]
+async fn process_streaming_estimates_missing_tool_call_usage() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
 	let provider = AIProvider::OpenAI(openai::Provider { model: None });
-	let mut req = llm_request_with_tokens(None);
-	req.input_format = InputFormat::Messages;
-	req.request_model = "gpt-4o".into();
-
-	let error = Bytes::from_static(
-		br#"{"error":{"message":"bad request","type":"invalid_request_error","param":null,"code":null}}"#,
+	let sse = concat!(
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",",
+		"\"type\":\"function\",\"function\":{\"name\":\"weather\",\"arguments\":\"{\\\"city\\\":\"}}]},\"finish_reason\":null}]}\n\n",
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"Paris\\\"}\"}}]},",
+		"\"finish_reason\":\"tool_calls\"}]}\n\n",
+		"data: [DONE]\n\n",
 	);
-	let translated = provider
-		.process_error(&req, ::http::StatusCode::BAD_REQUEST, &error)
-		.expect("OpenAI error should translate to messages error");
-	let body: Value = serde_json::from_slice(&translated).expect("translated error should be JSON");
-
-	assert_eq!(body["type"], json!("error"));
-	assert_eq!(body["error"]["type"], json!("invalid_request_error"));
-	assert_eq!(body["error"]["message"], json!("bad request"));
-}
-
-#[test]
-fn custom_messages_error_translates_to_completions_client() {
-	let provider = custom_provider(custom::ProviderFormat::Messages);
-	let mut req = llm_request_with_tokens(None);
-	req.input_format = InputFormat::Completions;
-	req.request_model = "claude-test".into();
-
-	let error = Bytes::from_static(
-		br#"{"type":"error","error":{"type":"invalid_request_error","message":"bad request"}}"#,
+	let mut resp = Response::new(Body::from(sse));
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"text/event-stream".parse().unwrap(),
 	);
-	let translated = provider
-		.process_error(&req, ::http::StatusCode::BAD_REQUEST, &error)
-		.expect("Anthropic error should translate to completions error");
-	let body: Value = serde_json::from_slice(&translated).expect("translated error should be JSON");
 
-	assert_eq!(body["error"]["type"], json!("invalid_request_error"));
-	assert_eq!(body["error"]["message"], json!("bad request"));
+	let log = AsyncLog::default();
+	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
+	let translated = provider
+		.process_streaming(
+			client,
+			LLMRequest {
+				input_tokens: Some(12),
+				input_format: InputFormat::Completions,
+				cache_convention: CacheTokenConvention::pending(),
+				request_model: "gpt-4o".into(),
+				provider: Default::default(),
+				streaming: true,
+				params: Default::default(),
+				prompt: None,
+				provider_state: None,
+			},
+			LLMResponsePolicies {
+				estimate_missing_stream_usage: true,
+				..Default::default()
+			},
+			None,
+			log.clone(),
+			false,
+			None,
+			resp,
+		)
+		.expect("streaming translation should succeed");
+	translated.into_body().collect().await.unwrap();
+
+	let info = log.take().expect("llm info should be logged");
+	assert!(info.response.usage_estimated);
+	assert_eq!(info.response.input_tokens, Some(12));
+	// `{"city":"Paris"}` is 16 bytes of streamed arguments.
+	assert_eq!(info.response.output_tokens, Some(4));
+	assert_eq!(info.response.total_tokens, Some(16));
 }
 
-#[test]
-fn foundry_claude_messages_error_uses_anthropic_shape() {
-	let provider = AIProvider::azure(azure::Provider {
-		model: None,
-		resource_name: strng::new("example"),
-		resource_type: azure::AzureResourceType::Foundry,
-		api_version: None,
-		project_name: Some(strng::new("project")),
-	});
-	let mut req = llm_request_with_tokens(None);
-	req.input_format = InputFormat::Messages;
-	req.request_model = "claude-haiku-4-5".into();
-
-	let error = Bytes::from_static(
-		br#"{"type":"error","error":{"type":"invalid_request_error","message":"bad request"}}"#,
+#[tokio::test]
+async fn passthrough_sse_response_parses_trailing_usage() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let sse = concat!(
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n",
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n",
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[],\"usage\":{\"prompt_tokens\":11,\"completion_tokens\":3,\"total_tokens\":14}}\n\n",
+		"data: [DONE]\n\n",
+	);
+	let mut resp = Response::new(Body::from(sse));
+	resp.headers_mut().insert(
+		::http::header::CONTENT_TYPE,
+		"text/event-stream".parse().unwrap(),
 	);
-	let translated = provider
-		.process_error(&req, ::http::StatusCode::BAD_REQUEST, &error)
-		.expect("Foundry Claude messages error should stay Anthropic-shaped");
-	let body: Value = serde_json::from_slice(&translated).expect("translated error should be JSON");
 
-	assert_eq!(body["type"], json!("error"));
-	assert_eq!(body["error"]["type"], json!("invalid_request_error"));
-	assert_eq!(body["error"]["message"], json!("bad request"));
+	let log = AsyncLog::default();
+	let result = provider
+		.process_response(
+			PolicyClient::new(setup_proxy_test("{}").unwrap().pi),
+			LLMRequest {
+				input_tokens: None,
+				input_format: InputFormat::Detect,
+				cache_convention: CacheTokenConvention::pending(),
+				request_model: "gpt-4o".into(),
+				provider: Default::default(),
+				// The request carried no stream flag; only the response reveals it is SSE.
+				streaming: false,
+				params: Default::default(),
+				prompt: None,
+				provider_state: None,
+			},
+			LLMResponsePolicies::default(),
+			None,
+			log.clone(),
+			false,
+			None,
+			resp,
+		)
+		.await
+		.expect("passthrough response should succeed");
+	result.into_body().collect().await.unwrap();
+
+	let info = log.take().expect("llm info should be logged");
+	assert!(info.request.streaming);
+	assert_eq!(info.response.input_tokens, Some(11));
+	assert_eq!(info.response.output_tokens, Some(3));
+	assert_eq!(info.response.total_tokens, Some(14));
 }
 
 #[tokio::test]
-async fn process_streaming_bedrock_completions_normalizes_sse_headers_and_done() {
+async fn process_streaming_normalizes_upstream_reset() {
 	use crate::proxy::httpproxy::PolicyClient;
 	use crate::test_helpers::proxymock::setup_proxy_test;
-	let bedrock = AIProvider::bedrock(bedrock::Provider {
-		model: Some(strng::new("openai.gpt-oss-120b-1:0")),
-		region: strng::new("us-east-1"),
-		guardrail_identifier: None,
-		guardrail_version: None,
-	});
-
-	let body = Body::from(
-		fs::read(fixture_path("response/bedrock/basic.bin"))
-			.expect("failed to read Bedrock streaming fixture"),
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let chunk = concat!(
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"finish_reason\":null}],",
+		"\"usage\":{\"prompt_tokens\":9,\"completion_tokens\":1,\"total_tokens\":10}}\n\n",
 	);
-	let mut resp = Response::new(body);
+	let frames: Vec<Result<Bytes, std::io::Error>> = vec![
+		Ok(Bytes::from_static(chunk.as_bytes())),
+		Err(std::io::Error::new(
+			std::io::ErrorKind::ConnectionReset,
+			"connection reset by peer",
+		)),
+	];
+	let mut resp = Response::new(Body::from_stream(futures_util::stream::iter(frames)));
 	resp.headers_mut().insert(
 		::http::header::CONTENT_TYPE,
-		"application/vnd.amazon.eventstream".parse().unwrap(),
-	);
-	resp.headers_mut().insert(
-		crate::http::x_headers::X_AMZN_REQUESTID,
-		"request_id".parse().unwrap(),
+		"text/event-stream".parse().unwrap(),
 	);
 
+	let log = AsyncLog::default();
 	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
-	let translated = bedrock
+	let translated = provider
 		.process_streaming(
 			client,
 			LLMRequest {
 				input_tokens: None,
 				input_format: InputFormat::Completions,
 				cache_convention: CacheTokenConvention::pending(),
-				request_model: "input-model".into(),
+				request_model: "gpt-4o".into(),
 				provider: Default::default(),
 				streaming: true,
 				params: Default::default(),
@@ -969,29 +3367,108 @@ async fn process_streaming_bedrock_completions_normalizes_sse_headers_and_done()
 			},
 			LLMResponsePolicies::default(),
 			None,
-			AsyncLog::default(),
+			log.clone(),
 			false,
 			None,
 			resp,
 		)
-		.expect("Bedrock streaming translation should succeed");
+		.expect("streaming translation should succeed");
 
-	crate::http::tests_common::assert_header(
-		&translated,
-		::http::header::CONTENT_TYPE,
-		"text/event-stream",
+	let body = translated
+		.into_body()
+		.collect()
+		.await
+		.expect("a mid-stream reset should end the stream cleanly")
+		.to_bytes();
+	let text = String::from_utf8(body.to_vec()).unwrap();
+	let events: Vec<&str> = text.split("\n\n").filter(|e| !e.is_empty()).collect();
+	assert_eq!(events.len(), 3, "got:\n{text}");
+	assert!(events[0].contains("\"content\":\"Hi\""), "got:\n{text}");
+	let error: Value = serde_json::from_str(events[1].strip_prefix("data: ").unwrap()).unwrap();
+	assert_eq!(error["error"]["type"], json!("connection_reset"));
+	assert_eq!(events[2], "data: [DONE]");
+
+	let info = log.take().expect("llm info should be logged");
+	assert_eq!(
+		info.response.error_type.as_deref(),
+		Some("connection_reset")
 	);
+	assert_eq!(info.response.input_tokens, Some(9));
+	assert_eq!(info.response.output_tokens, Some(1));
+}
 
-	let body = translated.collect().await.unwrap().to_bytes();
-	let text = String::from_utf8(body.to_vec()).expect("stream should be valid UTF-8");
-	assert!(
-		text.ends_with("data: [DONE]\n\n"),
-		"translated Bedrock completions stream must end with [DONE], got:\n{text}",
-	);
-	assert!(
-		!text.contains("event: \n"),
-		"translated Bedrock completions stream must not emit empty event fields:\n{text}",
+#[test]
+fn stream_reset_events_match_client_format() {
+	let text = |format| String::from_utf8(stream_reset_events(format).to_vec()).unwrap();
+
+	let responses = text(InputFormat::Responses);
+	assert!(responses.starts_with("event: error\n"), "got:\n{responses}");
+	assert!(!responses.contains("[DONE]"), "got:\n{responses}");
+
+	let messages = text(InputFormat::Messages);
+	assert!(messages.ends_with("event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"));
+	assert!(!messages.contains("[DONE]"), "got:\n{messages}");
+
+	assert!(text(InputFormat::Completions).ends_with("data: [DONE]\n\n"));
+}
+
+#[tokio::test]
+async fn process_streaming_synthesizes_missing_done() {
+	use crate::proxy::httpproxy::PolicyClient;
+	use crate::test_helpers::proxymock::setup_proxy_test;
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let chunk = concat!(
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"finish_reason\":\"stop\"}]}\n\n",
 	);
+	let stream = |synthesize_stream_done: bool| {
+		let mut resp = Response::new(Body::from(chunk));
+		resp.headers_mut().insert(
+			::http::header::CONTENT_TYPE,
+			"text/event-stream".parse().unwrap(),
+		);
+		provider
+			.process_streaming(
+				PolicyClient::new(setup_proxy_test("{}").unwrap().pi),
+				LLMRequest {
+					input_tokens: None,
+					input_format: InputFormat::Completions,
+					cache_convention: CacheTokenConvention::pending(),
+					request_model: "gpt-4o".into(),
+					provider: Default::default(),
+					streaming: true,
+					params: Default::default(),
+					prompt: None,
+					provider_state: None,
+				},
+				LLMResponsePolicies {
+					synthesize_stream_done,
+					..Default::default()
+				},
+				None,
+				AsyncLog::default(),
+				false,
+				None,
+				resp,
+			)
+			.expect("streaming translation should succeed")
+	};
+
+	let body = stream(true).into_body().collect().await.unwrap().to_bytes();
+	let text = String::from_utf8(body.to_vec()).unwrap();
+	let events: Vec<&str> = text.split("\n\n").filter(|e| !e.is_empty()).collect();
+	assert_eq!(events.len(), 2, "got:\n{text}");
+	assert!(events[0].contains("\"content\":\"Hi\""), "got:\n{text}");
+	assert_eq!(events[1], "data: [DONE]");
+
+	// Without the policy the stream is passed through as-is.
+	let body = stream(false)
+		.into_body()
+		.collect()
+		.await
+		.unwrap()
+		.to_bytes();
+	assert_eq!(body, Bytes::from_static(chunk.as_bytes()));
 }
 
 #[test]
@@ -1011,6 +3488,7 @@ fn setup_request_openai_applies_prefixed_path_without_host_override() {
 			None,
 			Some("/v1/custom"),
 			false,
+			None,
 		)
 		.expect("setup_request should succeed");
 
@@ -1039,6 +3517,7 @@ fn setup_request_openai_normalizes_trailing_slash_in_path_prefix() {
 			None,
 			Some("/v1/custom/"),
 			false,
+			None,
 		)
 		.expect("setup_request should succeed");
 
@@ -1081,6 +3560,7 @@ fn setup_request_custom_path_override_wins_over_format_path() {
 			Some("/override/messages"),
 			None,
 			true,
+			None,
 		)
 		.expect("setup_request should succeed");
 
@@ -1123,6 +3603,7 @@ fn assert_prefixed_host_override_path(
 			None,
 			Some("/proxy/"),
 			true,
+			None,
 		)
 		.expect("setup_request should succeed");
 
@@ -1147,6 +3628,7 @@ fn setup_request_vertex_applies_path_prefix_with_host_override() {
 			model: None,
 			region: Some(strng::new("us-central1")),
 			project_id: strng::new("example-project"),
+			anthropic_version: None,
 		}),
 		"gemini-2.5-pro",
 		"/proxy/v1/projects/example-project/locations/us-central1/endpoints/openapi/chat/completions",
@@ -1162,6 +3644,7 @@ fn setup_request_bedrock_applies_path_prefix_with_host_override() {
 			region: strng::new("us-east-1"),
 			guardrail_identifier: None,
 			guardrail_version: None,
+			anthropic_version: None,
 		}),
 		"anthropic.claude-3-5-sonnet-20241022-v2:0",
 		"/proxy/model/anthropic.claude-3-5-sonnet-20241022-v2:0/converse",
@@ -1185,6 +3668,71 @@ fn setup_request_azure_applies_path_prefix_with_host_override() {
 	);
 }
 
+#[test]
+fn setup_request_resolves_path_override_template() {
+	let azure = |api_version: Option<&str>| {
+		AIProvider::azure(azure::Provider {
+			model: None,
+			resource_name: strng::new("example"),
+			resource_type: azure::AzureResourceType::OpenAI,
+			api_version: api_version.map(strng::new),
+			project_name: None,
+		})
+	};
+	let setup = |provider: &AIProvider, template: &str| {
+		let llm_request = llm_request_for_path("gpt-4.1");
+		let mut req = crate::http::tests_common::request(
+			"https://proxy.example.com/v1/chat/completions",
+			http::Method::POST,
+			&[],
+		);
+		provider
+			.setup_request(
+				&mut req,
+				RouteType::Completions,
+				Some(&llm_request),
+				Some(template),
+				None,
+				true,
+				None,
+			)
+			.map(|_| req.uri().path().to_string())
+	};
+
+	assert_eq!(
+		setup(&azure(Some("2")), "/v{version}/models/{model}:generate").unwrap(),
+		"/v2/models/gpt-4.1:generate"
+	);
+	let vertex = AIProvider::Vertex(vertex::Provider {
+		model: Some(strng::new("gemini-2.5-pro")),
+		region: Some(strng::new("us-central1")),
+		project_id: strng::new("example-project"),
+		anthropic_version: None,
+	});
+	assert_eq!(
+		setup(
+			&vertex,
+			"/projects/{project}/locations/{region}/models/{model}"
+		)
+		.unwrap(),
+		"/projects/example-project/locations/us-central1/models/gemini-2.5-pro"
+	);
+	assert!(setup(&azure(None), "/v{version}/chat").is_err());
+	assert!(setup(&vertex, "/{unknown}").is_err());
+
+	// Values cannot escape their path segment.
+	let openai = |model: &str| {
+		AIProvider::OpenAI(openai::Provider {
+			model: Some(strng::new(model)),
+		})
+	};
+	assert_eq!(
+		setup(&openai("../admin?x=1#f"), "/v1/models/{model}").unwrap(),
+		"/v1/models/..%2Fadmin%3Fx=1%23f"
+	);
+	assert!(setup(&openai(".."), "/v1/{model}/chat").is_err());
+}
+
 #[test]
 fn completions_response_missing_message_and_usage_fields() {
 	// Gemini's OpenAI-compat endpoint can omit `message` from choices and
@@ -1255,7 +3803,7 @@ async fn bedrock_from_messages_stream_captures_completion() {
 		response: LLMResponse::default(),
 	};
 	log.store(Some(llmresp));
-	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None).into_llm();
+	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None, None).into_llm();
 	let buffer_limit = 1024 * 1024;
 	let body = conversion::bedrock::from_messages::translate_stream(
 		body,
@@ -1302,7 +3850,7 @@ async fn bedrock_from_messages_stream_skips_completion_when_disabled() {
 		response: LLMResponse::default(),
 	};
 	log.store(Some(llmresp));
-	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None).into_llm();
+	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None, None).into_llm();
 	let buffer_limit = 1024 * 1024;
 	let body = conversion::bedrock::from_messages::translate_stream(
 		body,
@@ -1345,7 +3893,7 @@ async fn messages_passthrough_stream_captures_completion() {
 		response: LLMResponse::default(),
 	};
 	log.store(Some(llmresp));
-	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None).into_llm();
+	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None, None).into_llm();
 	let buffer_limit = 1024 * 1024;
 	let body = conversion::messages::passthrough_stream(body, buffer_limit, logger, true);
 	// Consume the body to drive the stream to completion
@@ -1385,7 +3933,7 @@ async fn messages_passthrough_stream_skips_completion_when_disabled() {
 		response: LLMResponse::default(),
 	};
 	log.store(Some(llmresp));
-	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None).into_llm();
+	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None, None).into_llm();
 	let buffer_limit = 1024 * 1024;
 	let body = conversion::messages::passthrough_stream(body, buffer_limit, logger, false);
 	let _ = body.collect().await.unwrap();
@@ -1420,7 +3968,7 @@ async fn responses_passthrough_stream_captures_completion() {
 		response: LLMResponse::default(),
 	};
 	log.store(Some(llmresp));
-	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None).into_llm();
+	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None, None).into_llm();
 	let buffer_limit = 1024 * 1024;
 	let body = conversion::responses::passthrough_stream(body, buffer_limit, logger, true);
 	let _ = body.collect().await.unwrap();
@@ -1456,7 +4004,7 @@ async fn responses_passthrough_stream_skips_completion_when_disabled() {
 		response: LLMResponse::default(),
 	};
 	log.store(Some(llmresp));
-	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None).into_llm();
+	let logger = AmendOnDrop::new(log, LLMResponsePolicies::default(), None, None, None).into_llm();
 	let buffer_limit = 1024 * 1024;
 	let body = conversion::responses::passthrough_stream(body, buffer_limit, logger, false);
 	let _ = body.collect().await.unwrap();
@@ -1474,6 +4022,7 @@ fn vertex_provider(model: &str) -> AIProvider {
 		model: Some(strng::new(model)),
 		region: None,
 		project_id: strng::new("test-project"),
+		anthropic_version: None,
 	})
 }
 
@@ -1525,6 +4074,52 @@ async fn read_body_decodes_gzip_request_before_json_parse() {
 	);
 }
 
+#[tokio::test]
+async fn read_body_decodes_compressed_completions_request() {
+	let provider = AIProvider::OpenAI(openai::Provider { model: None });
+	let plaintext = br#"{"model":"gpt-4o","messages":[{"role":"user","content":"hi"}]}"#;
+
+	for encoding in ["gzip", "deflate", "br"] {
+		let body = crate::http::compression::encode_body(plaintext, encoding)
+			.await
+			.expect("encode");
+		let req = ::http::Request::builder()
+			.uri("/v1/chat/completions")
+			.header(::http::header::CONTENT_TYPE, "application/json")
+			.header(::http::header::CONTENT_ENCODING, encoding)
+			.body(Body::from(body.to_vec()))
+			.unwrap();
+
+		let (parts, parsed) = provider
+			.read_body_and_default_model::<types::completions::Request>(None, req, &mut None)
+			.await
+			.unwrap_or_else(|e| panic!("{encoding} request body should decode: {e}"));
+		assert_eq!(parsed.model.as_deref(), Some("gpt-4o"), "{encoding}");
+		assert!(
+			parts
+				.headers
+				.get(::http::header::CONTENT_ENCODING)
+				.is_none(),
+			"{encoding}"
+		);
+	}
+
+	let req = ::http::Request::builder()
+		.uri("/v1/chat/completions")
+		.header(::http::header::CONTENT_TYPE, "application/json")
+		.header(::http::header::CONTENT_ENCODING, "compress")
+		.body(Body::from(plaintext.to_vec()))
+		.unwrap();
+	let err = provider
+		.read_body_and_default_model::<types::completions::Request>(None, req, &mut None)
+		.await
+		.expect_err("unknown encodings are rejected");
+	assert!(
+		matches!(&err, AIError::UnsupportedEncoding(e) if e.as_str() == "compress"),
+		"{err:?}"
+	);
+}
+
 #[tokio::test]
 async fn read_body_still_parses_plaintext_request() {
 	// A plaintext (unencoded) request body must continue to parse unchanged — the
@@ -1615,7 +4210,10 @@ fn custom_completions_backend_uses_inclusive_convention() {
 fn fixed_providers_classify_by_family() {
 	assert_eq!(
 		cache_convention_for(
-			&AIProvider::Anthropic(anthropic::Provider { model: None }),
+			&AIProvider::Anthropic(anthropic::Provider {
+				model: None,
+				api_version: None,
+			}),
 			None,
 			"claude-sonnet-4-5"
 		),
@@ -1630,3 +4228,39 @@ fn fixed_providers_classify_by_family() {
 		CacheTokenConvention::InputIncludesCache,
 	);
 }
+
+#[tokio::test]
+async fn tokenizer_preload_holds_readiness_until_loaded() {
+	let ready = agent_core::readiness::Ready::new();
+	let (release, wait) = std::sync::mpsc::channel::<()>();
+	let preload = tokio::spawn(preload_with_timeout(
+		ready.register_task("tokenizers"),
+		std::time::Duration::from_secs(30),
+		move || {
+			let _ = wait.recv();
+		},
+	));
+	tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+	assert!(ready.pending().contains("tokenizers"));
+	assert!(!*ready.subscribe().borrow());
+
+	release.send(()).unwrap();
+	preload.await.unwrap();
+	assert!(ready.pending().is_empty());
+	assert!(*ready.subscribe().borrow());
+}
+
+#[tokio::test]
+async fn tokenizer_preload_releases_readiness_after_timeout() {
+	let ready = agent_core::readiness::Ready::new();
+	let (_release, wait) = std::sync::mpsc::channel::<()>();
+	preload_with_timeout(
+		ready.register_task("tokenizers"),
+		std::time::Duration::from_millis(10),
+		move || {
+			let _ = wait.recv();
+		},
+	)
+	.await;
+	assert!(ready.pending().is_empty());
+}
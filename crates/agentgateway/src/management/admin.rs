@@ -42,6 +42,8 @@ const PPROF_MIN_SECONDS: u64 = 1;
 #[cfg(target_os = "linux")]
 const PPROF_MAX_SECONDS: u64 = 300;
 
+const USAGE_DEFAULT_SECONDS: u64 = 3600;
+
 struct AdminError(anyhow::Error);
 
 impl IntoResponse for AdminError {
@@ -64,6 +66,7 @@ struct AdminState {
 	stores: crate::store::Stores,
 	#[cfg_attr(not(feature = "ui"), allow(dead_code))]
 	resource_manager: crate::resource_manager::ResourceManager,
+	metrics: Arc<crate::telemetry::metrics::Metrics>,
 	config: Arc<Config>,
 	#[cfg_attr(not(feature = "ui"), allow(dead_code))]
 	model_catalog: Arc<crate::llm::cost::ModelCatalog>,
@@ -93,6 +96,24 @@ pub struct ConfigDump {
 	config: Arc<Config>,
 }
 
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfo {
+	version: BuildInfo,
+	features: Vec<&'static str>,
+	config: crate::store::ConfigSummary,
+}
+
+const FEATURES: &[(&str, bool)] = &[
+	("ui", cfg!(feature = "ui")),
+	("schema", cfg!(feature = "schema")),
+	("jemalloc", cfg!(feature = "jemalloc")),
+	("mimalloc", cfg!(feature = "mimalloc")),
+	("tls-aws-lc", cfg!(feature = "tls-aws-lc")),
+	("tls-openssl", cfg!(feature = "tls-openssl")),
+	("upstream-validation", cfg!(feature = "upstream-validation")),
+];
+
 #[derive(serde::Serialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CertDump {
@@ -120,6 +141,7 @@ impl Service {
 		config_resource_store: Option<crate::config_store::ConfigResourceStore>,
 		stores: crate::store::Stores,
 		resource_manager: crate::resource_manager::ResourceManager,
+		metrics: Arc<crate::telemetry::metrics::Metrics>,
 		shutdown_trigger: signal::ShutdownTrigger,
 		drain_rx: DrainWatcher,
 		dataplane_handle: Handle,
@@ -130,6 +152,7 @@ impl Service {
 			config_resource_store,
 			stores,
 			resource_manager,
+			metrics,
 			shutdown_trigger,
 			dataplane_handle,
 		});
@@ -184,6 +207,9 @@ fn admin_router(state: Arc<AdminState>) -> Router {
 		.route("/debug/tasks", get(handle_tokio_tasks))
 		.route("/debug/trace", post(handle_debug_trace))
 		.route("/config_dump", get(handle_config_dump))
+		.route("/server_info", get(handle_server_info))
+		.route("/rate_limits", get(handle_rate_limits))
+		.route("/usage", get(handle_usage))
 		.route("/logging", post(handle_logging))
 		.with_state(state.clone());
 
@@ -244,6 +270,14 @@ async fn handle_dashboard(_req: Request) -> Response {
 		("memory", "dump allocator and process memory statistics"),
 		("quitquitquit", "shut down the server"),
 		("config_dump", "dump the current agentgateway configuration"),
+		(
+			"server_info",
+			"version, enabled features, and a summary of the configuration",
+		),
+		(
+			"usage",
+			"LLM token usage per identity and model. Use ?seconds=N to specify the window (default: 3600s, at most a day)",
+		),
 		("logging", "query/changing logging levels"),
 	];
 
@@ -478,6 +512,71 @@ async fn handle_config_dump(
 	)
 }
 
+async fn handle_server_info(
+	AxumState(state): AxumState<Arc<AdminState>>,
+) -> Result<Response, AdminError> {
+	let info = ServerInfo {
+		version: BuildInfo::new(),
+		features: FEATURES
+			.iter()
+			.filter(|(_, enabled)| *enabled)
+			.map(|(name, _)| *name)
+			.collect(),
+		config: state.stores.binds.summary(),
+	};
+	let body = serde_json::to_string_pretty(&info)?;
+	Ok(
+		::http::Response::builder()
+			.status(hyper::StatusCode::OK)
+			.header(hyper::header::CONTENT_TYPE, "application/json")
+			.body(body.into())
+			.expect("builder with known status code should not fail"),
+	)
+}
+
+async fn handle_rate_limits(
+	AxumState(state): AxumState<Arc<AdminState>>,
+) -> Result<Response, AdminError> {
+	let body = serde_json::to_string_pretty(&state.stores.binds.rate_limits())?;
+	Ok(
+		::http::Response::builder()
+			.status(hyper::StatusCode::OK)
+			.header(hyper::header::CONTENT_TYPE, "application/json")
+			.body(body.into())
+			.expect("builder with known status code should not fail"),
+	)
+}
+
+async fn handle_usage(
+	AxumState(state): AxumState<Arc<AdminState>>,
+	req: Request,
+) -> Result<Response, AdminError> {
+	let qp: HashMap<String, String> = req
+		.uri()
+		.query()
+		.map(|v| {
+			url::form_urlencoded::parse(v.as_bytes())
+				.into_owned()
+				.collect()
+		})
+		.unwrap_or_default();
+	let window = qp
+		.get("seconds")
+		.and_then(|s| s.parse::<u64>().ok())
+		.filter(|s| *s > 0)
+		.map(Duration::from_secs)
+		.unwrap_or(Duration::from_secs(USAGE_DEFAULT_SECONDS))
+		.min(crate::telemetry::usage::RETENTION);
+	let body = serde_json::to_string_pretty(&state.metrics.identity_usage.report(window))?;
+	Ok(
+		::http::Response::builder()
+			.status(hyper::StatusCode::OK)
+			.header(hyper::header::CONTENT_TYPE, "application/json")
+			.body(body.into())
+			.expect("builder with known status code should not fail"),
+	)
+}
+
 // mirror envoy's behavior: https://www.envoyproxy.io/docs/envoy/latest/operations/admin#post--logging
 // NOTE: multiple query parameters is not supported, for example
 // curl -X POST http://127.0.0.1:15000/logging?"tap=debug&router=debug"
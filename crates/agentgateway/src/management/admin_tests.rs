@@ -7,9 +7,22 @@ use tokio::runtime::Handle;
 use super::*;
 
 async fn spawn_admin(cfg: &str) -> (SocketAddr, agent_core::drain::DrainTrigger) {
+	spawn_admin_with(cfg, |_, _, _| {}).await
+}
+
+async fn spawn_admin_with(
+	cfg: &str,
+	setup: impl FnOnce(&crate::store::Stores, &crate::client::Client, &crate::telemetry::metrics::Metrics),
+) -> (SocketAddr, agent_core::drain::DrainTrigger) {
 	let config = Arc::new(crate::config::parse_config(cfg.to_string(), None).unwrap());
 	let stores = crate::store::Stores::new(config.ipv6_enabled, config.threading_mode);
 	let client = crate::client::Client::new(&config.dns, None, Default::default(), None);
+	let mut registry = prometheus_client::registry::Registry::default();
+	let metrics = Arc::new(crate::telemetry::metrics::Metrics::new(
+		&mut registry,
+		Default::default(),
+	));
+	setup(&stores, &client, &metrics);
 	let resource_manager =
 		crate::resource_manager::ResourceManager::new(client).expect("resource manager");
 	let shutdown = signal::Shutdown::new();
@@ -20,6 +33,7 @@ async fn spawn_admin(cfg: &str) -> (SocketAddr, agent_core::drain::DrainTrigger)
 		None,
 		stores,
 		resource_manager,
+		metrics,
 		shutdown.trigger(),
 		drain_rx,
 		Handle::current(),
@@ -60,6 +74,133 @@ config:
 	);
 }
 
+#[tokio::test]
+async fn test_admin_server_info_summarizes_without_credentials() {
+	let cfg = r#"
+config:
+  adminAddr: localhost:0
+"#;
+	let (addr, _drain_tx) = spawn_admin_with(cfg, |stores, client, _| {
+		let backend: crate::types::local::LocalAIBackend = serde_json::from_value(serde_json::json!({
+			"groups": [{"providers": [
+				{
+					"name": "primary",
+					"provider": {"openAI": {}},
+					"policies": {"backendAuth": {"key": "sk-super-secret"}}
+				},
+				{
+					"name": "secondary",
+					"provider": {"anthropic": {}}
+				}
+			]}]
+		}))
+		.unwrap();
+		let resources = crate::resource_manager::ResourceFetcher::direct(client.clone());
+		let ai = futures::executor::block_on(backend.translate(&resources)).unwrap();
+		let b = crate::types::agent::Backend::AI(
+			crate::types::agent::ResourceName::new("llm".into(), "".into()),
+			ai,
+		);
+		stores.binds.write().insert_backend(b.name(), b.into());
+	})
+	.await;
+
+	let resp = reqwest::get(format!("http://{addr}/server_info"))
+		.await
+		.expect("request should succeed");
+	assert_eq!(resp.status(), reqwest::StatusCode::OK);
+	let body = resp.text().await.unwrap();
+	assert!(
+		!body.contains("sk-super-secret"),
+		"server info must not leak credentials: {body}"
+	);
+	let info: serde_json::Value = serde_json::from_str(&body).unwrap();
+	assert_eq!(info["version"]["version"], BuildInfo::new().version);
+	assert_eq!(info["config"]["providers"], 2);
+	assert_eq!(info["config"]["providersByType"]["openai"], 1);
+	assert_eq!(info["config"]["backends"]["ai"], 1);
+}
+
+#[tokio::test]
+async fn test_admin_rate_limits_reflect_amended_tokens() {
+	let cfg = r#"
+config:
+  adminAddr: localhost:0
+"#;
+	let rl =
+		crate::http::localratelimit::RateLimit::try_from(crate::http::localratelimit::RateLimitSpec {
+			max_tokens: 100,
+			tokens_per_fill: 10,
+			fill_interval: Duration::from_secs(3600),
+			limit_type: crate::http::localratelimit::RateLimitType::Tokens,
+		})
+		.unwrap();
+	let amended = rl.clone();
+	let (addr, _drain_tx) = spawn_admin_with(cfg, |stores, _, _| {
+		stores
+			.binds
+			.write()
+			.insert_policy(crate::types::agent::TargetedPolicy {
+				key: "llm-budget".into(),
+				name: None,
+				target: crate::types::agent::PolicyTarget::Route(crate::types::agent::RouteName {
+					name: "route".into(),
+					namespace: "default".into(),
+					rule_name: None,
+					kind: None,
+				}),
+				inheritance: Default::default(),
+				policy: crate::types::agent::TrafficPolicy::LocalRateLimit(
+					crate::store::RequestPolicy::single(vec![rl]),
+				)
+				.into(),
+			});
+	})
+	.await;
+	amended.amend_tokens(30);
+
+	let resp = reqwest::get(format!("http://{addr}/rate_limits"))
+		.await
+		.expect("request should succeed");
+	assert_eq!(resp.status(), reqwest::StatusCode::OK);
+	let limits: serde_json::Value = serde_json::from_str(&resp.text().await.unwrap()).unwrap();
+	let budget = &limits["policies"]["llm-budget"][0];
+	assert_eq!(budget["type"], "tokens");
+	assert_eq!(budget["limit"], 100);
+	assert_eq!(budget["remaining"], 70);
+	assert!(budget["reset_seconds"].as_u64().unwrap() <= 3600);
+}
+
+#[tokio::test]
+async fn test_admin_usage_reports_identities_separately() {
+	let cfg = r#"
+config:
+  adminAddr: localhost:0
+"#;
+	let (addr, _drain_tx) = spawn_admin_with(cfg, |_, _, metrics| {
+		let usage = &metrics.identity_usage;
+		usage.record("alice".into(), "gpt-4o".into(), 10, 5);
+		usage.record("alice".into(), "gpt-4o".into(), 20, 7);
+		usage.record("bob".into(), "gpt-4o".into(), 3, 1);
+		usage.record("bob".into(), "claude".into(), 100, 50);
+	})
+	.await;
+
+	let resp = reqwest::get(format!("http://{addr}/usage?seconds=600"))
+		.await
+		.expect("request should succeed");
+	assert_eq!(resp.status(), reqwest::StatusCode::OK);
+	let usage: serde_json::Value = serde_json::from_str(&resp.text().await.unwrap()).unwrap();
+	assert_eq!(
+		usage,
+		serde_json::json!([
+			{"identity": "alice", "model": "gpt-4o", "requests": 2, "inputTokens": 30, "outputTokens": 12},
+			{"identity": "bob", "model": "claude", "requests": 1, "inputTokens": 100, "outputTokens": 50},
+			{"identity": "bob", "model": "gpt-4o", "requests": 1, "inputTokens": 3, "outputTokens": 1},
+		])
+	);
+}
+
 #[tokio::test]
 async fn trace_sse_stream_does_not_repoll_after_eof() {
 	let stream = trace_sse_stream(crate::proxy::dtrace::TraceReceiver::closed_for_test());
@@ -0,0 +1,84 @@
+//! Per-session token budgets for tool calls.
+//!
+//! Tools backed by an LLM may report the tokens a call spent in the result's `_meta.usage`, as
+//! `total_tokens` or as `input_tokens` and `output_tokens`. The usage reported within a session
+//! is accumulated, and once it reaches the budget, further tool calls in the session are rejected.
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rmcp::model::CallToolResult;
+
+#[derive(Debug)]
+pub struct SessionTokenBudget {
+	limit: u64,
+	used: AtomicU64,
+}
+
+impl SessionTokenBudget {
+	pub fn new(limit: u64) -> Self {
+		Self {
+			limit,
+			used: AtomicU64::new(0),
+		}
+	}
+
+	pub fn limit(&self) -> u64 {
+		self.limit
+	}
+
+	pub fn used(&self) -> u64 {
+		self.used.load(Ordering::Relaxed)
+	}
+
+	/// Whether the session has spent its budget, so no further tool calls are allowed.
+	pub fn is_exhausted(&self) -> bool {
+		self.used() >= self.limit
+	}
+
+	/// record adds the usage reported by a tool call result, if any.
+	pub fn record(&self, result: &CallToolResult) {
+		if let Some(tokens) = reported_tokens(result) {
+			self.used.fetch_add(tokens, Ordering::Relaxed);
+		}
+	}
+}
+
+/// The tokens a tool call reports spending in `_meta.usage`.
+fn reported_tokens(result: &CallToolResult) -> Option<u64> {
+	let result = serde_json::to_value(result).ok()?;
+	let usage = result.pointer("/_meta/usage")?;
+	let field = |name: &str| usage.get(name).and_then(serde_json::Value::as_u64);
+	field("total_tokens").or_else(|| match (field("input_tokens"), field("output_tokens")) {
+		(None, None) => None,
+		(input, output) => Some(input.unwrap_or_default() + output.unwrap_or_default()),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+
+	fn result(v: serde_json::Value) -> CallToolResult {
+		serde_json::from_value(v).unwrap()
+	}
+
+	#[test]
+	fn accumulates_reported_usage() {
+		let budget = SessionTokenBudget::new(100);
+		budget.record(&result(json!({
+			"content": [],
+			"_meta": {"usage": {"input_tokens": 30, "output_tokens": 20}}
+		})));
+		budget.record(&result(json!({"content": []})));
+		assert_eq!(budget.used(), 50);
+		assert!(!budget.is_exhausted());
+
+		budget.record(&result(json!({
+			"content": [],
+			"_meta": {"usage": {"total_tokens": 50, "input_tokens": 1}}
+		})));
+		assert_eq!(budget.used(), 100);
+		assert!(budget.is_exhausted());
+	}
+}
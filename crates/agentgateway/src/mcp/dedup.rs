@@ -0,0 +1,152 @@
+//! De-duplication of repeated `tools/call` requests to a backend.
+//!
+//! A retried tool call would otherwise repeat its side effects upstream. Each call is identified
+//! by a dedup key: the caller's identity, the target, tool name, and either the client's
+//! `idempotency-key` header if present, or else the arguments. While a call is in flight,
+//! identical calls wait for it; once it completes, its result is replayed for the rest of the dedup
+//! window. The state is kept per backend rather than per session, so retries that arrive as
+//! separate stateless requests, or on a new session, are still recognized.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rmcp::model::{CallToolRequestParams, ServerResult};
+use sha2::{Digest, Sha256};
+use tokio::sync::watch;
+
+/// Header a client sets to name a tool call explicitly, so retries share a key even when the
+/// arguments are not byte-identical.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+#[derive(Debug)]
+pub struct ToolCallDedup {
+	window: Duration,
+	entries: Mutex<HashMap<String, Entry>>,
+}
+
+#[derive(Debug)]
+enum Entry {
+	InFlight(watch::Receiver<Option<ServerResult>>),
+	Done {
+		result: ServerResult,
+		expires: Instant,
+	},
+}
+
+/// The outcome of claiming a dedup key.
+pub enum Claim {
+	/// No identical call is in flight or recent; the caller must make the call and complete the
+	/// slot with its result.
+	Leader(Slot),
+	/// An identical call is in flight; its result can be awaited.
+	Wait(watch::Receiver<Option<ServerResult>>),
+	/// An identical call completed within the window.
+	Done(ServerResult),
+}
+
+impl ToolCallDedup {
+	pub fn new(window: Duration) -> Self {
+		Self {
+			window,
+			entries: Mutex::new(HashMap::new()),
+		}
+	}
+
+	pub fn window(&self) -> Duration {
+		self.window
+	}
+
+	/// key returns the dedup key for a call to `tool` on `target` by the caller with `identity`. A
+	/// client-provided idempotency key is scoped to the caller, target and tool, so reusing it for a
+	/// different call does not replay an unrelated result, and one caller never receives another's.
+	/// Callers without an identity share a scope.
+	pub fn key(
+		identity: Option<&str>,
+		headers: &::http::HeaderMap,
+		target: &str,
+		params: &CallToolRequestParams,
+	) -> String {
+		let mut hasher = Sha256::new();
+		hasher.update(identity.unwrap_or_default().as_bytes());
+		hasher.update([0]);
+		hasher.update(target.as_bytes());
+		hasher.update([0]);
+		hasher.update(params.name.as_bytes());
+		hasher.update([0]);
+		if let Some(key) = headers
+			.get(IDEMPOTENCY_KEY_HEADER)
+			.and_then(|v| v.to_str().ok())
+		{
+			hasher.update(key.as_bytes());
+			return format!("client:{}", hex::encode(hasher.finalize()));
+		}
+		if let Some(arguments) = &params.arguments {
+			hasher.update(serde_json::to_vec(arguments).unwrap_or_default());
+		}
+		format!("auto:{}", hex::encode(hasher.finalize()))
+	}
+
+	pub fn claim(self: &Arc<Self>, key: String) -> Claim {
+		let now = Instant::now();
+		let mut entries = self.entries.lock().expect("mutex poisoned");
+		entries.retain(|_, e| match e {
+			Entry::InFlight(_) => true,
+			Entry::Done { expires, .. } => *expires > now,
+		});
+		match entries.get(&key) {
+			Some(Entry::InFlight(rx)) => Claim::Wait(rx.clone()),
+			Some(Entry::Done { result, .. }) => Claim::Done(result.clone()),
+			None => {
+				let (tx, rx) = watch::channel(None);
+				entries.insert(key.clone(), Entry::InFlight(rx));
+				Claim::Leader(Slot {
+					dedup: self.clone(),
+					key,
+					tx,
+				})
+			},
+		}
+	}
+}
+
+/// Slot is held by the call that owns a dedup key. Dropping it without completing it releases
+/// the key, so waiters (and later retries) make the call themselves.
+pub struct Slot {
+	dedup: Arc<ToolCallDedup>,
+	key: String,
+	tx: watch::Sender<Option<ServerResult>>,
+}
+
+impl Slot {
+	pub fn complete(self, result: ServerResult) {
+		let expires = Instant::now() + self.dedup.window;
+		self.dedup.entries.lock().expect("mutex poisoned").insert(
+			self.key.clone(),
+			Entry::Done {
+				result: result.clone(),
+				expires,
+			},
+		);
+		self.tx.send_replace(Some(result));
+	}
+}
+
+impl Drop for Slot {
+	fn drop(&mut self) {
+		if self.tx.borrow().is_some() {
+			return;
+		}
+		let mut entries = self.dedup.entries.lock().expect("mutex poisoned");
+		if matches!(entries.get(&self.key), Some(Entry::InFlight(_))) {
+			entries.remove(&self.key);
+		}
+	}
+}
+
+/// wait returns the result of an in-flight call, or None if it ended without one.
+pub async fn wait(mut rx: watch::Receiver<Option<ServerResult>>) -> Option<ServerResult> {
+	rx.wait_for(Option::is_some)
+		.await
+		.ok()
+		.and_then(|r| (*r).clone())
+}
@@ -0,0 +1,133 @@
+//! Native request processor that rejects `tools/call` invocations whose arguments match a
+//! configured pattern, before the call reaches the upstream.
+
+use bytes::Bytes;
+use rmcp::model::{CallToolRequestParams, ErrorData};
+use serde_json::Value;
+
+use super::client::PERMISSION_DENIED;
+use super::{Outcome, methods};
+use crate::*;
+
+#[apply(schema!)]
+pub struct DenyArguments {
+	/// Regular expressions matched against every string value in the tool arguments, at any
+	/// depth. The first match rejects the call.
+	#[serde(with = "serde_regex")]
+	#[cfg_attr(feature = "schema", schemars(with = "Vec<String>"))]
+	pub patterns: Vec<regex::Regex>,
+}
+
+impl DenyArguments {
+	/// Checks the serialized `tools/call` params. Other methods, and params that do not decode,
+	/// pass untouched.
+	pub(super) fn request<P>(&self, method: &str, params: Option<&Bytes>) -> Outcome<P> {
+		if method != methods::TOOLS_CALL {
+			return Outcome::Pass;
+		}
+		let Some(params) = params else {
+			return Outcome::Pass;
+		};
+		let params = match serde_json::from_slice::<CallToolRequestParams>(params) {
+			Ok(p) => p,
+			Err(e) => {
+				debug!(%method, error = %e, "mcpGuardrails: denyArguments skipped undecodable params");
+				return Outcome::Pass;
+			},
+		};
+		let Some(args) = params.arguments else {
+			return Outcome::Pass;
+		};
+		for v in args.values() {
+			if let Some(pattern) = self.matching(v) {
+				debug!(tool = %params.name, %pattern, "mcpGuardrails: denyArguments rejected tool call");
+				return Outcome::Reject(ErrorData::new(
+					PERMISSION_DENIED,
+					format!("tool {} arguments are not allowed", params.name),
+					None,
+				));
+			}
+		}
+		Outcome::Pass
+	}
+
+	fn matching(&self, value: &Value) -> Option<&regex::Regex> {
+		match value {
+			Value::String(s) => self.patterns.iter().find(|p| p.is_match(s)),
+			Value::Array(a) => a.iter().find_map(|v| self.matching(v)),
+			Value::Object(o) => o.values().find_map(|v| self.matching(v)),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+
+	fn run(patterns: &[&str], method: &str, params: Value) -> Outcome<Value> {
+		let deny = DenyArguments {
+			patterns: patterns
+				.iter()
+				.map(|p| regex::Regex::new(p).unwrap())
+				.collect(),
+		};
+		let body = Bytes::from(serde_json::to_vec(&params).unwrap());
+		deny.request(method, Some(&body))
+	}
+
+	#[test]
+	fn deser_local_config() {
+		let cfg = r#"
+processors:
+  - kind: denyArguments
+    methods: { "tools/call": request }
+    patterns: ['rm\s+-rf']
+"#;
+		let ext: super::super::McpGuardrails = serde_yaml::from_str(cfg).expect("deser McpGuardrails");
+		let super::super::ProcessorKind::DenyArguments(d) = &ext.processors[0].kind else {
+			panic!("expected denyArguments processor");
+		};
+		assert_eq!(d.patterns[0].as_str(), r"rm\s+-rf");
+		assert!(
+			serde_yaml::from_str::<super::super::McpGuardrails>(
+				"processors: [{kind: denyArguments, patterns: ['(']}]"
+			)
+			.is_err()
+		);
+	}
+
+	#[test]
+	fn rejects_nested_match() {
+		let out = run(
+			&[r"rm\s+-rf"],
+			methods::TOOLS_CALL,
+			json!({"name": "shell", "arguments": {"steps": [{"cmd": "ls"}, {"cmd": "rm -rf /"}]}}),
+		);
+		let Outcome::Reject(e) = out else {
+			panic!("expected reject, got {out:?}");
+		};
+		assert_eq!(e.code, PERMISSION_DENIED);
+		assert_eq!(e.message, "tool shell arguments are not allowed");
+	}
+
+	#[test]
+	fn passes_without_match() {
+		for (method, params) in [
+			(
+				methods::TOOLS_CALL,
+				json!({"name": "shell", "arguments": {"cmd": "ls -la", "n": 1}}),
+			),
+			(methods::TOOLS_CALL, json!({"name": "shell"})),
+			(
+				methods::PROMPTS_GET,
+				json!({"name": "p", "arguments": {"cmd": "rm -rf /"}}),
+			),
+		] {
+			let out = run(&[r"rm\s+-rf"], method, params);
+			assert!(matches!(out, Outcome::Pass), "{method}: {out:?}");
+		}
+	}
+}
@@ -0,0 +1,268 @@
+//! Native response processor that redacts configured JSON fields.
+//!
+//! Paths address the JSON-RPC response, so they start at `result`: `result.structuredContent.token`
+//! masks one field, `result.content[].secret` masks `secret` in every array element and
+//! `result.content[0].secret` only in the first.
+
+use std::fmt;
+
+use bytes::Bytes;
+use rmcp::model::ServerResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::Outcome;
+use crate::*;
+
+const MASK: &str = "<redacted>";
+
+#[apply(schema!)]
+pub struct MaskFields {
+	/// Dotted paths to redact, rooted at the JSON-RPC `result`. Use `[]` to descend into every
+	/// array element and `[N]` for a single one.
+	pub paths: Vec<FieldPath>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FieldPath {
+	raw: String,
+	segments: Vec<Segment>,
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for FieldPath {
+	fn schema_name() -> std::borrow::Cow<'static, str> {
+		"FieldPath".into()
+	}
+
+	fn json_schema(schema_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+		String::json_schema(schema_gen)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+	Key(String),
+	Each,
+	Index(usize),
+}
+
+impl FieldPath {
+	pub fn parse(s: &str) -> Result<FieldPath, String> {
+		let mut segments = Vec::new();
+		for part in s.split('.') {
+			let (key, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+			if key.is_empty() && segments.is_empty() {
+				return Err(format!("field path {s:?} must start with a key"));
+			}
+			if !key.is_empty() {
+				segments.push(Segment::Key(key.to_string()));
+			} else if rest.is_empty() {
+				return Err(format!("field path {s:?} has an empty segment"));
+			}
+			while !rest.is_empty() {
+				let Some(end) = rest.strip_prefix('[').and_then(|r| r.find(']')) else {
+					return Err(format!("field path {s:?} has an unterminated index"));
+				};
+				let index = &rest[1..end + 1];
+				segments.push(if index.is_empty() {
+					Segment::Each
+				} else {
+					Segment::Index(
+						index
+							.parse()
+							.map_err(|_| format!("field path {s:?} has an invalid index {index:?}"))?,
+					)
+				});
+				rest = &rest[end + 2..];
+			}
+		}
+		Ok(FieldPath {
+			raw: s.to_string(),
+			segments,
+		})
+	}
+}
+
+impl fmt::Display for FieldPath {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.raw)
+	}
+}
+
+impl TryFrom<String> for FieldPath {
+	type Error = String;
+
+	fn try_from(s: String) -> Result<FieldPath, String> {
+		FieldPath::parse(&s)
+	}
+}
+
+impl From<FieldPath> for String {
+	fn from(p: FieldPath) -> String {
+		p.raw
+	}
+}
+
+impl MaskFields {
+	/// Redacts every configured path present in `body`, a serialized `ServerResult`. Paths that do
+	/// not resolve are skipped; `Pass` is returned when nothing matched.
+	pub(super) fn response(&self, method: &str, body: &mut Bytes) -> Outcome<ServerResult> {
+		let result = match serde_json::from_slice::<Value>(body) {
+			Ok(v) => v,
+			Err(e) => {
+				debug!(%method, error = %e, "mcpGuardrails: mask skipped undecodable result");
+				return Outcome::Pass;
+			},
+		};
+		let mut response = serde_json::json!({ "result": result });
+		let mut masked = false;
+		for path in &self.paths {
+			masked |= mask(&mut response, &path.segments);
+		}
+		if !masked {
+			return Outcome::Pass;
+		}
+		let result = response["result"].take();
+		match serde_json::from_value::<ServerResult>(result.clone()) {
+			Ok(r) => {
+				*body = serde_json::to_vec(&result)
+					.expect("serializing a json value cannot fail")
+					.into();
+				Outcome::Mutated(r)
+			},
+			Err(e) => Outcome::Reject(rmcp::model::ErrorData::internal_error(
+				format!("mcpGuardrails: masked result is not a valid {method} result: {e}"),
+				None,
+			)),
+		}
+	}
+}
+
+fn mask(value: &mut Value, segments: &[Segment]) -> bool {
+	let Some((segment, rest)) = segments.split_first() else {
+		*value = Value::String(MASK.to_string());
+		return true;
+	};
+	match segment {
+		Segment::Key(k) => value
+			.as_object_mut()
+			.and_then(|o| o.get_mut(k))
+			.is_some_and(|v| mask(v, rest)),
+		Segment::Index(i) => value
+			.as_array_mut()
+			.and_then(|a| a.get_mut(*i))
+			.is_some_and(|v| mask(v, rest)),
+		Segment::Each => value
+			.as_array_mut()
+			.is_some_and(|a| a.iter_mut().fold(false, |masked, v| mask(v, rest) | masked)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+
+	fn run(paths: &[&str], result: Value) -> (Value, bool) {
+		let mf = MaskFields {
+			paths: paths.iter().map(|p| FieldPath::parse(p).unwrap()).collect(),
+		};
+		let mut body = Bytes::from(serde_json::to_vec(&result).unwrap());
+		let mutated = match mf.response("tools/call", &mut body) {
+			Outcome::Pass => false,
+			Outcome::Mutated(_) => true,
+			Outcome::Reject(e) => panic!("unexpected reject: {e:?}"),
+		};
+		(serde_json::from_slice(&body).unwrap(), mutated)
+	}
+
+	#[test]
+	fn parses_paths() {
+		let p = FieldPath::parse("result.content[].items[2].secret").unwrap();
+		assert_eq!(
+			p.segments,
+			vec![
+				Segment::Key("result".into()),
+				Segment::Key("content".into()),
+				Segment::Each,
+				Segment::Key("items".into()),
+				Segment::Index(2),
+				Segment::Key("secret".into()),
+			]
+		);
+		assert!(FieldPath::parse("[0].a").is_err());
+		assert!(FieldPath::parse("a..b").is_err());
+		assert!(FieldPath::parse("a[1").is_err());
+		assert!(FieldPath::parse("a[x]").is_err());
+	}
+
+	#[test]
+	fn deser_local_config() {
+		let cfg = r#"
+processors:
+  - kind: maskFields
+    methods: { "tools/call": response }
+    paths: ["result.content[].secret"]
+"#;
+		let ext: super::super::McpGuardrails = serde_yaml::from_str(cfg).expect("deser McpGuardrails");
+		let super::super::ProcessorKind::MaskFields(mf) = &ext.processors[0].kind else {
+			panic!("expected maskFields processor");
+		};
+		assert_eq!(mf.paths[0].to_string(), "result.content[].secret");
+		assert!(
+			serde_yaml::from_str::<super::super::McpGuardrails>(
+				"processors: [{kind: maskFields, paths: ['a[']}]"
+			)
+			.is_err()
+		);
+	}
+
+	#[test]
+	fn masks_nested_object_field() {
+		let (got, mutated) = run(
+			&["result.structuredContent.account.token"],
+			json!({
+				"content": [],
+				"structuredContent": {"account": {"id": "a1", "token": "sk-123"}},
+			}),
+		);
+		assert!(mutated);
+		assert_eq!(
+			got["structuredContent"]["account"],
+			json!({"id": "a1", "token": "<redacted>"})
+		);
+	}
+
+	#[test]
+	fn masks_array_elements() {
+		let result = json!({
+			"content": [
+				{"type": "text", "text": "a", "secret": "s1"},
+				{"type": "text", "text": "b"},
+				{"type": "text", "text": "c", "secret": "s3"},
+			],
+		});
+		let (got, mutated) = run(&["result.content[].secret"], result.clone());
+		assert!(mutated);
+		assert_eq!(got["content"][0]["secret"], "<redacted>");
+		assert!(got["content"][1].get("secret").is_none());
+		assert_eq!(got["content"][2]["secret"], "<redacted>");
+
+		let (got, _) = run(&["result.content[2].secret"], result);
+		assert_eq!(got["content"][0]["secret"], "s1");
+		assert_eq!(got["content"][2]["secret"], "<redacted>");
+	}
+
+	#[test]
+	fn unmatched_paths_pass() {
+		let (got, mutated) = run(
+			&["result.content[].secret", "result.missing"],
+			json!({"content": [{"type": "text", "text": "a"}]}),
+		);
+		assert!(!mutated);
+		assert_eq!(got["content"][0]["text"], "a");
+	}
+}
@@ -36,9 +36,13 @@ impl McpGuardrailsDynamicMetadata {
 }
 
 mod client;
+pub mod deny;
+pub mod mask;
 pub mod methods;
 pub mod phase;
 
+pub use deny::DenyArguments;
+pub use mask::MaskFields;
 pub use phase::Phase;
 
 #[derive(Debug)]
@@ -80,6 +84,10 @@ pub struct Processor {
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ProcessorKind {
 	Remote(Remote),
+	/// Redact fields of the result before it reaches the client. Runs in the response phase only.
+	MaskFields(MaskFields),
+	/// Reject `tools/call` requests whose arguments match a pattern. Runs in the request phase only.
+	DenyArguments(DenyArguments),
 }
 
 impl McpGuardrails {
@@ -116,6 +124,24 @@ impl McpGuardrails {
 			.collect();
 		bad_patterns.sort();
 		out.append(&mut bad_patterns);
+		for d in &self.processors {
+			if matches!(d.kind, ProcessorKind::MaskFields(_))
+				&& d.methods.values().any(|p| p.runs_request())
+			{
+				out.push(
+					"mcpGuardrails: maskFields only runs in the response phase; its request phase is ignored"
+						.to_string(),
+				);
+			}
+			if matches!(d.kind, ProcessorKind::DenyArguments(_))
+				&& d.methods.values().any(|p| p.runs_response())
+			{
+				out.push(
+					"mcpGuardrails: denyArguments only runs in the request phase; its response phase is ignored"
+						.to_string(),
+				);
+			}
+		}
 		out
 	}
 }
@@ -210,6 +236,8 @@ impl Processor {
 				)
 				.await
 			},
+			ProcessorKind::MaskFields(_) => Outcome::Pass,
+			ProcessorKind::DenyArguments(deny) => deny.request(ctx.method, ctx.params.as_ref()),
 		}
 	}
 
@@ -225,6 +253,8 @@ impl Processor {
 			ProcessorKind::Remote(remote) => {
 				client::check_response(remote, method, backends, body, req_ctx, client).await
 			},
+			ProcessorKind::MaskFields(mask) => mask.response(method, body),
+			ProcessorKind::DenyArguments(_) => Outcome::Pass,
 		}
 	}
 }
@@ -305,7 +335,9 @@ processors:
 		let d0 = &ext.processors[0];
 		assert_eq!(d0.methods.get("tools/call"), Some(&Phase::Request));
 		assert_eq!(d0.methods.get("*/list"), Some(&Phase::Response));
-		let ProcessorKind::Remote(r0) = &d0.kind;
+		let ProcessorKind::Remote(r0) = &d0.kind else {
+			panic!("expected remote processor");
+		};
 		assert!(matches!(
 			r0.target.target.as_ref(),
 			SimpleBackendReference::InlineBackend(_)
@@ -319,7 +351,9 @@ processors:
 				.contains(&crate::http::HeaderOrPseudo::Authority)
 		);
 
-		let ProcessorKind::Remote(r1) = &ext.processors[1].kind;
+		let ProcessorKind::Remote(r1) = &ext.processors[1].kind else {
+			panic!("expected remote processor");
+		};
 		assert!(matches!(
 			r1.target.target.as_ref(),
 			SimpleBackendReference::Backend(_)
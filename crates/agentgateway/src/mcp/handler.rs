@@ -11,27 +11,29 @@ use http::request::Parts;
 use itertools::Itertools;
 use rmcp::ErrorData;
 use rmcp::model::{
-	CacheScope, ClientNotification, ClientRequest, ConstString, DiscoverResult,
-	ExtensionCapabilities, Implementation, JsonRpcNotification, JsonRpcRequest, ListPromptsResult,
-	ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, PaginatedRequestParams,
-	ProtocolVersion, RequestId, ResultType, ServerCapabilities, ServerInfo, ServerJsonRpcMessage,
-	ServerNotification, ServerRequest, ServerResult, SubscriptionFilter,
+	CacheScope, CallToolResult, ClientNotification, ClientRequest, ConstString, ContentBlock,
+	DiscoverResult, ExtensionCapabilities, Implementation, JsonObject, JsonRpcNotification,
+	JsonRpcRequest, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult,
+	ListToolsResult, PaginatedRequestParams, ProtocolVersion, RequestId, ResultType,
+	ServerCapabilities, ServerInfo, ServerJsonRpcMessage, ServerNotification, ServerRequest,
+	ServerResult, SubscriptionFilter, Tool,
 };
 use tracing::{debug, info, warn};
 
 use crate::http::Response;
 use crate::http::sessionpersistence::MCPSession;
 use crate::mcp;
+use crate::mcp::initcache::ReusedHandshake;
 use crate::mcp::mergestream::{MergeFn, Messages};
 use crate::mcp::rbac::{CelExecWrapper, McpAuthorizationSet};
 use crate::mcp::router::McpBackendGroup;
 use crate::mcp::streamablehttp::{RequestProtocol, ServerSseMessage};
 use crate::mcp::subscriptions::ResourceSubscription;
 use crate::mcp::upstream::{IncomingRequestContext, UpstreamError};
-use crate::mcp::{ClientError, FailureMode, MCPInfo, apps, mergestream, rbac, upstream};
+use crate::mcp::{ClientError, FailureMode, MCPInfo, apps, dedup, mergestream, rbac, upstream};
 use crate::proxy::httpproxy::PolicyClient;
 use crate::telemetry::log::{AsyncLog, SpanWriteOnDrop, SpanWriter};
-use crate::types::agent::McpPrefixMode;
+use crate::types::agent::{McpInjectedTool, McpPrefixMode};
 
 const DELIMITER: &str = "_";
 
@@ -43,6 +45,17 @@ fn resource_name(prefix_names: bool, target: &str, name: &str) -> String {
 	}
 }
 
+/// The tool listed to clients for a tool injected by the gateway.
+fn injected_tool(tool: &McpInjectedTool) -> Tool {
+	let input_schema = tool
+		.input_schema
+		.clone()
+		.unwrap_or_else(|| JsonObject::from_iter([("type".to_string(), "object".into())]));
+	let mut t = Tool::new(tool.name.to_string(), "", Arc::new(input_schema));
+	t.description = tool.description.as_ref().map(|d| Cow::Owned(d.to_string()));
+	t
+}
+
 fn duplicate_names<'a>(enabled: bool, names: impl Iterator<Item = &'a str>) -> HashSet<String> {
 	if !enabled {
 		return HashSet::new();
@@ -264,11 +277,20 @@ impl Relay {
 		target: &str,
 		stream: Messages,
 		cel: CelExecWrapper,
+		ctx: &IncomingRequestContext,
 	) -> Messages {
 		let target = target.to_string();
 		let default_target_name = self.upstreams.default_target_name.clone();
 		let policies = self.policies.clone();
+		let reused_handshake = ctx.extensions().get::<ReusedHandshake>().cloned();
 		stream.map_server_messages(move |message| {
+			// The error may mean the target lost the state the skipped handshake relied on.
+			if let Some(reused) = &reused_handshake
+				&& matches!(message, ServerJsonRpcMessage::Error(_))
+			{
+				debug!(%target, "forgetting cached initialize handshake after an error response");
+				reused.invalidate(&target);
+			}
 			let message = rewrite_resource_messages(default_target_name.as_ref(), &target, message);
 
 			let mut resource_allowed = |uri: &str| {
@@ -299,6 +321,11 @@ impl Relay {
 		self.upstreams.default_target_name.is_none() && !self.needs_resolution()
 	}
 
+	/// The name clients call `tool` on `target` by.
+	pub fn client_tool_name(&self, target: &str, tool: &str) -> String {
+		resource_name(self.prefix_names(), target, tool)
+	}
+
 	pub fn parse_resource_name<'a, 'b: 'a>(
 		&'a self,
 		res: &'b str,
@@ -622,6 +649,13 @@ impl Relay {
 		let policies = self.policies.clone();
 		let prefix_names = self.prefix_names();
 		let reject_duplicates = self.needs_resolution();
+		let tool_description = self.upstreams.tool_description().cloned();
+		let injected_tools = self
+			.upstreams
+			.injected_tools()
+			.iter()
+			.map(injected_tool)
+			.collect_vec();
 		Box::new(move |streams, cel| {
 			let per_target = per_target_deduped(
 				streams,
@@ -647,13 +681,19 @@ impl Relay {
 								cel,
 							)
 						})
-						// Rename to handle multiplexing
 						.map(|mut t| {
+							if let Some(expr) = &tool_description {
+								rewrite_tool_description(expr, cel, server_name.as_str(), &mut t);
+							}
+							// Rename to handle multiplexing
 							t.name = Cow::Owned(resource_name(prefix_names, server_name.as_str(), &t.name));
 							t
 						})
 						.collect_vec()
 				})
+				// Injected tools replace any target tool listed under the same name.
+				.filter(|t| !injected_tools.iter().any(|i| i.name == t.name))
+				.chain(injected_tools.iter().cloned())
 				.collect_vec();
 			Ok(
 				ListToolsResult {
@@ -1119,6 +1159,20 @@ impl Relay {
 		ctx: IncomingRequestContext,
 		service_name: &str,
 		mcp_log: Option<AsyncLog<MCPInfo>>,
+	) -> Result<Response, UpstreamError> {
+		self
+			.send_single_inspecting(r, ctx, service_name, mcp_log, |_| {})
+			.await
+	}
+	/// send_single_inspecting is send_single, additionally calling `inspect` with each message that
+	/// passes guardrails.
+	async fn send_single_inspecting(
+		&self,
+		r: JsonRpcRequest<ClientRequest>,
+		ctx: IncomingRequestContext,
+		service_name: &str,
+		mcp_log: Option<AsyncLog<MCPInfo>>,
+		mut inspect: impl FnMut(&ServerJsonRpcMessage) + Send + 'static,
 	) -> Result<Response, UpstreamError> {
 		let id = r.id.clone();
 		let Ok(us) = self.upstreams.get(service_name) else {
@@ -1128,13 +1182,104 @@ impl Relay {
 		};
 		let guardrails = self.build_guardrails_ctx(&r, &ctx, vec![service_name.to_string()]);
 		let cel = CelExecWrapper::new(ctx.as_request().map(|_| ()));
-		let stream = self.rewrite_outbound_server_messages(
-			service_name,
-			Box::pin(us.generic_stream(r, &ctx).assert_size::<{ 3 * 1024 }>()).await?,
-			cel,
-		);
+		let in_flight = self
+			.upstreams
+			.in_flight
+			.track(id.clone(), Strng::from(service_name));
+		let stream = self
+			.rewrite_outbound_server_messages(
+				service_name,
+				Box::pin(us.generic_stream(r, &ctx).assert_size::<{ 3 * 1024 }>()).await?,
+				cel,
+				&ctx,
+			)
+			.with_guard(in_flight);
+		let inspect = move |msg: &Result<ServerJsonRpcMessage, ClientError>| {
+			if let Ok(msg) = msg {
+				inspect(msg);
+			}
+		};
 
-		respond_with_guardrails(id, stream, guardrails, mcp_log, &ctx)
+		let downstream_modern = ctx_downstream_modern(&ctx);
+		match guardrails {
+			Some(guardrails) => messages_to_response(
+				id,
+				wrap_with_guardrails(stream, guardrails).inspect(inspect),
+				mcp_log,
+				downstream_modern,
+			),
+			None => messages_to_response(id, stream.inspect(inspect), mcp_log, downstream_modern),
+		}
+	}
+	/// send_tool_call sends a `tools/call` request like send_single, but when a dedup key is given,
+	/// identical calls within the backend's dedup window share a single upstream invocation. The
+	/// usage reported by the result counts towards the session's token budget, if one is given.
+	pub async fn send_tool_call(
+		&self,
+		r: JsonRpcRequest<ClientRequest>,
+		ctx: IncomingRequestContext,
+		service_name: &str,
+		mcp_log: Option<AsyncLog<MCPInfo>>,
+		dedup_key: Option<String>,
+		budget: Option<Arc<mcp::budget::SessionTokenBudget>>,
+	) -> Result<Response, UpstreamError> {
+		let dedup = self.upstreams.tool_call_dedup.as_ref().zip(dedup_key);
+		if budget.is_none() && dedup.is_none() {
+			return self.send_single(r, ctx, service_name, mcp_log).await;
+		}
+		let id = r.id.clone();
+		let mut slot = match dedup {
+			None => None,
+			Some((dedup, key)) => loop {
+				let result = match dedup.claim(key.clone()) {
+					dedup::Claim::Leader(slot) => break Some(slot),
+					dedup::Claim::Done(result) => Some(result),
+					// If the in-flight call ends without a result, claim the key again.
+					dedup::Claim::Wait(rx) => dedup::wait(rx).await,
+				};
+				if let Some(result) = result {
+					debug!("replaying deduplicated tool call result");
+					let stream = futures::stream::once(std::future::ready(Ok(
+						ServerJsonRpcMessage::response(result, id.clone()),
+					)));
+					return messages_to_response(id, stream, mcp_log, ctx_downstream_modern(&ctx));
+				}
+			},
+		};
+
+		// Record the result once it has passed guardrails, so replays match what the first caller saw.
+		// If the stream ends without a response, the slot is dropped and the key released.
+		self
+			.send_single_inspecting(r, ctx, service_name, mcp_log, move |msg| {
+				if let ServerJsonRpcMessage::Response(resp) = msg
+					&& resp.id == id
+				{
+					if let Some(budget) = &budget
+						&& let ServerResult::CallToolResult(result) = &resp.result
+					{
+						budget.record(result);
+					}
+					if let Some(slot) = slot.take() {
+						slot.complete(resp.result.clone());
+					}
+				}
+			})
+			.await
+	}
+	/// Responds to a call to an injected tool that the gateway answers itself.
+	pub fn respond_to_injected_tool(
+		&self,
+		id: RequestId,
+		text: &str,
+		ctx: &IncomingRequestContext,
+		mcp_log: Option<AsyncLog<MCPInfo>>,
+	) -> Result<Response, UpstreamError> {
+		let result = CallToolResult::success(vec![ContentBlock::text(text)]);
+		let stream = futures::stream::once(std::future::ready(Ok(ServerJsonRpcMessage::response(
+			ServerResult::CallToolResult(result),
+			id.clone(),
+		))));
+		messages_to_response(id, stream, mcp_log, ctx_downstream_modern(ctx))
 	}
 	pub async fn send_fanout_deletion(
 		&self,
@@ -1190,7 +1335,7 @@ impl Relay {
 		for (name, result) in fut_results {
 			match result {
 				Ok(s) => {
-					let s = self.rewrite_outbound_server_messages(name.as_str(), s, cel.clone());
+					let s = self.rewrite_outbound_server_messages(name.as_str(), s, cel.clone(), &ctx);
 					streams.push((name, s));
 				},
 				Err(e) => {
@@ -1220,6 +1365,8 @@ impl Relay {
 			}
 		}
 
+		let keep_alive = self.upstreams.sse_keep_alive();
+		let downstream_modern = ctx_downstream_modern(&ctx);
 		if streams.is_empty() {
 			if unsupported_get_streams > 0 && unsupported_get_streams == self.upstreams.size() {
 				return Err(crate::proxy::ProxyError::MCP(mcp::Error::GetStreamNotSupported).into());
@@ -1227,16 +1374,18 @@ impl Relay {
 			// FailClosed: unreachable — InitializeRequest would have failed with NoBackends.
 			// FailOpen: keep the SSE connection open so legacy SSE clients do not immediately
 			// reconnect in a tight loop after all upstream GET streams disappear.
-			return messages_to_response(
+			let stream = into_sse_stream(
 				RequestId::Number(0),
 				Messages::pending(),
 				None,
-				ctx_downstream_modern(&ctx),
+				downstream_modern,
 			);
+			return Ok(mcp::session::sse_stream_response(stream, keep_alive));
 		}
 
 		let ms = mergestream::MergeStream::new_without_merge(streams, self.upstreams.failure_mode);
-		messages_to_response(RequestId::Number(0), ms, None, ctx_downstream_modern(&ctx))
+		let stream = into_sse_stream(RequestId::Number(0), ms, None, downstream_modern);
+		Ok(mcp::session::sse_stream_response(stream, keep_alive))
 	}
 
 	pub async fn send_fanout(
@@ -1264,13 +1413,14 @@ impl Relay {
 		let streams = streams
 			.into_iter()
 			.map(|(name, s)| {
-				let s = self.rewrite_outbound_server_messages(name.as_str(), s, cel.clone());
+				let s = self.rewrite_outbound_server_messages(name.as_str(), s, cel.clone(), &ctx);
 				(name, s)
 			})
 			.collect::<Vec<_>>();
 
 		let ms =
-			mergestream::MergeStream::new(streams, id.clone(), merge, cel, self.upstreams.failure_mode);
+			mergestream::MergeStream::new(streams, id.clone(), merge, cel, self.upstreams.failure_mode)
+				.with_max_notifications(self.upstreams.max_fanout_notifications);
 
 		// Response-phase hook runs once on the merged (muxed) result.
 		respond_with_guardrails(
@@ -1286,6 +1436,15 @@ impl Relay {
 		r: JsonRpcNotification<ClientNotification>,
 		ctx: IncomingRequestContext,
 	) -> Result<Response, UpstreamError> {
+		if let Some(target) = self
+			.upstreams
+			.in_flight
+			.target(self.upstreams.notification_routing, &r.notification)
+		{
+			return self
+				.send_notification_single(r.notification, ctx, target.as_str())
+				.await;
+		}
 		let futs: Vec<_> = self
 			.upstreams
 			.iter_named()
@@ -1422,6 +1581,35 @@ pub(crate) struct GuardrailsCtx {
 	pub req_ctx: Arc<IncomingRequestContext>,
 }
 
+/// Replaces the description of a tool listed by `target` with the result of `expr`. On an
+/// evaluation error or a non-string result the description is left unchanged.
+fn rewrite_tool_description(
+	expr: &crate::cel::Expression,
+	cel: &CelExecWrapper,
+	target: &str,
+	tool: &mut rmcp::model::Tool,
+) {
+	let mcp = MCPInfo {
+		tool: Some(mcp::MCPTool {
+			target: target.to_string(),
+			name: tool.name.to_string(),
+			description: tool.description.as_deref().map(str::to_string),
+			..Default::default()
+		}),
+		..Default::default()
+	};
+	let exec = cel.mcp_executor(&mcp);
+	match exec
+		.eval(expr)
+		.map_err(|e| e.to_string())
+		.and_then(|v| v.json().map_err(|e| e.to_string()))
+	{
+		Ok(serde_json::Value::String(s)) => tool.description = Some(Cow::Owned(s)),
+		Ok(other) => debug!("tool description expression returned non-string value {other}"),
+		Err(e) => debug!("tool description expression failed: {e}"),
+	}
+}
+
 pub(super) fn messages_to_response(
 	id: RequestId,
 	stream: impl Stream<Item = Result<ServerJsonRpcMessage, ClientError>> + Send + 'static,
@@ -0,0 +1,136 @@
+//! Reuse of upstream initialize handshakes in stateless mode.
+//!
+//! Stateless requests run on fresh upstream connections, so each one is normally preceded by an
+//! `initialize` request and `notifications/initialized`. Targets that did not assign a session id
+//! keep no state between requests, and for them the handshake only adds round-trips. A completed
+//! handshake is remembered per target, resolved address and protocol version, and later requests skip it until the
+//! TTL expires, a request sent without it fails, or a target answers it with a JSON-RPC error.
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use rmcp::model::ProtocolVersion;
+
+use crate::*;
+
+/// A target whose handshake is cached: its name and the resolved address its requests are sent
+/// to, so a target that now points somewhere else is initialized again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HandshakeTarget {
+	pub name: Strng,
+	pub address: String,
+}
+
+#[derive(Debug)]
+pub struct InitializeCache {
+	ttl: Duration,
+	initialized: Mutex<HashMap<(HandshakeTarget, String), Instant>>,
+}
+
+impl InitializeCache {
+	pub fn new(ttl: Duration) -> Self {
+		Self {
+			ttl,
+			initialized: Default::default(),
+		}
+	}
+
+	pub fn ttl(&self) -> Duration {
+		self.ttl
+	}
+
+	/// Whether `target` completed a handshake for `version` within the TTL.
+	pub fn is_initialized(&self, target: &HandshakeTarget, version: &ProtocolVersion) -> bool {
+		self.is_initialized_at(Instant::now(), target, version)
+	}
+
+	fn is_initialized_at(
+		&self,
+		now: Instant,
+		target: &HandshakeTarget,
+		version: &ProtocolVersion,
+	) -> bool {
+		let mut initialized = self.initialized.lock();
+		let key = (target.clone(), version.to_string());
+		match initialized.get(&key) {
+			Some(at) if now.duration_since(*at) < self.ttl => true,
+			Some(_) => {
+				initialized.remove(&key);
+				false
+			},
+			None => false,
+		}
+	}
+
+	pub fn insert(&self, target: &HandshakeTarget, version: &ProtocolVersion) {
+		self
+			.initialized
+			.lock()
+			.insert((target.clone(), version.to_string()), Instant::now());
+	}
+
+	/// Forgets the handshake of `target`, so the next request initializes it again.
+	pub fn remove(&self, target: &HandshakeTarget, version: &ProtocolVersion) {
+		self
+			.initialized
+			.lock()
+			.remove(&(target.clone(), version.to_string()));
+	}
+}
+
+/// A cached handshake that a request skipped. It rides in the request extensions so that a target
+/// answering with a JSON-RPC error, such as "not initialized" inside a 200 response, loses its
+/// cached handshake.
+#[derive(Debug, Clone)]
+pub struct ReusedHandshake {
+	pub cache: Arc<InitializeCache>,
+	pub targets: Vec<HandshakeTarget>,
+	pub version: ProtocolVersion,
+}
+
+impl ReusedHandshake {
+	/// Forgets the skipped handshake of the target named `target`.
+	pub fn invalidate(&self, target: &str) {
+		for t in self.targets.iter().filter(|t| t.name.as_str() == target) {
+			self.cache.remove(t, &self.version);
+		}
+	}
+
+	/// Forgets every skipped handshake.
+	pub fn invalidate_all(&self) {
+		for t in &self.targets {
+			self.cache.remove(t, &self.version);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn target(name: &str, address: &str) -> HandshakeTarget {
+		HandshakeTarget {
+			name: strng::new(name),
+			address: address.to_string(),
+		}
+	}
+
+	#[test]
+	fn expires_after_ttl() {
+		let cache = InitializeCache::new(Duration::from_secs(60));
+		let v = ProtocolVersion::V_2025_06_18;
+		let a = target("a", "a.example.com:80/mcp");
+		assert!(!cache.is_initialized(&a, &v));
+		cache.insert(&a, &v);
+		let at = *cache.initialized.lock().values().next().unwrap();
+		assert!(cache.is_initialized_at(at + Duration::from_secs(59), &a, &v));
+		assert!(!cache.is_initialized(&target("b", "a.example.com:80/mcp"), &v));
+		assert!(!cache.is_initialized(&target("a", "b.example.com:80/mcp"), &v));
+		assert!(!cache.is_initialized(&a, &ProtocolVersion::V_2025_03_26));
+		assert!(!cache.is_initialized_at(at + Duration::from_secs(60), &a, &v));
+		assert!(cache.initialized.lock().is_empty());
+
+		cache.insert(&a, &v);
+		cache.remove(&a, &v);
+		assert!(!cache.is_initialized(&a, &v));
+	}
+}
@@ -1528,6 +1528,545 @@ fn modern_meta() -> serde_json::Value {
 	})
 }
 
+#[tokio::test]
+async fn oversized_jsonrpc_message_returns_invalid_request() {
+	let mock = mock_streamable_http_server(true).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_max_message_size(mock.addr, 256)
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = reqwest::Client::new();
+	let url = format!("http://{io}/mcp");
+
+	let body = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "tools/call",
+		"params": {
+			"name": "echo",
+			"arguments": {"hi": "x".repeat(1024)}
+		}
+	});
+	let response = mcp_json_post(&client, &url, &body).send().await.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::PAYLOAD_TOO_LARGE);
+	let json: serde_json::Value = response.json().await.unwrap();
+	assert!(
+		is_json_subset(
+			&serde_json::json!({"jsonrpc": "2.0", "error": {"code": -32600}}),
+			&json
+		),
+		"unexpected body: {json}"
+	);
+}
+
+#[tokio::test]
+async fn sessions_beyond_identity_limit_are_rejected() {
+	let mock = mock_streamable_http_server(true).await;
+	let mut t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_max_sessions_per_identity(mock.addr, 1)
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	t.attach_route_policy(serde_json::json!({
+		"apiKey": {
+			"keys": [{"key": "sk-alice"}, {"key": "sk-bob"}],
+			"mode": "strict",
+		},
+	}))
+	.await;
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = reqwest::Client::new();
+	let url = format!("http://{io}/mcp");
+
+	let initialize = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "initialize",
+		"params": {
+			"protocolVersion": "2025-06-18",
+			"capabilities": {},
+			"clientInfo": {"name": "test-client", "version": "0.0.1"}
+		}
+	});
+	let init = |key: &'static str| {
+		mcp_json_post(&client, &url, &initialize).header("authorization", format!("bearer {key}"))
+	};
+
+	let response = init("sk-alice").send().await.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::OK);
+	assert!(response.headers().get("mcp-session-id").is_some());
+
+	// alice is at the limit, so a second session is rejected
+	let response = init("sk-alice").send().await.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+	assert!(response.headers().get("mcp-session-id").is_none());
+	let json: serde_json::Value = response.json().await.unwrap();
+	assert!(
+		is_json_subset(
+			&serde_json::json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -32600}}),
+			&json
+		),
+		"unexpected body: {json}"
+	);
+
+	// bob is unaffected by alice's sessions
+	let response = init("sk-bob").send().await.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::OK);
+	assert!(response.headers().get("mcp-session-id").is_some());
+}
+
+#[test]
+fn session_reservations_count_against_identity_limit() {
+	let session_manager =
+		super::session::SessionManager::new(http::sessionpersistence::Encoder::base64());
+	let alice = || Some(strng::new("apikey:alice"));
+
+	// A pending reservation holds the slot, so a concurrent session cannot also claim it.
+	let slot = session_manager
+		.reserve_session(alice(), Some(1), None)
+		.unwrap();
+	assert!(matches!(
+		session_manager.reserve_session(alice(), Some(1), None),
+		Err(crate::mcp::Error::TooManySessions(None, 1))
+	));
+
+	// Session creation failed; dropping the slot releases it.
+	drop(slot);
+	session_manager
+		.reserve_session(alice(), Some(1), None)
+		.unwrap();
+}
+
+#[tokio::test]
+async fn custom_session_header_is_used_on_create_and_resume() {
+	let mock = mock_streamable_http_server(true).await;
+	let addr = mock.addr;
+	let gateway = move || async move {
+		let t = setup_proxy_test("{}")
+			.unwrap()
+			.with_mcp_backend_session_header(addr, "x-session")
+			.with_bind(simple_bind())
+			.with_route(basic_route(addr));
+		let io = t.serve_real_listener(BIND_KEY).await;
+		(t, format!("http://{io}/mcp"))
+	};
+	let client = reqwest::Client::new();
+	let list_tools = serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"});
+
+	let (_first, url) = gateway().await;
+	let initialize = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "initialize",
+		"params": {
+			"protocolVersion": "2025-06-18",
+			"capabilities": {},
+			"clientInfo": {"name": "test-client", "version": "0.0.1"}
+		}
+	});
+	let response = mcp_json_post(&client, &url, &initialize)
+		.send()
+		.await
+		.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::OK);
+	assert!(response.headers().get("mcp-session-id").is_none());
+	let session_id = response
+		.headers()
+		.get("x-session")
+		.expect("session id in the configured header")
+		.to_str()
+		.unwrap()
+		.to_string();
+	let initialized = serde_json::json!({
+		"jsonrpc": "2.0",
+		"method": "notifications/initialized"
+	});
+	mcp_json_post(&client, &url, &initialized)
+		.header("x-session", &session_id)
+		.send()
+		.await
+		.unwrap();
+	let response = mcp_json_post(&client, &url, &list_tools)
+		.header("x-session", &session_id)
+		.send()
+		.await
+		.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+	// A second gateway does not know the session, so it resumes it from the header.
+	let (_second, url) = gateway().await;
+	let response = mcp_json_post(&client, &url, &list_tools)
+		.header("mcp-session-id", &session_id)
+		.send()
+		.await
+		.unwrap();
+	assert!(
+		response.status().is_client_error(),
+		"the default header is not read"
+	);
+	let response = mcp_json_post(&client, &url, &list_tools)
+		.header("x-session", &session_id)
+		.send()
+		.await
+		.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::OK);
+	let message = read_response_message(response).await;
+	assert!(
+		message["result"]["tools"].is_array(),
+		"unexpected message: {message}"
+	);
+}
+
+#[tokio::test]
+async fn stateless_initialize_is_reused_within_ttl() {
+	let mock = mock_streamable_http_server(false).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_initialize_cache_ttl(mock.addr, std::time::Duration::from_secs(60))
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = mcp_streamable_client(io).await;
+	let init_before = mock.init_count().await;
+
+	// Only the first request initializes the upstream; later ones reuse its handshake.
+	for _ in 0..3 {
+		let tools = client.list_tools(None).await.unwrap();
+		assert!(tools.tools.iter().any(|t| t.name == "echo"));
+	}
+	let _ = client
+		.call_tool(
+			rmcp::model::CallToolRequestParams::new("echo").with_arguments(
+				serde_json::json!({"hi": "world"})
+					.as_object()
+					.cloned()
+					.unwrap(),
+			),
+		)
+		.await
+		.unwrap();
+	assert_eq!(mock.init_count().await, init_before + 1);
+}
+
+#[tokio::test]
+async fn stateless_initialize_is_forgotten_after_upstream_error() {
+	let mock = mock_streamable_http_server(false).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_initialize_cache_ttl(mock.addr, std::time::Duration::from_secs(60))
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = mcp_streamable_client(io).await;
+	let init_before = mock.init_count().await;
+
+	client.list_tools(None).await.unwrap();
+	// The upstream answers with a JSON-RPC error, so the reused handshake is no longer trusted.
+	let err = client
+		.call_tool(rmcp::model::CallToolRequestParams::new("does_not_exist"))
+		.await;
+	assert!(err.is_err());
+	client.list_tools(None).await.unwrap();
+	assert_eq!(mock.init_count().await, init_before + 2);
+}
+
+#[test]
+fn initialize_caches_are_dropped_on_config_reload() {
+	let session_manager =
+		super::session::SessionManager::new(http::sessionpersistence::Encoder::base64());
+	let backend = crate::types::agent::ResourceName::new(strng::new("backend"), "".into());
+	let ttl = std::time::Duration::from_secs(60);
+
+	let first = session_manager.initialize_cache(1, &backend, ttl);
+	assert!(Arc::ptr_eq(
+		&first,
+		&session_manager.initialize_cache(1, &backend, ttl)
+	));
+	assert!(!Arc::ptr_eq(
+		&first,
+		&session_manager.initialize_cache(2, &backend, ttl)
+	));
+}
+
+#[tokio::test]
+async fn duplicate_tool_calls_within_window_reach_upstream_once() {
+	let mock = mock_streamable_http_server(true).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_tool_call_dedup_window(mock.addr, true, std::time::Duration::from_secs(60))
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = reqwest::Client::new();
+	let url = format!("http://{io}/mcp");
+
+	let initialize = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "initialize",
+		"params": {
+			"protocolVersion": "2025-06-18",
+			"capabilities": {},
+			"clientInfo": {"name": "test-client", "version": "0.0.1"}
+		}
+	});
+	let response = mcp_json_post(&client, &url, &initialize)
+		.send()
+		.await
+		.unwrap();
+	assert_eq!(response.status(), reqwest::StatusCode::OK);
+	let session_id = response
+		.headers()
+		.get("mcp-session-id")
+		.unwrap()
+		.to_str()
+		.unwrap()
+		.to_string();
+	let initialized = serde_json::json!({
+		"jsonrpc": "2.0",
+		"method": "notifications/initialized"
+	});
+	mcp_json_post(&client, &url, &initialized)
+		.header("mcp-session-id", &session_id)
+		.send()
+		.await
+		.unwrap();
+
+	let increment = |id: i64, key: &'static str| {
+		let body = serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": id,
+			"method": "tools/call",
+			"params": {"name": "increment", "arguments": {}}
+		});
+		let request = mcp_json_post(&client, &url, &body)
+			.header("mcp-session-id", &session_id)
+			.header("idempotency-key", key);
+		async move {
+			let response = request.send().await.unwrap();
+			assert_eq!(response.status(), reqwest::StatusCode::OK);
+			let message = read_response_message(response).await;
+			assert_eq!(
+				message["id"],
+				serde_json::json!(id),
+				"unexpected message: {message}"
+			);
+			message["result"]["content"][0]["text"].clone()
+		}
+	};
+
+	// The retry shares the first call's key, so it replays the result instead of incrementing again.
+	assert_eq!(increment(2, "k1").await, serde_json::json!("1"));
+	assert_eq!(increment(3, "k1").await, serde_json::json!("1"));
+	// A new key is a new call.
+	assert_eq!(increment(4, "k2").await, serde_json::json!("2"));
+}
+
+#[tokio::test]
+async fn duplicate_stateless_tool_calls_within_window_reach_upstream_once() {
+	let mock = mock_streamable_http_server(false).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_tool_call_dedup_window(mock.addr, false, std::time::Duration::from_secs(60))
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = mcp_streamable_client(io).await;
+	let echo = |hi: &str| {
+		rmcp::model::CallToolRequestParams::new("echo")
+			.with_arguments(serde_json::json!({"hi": hi}).as_object().cloned().unwrap())
+	};
+	let init_before = mock.init_count().await;
+
+	// Each stateless request initializes the upstream, so the count tracks upstream calls. The
+	// retry arrives as a separate request, yet is still answered from the first call.
+	let first = client.call_tool(echo("world")).await.unwrap();
+	assert_eq!(mock.init_count().await, init_before + 1);
+	let retry = client.call_tool(echo("world")).await.unwrap();
+	assert_eq!(retry.content, first.content);
+	assert_eq!(mock.init_count().await, init_before + 1);
+	// Different arguments are a new call.
+	client.call_tool(echo("again")).await.unwrap();
+	assert_eq!(mock.init_count().await, init_before + 2);
+}
+
+#[test]
+fn tool_call_dedup_is_shared_per_backend_and_dropped_on_config_reload() {
+	let session_manager =
+		super::session::SessionManager::new(http::sessionpersistence::Encoder::base64());
+	let backend = crate::types::agent::ResourceName::new(strng::new("backend"), "".into());
+	let window = std::time::Duration::from_secs(60);
+
+	let first = session_manager.tool_call_dedup(1, &backend, window);
+	assert!(Arc::ptr_eq(
+		&first,
+		&session_manager.tool_call_dedup(1, &backend, window)
+	));
+	assert!(!Arc::ptr_eq(
+		&first,
+		&session_manager.tool_call_dedup(1, &backend, window * 2)
+	));
+	assert!(!Arc::ptr_eq(
+		&first,
+		&session_manager.tool_call_dedup(2, &backend, window)
+	));
+}
+
+#[test]
+fn idempotency_keys_are_scoped_to_identity_target_and_tool() {
+	use crate::mcp::dedup::{IDEMPOTENCY_KEY_HEADER, ToolCallDedup};
+	let mut headers = ::http::HeaderMap::new();
+	headers.insert(IDEMPOTENCY_KEY_HEADER, "k1".parse().unwrap());
+	let increment = rmcp::model::CallToolRequestParams::new("increment");
+	let echo = rmcp::model::CallToolRequestParams::new("echo");
+	let alice = Some("jwt:alice");
+
+	let key = ToolCallDedup::key(alice, &headers, "a", &increment);
+	assert_eq!(key, ToolCallDedup::key(alice, &headers, "a", &increment));
+	assert_ne!(key, ToolCallDedup::key(alice, &headers, "a", &echo));
+	assert_ne!(key, ToolCallDedup::key(alice, &headers, "b", &increment));
+	assert_ne!(
+		key,
+		ToolCallDedup::key(Some("jwt:bob"), &headers, "a", &increment)
+	);
+	assert_ne!(key, ToolCallDedup::key(None, &headers, "a", &increment));
+}
+
+#[tokio::test]
+async fn tool_calls_rejected_once_session_token_budget_is_spent() {
+	let mock = mock_streamable_http_server(true).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_session_token_budget(mock.addr, 100)
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = mcp_streamable_client(io).await;
+	let call = |tokens: u64| {
+		client.call_tool(
+			rmcp::model::CallToolRequestParams::new("echo").with_arguments(
+				serde_json::json!({"usage": {"input_tokens": tokens / 2, "output_tokens": tokens / 2}})
+					.as_object()
+					.cloned()
+					.unwrap(),
+			),
+		)
+	};
+
+	call(60).await.unwrap();
+	// The budget is checked before each call, so this call takes the session over it.
+	call(60).await.unwrap();
+	let err = call(2).await.unwrap_err();
+	let rmcp::ServiceError::McpError(mcp_error) = &err else {
+		panic!("expected ServiceError::McpError, got: {err:?}");
+	};
+	assert_eq!(
+		mcp_error.message,
+		"session token budget of 100 tokens exhausted"
+	);
+	// Other requests in the session are unaffected.
+	client.list_tools(Default::default()).await.unwrap();
+}
+
+#[tokio::test]
+async fn listed_tool_descriptions_are_rewritten() {
+	let mock = mock_streamable_http_server(true).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_tool_description(mock.addr, r#"mcp.tool.description + " (reviewed)""#)
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = mcp_streamable_client(io).await;
+
+	let tools = client.list_tools(Default::default()).await.unwrap().tools;
+	assert!(!tools.is_empty());
+	for tool in &tools {
+		let description = tool.description.as_deref().unwrap_or_default();
+		assert!(
+			description.ends_with(" (reviewed)"),
+			"tool {} has description {description:?}",
+			tool.name
+		);
+	}
+	let increment = tools.iter().find(|t| t.name == "increment").unwrap();
+	assert_eq!(
+		increment.description.as_deref(),
+		Some("Increment the counter by 1 (reviewed)")
+	);
+}
+
+#[tokio::test]
+async fn injected_tools_are_listed_and_handled_by_the_gateway() {
+	use crate::types::agent::{McpInjectedTool, McpInjectedToolHandler};
+
+	let mock = mock_streamable_http_server(true).await;
+	let injected = |name: &str, handler| McpInjectedTool {
+		name: name.into(),
+		description: Some("Report unsafe output".into()),
+		input_schema: None,
+		handler,
+	};
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_injected_tools(
+			mock.addr,
+			vec![
+				injected(
+					"report_unsafe_output",
+					McpInjectedToolHandler::Respond("Thanks, the report was recorded.".to_string()),
+				),
+				injected(
+					"report_to_upstream",
+					McpInjectedToolHandler::Forward {
+						target: "mcp".into(),
+						tool: "echo".into(),
+					},
+				),
+			],
+		)
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	let client = mcp_streamable_client(io).await;
+
+	let tools = client.list_tools(Default::default()).await.unwrap().tools;
+	let report = tools
+		.iter()
+		.find(|t| t.name == "report_unsafe_output")
+		.expect("injected tool is listed");
+	assert_eq!(report.description.as_deref(), Some("Report unsafe output"));
+	assert!(tools.iter().any(|t| t.name == "report_to_upstream"));
+	// Target tools are still listed alongside.
+	assert!(tools.iter().any(|t| t.name == "echo"));
+
+	let result = client
+		.call_tool(rmcp::model::CallToolRequestParams::new(
+			"report_unsafe_output",
+		))
+		.await
+		.unwrap();
+	assert_eq!(
+		result.content[0].as_text().unwrap().text,
+		"Thanks, the report was recorded."
+	);
+
+	let args = serde_json::json!({"hello": "world"});
+	let result = client
+		.call_tool(
+			rmcp::model::CallToolRequestParams::new("report_to_upstream")
+				.with_arguments(args.as_object().cloned().unwrap()),
+		)
+		.await
+		.unwrap();
+	assert!(
+		result.content[0].as_text().unwrap().text.contains("world"),
+		"forwarded call is answered by the target's echo tool: {result:?}"
+	);
+}
+
 #[tokio::test]
 async fn modern_removed_and_unknown_methods_return_404() {
 	let mock = mock_modern_streamable_http_server().await;
@@ -2832,6 +3371,11 @@ fn access_log_payload_policy() -> crate::types::frontend::LoggingPolicy {
 
 async fn setup_access_log_mcp_proxy(mock: &MockServer) -> (TestBind, SocketAddr) {
 	let (mut t, io) = setup_proxy(mock, true, false).await;
+	attach_access_log_policy(&mut t);
+	(t, io)
+}
+
+fn attach_access_log_policy(t: &mut TestBind) {
 	let listener_name = t
 		.pi
 		.stores
@@ -2859,7 +3403,33 @@ async fn setup_access_log_mcp_proxy(mock: &MockServer) -> (TestBind, SocketAddr)
 			.access_log
 			.is_some()
 	);
-	(t, io)
+}
+
+#[tokio::test]
+async fn initialize_logs_client_implementation() {
+	use rmcp::ServiceExt;
+	use rmcp::model::{ClientCapabilities, ClientInfo, Implementation};
+	use rmcp::transport::StreamableHttpClientTransport;
+
+	let mock = mock_streamable_http_server(true).await;
+	let (_t, io) = setup_proxy(&mock, true, false).await;
+	let client_name = format!("analytics-client-{}", uuid::Uuid::new_v4());
+	let transport =
+		StreamableHttpClientTransport::<reqwest::Client>::from_uri(format!("http://{io}/mcp"));
+	let client_info = ClientInfo::new(
+		ClientCapabilities::default(),
+		Implementation::new(client_name.clone(), "2.4.1".to_string()),
+	);
+	let _client = Box::pin(client_info.serve(transport)).await.unwrap();
+
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("scope", "request"),
+		("mcp.client.name", &client_name),
+	])
+	.await
+	.unwrap();
+	assert_eq!(log["mcp.method.name"], "initialize");
+	assert_eq!(log["mcp.client.version"], "2.4.1");
 }
 
 #[tokio::test]
@@ -2937,6 +3507,124 @@ async fn tool_call_exposes_payload_fields_to_access_log_cel() {
 	assert!(log.get("gen_ai.tool.call.result").is_none());
 }
 
+#[tokio::test]
+async fn correlation_headers_reach_upstream() {
+	use std::collections::HashMap;
+
+	use ::http::{HeaderName, HeaderValue};
+	use rmcp::ServiceExt;
+	use rmcp::model::{ClientCapabilities, ClientInfo, Implementation};
+	use rmcp::transport::StreamableHttpClientTransport;
+	use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
+
+	use crate::types::agent::McpCorrelationHeader;
+
+	let (mock, captured) = mock_streamable_http_server_with_capture(true).await;
+	let t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_correlation_headers(
+			mock.addr,
+			vec![
+				McpCorrelationHeader {
+					name: strng::new("x-request-id"),
+					from: None,
+				},
+				McpCorrelationHeader {
+					name: strng::new("x-correlation-id"),
+					from: Some(strng::new("x-client-trace")),
+				},
+			],
+		)
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+
+	let mut headers = HashMap::new();
+	headers.insert(
+		HeaderName::from_static("x-client-trace"),
+		HeaderValue::from_static("trace-123"),
+	);
+	let config = StreamableHttpClientTransportConfig::with_uri(format!("http://{io}/mcp"))
+		.custom_headers(headers);
+	let client = ClientInfo::new(
+		ClientCapabilities::default(),
+		Implementation::new("test client", "0.0.1"),
+	)
+	.serve(StreamableHttpClientTransport::from_config(config))
+	.await
+	.expect("client should connect");
+	client
+		.list_tools(Default::default())
+		.await
+		.expect("list_tools should succeed");
+
+	let headers = captured.lock().unwrap().clone();
+	assert!(!headers.is_empty(), "expected upstream requests");
+	for h in &headers {
+		assert_eq!(
+			h.get("x-correlation-id").map(|v| v.as_bytes()),
+			Some(b"trace-123".as_slice()),
+			"correlation id should be copied from x-client-trace; saw {h:?}"
+		);
+		let request_id = h
+			.get("x-request-id")
+			.and_then(|v| v.to_str().ok())
+			.expect("x-request-id should be generated");
+		assert!(
+			uuid::Uuid::parse_str(request_id).is_ok(),
+			"generated x-request-id should be a UUID, got {request_id}"
+		);
+	}
+}
+
+#[tokio::test]
+async fn tool_call_redacts_sensitive_arguments_in_access_log() {
+	let mock = mock_streamable_http_server(true).await;
+	let trace_id = format!("mcp-e2e-{}", uuid::Uuid::new_v4());
+	let mut t = setup_proxy_test("{}")
+		.unwrap()
+		.with_mcp_backend_sensitive_tool_arguments(
+			mock.addr,
+			std::collections::HashMap::from([("echo".to_string(), vec!["apiToken".to_string()])]),
+		)
+		.with_bind(simple_bind())
+		.with_route(basic_route(mock.addr));
+	let io = t.serve_real_listener(BIND_KEY).await;
+	attach_access_log_policy(&mut t);
+	let client = mcp_streamable_client(io).await;
+
+	let result = client
+		.call_tool(
+			rmcp::model::CallToolRequestParams::new("echo").with_arguments(
+				serde_json::json!({
+					"traceId": trace_id,
+					"apiToken": "s3cr3t",
+				})
+				.as_object()
+				.cloned()
+				.expect("tool arguments should serialize to an object"),
+			),
+		)
+		.await
+		.unwrap();
+	// The upstream still receives the original value.
+	let echoed: serde_json::Value =
+		serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap();
+	assert_eq!(echoed["apiToken"], "s3cr3t");
+
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("scope", "request"),
+		("mcp_trace", &trace_id),
+	])
+	.await
+	.unwrap();
+	assert_eq!(log["mcp_args_cel"]["traceId"], trace_id);
+	assert_eq!(
+		log["mcp_args_cel"]["apiToken"],
+		crate::mcp::REDACTED_ARGUMENT
+	);
+}
+
 fn assert_duration_log_field(log: &serde_json::Value, field: &str) {
 	assert!(
 		log
@@ -3906,9 +4594,15 @@ mod mockserver {
 
 		#[tool(description = "Repeat what you say")]
 		fn echo(&self, Parameters(object): Parameters<JsonObject>) -> Result<CallToolResult, McpError> {
-			Ok(CallToolResult::success(vec![ContentBlock::text(
+			let usage = object.get("usage").cloned();
+			let mut result = CallToolResult::success(vec![ContentBlock::text(
 				serde_json::Value::Object(object).to_string(),
-			)]))
+			)]);
+			// Report the given usage, like a tool backed by an LLM.
+			if let Some(usage) = usage {
+				result.meta = Some(Meta(json!({"usage": usage}).as_object().cloned().unwrap()));
+			}
+			Ok(result)
 		}
 
 		#[tool(description = "Calculate the sum of two numbers")]
@@ -4413,6 +5107,24 @@ async fn test_zero_targets_fail_closed() {
 	assert!(matches!(err, crate::mcp::Error::NoBackends));
 }
 
+#[tokio::test]
+async fn test_too_many_targets_rejected() {
+	let backend = McpBackendGroup {
+		targets: vec![
+			fake_streamable_target("a", "127.0.0.1:1".parse().unwrap()),
+			fake_streamable_target("b", "127.0.0.1:2".parse().unwrap()),
+		],
+		max_targets: Some(1),
+		..Default::default()
+	};
+	let client = PolicyClient::new(setup_proxy_test("{}").unwrap().pi);
+	let err = crate::mcp::upstream::UpstreamGroup::new(client, backend).unwrap_err();
+	assert!(matches!(
+		err,
+		crate::mcp::Error::TooManyTargets { targets: 2, max: 1 }
+	));
+}
+
 #[tokio::test]
 async fn test_zero_targets_fail_open() {
 	let backend = McpBackendGroup {
@@ -5250,6 +5962,56 @@ async fn test_runtime_fanout_fail_open_all_fail() {
 	);
 }
 
+#[tokio::test]
+async fn test_fanout_notification_cap_terminates_aggregation() {
+	use futures_util::StreamExt;
+	use rmcp::model::{ListToolsResult, RequestId, ServerJsonRpcMessage};
+
+	use crate::mcp::mergestream::{MergeStream, Messages};
+
+	let progress = |i: u32| -> ServerJsonRpcMessage {
+		serde_json::from_value(serde_json::json!({
+			"jsonrpc": "2.0",
+			"method": "notifications/progress",
+			"params": {"progressToken": 1, "progress": i},
+		}))
+		.unwrap()
+	};
+	let mut items = (0..100).map(|i| Ok(progress(i))).collect::<Vec<_>>();
+	items.push(Ok(ServerJsonRpcMessage::response(
+		rmcp::model::ServerResult::ListToolsResult(ListToolsResult::default()),
+		RequestId::Number(1),
+	)));
+	let streams = vec![("noisy".into(), Messages::from_results(items))];
+
+	let merge = Box::new(
+		|_: Vec<(Strng, rmcp::model::ServerResult)>, _cel: &_| -> Result<_, _> {
+			panic!("aggregation must stop before merging")
+		},
+	);
+	let ms = MergeStream::new(
+		streams,
+		RequestId::Number(1),
+		merge,
+		empty_cel(),
+		FailureMode::FailClosed,
+	)
+	.with_max_notifications(Some(3));
+
+	let out = ms.collect::<Vec<_>>().await;
+	assert_eq!(out.len(), 4, "3 notifications then the error: {out:?}");
+	assert!(
+		out[..3]
+			.iter()
+			.all(|m| matches!(m, Ok(ServerJsonRpcMessage::Notification(_))))
+	);
+	let err = out[3].as_ref().unwrap_err();
+	assert!(
+		err.to_string().contains("more than 3 notifications"),
+		"{err}"
+	);
+}
+
 #[tokio::test]
 async fn mcp_local_ratelimit() {
 	let mock = mock_streamable_http_server(true).await;
@@ -5643,6 +6405,63 @@ async fn mcp_guardrails_denies_tool_by_name() {
 	assert!(!result.content.is_empty(), "echo should return content");
 }
 
+#[tokio::test]
+async fn mcp_guardrails_deny_arguments_blocks_before_upstream() {
+	let deny = guardrails::DenyArguments {
+		patterns: vec![regex::Regex::new(r"rm\s+-rf").unwrap()],
+	};
+	let policy = BackendTrafficPolicy::McpGuardrails(Arc::new(guardrails::McpGuardrails {
+		processors: vec![guardrails::Processor {
+			methods: [("tools/call".to_string(), guardrails::Phase::Request)].into(),
+			kind: guardrails::ProcessorKind::DenyArguments(deny),
+		}],
+	}));
+
+	// Stateless upstream: every client call is exactly one captured POST.
+	let (mock, capture) = mock_streamable_http_server_with_capture(false).await;
+	let (_bind, io) = setup_proxy_policies(&mock, false, false, vec![policy]).await;
+	let client = mcp_streamable_client(io).await;
+	let upstream_calls = || capture.lock().unwrap().len();
+
+	let before = upstream_calls();
+	let err = client
+		.call_tool(
+			rmcp::model::CallToolRequestParams::new("echo").with_arguments(
+				serde_json::json!({"cmd": "rm -rf /"})
+					.as_object()
+					.cloned()
+					.unwrap(),
+			),
+		)
+		.await
+		.expect_err("tool call with denied arguments should fail");
+	let rmcp::ServiceError::McpError(e) = &err else {
+		panic!("expected McpError, got {err:?}");
+	};
+	assert_eq!(e.code.0, -32001, "denied arguments should map to -32001");
+	assert_eq!(
+		upstream_calls(),
+		before,
+		"denied call must not reach upstream"
+	);
+
+	client
+		.call_tool(
+			rmcp::model::CallToolRequestParams::new("echo").with_arguments(
+				serde_json::json!({"cmd": "ls"})
+					.as_object()
+					.cloned()
+					.unwrap(),
+			),
+		)
+		.await
+		.expect("tool call with allowed arguments should pass");
+	assert!(
+		upstream_calls() > before,
+		"allowed call should reach upstream"
+	);
+}
+
 #[tokio::test]
 async fn mcp_guardrails_mutated_request_reaches_upstream() {
 	use crate::test_helpers::extmcpmock::{
@@ -6571,3 +7390,42 @@ async fn mcp_guardrails_mutated_resource_read_reaches_upstream() {
 		.expect("resource should return text");
 	assert!(text.contains("Business Intelligence Memo"));
 }
+
+#[tokio::test]
+async fn sse_stream_response_sends_keep_alive_comments() {
+	use http_body_util::BodyExt;
+
+	let interval = Duration::from_millis(50);
+	let resp = super::session::sse_stream_response(
+		futures::stream::pending::<crate::mcp::streamablehttp::ServerSseMessage>(),
+		Some(interval),
+	);
+	let mut body = resp.into_body();
+	let mut last = Instant::now();
+	for _ in 0..3 {
+		let frame = tokio::time::timeout(Duration::from_secs(5), body.frame())
+			.await
+			.expect("keep-alive before timeout")
+			.expect("stream stays open")
+			.unwrap();
+		let data = frame.into_data().unwrap();
+		assert!(
+			data.starts_with(b":"),
+			"expected an SSE comment, got {data:?}"
+		);
+		assert!(last.elapsed() >= interval - Duration::from_millis(5));
+		last = Instant::now();
+	}
+
+	let resp = super::session::sse_stream_response(
+		futures::stream::pending::<crate::mcp::streamablehttp::ServerSseMessage>(),
+		None,
+	);
+	let mut body = resp.into_body();
+	assert!(
+		tokio::time::timeout(interval * 4, body.frame())
+			.await
+			.is_err(),
+		"no keep-alive when disabled"
+	);
+}
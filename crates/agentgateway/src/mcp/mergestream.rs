@@ -31,6 +31,19 @@ impl Messages {
 		Self::from(ServerJsonRpcMessage::response(result.into(), id))
 	}
 
+	/// with_guard holds `guard` until the stream is dropped.
+	pub fn with_guard(self, guard: impl Send + 'static) -> Self {
+		Messages(
+			self
+				.0
+				.map(move |message| {
+					let _ = &guard;
+					message
+				})
+				.boxed(),
+		)
+	}
+
 	pub fn map_server_messages(
 		self,
 		mut f: impl FnMut(ServerJsonRpcMessage) -> ServerJsonRpcMessage + Send + 'static,
@@ -155,6 +168,9 @@ pub struct MergeStream {
 	// Present iff `merge` is; supplied to the merge fn for RBAC filtering.
 	cel: Option<CelExecWrapper>,
 	failure_mode: FailureMode,
+	// Caps the notifications forwarded before the terminal responses arrive.
+	max_notifications: Option<usize>,
+	notifications: usize,
 }
 
 impl MergeStream {
@@ -186,9 +202,17 @@ impl MergeStream {
 			merge,
 			cel,
 			failure_mode,
+			max_notifications: None,
+			notifications: 0,
 		}
 	}
 
+	/// Terminates the stream with an error once more than `max` notifications have been forwarded.
+	pub fn with_max_notifications(mut self, max: Option<usize>) -> Self {
+		self.max_notifications = max;
+		self
+	}
+
 	fn merge_terminal_messages(
 		mut self: Pin<&mut Self>,
 	) -> Result<ServerJsonRpcMessage, ClientError> {
@@ -260,7 +284,22 @@ impl Stream for MergeStream {
 								return Poll::Ready(Some(Err(e)));
 							}
 						},
-						_ => return Poll::Ready(Some(msg)),
+						_ => {
+							self.notifications += 1;
+							if let Some(max) = self.max_notifications
+								&& self.notifications > max
+							{
+								warn!(
+									max,
+									"upstream sent too many notifications, terminating the response"
+								);
+								self.complete = true;
+								return Poll::Ready(Some(Err(ClientError::new(anyhow!(
+									"upstream sent more than {max} notifications"
+								)))));
+							}
+							return Poll::Ready(Some(msg));
+						},
 					}
 				},
 				Poll::Ready(None) => {
@@ -1,8 +1,12 @@
 mod apps;
 pub(crate) mod auth;
+pub(crate) mod budget;
+pub(crate) mod dedup;
 pub(crate) mod guardrails;
 mod handler;
+mod initcache;
 mod mergestream;
+mod notification;
 mod rbac;
 mod router;
 mod session;
@@ -49,7 +53,21 @@ pub enum FailureMode {
 	FailOpen,
 }
 
+#[apply(schema!)]
+#[derive(Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", schemars(rename = "McpNotificationRouting"))]
+pub enum NotificationRouting {
+	/// Send notifications about a specific request, such as cancellations, only to the target
+	/// serving that request, and fan out all others. Notifications whose target is unknown are
+	/// fanned out.
+	#[default]
+	Targeted,
+	/// Fan out every notification to all targets.
+	Fanout,
+}
+
 pub(crate) const DEFAULT_SESSION_IDLE_TTL: Duration = Duration::from_mins(30);
+pub(crate) const DEFAULT_SSE_KEEP_ALIVE: Duration = Duration::from_secs(30);
 
 /// Method names of rmcp's typed `ClientRequest` variants. Keep this list in sync with rmcp rev
 /// bumps; only `CustomRequest` and failed typed parses consult it, so drift cannot 404 typed
@@ -115,6 +133,8 @@ pub enum Error {
 	InvalidContentType,
 	#[error("fail to deserialize request body: {0}")]
 	Deserialize(crate::http::Error),
+	#[error("JSON-RPC message exceeds the maximum size of {0} bytes")]
+	MessageTooLarge(usize),
 	#[error("fail to create session: {0}")]
 	StartSession(crate::http::Error),
 	#[error("session not found")]
@@ -123,6 +143,10 @@ pub enum Error {
 	MissingSessionHeader,
 	#[error("session ID is required")]
 	SessionIdRequired,
+	#[error("too many active sessions for this identity (limit {1})")]
+	TooManySessions(Option<RequestId>, usize),
+	#[error("session token budget of {1} tokens exhausted")]
+	TokenBudgetExhausted(Option<RequestId>, u64),
 	#[error("invalid session ID header")]
 	InvalidSessionIdHeader,
 	#[error("invalid MCP protocol version header")]
@@ -171,10 +195,25 @@ pub enum Error {
 	OpenAPI(upstream::OpenAPIParseError),
 	#[error("no backends configured")]
 	NoBackends,
+	#[error("{targets} targets configured, exceeding the maximum of {max}")]
+	TooManyTargets { targets: usize, max: usize },
 }
 
 impl Error {
 	pub fn jsonrpc_error_body(&self) -> Option<String> {
+		if let Error::MessageTooLarge(_) = self {
+			// The message was never parsed, so there is no request id to echo back.
+			return serde_json::to_string(&JsonRpcError {
+				jsonrpc: Default::default(),
+				id: None,
+				error: ErrorData {
+					code: ErrorCode::INVALID_REQUEST,
+					message: self.to_string().into(),
+					data: None,
+				},
+			})
+			.ok();
+		}
 		let (id, error) = match self {
 			Error::McpGuardrails(id, rejection) => (id.clone(), rejection.clone()),
 			Error::UnsupportedVersion {
@@ -208,6 +247,9 @@ impl Error {
 					| Error::InvalidRoutingHeader(Some(id), _) => (id.clone(), ErrorCode::HEADER_MISMATCH),
 					Error::MethodNotFound(Some(id), _) => (id.clone(), ErrorCode::METHOD_NOT_FOUND),
 					Error::InvalidParams(Some(id), _) => (id.clone(), ErrorCode::INVALID_PARAMS),
+					Error::TooManySessions(Some(id), _) | Error::TokenBudgetExhausted(Some(id), _) => {
+						(id.clone(), ErrorCode::INVALID_REQUEST)
+					},
 					_ => return None,
 				};
 				(
@@ -230,6 +272,15 @@ impl Error {
 	}
 }
 
+/// Whether reading a message body failed because it exceeded the configured limit.
+pub(crate) fn is_length_limit_error(err: &crate::http::Error) -> bool {
+	use std::error::Error as _;
+
+	err
+		.source()
+		.is_some_and(|source| source.is::<http_body_util::LengthLimitError>())
+}
+
 impl From<Error> for ProxyError {
 	fn from(value: Error) -> Self {
 		ProxyError::MCP(value)
@@ -282,6 +333,9 @@ impl Display for MCPOperation {
 	}
 }
 
+/// Replaces the value of a sensitive tool argument in logs and traces.
+pub const REDACTED_ARGUMENT: &str = "<redacted>";
+
 #[apply(schema!)]
 #[derive(Default, PartialEq, ::cel::DynamicType)]
 #[dynamic(rename_all = "camelCase")]
@@ -299,6 +353,9 @@ pub struct MCPTool {
 	/// The terminal JSON-RPC error payload, if available.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub error: Option<serde_json::Value>,
+	/// The tool description. Only set while rewriting the descriptions of listed tools.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
 }
 
 #[apply(schema!)]
@@ -315,6 +372,38 @@ pub struct MCPInfo {
 	pub prompt: Option<ResourceId>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub resource: Option<ResourceId>,
+	/// The client implementation reported by an `initialize` request.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub client: Option<MCPClient>,
+}
+
+#[apply(schema!)]
+#[derive(Default, PartialEq, ::cel::DynamicType)]
+#[dynamic(rename_all = "camelCase")]
+pub struct MCPClient {
+	/// The name of the client implementation.
+	pub name: String,
+	/// The version of the client implementation.
+	pub version: String,
+}
+
+impl MCPClient {
+	/// The version truncated to `major.minor`, to bound the cardinality of metrics.
+	pub fn version_bucket(&self) -> &str {
+		match self.version.match_indices('.').nth(1) {
+			Some((i, _)) => &self.version[..i],
+			None => &self.version,
+		}
+	}
+}
+
+impl From<&rmcp::model::Implementation> for MCPClient {
+	fn from(value: &rmcp::model::Implementation) -> Self {
+		Self {
+			name: value.name.clone(),
+			version: value.version.clone(),
+		}
+	}
 }
 
 impl MCPInfo {
@@ -324,6 +413,7 @@ impl MCPInfo {
 			&& self.tool.is_none()
 			&& self.prompt.is_none()
 			&& self.resource.is_none()
+			&& self.client.is_none()
 	}
 
 	pub fn resource_type(&self) -> Option<MCPOperation> {
@@ -386,14 +476,21 @@ impl MCPInfo {
 		self.resource = Some(ResourceId::new(target, name));
 	}
 
+	/// Records the tool call arguments for logging, replacing the values of `sensitive` fields.
 	pub fn capture_call_arguments(
 		&mut self,
-		arguments: Option<serde_json::Map<String, serde_json::Value>>,
+		mut arguments: Option<serde_json::Map<String, serde_json::Value>>,
+		sensitive: &[String],
 	) {
 		let Some(tool) = self.tool.as_mut() else {
 			return;
 		};
 
+		for field in sensitive {
+			if let Some(v) = arguments.as_mut().and_then(|args| args.get_mut(field)) {
+				*v = serde_json::Value::String(REDACTED_ARGUMENT.to_string());
+			}
+		}
 		tool.arguments = arguments;
 	}
 
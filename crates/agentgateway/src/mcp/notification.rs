@@ -0,0 +1,134 @@
+//! Routing of client notifications to MCP targets.
+//!
+//! Most notifications concern the whole session and are fanned out to every target. A
+//! cancellation names the request it cancels; when that request is in flight to a single target,
+//! the cancellation is sent only there, since no other target has seen the request.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use agent_core::strng::Strng;
+use rmcp::model::{ClientNotification, RequestId};
+
+use crate::mcp::NotificationRouting;
+
+/// The single-target requests of a session that are awaiting a response, by request ID.
+#[derive(Debug, Default)]
+pub struct InFlightRequests(Arc<Mutex<HashMap<RequestId, Strng>>>);
+
+impl InFlightRequests {
+	/// track records that `id` is in flight to `target` until the returned guard is dropped.
+	pub fn track(&self, id: RequestId, target: Strng) -> InFlightGuard {
+		self
+			.0
+			.lock()
+			.expect("mutex poisoned")
+			.insert(id.clone(), target);
+		InFlightGuard {
+			requests: self.0.clone(),
+			id,
+		}
+	}
+
+	/// target returns the single target `notification` should be sent to, or None if it should be
+	/// fanned out.
+	pub fn target(
+		&self,
+		routing: NotificationRouting,
+		notification: &ClientNotification,
+	) -> Option<Strng> {
+		if routing == NotificationRouting::Fanout {
+			return None;
+		}
+		match notification {
+			ClientNotification::CancelledNotification(n) => self
+				.0
+				.lock()
+				.expect("mutex poisoned")
+				.get(&n.params.request_id)
+				.cloned(),
+			_ => None,
+		}
+	}
+}
+
+pub struct InFlightGuard {
+	requests: Arc<Mutex<HashMap<RequestId, Strng>>>,
+	id: RequestId,
+}
+
+impl Drop for InFlightGuard {
+	fn drop(&mut self) {
+		self
+			.requests
+			.lock()
+			.expect("mutex poisoned")
+			.remove(&self.id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use agent_core::strng;
+	use rmcp::model::{CancelledNotification, CancelledNotificationParam, InitializedNotification};
+
+	use super::*;
+
+	fn cancelled(id: i64) -> ClientNotification {
+		CancelledNotification {
+			method: Default::default(),
+			params: CancelledNotificationParam {
+				request_id: RequestId::Number(id),
+				reason: None,
+			},
+			extensions: Default::default(),
+		}
+		.into()
+	}
+
+	#[test]
+	fn cancellation_targets_in_flight_request_server() {
+		let in_flight = InFlightRequests::default();
+		let a = in_flight.track(RequestId::Number(1), strng::literal!("a"));
+		let _b = in_flight.track(RequestId::Number(2), strng::literal!("b"));
+
+		assert_eq!(
+			in_flight.target(NotificationRouting::Targeted, &cancelled(1)),
+			Some(strng::literal!("a"))
+		);
+		assert_eq!(
+			in_flight.target(NotificationRouting::Targeted, &cancelled(2)),
+			Some(strng::literal!("b"))
+		);
+		// Unknown requests cannot be attributed to a target, so the cancellation fans out.
+		assert_eq!(
+			in_flight.target(NotificationRouting::Targeted, &cancelled(3)),
+			None
+		);
+		assert_eq!(
+			in_flight.target(NotificationRouting::Fanout, &cancelled(1)),
+			None
+		);
+
+		// Once the request completes, it is no longer in flight.
+		drop(a);
+		assert_eq!(
+			in_flight.target(NotificationRouting::Targeted, &cancelled(1)),
+			None
+		);
+	}
+
+	#[test]
+	fn initialized_notification_fans_out() {
+		let in_flight = InFlightRequests::default();
+		let _a = in_flight.track(RequestId::Number(1), strng::literal!("a"));
+		let initialized: ClientNotification = InitializedNotification {
+			method: Default::default(),
+			extensions: Default::default(),
+		}
+		.into();
+		assert_eq!(
+			in_flight.target(NotificationRouting::Targeted, &initialized),
+			None
+		);
+	}
+}
@@ -33,6 +33,10 @@ impl CelExecWrapper {
 	pub fn new(req: ::http::Request<()>) -> CelExecWrapper {
 		CelExecWrapper(Arc::new(req))
 	}
+
+	pub fn mcp_executor<'a>(&'a self, mcp: &'a crate::mcp::MCPInfo) -> crate::cel::Executor<'a> {
+		crate::cel::Executor::new_mcp_request(self.0.as_ref(), mcp)
+	}
 }
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
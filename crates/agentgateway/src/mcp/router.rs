@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -17,8 +18,8 @@ use crate::proxy::httpproxy::{MustSnapshot, PolicyClient};
 use crate::store::{BackendPolicies, Stores};
 use crate::telemetry::log::RequestLog;
 use crate::types::agent::{
-	BackendTargetRef, McpBackend, McpPrefixMode, McpTargetSpec, ResourceName, SimpleBackend,
-	SimpleBackendReference,
+	BackendTargetRef, McpBackend, McpInjectedTool, McpPrefixMode, McpTargetSpec, ResourceName,
+	SimpleBackend, SimpleBackendReference,
 };
 use crate::{ProxyInputs, cel, mcp};
 
@@ -105,7 +106,30 @@ impl App {
 				stateful: backend.stateful,
 				prefix_mode: backend.prefix_mode,
 				failure_mode: backend.failure_mode,
+				notification_routing: backend.notification_routing,
 				session_idle_ttl: backend.session_idle_ttl,
+				sse_keep_alive: backend.sse_keep_alive,
+				max_message_size: backend.max_message_size,
+				max_sessions_per_identity: backend.max_sessions_per_identity,
+				max_targets: backend.max_targets,
+				max_fanout_notifications: backend.max_fanout_notifications,
+				tool_call_dedup: backend.tool_call_dedup_window.map(|window| {
+					self
+						.session
+						.tool_call_dedup(binds.config_generation(), &backend_group_name, window)
+				}),
+				session_token_budget: backend.session_token_budget,
+				sensitive_tool_arguments: backend.sensitive_tool_arguments.clone(),
+				tool_description: backend.tool_description.clone(),
+				injected_tools: backend.injected_tools.clone(),
+				initialize_cache: backend
+					.initialize_cache_ttl
+					.filter(|_| !backend.stateful)
+					.map(|ttl| {
+						self
+							.session
+							.initialize_cache(binds.config_generation(), &backend_group_name, ttl)
+					}),
 			}
 		};
 		let sessions = self.session.clone();
@@ -142,7 +166,12 @@ impl App {
 		}
 
 		// MCP requires CEL execution after the snapshot so we do not clear extensions
-		let req = req.take_and_snapshot_without_clearing_extensions(Some(&mut log))?;
+		let mut req = req.take_and_snapshot_without_clearing_extensions(Some(&mut log))?;
+		// Set after the snapshot so logs reflect what the client sent; the upstream requests
+		// inherit the incoming headers, so every target sees the same values.
+		for header in &backend.correlation_headers {
+			header.apply(req.headers_mut());
+		}
 		if log.request_processing_duration.is_none() {
 			// This is a bit inaccurate but the best we can do for the MCP path without very invasive changes.
 			log.request_processing_duration = Some(log.request_processing_start.elapsed());
@@ -171,6 +200,7 @@ impl App {
 				sessions,
 				StreamableHttpServerConfig {
 					stateful_mode: backend.stateful,
+					session_header: backend.session_header().map_err(ProxyError::Processing)?,
 				},
 			);
 			Box::pin(
@@ -224,7 +254,19 @@ pub struct McpBackendGroup {
 	pub stateful: bool,
 	pub prefix_mode: McpPrefixMode,
 	pub failure_mode: FailureMode,
+	pub notification_routing: mcp::NotificationRouting,
 	pub session_idle_ttl: Duration,
+	pub sse_keep_alive: Option<Duration>,
+	pub max_message_size: Option<usize>,
+	pub max_sessions_per_identity: Option<usize>,
+	pub max_targets: Option<usize>,
+	pub max_fanout_notifications: Option<usize>,
+	pub tool_call_dedup: Option<Arc<mcp::dedup::ToolCallDedup>>,
+	pub session_token_budget: Option<u64>,
+	pub sensitive_tool_arguments: HashMap<String, Vec<String>>,
+	pub tool_description: Option<Arc<cel::Expression>>,
+	pub injected_tools: Vec<McpInjectedTool>,
+	pub initialize_cache: Option<Arc<mcp::initcache::InitializeCache>>,
 }
 
 impl Default for McpBackendGroup {
@@ -234,7 +276,19 @@ impl Default for McpBackendGroup {
 			stateful: true,
 			prefix_mode: McpPrefixMode::default(),
 			failure_mode: crate::mcp::FailureMode::default(),
+			notification_routing: mcp::NotificationRouting::default(),
 			session_idle_ttl: mcp::DEFAULT_SESSION_IDLE_TTL,
+			sse_keep_alive: Some(mcp::DEFAULT_SSE_KEEP_ALIVE),
+			max_message_size: None,
+			max_sessions_per_identity: None,
+			max_targets: None,
+			max_fanout_notifications: None,
+			tool_call_dedup: None,
+			session_token_budget: None,
+			sensitive_tool_arguments: HashMap::new(),
+			tool_description: None,
+			injected_tools: vec![],
+			initialize_cache: None,
 		}
 	}
 }
@@ -246,3 +300,17 @@ pub struct McpTarget {
 	pub backend_policies: BackendPolicies,
 	pub backend: Option<SimpleBackend>,
 }
+
+impl McpTarget {
+	/// The resolved address and path requests to this target are sent to. Stdio targets have none.
+	pub fn address(&self) -> Option<String> {
+		let path = match &self.spec {
+			McpTargetSpec::Sse(s) => s.path.as_str(),
+			McpTargetSpec::Mcp(s) => s.path.as_str(),
+			McpTargetSpec::OpenAPI(_) => "",
+			McpTargetSpec::Stdio { .. } => return None,
+		};
+		let backend = self.backend.as_ref()?;
+		Some(format!("{}{path}", backend.hostport()))
+	}
+}
@@ -22,7 +22,10 @@ use sse_stream::{KeepAlive, Sse, SseBody, SseStream};
 use tokio::sync::mpsc::{Receiver, Sender};
 
 use crate::http::Response;
+use crate::mcp::budget::SessionTokenBudget;
+use crate::mcp::dedup::ToolCallDedup;
 use crate::mcp::handler::{Relay, RelayInputs, ResolveKind};
+use crate::mcp::initcache::{HandshakeTarget, InitializeCache, ReusedHandshake};
 use crate::mcp::mergestream::Messages;
 use crate::mcp::streamablehttp::{ServerSseMessage, StreamableHttpPostResponse};
 use crate::mcp::subscriptions::ResourceSubscription;
@@ -30,6 +33,7 @@ use crate::mcp::upstream::{IncomingRequestContext, UpstreamError};
 use crate::mcp::{ClientError, rbac};
 use crate::proxy::ProxyError;
 use crate::telemetry::log::{AsyncLog, SpanWriteOnDrop};
+use crate::types::agent::{McpInjectedToolHandler, ResourceName};
 use crate::{mcp, *};
 
 #[derive(Debug, Clone)]
@@ -38,6 +42,9 @@ pub struct Session {
 	relay: Arc<Relay>,
 	pub id: Arc<str>,
 	tx: Option<Sender<ServerJsonRpcMessage>>,
+	/// Tokens spent on tool calls in this session, if the backend sets a budget. Owned by the
+	/// [SessionManager], so it outlives the relay the session happens to be served by.
+	token_budget: Option<Arc<SessionTokenBudget>>,
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +52,8 @@ struct SessionEntry {
 	session: Session,
 	last_access: Instant,
 	idle_ttl: Duration,
+	/// The authenticated caller that opened the session, if any.
+	identity: Option<Strng>,
 }
 
 const SESSION_REAP_INTERVAL: Duration = Duration::from_secs(30);
@@ -75,7 +84,7 @@ impl Session {
 	/// handshake.
 	pub async fn stateless_send_and_initialize(
 		&mut self,
-		parts: Parts,
+		mut parts: Parts,
 		message: ClientJsonRpcMessage,
 		initialize_upstream: bool,
 	) -> Result<Response, ProxyError> {
@@ -85,6 +94,8 @@ impl Session {
 		};
 		let is_init = matches!(&message,
 			ClientJsonRpcMessage::Request(r) if matches!(r.request, ClientRequest::InitializeRequest(_)));
+		// Cached handshakes this request relies on; they are forgotten if the request fails.
+		let mut reused_handshake = None;
 		if initialize_upstream && !is_init {
 			let mut client_info = get_client_info();
 			if let Some(protocol_version) =
@@ -92,6 +103,14 @@ impl Session {
 			{
 				client_info.protocol_version = protocol_version;
 			}
+			let version = client_info.protocol_version.clone();
+			// Stdio and SSE targets hold per-connection state, so they always need a handshake.
+			let cache = self
+				.relay
+				.upstreams
+				.initialize_cache()
+				.filter(|_| !self.relay.upstreams.has_connection_teardown())
+				.cloned();
 			let init_request = rmcp::model::InitializeRequest::new(client_info);
 			let request_type = match &message {
 				ClientJsonRpcMessage::Request(r) => Some(&r.request),
@@ -112,50 +131,97 @@ impl Session {
 						Ok(target) => target,
 						Err(err) => return Self::handle_error(req_id.clone(), Err(err), false).await,
 					};
-					let res = self
-						.send_init_single(parts.clone(), init_request, service_name)
-						.await;
-					if let Some(sessions) = self.relay.get_sessions() {
-						let s = http::sessionpersistence::SessionState::MCP(
-							http::sessionpersistence::MCPSessionState::new(sessions),
-						);
-						if let Ok(id) = s.encode(&self.encoder) {
-							self.id = id.into();
+					let targets = vec![self.relay.upstreams.handshake_target(service_name)];
+					if let Some(cache) = cache
+						.as_ref()
+						.filter(|c| c.is_initialized(&targets[0], &version))
+					{
+						trace!(target = service_name, "reusing cached initialize handshake");
+						reused_handshake = Some(ReusedHandshake {
+							cache: cache.clone(),
+							targets,
+							version,
+						});
+					} else {
+						let res = self
+							.send_init_single(parts.clone(), init_request, service_name)
+							.await;
+						if let Some(sessions) = self.relay.get_sessions() {
+							let s = http::sessionpersistence::SessionState::MCP(
+								http::sessionpersistence::MCPSessionState::new(sessions),
+							);
+							if let Ok(id) = s.encode(&self.encoder) {
+								self.id = id.into();
+							}
+						}
+						Self::handle_error(Some(RequestId::Number(0)), res, false).await?;
+						// Now send the initialized notification
+						let _ = Self::handle_error(
+							None,
+							self
+								.send_initialized_notification_single(parts.clone(), service_name)
+								.await,
+							false,
+						)
+						.await?;
+						if let Some(cache) = &cache {
+							self.cache_handshakes(cache, &targets, &version);
 						}
 					}
-					Self::handle_error(Some(RequestId::Number(0)), res, false).await?;
-					// Now send the initialized notification
-					let _ = Self::handle_error(
-						None,
-						self
-							.send_initialized_notification_single(parts.clone(), service_name)
-							.await,
-						false,
-					)
-					.await?;
 				},
 				_ => {
-					// We should fan out the initialize request to all MCP servers
-					let _ = self
-						.send(
-							parts.clone(),
-							ClientJsonRpcMessage::request(init_request.into(), RequestId::Number(0)),
-						)
-						.await?;
-					let notification = ClientJsonRpcMessage::notification(
-						rmcp::model::InitializedNotification {
-							method: Default::default(),
-							extensions: Default::default(),
+					let targets: Vec<HandshakeTarget> = self
+						.relay
+						.upstreams
+						.iter_named()
+						.map(|(name, _)| self.relay.upstreams.handshake_target(&name))
+						.collect();
+					if let Some(cache) = cache
+						.as_ref()
+						.filter(|c| targets.iter().all(|t| c.is_initialized(t, &version)))
+					{
+						trace!("reusing cached initialize handshakes");
+						reused_handshake = Some(ReusedHandshake {
+							cache: cache.clone(),
+							targets,
+							version,
+						});
+					} else {
+						// We should fan out the initialize request to all MCP servers
+						let _ = self
+							.send(
+								parts.clone(),
+								ClientJsonRpcMessage::request(init_request.into(), RequestId::Number(0)),
+							)
+							.await?;
+						let notification = ClientJsonRpcMessage::notification(
+							rmcp::model::InitializedNotification {
+								method: Default::default(),
+								extensions: Default::default(),
+							}
+							.into(),
+						);
+						let _ = self.send(parts.clone(), notification).await?;
+						if let Some(cache) = &cache {
+							self.cache_handshakes(cache, &targets, &version);
 						}
-						.into(),
-					);
-					let _ = self.send(parts.clone(), notification).await?;
+					}
 				},
 			}
 		}
 		// Now we can send the message like normal (if it's tools/call, it'll go to the initialized target)
 		if initialize_upstream {
-			return self.send(parts, message).await;
+			if let Some(reused) = &reused_handshake {
+				parts.extensions.insert(reused.clone());
+			}
+			let res = self.send(parts, message).await;
+			// A failure may mean the upstream lost the state our skipped handshake relied on.
+			if res.is_err()
+				&& let Some(reused) = reused_handshake
+			{
+				reused.invalidate_all();
+			}
+			return res;
 		}
 		let res = self
 			.send_internal(parts, message)
@@ -171,6 +237,28 @@ impl Session {
 		}
 	}
 
+	/// Records a completed handshake for the targets that did not assign a session id. Targets with
+	/// a session keep per-session state upstream and must be initialized on every request.
+	fn cache_handshakes(
+		&self,
+		cache: &InitializeCache,
+		targets: &[HandshakeTarget],
+		version: &ProtocolVersion,
+	) {
+		for target in targets {
+			let stateless = self
+				.relay
+				.upstreams
+				.get(&target.name)
+				.ok()
+				.and_then(|u| u.get_session_state())
+				.is_some_and(|s| s.session.is_none());
+			if stateless {
+				cache.insert(target, version);
+			}
+		}
+	}
+
 	pub fn with_inputs(mut self, inputs: RelayInputs) -> Self {
 		self.relay = Arc::new(self.relay.with_policies(inputs.policies));
 		self
@@ -364,6 +452,9 @@ impl Session {
 			Err(UpstreamError::McpGuardrails(rej)) if req_id.is_some() => {
 				Err(mcp::Error::McpGuardrails(req_id.unwrap(), rej).into())
 			},
+			Err(UpstreamError::TokenBudgetExhausted(limit)) => {
+				Err(mcp::Error::TokenBudgetExhausted(req_id, limit).into())
+			},
 			Err(UpstreamError::InvalidRequest(message)) if req_id.is_some() && downstream_modern => {
 				Err(mcp::Error::InvalidParams(req_id, message).into())
 			},
@@ -452,6 +543,8 @@ impl Session {
 				match &mut r.request {
 					ClientRequest::InitializeRequest(ir) => {
 						self.strip_unsupported_client_capabilities(&mut ir.params.capabilities, &ctx);
+						let client = mcp::MCPClient::from(&ir.params.client_info);
+						log.non_atomic_mutate(|l| l.client = Some(client));
 
 						let pv = ir.params.protocol_version.clone();
 						let res = Box::pin(
@@ -539,6 +632,28 @@ impl Session {
 						.await
 					},
 					ClientRequest::CallToolRequest(ctr) => {
+						match self
+							.relay
+							.upstreams
+							.injected_tool(&ctr.params.name)
+							.map(|t| &t.handler)
+						{
+							Some(McpInjectedToolHandler::Respond(text)) => {
+								debug!(tool = %ctr.params.name, "responding to injected tool call");
+								return self
+									.relay
+									.respond_to_injected_tool(r.id.clone(), text, &ctx, Some(log));
+							},
+							Some(McpInjectedToolHandler::Forward { target, tool }) => {
+								ctr.params.name = self.relay.client_tool_name(target, tool).into();
+							},
+							None => {},
+						}
+						if let Some(budget) = &self.token_budget
+							&& budget.is_exhausted()
+						{
+							return Err(UpstreamError::TokenBudgetExhausted(budget.limit()));
+						}
 						let name = ctr.params.name.clone();
 						let (service_name, tool) = Box::pin(self.relay.resolve_resource_name(
 							ResolveKind::Tool,
@@ -548,9 +663,10 @@ impl Session {
 						.await?;
 						span.rename_span(format!("{method} {service_name}"));
 						let call_arguments = ctr.params.arguments.clone();
+						let sensitive = self.relay.upstreams.sensitive_tool_arguments(&name);
 						log.non_atomic_mutate(|l| {
 							l.set_tool(service_name.to_string(), tool.to_string());
-							l.capture_call_arguments(call_arguments);
+							l.capture_call_arguments(call_arguments, sensitive);
 						});
 						let tn = tool.to_string();
 						ctr.params.name = tn.into();
@@ -567,11 +683,22 @@ impl Session {
 							&name,
 						))
 						.await?;
-						Box::pin(
-							self
-								.relay
-								.send_single(r, ctx, &service_name, Some(log.clone())),
-						)
+						let dedup_key = self.relay.upstreams.tool_call_dedup.as_ref().map(|_| {
+							ToolCallDedup::key(
+								crate::http::caller_identity(ctx.extensions()).as_deref(),
+								ctx.headers(),
+								&service_name,
+								&ctr.params,
+							)
+						});
+						Box::pin(self.relay.send_tool_call(
+							r,
+							ctx,
+							&service_name,
+							Some(log.clone()),
+							dedup_key,
+							self.token_budget.clone(),
+						))
 						.await
 					},
 					ClientRequest::GetPromptRequest(gpr) => {
@@ -716,8 +843,6 @@ impl Session {
 					l.session_id = Some(session_id);
 				});
 				self.strip_unsupported_client_capabilities_from_meta(&mut r.notification, &ctx);
-				// TODO: the notification needs to be fanned out in some cases and sent to a single one in others
-				// however, we don't have a way to map to the correct service yet
 				Box::pin(self.relay.send_notification(r, ctx)).await
 			},
 
@@ -767,6 +892,86 @@ pub struct SessionManager {
 	encoder: http::sessionpersistence::Encoder,
 	sessions: Arc<RwLock<HashMap<String, SessionEntry>>>,
 	idle_reaper: OnceLock<tokio::task::AbortHandle>,
+	initialize_caches: parking_lot::Mutex<BackendCaches<InitializeCache>>,
+	tool_call_dedups: parking_lot::Mutex<BackendCaches<ToolCallDedup>>,
+	/// Per-identity count of sessions that have reserved a slot but are not yet inserted.
+	reserved_sessions: Arc<parking_lot::Mutex<HashMap<Strng, usize>>>,
+	/// Tool call token budgets, by session ID. Removed along with their session.
+	token_budgets: TokenBudgets,
+}
+
+/// State shared by every session of a backend, such as its initialize handshake cache, for the
+/// configuration generation it was built under.
+#[derive(Debug)]
+struct BackendCaches<T> {
+	generation: u64,
+	caches: HashMap<ResourceName, Arc<T>>,
+}
+
+impl<T> Default for BackendCaches<T> {
+	fn default() -> Self {
+		Self {
+			generation: 0,
+			caches: HashMap::new(),
+		}
+	}
+}
+
+impl<T> BackendCaches<T> {
+	/// The cache for `backend`, built with `new` if there is none or if `current` rejects the
+	/// existing one. A config reload, seen as a new `generation`, drops every cache: removed
+	/// backends would otherwise keep theirs, and a backend may now name different targets.
+	fn get(
+		&mut self,
+		generation: u64,
+		backend: &ResourceName,
+		current: impl FnOnce(&T) -> bool,
+		new: impl FnOnce() -> T,
+	) -> Arc<T> {
+		if self.generation != generation {
+			self.generation = generation;
+			self.caches.clear();
+		}
+		match self.caches.get(backend) {
+			Some(cache) if current(cache) => cache.clone(),
+			_ => {
+				let cache = Arc::new(new());
+				self.caches.insert(backend.clone(), cache.clone());
+				cache
+			},
+		}
+	}
+}
+
+type TokenBudgets = Arc<parking_lot::Mutex<HashMap<Arc<str>, Arc<SessionTokenBudget>>>>;
+
+/// A slot reserved under an identity's session limit. It counts against the limit until the
+/// session is inserted into the [SessionManager], and is released if it is dropped first.
+#[derive(Debug)]
+pub struct SessionSlot {
+	identity: Option<Strng>,
+	reserved: Option<Arc<parking_lot::Mutex<HashMap<Strng, usize>>>>,
+}
+
+impl SessionSlot {
+	fn release(&mut self) {
+		let (Some(reserved), Some(identity)) = (self.reserved.take(), self.identity.as_ref()) else {
+			return;
+		};
+		let mut reserved = reserved.lock();
+		if let Some(count) = reserved.get_mut(identity) {
+			*count -= 1;
+			if *count == 0 {
+				reserved.remove(identity);
+			}
+		}
+	}
+}
+
+impl Drop for SessionSlot {
+	fn drop(&mut self) {
+		self.release();
+	}
 }
 
 fn session_id() -> Arc<str> {
@@ -779,13 +984,69 @@ impl SessionManager {
 			encoder,
 			sessions: Arc::new(RwLock::new(HashMap::new())),
 			idle_reaper: OnceLock::new(),
+			initialize_caches: Default::default(),
+			tool_call_dedups: Default::default(),
+			reserved_sessions: Default::default(),
+			token_budgets: Default::default(),
 		})
 	}
 
+	/// The token budget of session `id`, if its backend sets one. A changed limit starts a new
+	/// budget.
+	fn token_budget(&self, id: &Arc<str>, relay: &Relay) -> Option<Arc<SessionTokenBudget>> {
+		let mut budgets = self.token_budgets.lock();
+		let Some(limit) = relay.upstreams.session_token_budget() else {
+			budgets.remove(id);
+			return None;
+		};
+		let budget = budgets
+			.entry(id.clone())
+			.or_insert_with(|| Arc::new(SessionTokenBudget::new(limit)));
+		if budget.limit() != limit {
+			*budget = Arc::new(SessionTokenBudget::new(limit));
+		}
+		Some(budget.clone())
+	}
+
+	/// The initialize handshakes cached for `backend`. A changed TTL starts a new cache.
+	pub fn initialize_cache(
+		&self,
+		generation: u64,
+		backend: &ResourceName,
+		ttl: Duration,
+	) -> Arc<InitializeCache> {
+		self.initialize_caches.lock().get(
+			generation,
+			backend,
+			|cache| cache.ttl() == ttl,
+			|| InitializeCache::new(ttl),
+		)
+	}
+
+	/// The tool calls deduplicated across every session of `backend`, and across requests when it
+	/// is stateless. A changed window starts a new dedup state.
+	pub fn tool_call_dedup(
+		&self,
+		generation: u64,
+		backend: &ResourceName,
+		window: Duration,
+	) -> Arc<ToolCallDedup> {
+		self.tool_call_dedups.lock().get(
+			generation,
+			backend,
+			|dedup| dedup.window() == window,
+			|| ToolCallDedup::new(window),
+		)
+	}
+
 	pub fn ensure_idle_running(&self) {
-		self
-			.idle_reaper
-			.get_or_init(|| tokio::spawn(run_idle_reaper(self.sessions.clone())).abort_handle());
+		self.idle_reaper.get_or_init(|| {
+			tokio::spawn(run_idle_reaper(
+				self.sessions.clone(),
+				self.token_budgets.clone(),
+			))
+			.abort_handle()
+		});
 	}
 
 	pub fn get_session(&self, id: &str, builder: RelayInputs) -> Option<Session> {
@@ -799,6 +1060,8 @@ impl SessionManager {
 		&self,
 		id: &str,
 		builder: RelayInputs,
+		identity: Option<Strng>,
+		request_id: Option<RequestId>,
 	) -> Result<Option<Session>, mcp::Error> {
 		if let Some(s) = self.sessions.write().expect("poisoned").get_mut(id) {
 			s.last_access = Instant::now();
@@ -810,27 +1073,27 @@ impl SessionManager {
 		let http::sessionpersistence::SessionState::MCP(state) = d else {
 			return Ok(None);
 		};
+		// A resumed session becomes active again, so it counts against the identity's limit.
+		let slot = self.reserve_session(
+			identity,
+			builder.backend.max_sessions_per_identity,
+			request_id,
+		)?;
 		let relay = builder.build_new_connections()?;
 		if let Err(err) = relay.set_sessions(state.sessions) {
 			warn!("failed to resume session: {err}");
 			return Ok(None);
 		}
 
+		let id: Arc<str> = id.into();
 		let sess = Session {
-			id: id.into(),
+			token_budget: self.token_budget(&id, &relay),
+			id,
 			relay: Arc::new(relay),
 			tx: None,
 			encoder: self.encoder.clone(),
 		};
-		let mut sm = self.sessions.write().expect("write lock");
-		sm.insert(
-			id.to_string(),
-			SessionEntry {
-				session: sess.clone(),
-				last_access: Instant::now(),
-				idle_ttl,
-			},
-		);
+		self.insert_session(sess.clone(), idle_ttl, slot);
 		Ok(Some(sess))
 	}
 
@@ -840,6 +1103,7 @@ impl SessionManager {
 
 		// Do NOT insert yet
 		Session {
+			token_budget: self.token_budget(&id, &relay),
 			id: id.clone(),
 			relay: Arc::new(relay),
 			tx: None,
@@ -847,7 +1111,9 @@ impl SessionManager {
 		}
 	}
 
-	pub fn insert_session(&self, sess: Session, idle_ttl: Duration) {
+	/// insert_session registers a created session, converting its reserved slot into an active
+	/// session.
+	pub fn insert_session(&self, sess: Session, idle_ttl: Duration, slot: SessionSlot) {
 		let mut sm = self.sessions.write().expect("write lock");
 		sm.insert(
 			sess.id.to_string(),
@@ -855,8 +1121,45 @@ impl SessionManager {
 				session: sess,
 				last_access: Instant::now(),
 				idle_ttl,
+				identity: slot.identity.clone(),
 			},
 		);
+		// Release the reservation while the new entry is visible to concurrent reservations.
+		drop(slot);
+	}
+
+	/// reserve_session claims a slot under `identity`'s session limit for a session about to be
+	/// created or resumed, rejecting it if the identity already holds `limit` sessions. Sessions
+	/// without an identity, or without a limit, are always allowed.
+	pub fn reserve_session(
+		&self,
+		identity: Option<Strng>,
+		limit: Option<usize>,
+		request_id: Option<RequestId>,
+	) -> Result<SessionSlot, mcp::Error> {
+		let (Some(id), Some(limit)) = (identity.as_ref(), limit) else {
+			return Ok(SessionSlot {
+				identity,
+				reserved: None,
+			});
+		};
+		// Inserts take the write lock, so the active count cannot change until the slot is reserved.
+		let sessions = self.sessions.read().expect("read lock");
+		let mut reserved = self.reserved_sessions.lock();
+		let active = sessions
+			.values()
+			.filter(|e| e.identity.as_ref() == Some(id))
+			.count()
+			+ reserved.get(id).copied().unwrap_or_default();
+		if active >= limit {
+			debug!("rejecting session for {id}: {active} active sessions (limit {limit})");
+			return Err(mcp::Error::TooManySessions(request_id, limit));
+		}
+		*reserved.entry(id.clone()).or_default() += 1;
+		Ok(SessionSlot {
+			identity,
+			reserved: Some(self.reserved_sessions.clone()),
+		})
 	}
 
 	/// create_stateless_session creates a session for stateless mode.
@@ -870,6 +1173,8 @@ impl SessionManager {
 			relay: Arc::new(relay),
 			tx: None,
 			encoder: self.encoder.clone(),
+			// A stateless session ends with its request, so there is nothing to accumulate.
+			token_budget: None,
 		}
 	}
 
@@ -879,30 +1184,25 @@ impl SessionManager {
 		&self,
 		relay: Relay,
 		idle_ttl: Duration,
+		slot: SessionSlot,
 	) -> (Session, Receiver<ServerJsonRpcMessage>) {
 		let (tx, rx) = tokio::sync::mpsc::channel(64);
 		let id = session_id();
 		let sess = Session {
+			token_budget: self.token_budget(&id, &relay),
 			id: id.clone(),
 			relay: Arc::new(relay),
 			tx: Some(tx),
 			encoder: self.encoder.clone(),
 		};
-		let mut sm = self.sessions.write().expect("write lock");
-		sm.insert(
-			id.to_string(),
-			SessionEntry {
-				session: sess.clone(),
-				last_access: Instant::now(),
-				idle_ttl,
-			},
-		);
+		self.insert_session(sess.clone(), idle_ttl, slot);
 		(sess, rx)
 	}
 
 	pub async fn delete_session(&self, id: &str, parts: Parts) -> Option<Response> {
 		let sess = {
 			let mut sm = self.sessions.write().expect("write lock");
+			self.token_budgets.lock().remove(id);
 			sm.remove(id)?.session
 		};
 		// Swallow the error
@@ -918,20 +1218,30 @@ impl Drop for SessionManager {
 	}
 }
 
-async fn run_idle_reaper(sessions: Arc<RwLock<HashMap<String, SessionEntry>>>) {
+async fn run_idle_reaper(
+	sessions: Arc<RwLock<HashMap<String, SessionEntry>>>,
+	token_budgets: TokenBudgets,
+) {
 	let mut ticker = tokio::time::interval(SESSION_REAP_INTERVAL);
 	ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 	loop {
 		ticker.tick().await;
-		reap_expired_entries(&sessions);
+		reap_expired_entries(&sessions, &token_budgets);
 	}
 }
 
-fn reap_expired_entries(sessions: &Arc<RwLock<HashMap<String, SessionEntry>>>) {
+fn reap_expired_entries(
+	sessions: &Arc<RwLock<HashMap<String, SessionEntry>>>,
+	token_budgets: &TokenBudgets,
+) {
 	let now = Instant::now();
 	let mut guard = sessions.write().expect("write lock");
 	let pre = guard.len();
 	guard.retain(|_, entry| now.duration_since(entry.last_access) < entry.idle_ttl);
+	// Budgets of sessions that were never inserted, or were removed, go too.
+	token_budgets
+		.lock()
+		.retain(|id, _| guard.contains_key(id.as_ref()));
 	let post = guard.len();
 	if post < pre {
 		tracing::debug!("reaped {} sessions", pre - post);
@@ -960,6 +1270,7 @@ impl Drop for SessionDropper {
 		let mut sm = self.sm.sessions.write().expect("write lock");
 		debug!("delete session {}", s.id);
 		sm.remove(s.id.as_ref());
+		self.sm.token_budgets.lock().remove(&s.id);
 		tokio::task::spawn(async move { s.delete_session(parts).await });
 	}
 }
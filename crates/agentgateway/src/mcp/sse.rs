@@ -3,7 +3,7 @@ use std::sync::Arc;
 use ::http::StatusCode;
 use axum::extract::Query;
 use axum::response::Sse;
-use axum::response::sse::Event;
+use axum::response::sse::{Event, KeepAlive};
 use axum_core::response::IntoResponse;
 use futures_util::StreamExt;
 use rmcp::model::{ClientJsonRpcMessage, ClientRequest};
@@ -56,11 +56,18 @@ impl LegacySSEService {
 		else {
 			return mcp::Error::InvalidSessionIdQuery.into();
 		};
-		let limit = http::buffer_limit(&request);
+		let max_message_size = inputs.backend.max_message_size;
+		let limit = max_message_size.unwrap_or_else(|| http::buffer_limit(&request));
 		let (part, body) = request.into_parts();
 		let message = json::from_body_with_limit::<ClientJsonRpcMessage>(body, limit)
 			.await
-			.map_err(mcp::Error::Deserialize)?;
+			.map_err(|e| {
+				if max_message_size.is_some() && mcp::is_length_limit_error(&e) {
+					mcp::Error::MessageTooLarge(limit)
+				} else {
+					mcp::Error::Deserialize(e)
+				}
+			})?;
 
 		let Some(mut session) = self.session_manager.get_session(&session_id, inputs) else {
 			return mcp::Error::UnknownSession.into();
@@ -91,11 +98,19 @@ impl LegacySSEService {
 		inputs: RelayInputs,
 	) -> Result<Response, ProxyError> {
 		let idle_ttl = inputs.backend.session_idle_ttl;
+		let keep_alive = inputs.backend.sse_keep_alive;
+		let slot = self.session_manager.reserve_session(
+			crate::http::caller_identity(request.extensions()),
+			inputs.backend.max_sessions_per_identity,
+			None,
+		)?;
 		let relay = inputs.build_new_connections()?;
 
 		// GET requests establish an SSE stream.
 		// We will return the sessionId, and all future responses will get sent on the rx channel to send to this channel.
-		let (session, rx) = self.session_manager.create_legacy_session(relay, idle_ttl);
+		let (session, rx) = self
+			.session_manager
+			.create_legacy_session(relay, idle_ttl, slot);
 		let mut base_url = request
 			.extensions()
 			.get::<filters::OriginalUrl>()
@@ -122,7 +137,14 @@ impl LegacySSEService {
 			}),
 		);
 		let (parts, _) = request.into_parts();
-		Ok(Sse::new(stream).into_response().map(|b| {
+		let sse = Sse::new(stream);
+		let resp = match keep_alive {
+			Some(interval) => sse
+				.keep_alive(KeepAlive::new().interval(interval))
+				.into_response(),
+			None => sse.into_response(),
+		};
+		Ok(resp.map(|b| {
 			DropBody::new(
 				b,
 				session::dropper(self.session_manager.clone(), session, parts),
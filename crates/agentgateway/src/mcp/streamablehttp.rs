@@ -8,7 +8,7 @@ use rmcp::model::{
 };
 use rmcp::transport::common::http_header::{
 	EVENT_STREAM_MIME_TYPE, HEADER_MCP_METHOD, HEADER_MCP_NAME, HEADER_MCP_PARAM_PREFIX,
-	HEADER_MCP_PROTOCOL_VERSION, HEADER_SESSION_ID, JSON_MIME_TYPE,
+	HEADER_MCP_PROTOCOL_VERSION, JSON_MIME_TYPE,
 };
 use rmcp::transport::common::mcp_headers::{decode_header_value, encode_header_value};
 
@@ -23,6 +23,8 @@ use crate::*;
 pub struct StreamableHttpServerConfig {
 	/// If true, the server will create a session for each request and keep it alive.
 	pub stateful_mode: bool,
+	/// Header carrying the session id to and from clients. It is not forwarded to the upstreams.
+	pub session_header: ::http::HeaderName,
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +42,10 @@ pub enum StreamableHttpPostResponse {
 	Sse(BoxedSseStream, Option<String>),
 }
 
+/// The header carrying the client's session id, which must not be forwarded to upstreams.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientSessionHeader(pub(crate) ::http::HeaderName);
+
 #[derive(Debug, Clone)]
 pub(crate) struct RequestProtocol {
 	version: Option<ProtocolVersion>,
@@ -79,10 +85,14 @@ impl StreamableHttpService {
 
 	pub async fn handle(
 		&self,
-		request: Request,
+		mut request: Request,
 		inputs: RelayInputs,
 	) -> Result<Response, ProxyError> {
 		let method = request.method().clone();
+		// Upstream requests inherit the incoming headers; this tells them which one to strip.
+		request
+			.extensions_mut()
+			.insert(ClientSessionHeader(self.config.session_header.clone()));
 
 		match (method, self.config.stateful_mode) {
 			(http::Method::POST, _) => {
@@ -126,10 +136,14 @@ impl StreamableHttpService {
 			return mcp::Error::InvalidContentType.into();
 		}
 
-		let limit = http::buffer_limit(&request);
+		let max_message_size = inputs.backend.max_message_size;
+		let limit = max_message_size.unwrap_or_else(|| http::buffer_limit(&request));
 		let (mut part, body) = request.into_parts();
 		let bytes = match http::read_body_with_limit(body, limit).await {
 			Ok(b) => b,
+			Err(e) if max_message_size.is_some() && mcp::is_length_limit_error(&e) => {
+				return mcp::Error::MessageTooLarge(limit).into();
+			},
 			Err(e) => return mcp::Error::Deserialize(e).into(),
 		};
 		let message = match serde_json::from_slice::<ClientJsonRpcMessage>(&bytes) {
@@ -155,16 +169,19 @@ impl StreamableHttpService {
 
 		let session_id = part
 			.headers
-			.get(HEADER_SESSION_ID)
+			.get(&self.config.session_header)
 			.and_then(|v| v.to_str().ok());
 
 		if let Some(session_id) = session_id {
 			if !protocol.uses_sessions() {
 				return mcp::Error::InvalidSessionIdHeader.into();
 			}
-			let Some(mut session) = self
-				.session_manager
-				.get_or_resume_session(session_id, inputs)?
+			let Some(mut session) = self.session_manager.get_or_resume_session(
+				session_id,
+				inputs,
+				crate::http::caller_identity(&part.extensions),
+				request_id,
+			)?
 			else {
 				return mcp::Error::UnknownSession.into();
 			};
@@ -188,6 +205,11 @@ impl StreamableHttpService {
 			return mcp::Error::MissingSessionHeader.into();
 		}
 		let idle_ttl = inputs.backend.session_idle_ttl;
+		let slot = self.session_manager.reserve_session(
+			crate::http::caller_identity(&part.extensions),
+			inputs.backend.max_sessions_per_identity,
+			request_id,
+		)?;
 		let relay = inputs.build_new_connections()?;
 		let mut session = self.session_manager.create_session(relay);
 		let mut resp = Box::pin(session.send(part, message)).await?;
@@ -195,8 +217,10 @@ impl StreamableHttpService {
 		let Ok(sid) = session.id.parse() else {
 			return mcp::Error::InvalidSessionIdHeader.into();
 		};
-		resp.headers_mut().insert(HEADER_SESSION_ID, sid);
-		self.session_manager.insert_session(session, idle_ttl);
+		resp
+			.headers_mut()
+			.insert(self.config.session_header.clone(), sid);
+		self.session_manager.insert_session(session, idle_ttl, slot);
 		Ok(resp)
 	}
 
@@ -253,7 +277,7 @@ impl StreamableHttpService {
 
 		let Some(session_id) = request
 			.headers()
-			.get(HEADER_SESSION_ID)
+			.get(&self.config.session_header)
 			.and_then(|v| v.to_str().ok())
 		else {
 			return mcp::Error::SessionIdRequired.into();
@@ -273,7 +297,7 @@ impl StreamableHttpService {
 		// check session id
 		let session_id = request
 			.headers()
-			.get(HEADER_SESSION_ID)
+			.get(&self.config.session_header)
 			.and_then(|v| v.to_str().ok());
 		let Some(session_id) = session_id else {
 			return mcp::Error::SessionIdRequired.into();
@@ -21,11 +21,11 @@ use tokio::process::Command;
 
 use crate::mcp::mergestream::Messages;
 use crate::mcp::router::{McpBackendGroup, McpTarget};
-use crate::mcp::streamablehttp::StreamableHttpPostResponse;
+use crate::mcp::streamablehttp::{ClientSessionHeader, StreamableHttpPostResponse};
 use crate::mcp::{FailureMode, mergestream, upstream};
 use crate::proxy::ProxyError;
 use crate::proxy::httpproxy::PolicyClient;
-use crate::types::agent::{McpPrefixMode, McpTargetSpec};
+use crate::types::agent::{McpInjectedTool, McpPrefixMode, McpTargetSpec};
 use crate::*;
 
 #[derive(Debug, Clone)]
@@ -57,6 +57,9 @@ impl IncomingRequestContext {
 			authority: parts.uri.authority().cloned(),
 		}
 	}
+	pub fn headers(&self) -> &http::HeaderMap {
+		&self.headers
+	}
 	pub fn headers_mut(&mut self) -> &mut http::HeaderMap {
 		&mut self.headers
 	}
@@ -83,11 +86,13 @@ impl IncomingRequestContext {
 				auto.target = Some(authority);
 			}
 		}
+		let session_header = self.ext.get::<ClientSessionHeader>().map(|h| &h.0);
 		for (k, v) in &self.headers {
 			// Remove headers we do not want to propagate to the backend
 			if k == http::header::CONTENT_ENCODING
 				|| k == http::header::CONTENT_LENGTH
 				|| k.as_str().eq_ignore_ascii_case(HEADER_SESSION_ID)
+				|| session_header.is_some_and(|h| k == h)
 			{
 				continue;
 			}
@@ -136,6 +141,8 @@ pub enum UpstreamError {
 	},
 	#[error("mcpGuardrails rejected: {}", .0.message)]
 	McpGuardrails(rmcp::ErrorData),
+	#[error("session token budget of {0} tokens exhausted")]
+	TokenBudgetExhausted(u64),
 	#[error("invalid request: {0}")]
 	InvalidRequest(String),
 	/// A server-side availability/capability gap. Distinct from `InvalidRequest`,
@@ -306,20 +313,95 @@ pub(crate) struct UpstreamGroup {
 	pub prefix_mode: McpPrefixMode,
 	pub is_multiplexing: bool,
 	pub failure_mode: FailureMode,
+	pub max_fanout_notifications: Option<usize>,
+	pub tool_call_dedup: Option<Arc<mcp::dedup::ToolCallDedup>>,
+	pub notification_routing: mcp::NotificationRouting,
+	pub in_flight: mcp::notification::InFlightRequests,
 }
 
 impl UpstreamGroup {
+	/// Argument fields of `tool` (as named by the client) that must not be logged.
+	pub fn sensitive_tool_arguments(&self, tool: &str) -> &[String] {
+		self
+			.backend
+			.sensitive_tool_arguments
+			.get(tool)
+			.map(Vec::as_slice)
+			.unwrap_or_default()
+	}
+
+	/// Expression rewriting the descriptions of listed tools, if configured.
+	pub fn tool_description(&self) -> Option<&Arc<cel::Expression>> {
+		self.backend.tool_description.as_ref()
+	}
+
+	/// Interval between keep-alive comments on GET streams, if enabled.
+	pub fn sse_keep_alive(&self) -> Option<Duration> {
+		self.backend.sse_keep_alive
+	}
+
+	/// Tokens a session may spend on tool calls, if limited.
+	pub fn session_token_budget(&self) -> Option<u64> {
+		self.backend.session_token_budget
+	}
+
+	/// Handshakes reused across stateless requests, if enabled.
+	pub(crate) fn initialize_cache(&self) -> Option<&Arc<mcp::initcache::InitializeCache>> {
+		self.backend.initialize_cache.as_ref()
+	}
+
+	/// Identifies the handshake of the target `name` in the initialize cache.
+	pub(crate) fn handshake_target(&self, name: &str) -> mcp::initcache::HandshakeTarget {
+		let address = self
+			.backend
+			.targets
+			.iter()
+			.find(|t| t.name.as_str() == name)
+			.and_then(|t| t.address())
+			.unwrap_or_default();
+		mcp::initcache::HandshakeTarget {
+			name: strng::new(name),
+			address,
+		}
+	}
+
+	/// Tools added to `tools/list` by the gateway.
+	pub fn injected_tools(&self) -> &[McpInjectedTool] {
+		&self.backend.injected_tools
+	}
+
+	/// The injected tool clients call as `name`, if any.
+	pub fn injected_tool(&self, name: &str) -> Option<&McpInjectedTool> {
+		self
+			.backend
+			.injected_tools
+			.iter()
+			.find(|t| t.name.as_str() == name)
+	}
+
 	pub fn size(&self) -> usize {
 		self.by_name.len()
 	}
 
 	pub(crate) fn new(client: PolicyClient, backend: McpBackendGroup) -> Result<Self, mcp::Error> {
+		if let Some(max) = backend.max_targets
+			&& backend.targets.len() > max
+		{
+			return Err(mcp::Error::TooManyTargets {
+				targets: backend.targets.len(),
+				max,
+			});
+		}
 		let is_multiplexing = backend.targets.len() != 1;
 		let default_target_name = (!is_multiplexing && backend.prefix_mode != McpPrefixMode::Always)
 			.then(|| backend.targets[0].name.to_string());
 		let mut s = Self {
 			failure_mode: backend.failure_mode,
+			max_fanout_notifications: backend.max_fanout_notifications,
 			prefix_mode: backend.prefix_mode,
+			notification_routing: backend.notification_routing,
+			in_flight: Default::default(),
+			tool_call_dedup: backend.tool_call_dedup.clone(),
 			backend,
 			client,
 			by_name: IndexMap::new(),
@@ -645,6 +727,20 @@ mod tests {
 		assert_eq!(req.headers().get("x-trace").unwrap(), "abc");
 	}
 
+	#[test]
+	fn apply_strips_configured_client_session_header() {
+		let mut ctx = ctx_with_headers(&[("x-session", "client-sid"), ("x-trace", "abc")]);
+		ctx
+			.extensions_mut()
+			.insert(ClientSessionHeader(::http::HeaderName::from_static(
+				"x-session",
+			)));
+		let mut req = empty_upstream_req();
+		ctx.apply(&mut req).unwrap();
+		assert!(req.headers().get("x-session").is_none());
+		assert_eq!(req.headers().get("x-trace").unwrap(), "abc");
+	}
+
 	#[test]
 	fn apply_preserves_upstream_session_id_when_already_set() {
 		let ctx = ctx_with_headers(&[(HEADER_SESSION_ID, "client-sid")]);
@@ -53,6 +53,7 @@ async fn setup_with_prefix(prefix: &str) -> (MockServer, Handler) {
 		upstream: client.clone(),
 		ca: None,
 
+		llm_in_flight: Default::default(),
 		mcp_state: mcp::router::App::new(stores.clone(), encoder),
 	});
 
@@ -1510,6 +1511,20 @@ async fn test_openapi_from_url() {
 		stateful_mode: McpStatefulMode::Stateful,
 		prefix_mode: None,
 		failure_mode: None,
+		notification_routing: None,
+		sse_keep_alive: None,
+		session_header: None,
+		initialize_cache_ttl: None,
+		max_message_size: None,
+		max_sessions_per_identity: None,
+		max_targets: None,
+		max_fanout_notifications: None,
+		tool_call_dedup_window: None,
+		session_token_budget: None,
+		sensitive_tool_arguments: Default::default(),
+		correlation_headers: Default::default(),
+		tool_description: None,
+		injected_tools: Default::default(),
 	});
 
 	// Convert to runtime backends
@@ -1788,6 +1803,7 @@ async fn test_call_tool_with_binary_body() {
 		admin: None,
 		upstream: client.clone(),
 		ca: None,
+		llm_in_flight: Default::default(),
 		mcp_state: mcp::router::App::new(stores.clone(), encoder),
 	});
 
@@ -71,9 +71,13 @@ impl<IO> Parser<IO> {
 						output_audio_tokens: None,
 						total_tokens: Some(usage.total_tokens as u64),
 						service_tier: None,
+						system_fingerprint: None,
 						provider_model: None,
 						completion: None,
 						first_token: None,
+						error_type: None,
+						guard_modifications: Vec::new(),
+						usage_estimated: false,
 						count_tokens: None,
 						reasoning_tokens: None,
 						cache_creation_input_tokens: None,
@@ -544,9 +548,13 @@ pub async fn guarded_realtime_proxy<C, S>(
 												output_audio_tokens: None,
 												total_tokens: Some(usage_clone.total_tokens as u64),
 												service_tier: None,
+												system_fingerprint: None,
 												provider_model: None,
 												completion: None,
 												first_token: None,
+												error_type: None,
+												guard_modifications: Vec::new(),
+												usage_estimated: false,
 												count_tokens: None,
 												reasoning_tokens: None,
 												cache_creation_input_tokens: None,
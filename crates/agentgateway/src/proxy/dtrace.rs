@@ -250,6 +250,7 @@ pub enum AuthorizationRuleMode {
 	Allow,
 	Deny,
 	Require,
+	Reject,
 }
 
 #[derive(Debug, Serialize)]
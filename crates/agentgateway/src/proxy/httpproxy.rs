@@ -23,6 +23,7 @@ use crate::cel::{BackendContext, RequestTime};
 use crate::client::{ApplicationTransport, HboneHeaders, HboneSourceRole, Transport};
 use crate::http::backendtls::BackendTLS;
 use crate::http::buffer::Buffer;
+use crate::http::contenttype::ContentTypeEnforcement;
 use crate::http::ext_proc::{ExtProcRequest, InferenceRoutingDestinationMode};
 use crate::http::filters::{AutoHostname, BackendRequestTimeout};
 use crate::http::transformation_cel::Transformation;
@@ -44,9 +45,13 @@ use crate::store::{
 };
 use crate::telemetry::log;
 use crate::telemetry::log::{AsyncLog, DropOnLog, LogBody, RequestLog, TraceSampler};
-use crate::telemetry::metrics::{OutboundCallKind, OutboundCallLabels, OutboundCallSubtype};
+use crate::telemetry::metrics::{
+	EndpointLabels, OutboundCallKind, OutboundCallLabels, OutboundCallSubtype,
+};
 use crate::telemetry::trc::TraceParent;
-use crate::transport::stream::{Extension, Socket, TCPConnectionInfo, TLSConnectionInfo};
+use crate::transport::stream::{
+	Extension, Socket, TCPConnectionInfo, TLSConnectionInfo, UpstreamTLSInfo,
+};
 use crate::types::local::InternalBackend;
 use crate::types::{backend, frontend};
 use crate::{ProxyInputs, store, *};
@@ -114,6 +119,9 @@ pub fn apply_logging_policy_to_log(log: &mut RequestLog, lp: &frontend::LoggingP
 	if lp.filter.is_some() {
 		log.cel.filter = lp.filter.clone();
 	}
+	if lp.format.is_some() {
+		log.cel.access_log_format = lp.format.clone();
+	}
 	if !lp.add.is_empty() {
 		log.cel.fields.add = lp.add.clone();
 	}
@@ -211,6 +219,11 @@ async fn apply_request_policies(
 		.apply_selected("remote rate limit", c, l, req, rp.headers())
 		.await?;
 
+	rp.content_type = pol
+		.content_type
+		.apply("content type", c, l, req, rp.headers())
+		.await?;
+
 	rp.buffer = pol.buffer.apply("buffer", c, l, req, rp.headers()).await?;
 
 	// ExtProc uses RequestPolicy for conditional selection and CEL registration only.
@@ -472,6 +485,7 @@ async fn apply_llm_request_policies(
 	client: PolicyClient,
 	req: &mut Request,
 	llm_req: &LLMRequest,
+	backend: Strng,
 	response_headers: &mut HeaderMap,
 ) -> Result<store::LLMResponsePolicies, ProxyResponse> {
 	let local_rate_limit = policies
@@ -485,6 +499,15 @@ async fn apply_llm_request_policies(
 	for lrl in &local_rate_limit {
 		lrl.check_llm_request(llm_req)?;
 	}
+	if let Some(llm::OutputTokensCapped(cap)) = req.extensions().get::<llm::OutputTokensCapped>() {
+		response_headers.insert(llm::MAX_OUTPUT_TOKENS_CAP_HEADER, HeaderValue::from(*cap));
+	}
+	if req.extensions().get::<llm::StreamDowngraded>().is_some() {
+		response_headers.insert(
+			llm::STREAM_DOWNGRADED_HEADER,
+			HeaderValue::from_static("true"),
+		);
+	}
 	let (rl_resp, response) = if let Some(rrl) = &policies.remote_rate_limit {
 		// For the LLM request side, request either the count of the input tokens (if tokenization was done)
 		// or 0.
@@ -500,12 +523,65 @@ async fn apply_llm_request_policies(
 		.llm
 		.as_deref()
 		.and_then(|llm| llm.prompt_guard.as_ref());
+	// HTTP/1.1 clients rarely negotiate trailers, so only send usage trailers where they are native.
+	let usage_trailers = req.version() >= ::http::Version::HTTP_2
+		&& policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.usage_trailers)
+			.unwrap_or_default();
 	Ok(store::LLMResponsePolicies {
 		local_rate_limit,
 		remote_rate_limit: response,
 		request_traceparent: req.headers().get(TRACEPARENT).cloned(),
+		backend,
 		prompt_guard: prompt_guard.map(|g| g.response.clone()).unwrap_or_default(),
 		streaming_prompt_guard_enabled: prompt_guard.is_some_and(|g| g.streaming.is_enabled()),
+		usage_trailers,
+		stream_transformation: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.stream_transformation.clone()),
+		empty_choices: policies.llm.as_deref().and_then(|llm| llm.empty_choices),
+		synthesize_stream_done: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.synthesize_stream_done)
+			.unwrap_or_default(),
+		default_retry_after: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.default_retry_after),
+		echo_upstream_request_id: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.echo_upstream_request_id)
+			.unwrap_or_default(),
+		stream_oversized_responses: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.stream_oversized_responses)
+			.unwrap_or_default(),
+		deduplicate_requests: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.deduplicate_requests)
+			.unwrap_or_default(),
+		guard_modifications_header: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.guard_modifications_header)
+			.unwrap_or_default(),
+		estimate_missing_stream_usage: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.estimate_missing_stream_usage)
+			.unwrap_or_default(),
+		model_deprecation_header: policies
+			.llm
+			.as_deref()
+			.and_then(|llm| llm.model_deprecation_header)
+			.unwrap_or_default(),
 	})
 }
 
@@ -628,8 +704,9 @@ impl HTTPProxy {
 			Ok(_) => ProxyResponseReason::Upstream,
 			Err(e) => e.as_reason(),
 		};
+		let error_verbosity = self.inputs.cfg.error_verbosity;
 		let mut resp = ret.unwrap_or_else(|err| match err {
-			ProxyResponse::Error(e) => e.into_response_with_grpc(is_grpc_request),
+			ProxyResponse::Error(e) => e.into_response_with_verbosity(is_grpc_request, error_verbosity),
 			ProxyResponse::DirectResponse(dr) => *dr,
 		});
 
@@ -647,7 +724,7 @@ impl HTTPProxy {
 		{
 			Ok(_) => resp,
 			Err(e) => match e {
-				ProxyResponse::Error(e) => e.into_response_with_grpc(is_grpc_request),
+				ProxyResponse::Error(e) => e.into_response_with_verbosity(is_grpc_request, error_verbosity),
 				ProxyResponse::DirectResponse(dr) => *dr,
 			},
 		};
@@ -669,12 +746,13 @@ impl HTTPProxy {
 			handle_connect_tunnel(connect, resp, log)
 		} else if resp.status() == StatusCode::SWITCHING_PROTOCOLS {
 			let Some(req_upgrade) = resp.extensions_mut().remove::<RequestUpgrade>() else {
-				return ProxyError::UpgradeFailed(None, None).into_response_with_grpc(is_grpc_request);
+				return ProxyError::UpgradeFailed(None, None)
+					.into_response_with_verbosity(is_grpc_request, error_verbosity);
 			};
 			let realtime_guard_context = resp.extensions_mut().remove::<RealtimeGuardContext>();
 			handle_upgrade(req_upgrade, resp, log, realtime_guard_context)
 				.await
-				.unwrap_or_else(|e| e.into_response_with_grpc(is_grpc_request))
+				.unwrap_or_else(|e| e.into_response_with_verbosity(is_grpc_request, error_verbosity))
 		} else {
 			resp.map(move |b| http::Body::new(LogBody::new(b, log)))
 		}
@@ -866,6 +944,7 @@ impl HTTPProxy {
 			route_inlines,
 		};
 		let route_policies = inputs.stores.read_binds().route_policies(&route_path);
+		log.route_policies = route_policies.policy_types();
 		// Register all expressions
 		route_policies.register_cel_expressions(log.cel.ctx());
 		let mut route_retry = route_policies.retry.select("retry", &req);
@@ -983,11 +1062,22 @@ impl HTTPProxy {
 		// attempts is the total number of attempts, not the retries
 		let attempts = retries.as_ref().map(|r| r.attempts.get() + 1).unwrap_or(1);
 		let retry_backoff = retries.as_ref().and_then(|r| r.backoff);
+		let retry_budget = retries.as_ref().and_then(|r| r.budget);
 		let request_timeout = response_policies
 			.timeout
 			.as_ref()
 			.and_then(|t| t.request_timeout);
-		let body = if attempts > 1 {
+		// A model fallback may need one extra attempt, outside the retry budget.
+		let model_fallbacks = llm_request_policies
+			.llm
+			.as_ref()
+			.is_some_and(|p| !p.model_fallbacks.is_empty());
+		// Likewise a response without choices may be retried once.
+		let empty_choices_retry = llm_request_policies
+			.llm
+			.as_ref()
+			.is_some_and(|p| p.empty_choices == Some(llm::policy::EmptyChoices::Retry));
+		let body = if attempts > 1 || model_fallbacks || empty_choices_retry {
 			// If we are going to attempt a retry we will need to track the incoming bytes for replay
 			let body = http::retry::ReplayBody::try_new(body, MAX_BUFFERED_BYTES);
 			if body.is_err() {
@@ -1012,14 +1102,29 @@ impl HTTPProxy {
 						backend_policies,
 						Some(route_path.clone()),
 						response_policies,
+						None,
 						req,
 					)
 					.await;
 			},
 		};
+		// The budget covers every attempt, so it starts with the first one.
+		let budget_deadline = retry_budget.map(|b| std::time::Instant::now() + b);
 		let mut last_res: Option<Result<Response, SnapshottedProxyResponse>> = None;
-		for n in 0..attempts {
+		let mut fallback: Option<Strng> = None;
+		let mut empty_choices_retried = false;
+		let mut n = 0;
+		loop {
 			let last = n == attempts - 1;
+			let can_fall_back = model_fallbacks && fallback.is_none();
+			let can_retry_empty = empty_choices_retry && !empty_choices_retried;
+			if let Some(deadline) = budget_deadline
+				&& std::time::Instant::now() >= deadline
+			{
+				debug!("retry budget exhausted after {n} attempts");
+				// This is safe because we guarantee in attempt_upstream to snapshot
+				return Err(ProxyError::RequestTimeout).explicitly_skip_snapshot();
+			}
 			let this = next.take().expect("next should be set");
 			debug!("attempt {n}/{}", attempts - 1);
 			if matches!(this.is_capped(), None | Some(true)) {
@@ -1027,11 +1132,14 @@ impl HTTPProxy {
 				debug!("buffered too much to attempt a retry");
 				return last_res.expect("should only be capped if we had a previous attempt");
 			}
-			if !last {
+			if !last || can_fall_back || can_retry_empty {
 				// Stop cloning on our last
 				next = Some(this.clone());
 			}
 			let mut head = head.clone();
+			if let Some(model) = &fallback {
+				head.extensions.insert(llm::ModelFallback(model.clone()));
+			}
 			if n > 0 {
 				log.retry_attempt = Some(n);
 				head.headers.insert(
@@ -1049,9 +1157,37 @@ impl HTTPProxy {
 					backend_policies.clone(),
 					Some(route_path.clone()),
 					response_policies,
+					budget_deadline,
 					req,
 				)
 				.await;
+			if can_fall_back
+				&& let Some(substitute) = model_fallback(
+					&res,
+					llm_request_policies.llm.as_deref(),
+					log.llm_request.as_ref(),
+				) {
+				info!(
+					model = %log.llm_request.as_ref().map(|r| r.request_model.as_str()).unwrap_or_default(),
+					%substitute,
+					"requested model unavailable, retrying with fallback model"
+				);
+				finalize_attempt_for_retry(log, &mut res);
+				last_res = Some(res);
+				fallback = Some(substitute);
+				continue;
+			}
+			if can_retry_empty
+				&& res
+					.as_ref()
+					.is_ok_and(|r| r.extensions().get::<llm::EmptyChoicesResponse>().is_some())
+			{
+				info!("completion response had no choices, retrying once");
+				finalize_attempt_for_retry(log, &mut res);
+				last_res = Some(res);
+				empty_choices_retried = true;
+				continue;
+			}
 			if last
 				|| !should_retry(
 					&res,
@@ -1072,8 +1208,13 @@ impl HTTPProxy {
 			finalize_attempt_for_retry(log, &mut res);
 			last_res = Some(res);
 			if let Some(bo) = retry_backoff {
-				let fut = if let Some(request_timeout) = request_timeout {
-					let deadline = tokio::time::Instant::from_std(log.start.as_instant() + request_timeout);
+				let deadline = request_timeout
+					.map(|t| log.start.as_instant() + t)
+					.into_iter()
+					.chain(budget_deadline)
+					.min();
+				let fut = if let Some(deadline) = deadline {
+					let deadline = tokio::time::Instant::from_std(deadline);
 					tokio::time::timeout_at(deadline, tokio::time::sleep(bo)).await
 				} else {
 					tokio::time::sleep(bo).await;
@@ -1084,8 +1225,8 @@ impl HTTPProxy {
 					// This is safe because we guarantee in attempt_upstream to snapshot
 					.explicitly_skip_snapshot()?
 			}
+			n += 1;
 		}
-		unreachable!()
 	}
 
 	async fn connect_tunnel(
@@ -1310,6 +1451,7 @@ impl HTTPProxy {
 		backend_policies: Arc<BackendPolicies>,
 		route_path: Option<RoutePath<'_>>,
 		response_policies: &mut ResponsePolicies,
+		budget_deadline: Option<std::time::Instant>,
 		mut req: Request,
 	) -> Result<Response, SnapshottedProxyResponse> {
 		if let Some(backend_timeout) = response_policies
@@ -1323,11 +1465,15 @@ impl HTTPProxy {
 		}
 		let upgrade_req_headers = req.headers().clone();
 		let mut req_opt = Some(req);
-		let timeout = response_policies
+		// The attempt is bounded by whichever comes first: the request timeout or the retry budget.
+		let deadline = response_policies
 			.timeout
 			.as_ref()
-			.and_then(|t| t.request_timeout);
-		let start = log.start;
+			.and_then(|t| t.request_timeout)
+			.map(|t| log.start.as_instant() + t)
+			.into_iter()
+			.chain(budget_deadline)
+			.min();
 		let call = make_backend_call(
 			self.inputs.clone(),
 			route_policies.clone(),
@@ -1341,8 +1487,8 @@ impl HTTPProxy {
 		.assert_size::<{ 7 * 1024 }>();
 
 		// Setup timeout
-		let call_result = if let Some(timeout) = timeout {
-			let deadline = tokio::time::Instant::from_std(start.as_instant() + timeout);
+		let call_result = if let Some(deadline) = deadline {
+			let deadline = tokio::time::Instant::from_std(deadline);
 			let fut = tokio::time::timeout_at(deadline, call);
 			fut.await
 		} else {
@@ -2024,7 +2170,60 @@ async fn make_backend_call(
 
 	let (mut backend_call, mut maybe_inference) = match backend {
 		Backend::AI(n, ai) => {
-			let (provider, handle) = ai.select_provider().ok_or(ProxyError::NoHealthyEndpoints)?;
+			let affinity_key = policies
+				.llm
+				.as_deref()
+				.and_then(|p| p.provider_affinity_header.as_ref())
+				.or_else(|| {
+					route_policies
+						.llm
+						.as_deref()
+						.and_then(|p| p.provider_affinity_header.as_ref())
+				})
+				.and_then(|h| req.headers().get(h.as_str()))
+				.and_then(|v| v.to_str().ok())
+				.map(str::to_owned);
+			let mut request_size = req
+				.headers()
+				.get(header::CONTENT_LENGTH)
+				.and_then(|v| v.to_str().ok())
+				.and_then(|v| v.parse::<u64>().ok());
+			// Chunked requests have no content-length, so buffer the body to size it.
+			if request_size.is_none() && ai.routes_by_size() {
+				request_size = match crate::http::inspect_body(&mut req).await {
+					Ok(crate::http::BodyInspection::Complete(body)) => Some(body.len() as u64),
+					Ok(crate::http::BodyInspection::Partial(_)) => {
+						debug!("request body exceeds buffer limit; ignoring request size ranges");
+						None
+					},
+					Err(e) => {
+						debug!("failed to read request body for size based selection: {e}");
+						None
+					},
+				};
+			}
+			let (provider, mut handle) = match affinity_key {
+				Some(key) => ai.select_provider_with_affinity(&key, request_size),
+				None => ai.select_provider_for_size(request_size),
+			}
+			.ok_or(ProxyError::NoHealthyEndpoints)?;
+			let endpoint_labels = EndpointLabels {
+				backend: n.name.clone().into(),
+				provider: provider.provider.provider().into(),
+				endpoint: provider.name.clone().into(),
+			};
+			inputs
+				.metrics
+				.endpoint_requests
+				.get_or_create(&endpoint_labels)
+				.inc();
+			handle.track_in_flight(
+				inputs
+					.metrics
+					.endpoint_active_requests
+					.get_or_create(&endpoint_labels)
+					.clone(),
+			);
 			log.add(move |l| l.request_handle = Some(handle));
 			let sub_backend_name = BackendTargetRef::Backend {
 				name: n.name.as_ref(),
@@ -2265,57 +2464,61 @@ async fn make_backend_call(
 				| RouteType::AnthropicTokenCount
 				| RouteType::Embeddings
 				| RouteType::Rerank
+				| RouteType::TextCompletions
 				| RouteType::Detect => {
+					let listener_body_limit = crate::http::buffer_limit(&req);
+					llm.apply_request_body_limit(&mut req);
 					let request_body_limit = crate::http::buffer_limit(&req);
 					let req = req.map(|b| {
 						dtrace::TracingBody::maybe_wrap("llm request before translation", b, request_body_limit)
 					});
+					let tokenize = llm.should_tokenize(llm_request_policies.llm.as_deref());
 					let r = match route_type {
 						RouteType::Completions => Box::pin(llm.provider.process_completions_request(
 							&backend_info,
 							llm_request_policies.llm.as_deref(),
 							req,
-							llm.tokenize,
+							tokenize,
 							&mut log,
 						))
 						.await
-						.map_err(|e| ProxyError::Processing(e.into()))?,
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
 						RouteType::Messages => Box::pin(llm.provider.process_messages_request(
 							&backend_info,
 							llm_request_policies.llm.as_deref(),
 							req,
-							llm.tokenize,
+							tokenize,
 							&mut log,
 						))
 						.await
-						.map_err(|e| ProxyError::Processing(e.into()))?,
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
 						RouteType::Responses => Box::pin(llm.provider.process_responses_request(
 							&backend_info,
 							llm_request_policies.llm.as_deref(),
 							req,
-							llm.tokenize,
+							tokenize,
 							&mut log,
 						))
 						.await
-						.map_err(|e| ProxyError::Processing(e.into()))?,
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
 						RouteType::Embeddings => Box::pin(llm.provider.process_embeddings_request(
 							&backend_info,
 							llm_request_policies.llm.as_deref(),
 							req,
-							llm.tokenize,
+							tokenize,
 							&mut log,
 						))
 						.await
-						.map_err(|e| ProxyError::Processing(e.into()))?,
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
 						RouteType::Rerank => Box::pin(llm.provider.process_rerank_request(
 							&backend_info,
 							llm_request_policies.llm.as_deref(),
 							req,
-							llm.tokenize,
+							tokenize,
 							&mut log,
 						))
 						.await
-						.map_err(|e| ProxyError::Processing(e.into()))?,
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
 						RouteType::AnthropicTokenCount => Box::pin(llm.provider.process_count_tokens_request(
 							&backend_info,
 							req,
@@ -2323,7 +2526,16 @@ async fn make_backend_call(
 							&mut log,
 						))
 						.await
-						.map_err(|e| ProxyError::Processing(e.into()))?,
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
+						RouteType::TextCompletions => Box::pin(llm.provider.process_text_completions_request(
+							&backend_info,
+							llm_request_policies.llm.as_deref(),
+							req,
+							tokenize,
+							&mut log,
+						))
+						.await
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
 						RouteType::Detect => Box::pin(llm.provider.process_detect_request(
 							&backend_info,
 							llm_request_policies.llm.as_deref(),
@@ -2331,7 +2543,7 @@ async fn make_backend_call(
 							&mut log,
 						))
 						.await
-						.map_err(|e| ProxyError::Processing(e.into()))?,
+						.map_err(|e| llm.provider.request_error(route_type, e, &inputs.metrics))?,
 						_ => unreachable!(),
 					};
 					let (mut req, llm_request, upstream_route_type) = match r {
@@ -2377,8 +2589,11 @@ async fn make_backend_call(
 							llm.path_override.as_deref(),
 							llm.path_prefix.as_deref(),
 							llm.host_override.is_some(),
+							llm.accept_encoding.as_deref(),
 						)
 						.map_err(ProxyError::Processing)?;
+					llm.set_user_agent(&mut req);
+					llm.apply_response_body_limit(&mut req, listener_body_limit);
 
 					// Apply all policies (rate limits, prompt guards, enrichment)
 					// count_tokens skips policies (no tokens generated, no prompts to manipulate)
@@ -2391,6 +2606,7 @@ async fn make_backend_call(
 								policy_client.clone(),
 								&mut req,
 								&llm_request,
+								backend_info.target.name(),
 								&mut response_policies.response_headers,
 							)
 							.assert_size::<{ 3 * 1024 }>(),
@@ -2424,8 +2640,10 @@ async fn make_backend_call(
 							llm.path_override.as_deref(),
 							llm.path_prefix.as_deref(),
 							llm.host_override.is_some(),
+							llm.accept_encoding.as_deref(),
 						)
 						.map_err(ProxyError::Processing)?;
+					llm.set_user_agent(&mut req);
 					if route_type == RouteType::Realtime {
 						let request_model = http::as_url(req.uri())
 							.map_err(ProxyError::Processing)?
@@ -2508,6 +2726,24 @@ async fn make_backend_call(
 	let transport = build_backend_transport(&inputs, &backend_call, hbone_source).await?;
 	dtrace::snapshot!(Request, "final request", &req);
 	let request_body_limit = crate::http::buffer_limit(&req);
+	let dedup_key = match (&backend_call.backend_policies.llm_provider, &llm_request) {
+		(Some(llm), Some(llm_request))
+			if llm_response_policies.deduplicate_requests
+				&& llm::dedup::is_deterministic(llm_request) =>
+		{
+			match crate::http::inspect_body(&mut req).await {
+				Ok(crate::http::BodyInspection::Complete(body)) => Some(llm::dedup::request_key(
+					&llm.name,
+					crate::http::caller_identity(req.extensions()).as_deref(),
+					req.headers(),
+					req.uri(),
+					&body,
+				)),
+				_ => None,
+			}
+		},
+		_ => None,
+	};
 	let req = req.map(|b| dtrace::TracingBody::maybe_wrap("final request", b, request_body_limit));
 	let call = client::Call {
 		req,
@@ -2528,13 +2764,30 @@ async fn make_backend_call(
 	} else {
 		OutboundCallSubtype::Http
 	};
+	// Passthrough and realtime traffic is not parsed as an LLM request, so it is not retried.
+	let llm_retry = backend_call
+		.backend_policies
+		.llm_provider
+		.as_ref()
+		.filter(|_| llm_request.is_some())
+		.and_then(|llm| llm.retry.clone());
 	let outbound_start = std::time::Instant::now();
 	log.add(|l| {
 		if l.request_processing_duration.is_none() {
 			l.request_processing_duration = Some(l.request_processing_start.elapsed());
 		}
 	});
-	let resp = upstream.call(call).await;
+	let send = || async move {
+		match llm_retry {
+			Some(retry) => call_llm_with_retries(&upstream, call, &retry).await,
+			None => upstream.call(call).await,
+		}
+	};
+	let resp = match dedup_key {
+		// Identical deterministic requests in flight share a single upstream call.
+		Some(key) => inputs.llm_in_flight.call(key, send).await,
+		None => send().await,
+	};
 	let outbound_end = Instant::now();
 	log.add(|l| {
 		l.metrics
@@ -2564,6 +2817,7 @@ async fn make_backend_call(
 		),
 	});
 	let mut resp = resp?;
+	log.add(|l| l.upstream_tls = resp.extensions().get::<UpstreamTLSInfo>().cloned());
 	if let Some(log) = log.as_ref() {
 		resp
 			.extensions_mut()
@@ -2590,6 +2844,26 @@ async fn make_backend_call(
 		backend_call.backend_policies.llm_provider.clone(),
 		llm_request,
 	) {
+		if let Some(default_retry_after) = &llm_response_policies.default_retry_after {
+			default_retry_after.apply(&mut resp);
+		}
+		if let Some(id) = llm::capture_upstream_request_id(
+			&llm.provider,
+			&mut resp,
+			llm_response_policies.echo_upstream_request_id,
+		) {
+			log.add(|l| l.upstream_request_id = Some(id));
+		}
+		if let Some(notice) =
+			llm::capture_model_deprecation(&mut resp, llm_response_policies.model_deprecation_header)
+		{
+			warn!(
+				provider = %llm.provider.provider(),
+				model = %llm_request.request_model,
+				%notice,
+				"provider reported model deprecation"
+			);
+		}
 		Box::pin(
 			llm
 				.provider
@@ -2627,6 +2901,48 @@ async fn make_backend_call(
 	Ok(resp)
 }
 
+/// Sends a call to an LLM provider, retrying it as configured by the provider's retry settings.
+/// Only the response head is inspected, so a response is never retried once its body is in flight.
+async fn call_llm_with_retries(
+	upstream: &client::Client,
+	call: client::Call,
+	retry: &llm::retry::Retry,
+) -> Result<Response, ProxyError> {
+	let client::Call {
+		req,
+		target,
+		transport,
+	} = call;
+	let limit = http::buffer_limit(&req);
+	let (head, body) = req.into_parts();
+	// The LLM request body was already read for processing, so buffering it for replay is cheap.
+	let body = http::read_body_with_limit(body, limit)
+		.await
+		.map_err(ProxyError::Body)?;
+	let mut retries = 0;
+	loop {
+		let call = client::Call {
+			req: Request::from_parts(head.clone(), http::Body::from(body.clone())),
+			target: target.clone(),
+			transport: transport.clone(),
+		};
+		let resp = upstream.call(call).await?;
+		let Some(delay) = retry.retry_delay(retries, resp.status(), resp.headers()) else {
+			return Ok(resp);
+		};
+		debug!(
+			status = %resp.status(),
+			?delay,
+			"retrying LLM provider call ({}/{})",
+			retries + 1,
+			retry.max_retries
+		);
+		drop(resp);
+		tokio::time::sleep(delay).await;
+		retries += 1;
+	}
+}
+
 fn set_backend_cel_context(req: &mut http::Request, log: Option<&&mut RequestLog>) {
 	if let Some(l) = log
 		&& let Some(bp) = l.backend_protocol
@@ -3030,10 +3346,23 @@ fn set_final_response_fields(
 ) {
 	log.status = Some(resp.status());
 	log.reason = Some(*reason);
-	log.retry_after = http::outlierdetection::retry_after(resp.status(), resp.headers());
+	log.retry_after = upstream_retry_after(resp);
 	log.response_snapshot = log.cel.cel_context.maybe_snapshot_response(resp);
 }
 
+/// The backoff requested by a rate limited response, used for outlier detection. A default
+/// retry-after injected by the LLM policy only counts when it opted in.
+fn upstream_retry_after(resp: &Response) -> Option<Duration> {
+	if resp
+		.extensions()
+		.get::<llm::policy::DefaultedRetryAfter>()
+		.is_some()
+	{
+		return None;
+	}
+	http::outlierdetection::retry_after(resp.status(), resp.headers())
+}
+
 fn finalize_attempt_for_retry(
 	log: &mut RequestLog,
 	res: &mut Result<Response, SnapshottedProxyResponse>,
@@ -3041,7 +3370,7 @@ fn finalize_attempt_for_retry(
 	let (status, retry_after, response_snapshot) = match res {
 		Ok(resp) => (
 			Some(resp.status()),
-			http::outlierdetection::retry_after(resp.status(), resp.headers()),
+			upstream_retry_after(resp),
 			log.cel.cel_context.maybe_snapshot_response(resp),
 		),
 		Err(SnapshottedProxyResponse(_)) => (None, None, None),
@@ -3066,6 +3395,22 @@ fn finalize_attempt_for_retry(
 	);
 }
 
+/// Returns the substitute model when the upstream reported the requested model as unavailable.
+fn model_fallback(
+	res: &Result<Response, SnapshottedProxyResponse>,
+	policy: Option<&llm::Policy>,
+	llm_request: Option<&llm::LLMRequest>,
+) -> Option<Strng> {
+	let (Ok(resp), Some(policy), Some(llm_request)) = (res, policy, llm_request) else {
+		return None;
+	};
+	// A fallback the policy does not allow is never tried; the original response is returned.
+	policy
+		.model_fallback(llm_request.request_model.as_str(), resp.status())
+		.filter(|model| policy.is_model_allowed(model))
+		.cloned()
+}
+
 fn should_retry(
 	res: &Result<Response, SnapshottedProxyResponse>,
 	pol: &retry::Policy,
@@ -3096,6 +3441,7 @@ mod tests {
 	use std::sync::Arc;
 
 	use ::http::Method;
+	use agent_core::strng;
 	use serde_json::json;
 	use wiremock::{Mock, ResponseTemplate};
 
@@ -3119,6 +3465,7 @@ mod tests {
 		crate::http::retry::Policy {
 			attempts: std::num::NonZeroU8::new(2).unwrap(),
 			backoff: None,
+			budget: None,
 			codes: codes
 				.iter()
 				.map(|c| ::http::StatusCode::from_u16(*c).unwrap())
@@ -3180,6 +3527,7 @@ mod tests {
 			crate::test_helpers::policy_client(),
 			&mut req,
 			&llm_request(),
+			strng::literal!("default/test"),
 			&mut response_headers,
 		)
 		.await
@@ -3204,6 +3552,36 @@ mod tests {
 		assert!(policies.streaming_prompt_guard_enabled);
 	}
 
+	#[tokio::test]
+	async fn apply_llm_request_policies_enables_usage_trailers_for_http2_only() {
+		let policies = LLMRequestPolicies {
+			llm: Some(Arc::new(llm::Policy {
+				usage_trailers: Some(true),
+				..Default::default()
+			})),
+			..Default::default()
+		};
+		for (version, want) in [
+			(::http::Version::HTTP_11, false),
+			(::http::Version::HTTP_2, true),
+		] {
+			let mut req = ::http::Request::builder()
+				.version(version)
+				.body(http::Body::empty())
+				.unwrap();
+			let response_policies = apply_llm_request_policies(
+				&policies,
+				crate::test_helpers::policy_client(),
+				&mut req,
+				&llm_request(),
+				&mut ::http::HeaderMap::new(),
+			)
+			.await
+			.expect("LLM request policies should apply");
+			assert_eq!(response_policies.usage_trailers, want, "{version:?}");
+		}
+	}
+
 	#[test]
 	fn should_retry_matches_status_codes() {
 		let pol = retry_policy(&[503], None);
@@ -3850,6 +4228,7 @@ struct ResponsePolicies {
 	route_response_header: ResponsePolicy<filters::HeaderModifier>,
 	backend_response_header: ResponsePolicy<filters::HeaderModifier>,
 	buffer: ResponsePolicy<Buffer>,
+	content_type: ResponsePolicy<ContentTypeEnforcement>,
 	transformation: ResponsePolicy<Transformation>,
 	backend_transformation: ResponsePolicy<Transformation>,
 	gateway_transformation: ResponsePolicy<Transformation>,
@@ -3895,6 +4274,7 @@ impl ResponsePolicies {
 			.backend_response_header
 			.apply("backend response header modifier", l, resp, rh)
 			.await?;
+		self.content_type.apply("content type", l, resp, rh).await?;
 		self.buffer.apply("buffer", l, resp, rh).await?;
 		self
 			.transformation
@@ -234,8 +234,43 @@ impl ProxyError {
 			_ => false,
 		}
 	}
+	/// The message returned to the client. In terse mode, the underlying cause of internal and upstream
+	/// failures is omitted; the full error is still recorded in the request log.
+	pub fn client_message(&self, verbosity: ErrorVerbosity) -> String {
+		if verbosity == ErrorVerbosity::Verbose {
+			return self.to_string();
+		}
+		let msg = match self {
+			ProxyError::FilterError(_) => "failed to apply filters",
+			ProxyError::JwtAuthenticationFailure(_) => "authentication failure",
+			ProxyError::OidcFailure(_) => "oidc failure",
+			ProxyError::McpJwtAuthenticationFailure(_, _) => "mcp authentication failure",
+			ProxyError::BasicAuthenticationFailure(_) => "basic authentication failure",
+			ProxyError::APIKeyAuthenticationFailure(_) => "api key authentication failure",
+			ProxyError::BackendAuthenticationFailed(_) => "backend authentication failed",
+			ProxyError::Body(_) => "parsing body failed",
+			ProxyError::UpstreamCallFailed(_) => "upstream call failed",
+			ProxyError::UpstreamTCPCallFailed(_) => "upstream tcp call failed",
+			ProxyError::UpstreamTCPProxy(_) => "upstream tcp proxy failed",
+			ProxyError::Processing(_) | ProxyError::ProcessingString(_) => "processing failed",
+			ProxyError::Http(_) => "invalid http",
+			ProxyError::ExtProc(_) => "ext_proc failed",
+			ProxyError::UpgradeFailed(_, _) => "request upgrade failed",
+			_ => return self.to_string(),
+		};
+		msg.to_string()
+	}
+
 	pub fn into_response_with_grpc(self, is_grpc_request: bool) -> Response {
-		let msg = self.to_string();
+		self.into_response_with_verbosity(is_grpc_request, ErrorVerbosity::default())
+	}
+
+	pub fn into_response_with_verbosity(
+		self,
+		is_grpc_request: bool,
+		verbosity: ErrorVerbosity,
+	) -> Response {
+		let msg = self.client_message(verbosity);
 		let code = match self {
 			ProxyError::BindNotFound => StatusCode::NOT_FOUND,
 			ProxyError::ListenerNotFound => StatusCode::NOT_FOUND,
@@ -308,10 +343,13 @@ impl ProxyError {
 			ProxyError::MCP(mcp::Error::InvalidAcceptGet) => StatusCode::NOT_ACCEPTABLE,
 			ProxyError::MCP(mcp::Error::InvalidContentType) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
 			ProxyError::MCP(mcp::Error::Deserialize(_)) => StatusCode::BAD_REQUEST,
+			ProxyError::MCP(mcp::Error::MessageTooLarge(_)) => StatusCode::PAYLOAD_TOO_LARGE,
 			ProxyError::MCP(mcp::Error::StartSession(_)) => StatusCode::INTERNAL_SERVER_ERROR,
 			ProxyError::MCP(mcp::Error::UnknownSession) => StatusCode::NOT_FOUND,
 			ProxyError::MCP(mcp::Error::MissingSessionHeader) => StatusCode::BAD_REQUEST,
 			ProxyError::MCP(mcp::Error::SessionIdRequired) => StatusCode::BAD_REQUEST,
+			ProxyError::MCP(mcp::Error::TooManySessions(_, _)) => StatusCode::TOO_MANY_REQUESTS,
+			ProxyError::MCP(mcp::Error::TokenBudgetExhausted(_, _)) => StatusCode::TOO_MANY_REQUESTS,
 			ProxyError::MCP(mcp::Error::InvalidSessionIdQuery) => StatusCode::UNPROCESSABLE_ENTITY,
 			ProxyError::MCP(mcp::Error::InvalidSessionIdHeader) => StatusCode::BAD_REQUEST,
 			ProxyError::MCP(mcp::Error::InvalidProtocolVersion) => StatusCode::BAD_REQUEST,
@@ -327,6 +365,7 @@ impl ProxyError {
 			ProxyError::MCP(mcp::Error::Stdio(_)) => StatusCode::INTERNAL_SERVER_ERROR,
 			ProxyError::MCP(mcp::Error::OpenAPI(_)) => StatusCode::INTERNAL_SERVER_ERROR,
 			ProxyError::MCP(mcp::Error::NoBackends) => StatusCode::SERVICE_UNAVAILABLE,
+			ProxyError::MCP(mcp::Error::TooManyTargets { .. }) => StatusCode::INTERNAL_SERVER_ERROR,
 			ProxyError::MCP(mcp::Error::UpstreamError(e)) => return e.0.map(http::Body::from),
 			ProxyError::MCP(mcp::Error::SendError(_, _)) => StatusCode::INTERNAL_SERVER_ERROR,
 			ProxyError::MCP(mcp::Error::Unavailable(_, _)) => StatusCode::SERVICE_UNAVAILABLE,
@@ -935,6 +935,7 @@ mod tests {
 			admin: None,
 			upstream: client,
 			ca: None,
+			llm_in_flight: Default::default(),
 			mcp_state: crate::mcp::App::new(stores, encoder),
 		})
 	}
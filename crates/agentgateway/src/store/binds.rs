@@ -103,6 +103,8 @@ pub struct Store {
 	tcp_routes: HbHashMap<RouteTarget, Arc<TCPRouteSet>>,
 	listener_change_tx: watch::Sender<u64>,
 	listener_change_rx: watch::Receiver<u64>,
+	/// Bumped on every config update, so caches derived from the config know to start over.
+	config_generation: u64,
 
 	tx: tokio::sync::mpsc::UnboundedSender<BindEvent>,
 	rx: Option<tokio::sync::mpsc::UnboundedReceiver<BindEvent>>,
@@ -194,6 +196,7 @@ impl FrontendPolices {
 			remove: _,
 			otlp,
 			database,
+			format: _,
 			access_log_policy: _,
 		}) = &self.access_log
 		{
@@ -378,6 +381,7 @@ pub struct RoutePolicies {
 	pub request_mirror: RequestPolicy<Vec<filters::RequestMirror>>,
 	pub cors: RequestPolicy<http::cors::Cors>,
 	pub buffer: RequestPolicy<http::buffer::Buffer>,
+	pub content_type: RequestPolicy<http::contenttype::ContentTypeEnforcement>,
 }
 
 #[derive(Debug, Default, Serialize)]
@@ -432,6 +436,7 @@ impl RoutePolicies {
 			&self.ext_proc as &dyn PolicyExpressions,
 			&self.transformation as &dyn PolicyExpressions,
 			&self.csrf as &dyn PolicyExpressions,
+			&self.content_type as &dyn PolicyExpressions,
 			&self.direct_response as &dyn PolicyExpressions,
 			&self.llm as &dyn PolicyExpressions,
 			&self.request_header_modifier as &dyn PolicyExpressions,
@@ -449,6 +454,47 @@ impl RoutePolicies {
 			policy.register_expressions(ctx);
 		}
 	}
+
+	/// Returns the types of policies that are set, for logging.
+	pub fn policy_types(&self) -> Vec<&'static str> {
+		[
+			("localRateLimit", self.local_rate_limit.is_empty()),
+			("remoteRateLimit", self.remote_rate_limit.is_empty()),
+			("authorization", self.authorization.is_empty()),
+			("jwt", self.jwt.is_empty()),
+			("oidc", self.oidc.is_empty()),
+			("basicAuth", self.basic_auth.is_empty()),
+			("apiKey", self.api_key.is_empty()),
+			("extAuthz", self.ext_authz.is_empty()),
+			("extProc", self.ext_proc.is_empty()),
+			("transformation", self.transformation.is_empty()),
+			("csrf", self.csrf.is_empty()),
+			("directResponse", self.direct_response.is_empty()),
+			("llm", self.llm.is_empty()),
+			("timeout", self.timeout.is_empty()),
+			("retry", self.retry.is_empty()),
+			("delay", self.delay.is_empty()),
+			(
+				"requestHeaderModifier",
+				self.request_header_modifier.is_empty(),
+			),
+			(
+				"responseHeaderModifier",
+				self.response_header_modifier.is_empty(),
+			),
+			("requestRedirect", self.request_redirect.is_empty()),
+			("urlRewrite", self.url_rewrite.is_empty()),
+			("hostnameRewrite", self.hostname_rewrite.is_empty()),
+			("requestMirror", self.request_mirror.is_empty()),
+			("cors", self.cors.is_empty()),
+			("buffer", self.buffer.is_empty()),
+			("contentType", self.content_type.is_empty()),
+		]
+		.into_iter()
+		.filter(|(_, empty)| !empty)
+		.map(|(name, _)| name)
+		.collect()
+	}
 }
 
 #[derive(Debug, Default, Clone)]
@@ -523,6 +569,87 @@ impl LLMRequestPolicies {
 			} else {
 				preferred.routes.clone()
 			},
+			tokenize: preferred.tokenize.or(fallback.tokenize),
+			external_tokenizer: preferred
+				.external_tokenizer
+				.clone()
+				.or_else(|| fallback.external_tokenizer.clone()),
+			tokenize_failure: preferred.tokenize_failure.or(fallback.tokenize_failure),
+			default_params: preferred
+				.default_params
+				.clone()
+				.or_else(|| fallback.default_params.clone()),
+			param_limits: preferred
+				.param_limits
+				.clone()
+				.or_else(|| fallback.param_limits.clone()),
+			max_output_tokens_cap: preferred
+				.max_output_tokens_cap
+				.or(fallback.max_output_tokens_cap),
+			usage_trailers: preferred.usage_trailers.or(fallback.usage_trailers),
+			normalize_tool_call_ids: preferred
+				.normalize_tool_call_ids
+				.or(fallback.normalize_tool_call_ids),
+			model_fallbacks: if preferred.model_fallbacks.is_empty() {
+				fallback.model_fallbacks.clone()
+			} else {
+				preferred.model_fallbacks.clone()
+			},
+			stream_transformation: preferred
+				.stream_transformation
+				.clone()
+				.or_else(|| fallback.stream_transformation.clone()),
+			empty_choices: preferred.empty_choices.or(fallback.empty_choices),
+			unsupported_logit_bias: preferred
+				.unsupported_logit_bias
+				.or(fallback.unsupported_logit_bias),
+			deprecated_functions: preferred
+				.deprecated_functions
+				.or(fallback.deprecated_functions),
+			synthesize_stream_done: preferred
+				.synthesize_stream_done
+				.or(fallback.synthesize_stream_done),
+			default_retry_after: preferred
+				.default_retry_after
+				.or(fallback.default_retry_after),
+			max_prompt_chars: preferred.max_prompt_chars.or(fallback.max_prompt_chars),
+			max_json_depth: preferred.max_json_depth.or(fallback.max_json_depth),
+			provider_affinity_header: preferred
+				.provider_affinity_header
+				.clone()
+				.or_else(|| fallback.provider_affinity_header.clone()),
+			streaming_requests: preferred.streaming_requests.or(fallback.streaming_requests),
+			echo_upstream_request_id: preferred
+				.echo_upstream_request_id
+				.or(fallback.echo_upstream_request_id),
+			stream_oversized_responses: preferred
+				.stream_oversized_responses
+				.or(fallback.stream_oversized_responses),
+			deduplicate_requests: preferred
+				.deduplicate_requests
+				.or(fallback.deduplicate_requests),
+			guard_modifications_header: preferred
+				.guard_modifications_header
+				.or(fallback.guard_modifications_header),
+			image_tokens: preferred.image_tokens.or(fallback.image_tokens),
+			allowed_models: preferred
+				.allowed_models
+				.clone()
+				.or_else(|| fallback.allowed_models.clone()),
+			denied_models: preferred
+				.denied_models
+				.clone()
+				.or_else(|| fallback.denied_models.clone()),
+			audit_mirror: preferred
+				.audit_mirror
+				.clone()
+				.or_else(|| fallback.audit_mirror.clone()),
+			estimate_missing_stream_usage: preferred
+				.estimate_missing_stream_usage
+				.or(fallback.estimate_missing_stream_usage),
+			model_deprecation_header: preferred
+				.model_deprecation_header
+				.or(fallback.model_deprecation_header),
 		})
 	}
 }
@@ -532,8 +659,21 @@ pub struct LLMResponsePolicies {
 	pub local_rate_limit: Vec<http::localratelimit::RateLimit>,
 	pub remote_rate_limit: Option<http::remoteratelimit::LLMResponseAmend>,
 	pub request_traceparent: Option<HeaderValue>,
+	/// Name of the backend serving the request, for metric labels.
+	pub backend: Strng,
 	pub prompt_guard: Vec<ResponseGuard>,
 	pub streaming_prompt_guard_enabled: bool,
+	pub usage_trailers: bool,
+	pub stream_transformation: Option<crate::llm::policy::StreamTransformation>,
+	pub empty_choices: Option<crate::llm::policy::EmptyChoices>,
+	pub synthesize_stream_done: bool,
+	pub default_retry_after: Option<crate::llm::policy::DefaultRetryAfter>,
+	pub echo_upstream_request_id: bool,
+	pub stream_oversized_responses: bool,
+	pub deduplicate_requests: bool,
+	pub guard_modifications_header: bool,
+	pub estimate_missing_stream_usage: bool,
+	pub model_deprecation_header: bool,
 }
 
 impl Default for Store {
@@ -641,6 +781,7 @@ impl Store {
 			tcp_routes: Default::default(),
 			listener_change_tx,
 			listener_change_rx,
+			config_generation: 0,
 			tx,
 			rx: Some(rx),
 		}
@@ -679,6 +820,10 @@ impl Store {
 		self.listener_change_rx.clone()
 	}
 
+	pub fn config_generation(&self) -> u64 {
+		self.config_generation
+	}
+
 	pub fn get_bind_listener(&self, bind: &BindKey, listener: &ListenerKey) -> Option<Arc<Listener>> {
 		self
 			.binds
@@ -719,6 +864,11 @@ impl Store {
 			.http_routes
 			.entry(target)
 			.or_insert_with(|| Arc::new(RouteSet::default()));
+		if let Some(old) = routes.get(&route.key)
+			&& resume_rate_limits(&old.inline_policies, &route.inline_policies) > 0
+		{
+			debug!(route = %route.key, "resumed local rate limit buckets");
+		}
 		Arc::make_mut(routes).insert(route);
 	}
 
@@ -954,6 +1104,9 @@ impl Store {
 				TrafficPolicy::Buffer(p) => {
 					pol.buffer.set_if_unset(p);
 				},
+				TrafficPolicy::ContentType(p) => {
+					pol.content_type.merge_with_inheritance(p, lock_inheritance);
+				},
 			}
 		}
 		if !authz.is_empty() {
@@ -1548,6 +1701,7 @@ impl Store {
 	pub fn insert_policy(&mut self, pol: TargetedPolicy) {
 		let pol = Arc::new(pol);
 		if let Some(old) = self.policies_by_key.insert(pol.key.clone(), pol.clone()) {
+			resume_policy_rate_limits(&old, &pol);
 			// Remove the old target. We may add it back, though.
 			if let Some(o) = self.policies_by_target.get_mut(&old.target) {
 				o.remove(&pol.key);
@@ -1753,6 +1907,73 @@ impl Store {
 pub struct StoreUpdater {
 	state: Arc<RwLock<Store>>,
 }
+/// Counts of the loaded configuration, safe to expose: it never includes policy or backend
+/// settings, so no credentials.
+#[derive(Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSummary {
+	pub binds: usize,
+	pub listeners: usize,
+	pub routes: usize,
+	pub backends: BTreeMap<String, usize>,
+	pub providers: usize,
+	pub providers_by_type: BTreeMap<Strng, usize>,
+	pub policies: BTreeMap<String, usize>,
+}
+
+/// Resumes the local rate limit buckets of a policy replaced under the same key.
+fn resume_policy_rate_limits(old: &TargetedPolicy, new: &TargetedPolicy) {
+	fn traffic(p: &TargetedPolicy) -> Option<&TrafficPolicy> {
+		match &p.policy {
+			agent::PolicyType::Traffic(agent::PhasedTrafficPolicy { policy, .. }) => Some(policy),
+			_ => None,
+		}
+	}
+	if resume_rate_limits(traffic(old), traffic(new)) > 0 {
+		debug!(policy = %new.key, "resumed local rate limit buckets");
+	}
+}
+
+/// Resumes the local rate limit buckets of replaced traffic policies, matching limits by position.
+/// Limits whose spec changed start with a fresh bucket. Returns how many buckets were resumed.
+fn resume_rate_limits<'a>(
+	old: impl IntoIterator<Item = &'a TrafficPolicy>,
+	new: impl IntoIterator<Item = &'a TrafficPolicy>,
+) -> usize {
+	fn local_rate_limits<'a>(
+		p: impl IntoIterator<Item = &'a TrafficPolicy>,
+	) -> impl Iterator<Item = &'a http::localratelimit::RateLimit> {
+		p.into_iter()
+			.filter_map(|p| match p {
+				TrafficPolicy::LocalRateLimit(rl) => Some(rl),
+				_ => None,
+			})
+			.flat_map(|rl| rl.iter().flat_map(|pwc| pwc.pol.iter()))
+	}
+	local_rate_limits(old)
+		.zip(local_rate_limits(new))
+		.filter(|(o, n)| n.resume_from(o))
+		.count()
+}
+
+// The serialized name of a unit or externally tagged enum variant.
+fn variant_name<T: serde::Serialize>(v: &T) -> String {
+	match serde_json::to_value(v) {
+		Ok(serde_json::Value::String(s)) => s,
+		Ok(serde_json::Value::Object(m)) if m.len() == 1 => {
+			m.keys().next().cloned().unwrap_or_default()
+		},
+		_ => "unknown".to_string(),
+	}
+}
+
+/// Local rate limit bucket state, as reported on the admin `/rate_limits` endpoint.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct RateLimitsDump {
+	pub policies: BTreeMap<PolicyKey, Vec<http::localratelimit::RateLimitStatus>>,
+	pub routes: BTreeMap<RouteKey, Vec<http::localratelimit::RateLimitStatus>>,
+}
+
 #[apply(schema_ser_schema!)]
 pub struct RoutesDump {
 	pub http_mesh: HashMap<NamespacedHostname, RouteSet>,
@@ -1797,6 +2018,83 @@ impl StoreUpdater {
 	pub fn write(&self) -> std::sync::RwLockWriteGuard<'_, Store> {
 		self.state.write().expect("mutex acquired")
 	}
+	pub fn summary(&self) -> ConfigSummary {
+		let store = self.state.read().expect("mutex");
+		let mut summary = ConfigSummary {
+			binds: store.binds.len(),
+			listeners: store
+				.binds
+				.values()
+				.map(|b| b.listeners.iter().count())
+				.sum(),
+			routes: store.http_routes.values().map(|r| r.iter().count()).sum(),
+			..Default::default()
+		};
+		for b in store.backends.values() {
+			*summary
+				.backends
+				.entry(variant_name(&b.backend.backend_type()))
+				.or_default() += 1;
+			if let Backend::AI(_, ai) = &b.backend {
+				for p in ai.providers.all_endpoints() {
+					summary.providers += 1;
+					*summary
+						.providers_by_type
+						.entry(p.provider.provider())
+						.or_default() += 1;
+				}
+			}
+		}
+		for p in store.policies_by_key.values() {
+			let name = match &p.policy {
+				agent::PolicyType::Frontend(p) => format!("frontend/{}", variant_name(p)),
+				agent::PolicyType::Traffic(p) => format!("traffic/{}", variant_name(&p.policy)),
+				agent::PolicyType::Backend(p) => format!("backend/{}", variant_name(p)),
+			};
+			*summary.policies.entry(name).or_default() += 1;
+		}
+		summary
+	}
+
+	/// Current state of every local rate limit, for targeted policies by policy key and for route
+	/// inline policies (including those generated from LLM config) by route key.
+	pub fn rate_limits(&self) -> RateLimitsDump {
+		fn statuses<'a>(
+			rl: impl IntoIterator<Item = &'a TrafficPolicy>,
+		) -> Vec<http::localratelimit::RateLimitStatus> {
+			rl.into_iter()
+				.filter_map(|p| match p {
+					TrafficPolicy::LocalRateLimit(rl) => Some(rl),
+					_ => None,
+				})
+				.flat_map(|rl| {
+					rl.iter()
+						.flat_map(|pwc| pwc.pol.iter().map(|rl| rl.status()))
+				})
+				.collect()
+		}
+		let store = self.state.read().expect("mutex");
+		let policies = store
+			.policies_by_key
+			.values()
+			.filter_map(|p| match &p.policy {
+				agent::PolicyType::Traffic(agent::PhasedTrafficPolicy { policy, .. }) => {
+					Some((p.key.clone(), statuses([policy])))
+				},
+				_ => None,
+			})
+			.filter(|(_, s)| !s.is_empty())
+			.collect();
+		let routes = store
+			.http_routes
+			.values()
+			.flat_map(|rs| rs.iter())
+			.map(|r| (r.key.clone(), statuses(&r.inline_policies)))
+			.filter(|(_, s)| !s.is_empty())
+			.collect();
+		RateLimitsDump { policies, routes }
+	}
+
 	pub fn dump(&self) -> Dump {
 		let store = self.state.read().expect("mutex");
 
@@ -1950,6 +2248,7 @@ impl StoreUpdater {
 		for remaining_rg in old_route_groups {
 			s.remove_route_group(remaining_rg);
 		}
+		s.config_generation += 1;
 		next_state
 	}
 }
@@ -1995,6 +2294,7 @@ impl agent_xds::Handler<ADPResource> for StoreUpdater {
 				},
 			}
 		}
+		state.config_generation += 1;
 
 		if rejects.is_empty() {
 			Ok(())
@@ -2101,6 +2401,51 @@ mod tests {
 			.insert(policy_key);
 	}
 
+	#[test]
+	fn local_rate_limit_buckets_survive_identical_reload() {
+		use crate::http::localratelimit::{RateLimit, RateLimitSpec};
+
+		let spec = RateLimitSpec {
+			max_tokens: 10,
+			tokens_per_fill: 1,
+			fill_interval: Duration::from_secs(3600),
+			limit_type: Default::default(),
+		};
+		let updater = StoreUpdater::new(Arc::new(std::sync::RwLock::new(Store::default())));
+		let reload = |prev, rl: RateLimit| {
+			let pol = TargetedPolicy {
+				key: strng::new("rl"),
+				name: None,
+				target: PolicyTarget::Route(route("r", "ns", None)),
+				inheritance: Default::default(),
+				policy: TrafficPolicy::LocalRateLimit(RequestPolicy::single(vec![rl])).into(),
+			};
+			updater.sync_local(vec![], vec![], vec![], vec![pol], vec![], vec![], prev)
+		};
+		let remaining = || updater.rate_limits().policies[&strng::new("rl")][0].remaining;
+
+		let rl = RateLimit::try_from(spec.clone()).unwrap();
+		let prev = reload(PreviousState::default(), rl.clone());
+		for _ in 0..4 {
+			rl.check_request().unwrap();
+		}
+		assert_eq!(remaining(), 6);
+
+		let prev = reload(prev, RateLimit::try_from(spec.clone()).unwrap());
+		assert_eq!(remaining(), 6, "identical reload keeps the drained bucket");
+
+		let changed = RateLimitSpec {
+			max_tokens: 20,
+			..spec
+		};
+		reload(prev, RateLimit::try_from(changed).unwrap());
+		assert_eq!(
+			remaining(),
+			20,
+			"a changed limit starts with a fresh bucket"
+		);
+	}
+
 	fn create_access_log_policy(remove_item: &str) -> FrontendPolicy {
 		FrontendPolicy::AccessLog(LoggingPolicy {
 			filter: None,
@@ -2108,6 +2453,7 @@ mod tests {
 			remove: Arc::new(FzHashSet::new(vec![remove_item.into()])),
 			otlp: None,
 			database: None,
+			format: None,
 			access_log_policy: None,
 		})
 	}
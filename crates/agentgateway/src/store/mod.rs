@@ -3,8 +3,8 @@ mod binds;
 use std::sync::{Arc, OnceLock};
 
 pub use binds::{
-	BackendPolicies, BindEvent, BindListeners, FrontendPolices, GatewayPolicies, LLMRequestPolicies,
-	LLMResponsePolicies, RoutePath, RoutePolicies, Store as BindStore,
+	BackendPolicies, BindEvent, BindListeners, ConfigSummary, FrontendPolices, GatewayPolicies,
+	LLMRequestPolicies, LLMResponsePolicies, RoutePath, RoutePolicies, Store as BindStore,
 	StoreUpdater as BindStoreUpdater,
 };
 use serde::{Serialize, Serializer};
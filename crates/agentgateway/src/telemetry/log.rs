@@ -39,13 +39,14 @@ use crate::llm::cost::{CostLookupStatus, ModelCatalog};
 use crate::mcp::{MCPInfo, MCPOperation};
 use crate::proxy::{ProxyResponseReason, dtrace};
 use crate::telemetry::metrics::{
-	CostCatalogLookupLabels, GenAILabels, GenAILabelsTokenUsage, HTTPLabels, MCPCall, Metrics,
-	RouteIdentifier,
+	CostCatalogLookupLabels, GenAILabels, GenAILabelsTokenUsage, HTTPLabels, MCPCall,
+	MCPClientLabels, Metrics, RouteIdentifier,
 };
 use crate::telemetry::trc::TraceParent;
-use crate::telemetry::{log_store, trc};
-use crate::transport::stream::{TCPConnectionInfo, TLSConnectionInfo};
+use crate::telemetry::{log_store, payload_export, trc};
+use crate::transport::stream::{TCPConnectionInfo, TLSConnectionInfo, UpstreamTLSInfo};
 use crate::types::agent::{BackendInfo, BindKey, ListenerName, RouteName, Target};
+use crate::types::frontend::AccessLogFormat;
 use crate::types::loadbalancer::ActiveHandle;
 use crate::{a2a, cel, llm, mcp};
 
@@ -153,6 +154,59 @@ fn user_agent_name(req: Option<&cel::RequestSnapshot>) -> Option<String> {
 	(!name.is_empty()).then(|| name.to_string())
 }
 
+fn request_header(
+	req: Option<&cel::RequestSnapshot>,
+	name: ::http::header::HeaderName,
+) -> Option<ValueBag<'_>> {
+	req?.headers.get(name)?.to_str().ok().map(ValueBag::from)
+}
+
+const CLF_TIME_FORMAT: &str = "%d/%b/%Y:%H:%M:%S %z";
+const COMMON_LOG_TEMPLATE: &str = r#"%{src.addr} - %{jwt.sub} [%{time}] "%{http.method} %{http.path} %{http.version}" %{http.status} %{http.response_bytes}"#;
+const COMBINED_LOG_TEMPLATE: &str = r#"%{src.addr} - %{jwt.sub} [%{time}] "%{http.method} %{http.path} %{http.version}" %{http.status} %{http.response_bytes} "%{http.referer}" "%{http.user_agent}""#;
+
+/// Renders a single access log line. `builtins` are only available to text templates; JSON output
+/// contains exactly the access log fields.
+pub fn render_access_log(
+	buf: &mut String,
+	format: &AccessLogFormat,
+	kv: &[(&str, Option<ValueBag>)],
+	builtins: &[(&str, Option<ValueBag>)],
+) -> anyhow::Result<()> {
+	use std::fmt::Write;
+	let template = match format {
+		AccessLogFormat::Json => {
+			return agent_core::telemetry::write_json_log(buf, "info", "request", kv);
+		},
+		AccessLogFormat::Common => COMMON_LOG_TEMPLATE,
+		AccessLogFormat::Combined => COMBINED_LOG_TEMPLATE,
+		AccessLogFormat::Template(t) => t.as_str(),
+	};
+	let lookup = |name: &str| {
+		// Later entries win, so CEL-added fields take precedence over built-in ones.
+		builtins
+			.iter()
+			.chain(kv)
+			.rev()
+			.find(|(k, _)| *k == name)
+			.and_then(|(_, v)| v.as_ref())
+	};
+	let mut rest = template;
+	while let Some(start) = rest.find("%{") {
+		let Some(len) = rest[start + 2..].find('}') else {
+			break;
+		};
+		buf.push_str(&rest[..start]);
+		match lookup(&rest[start + 2..start + 2 + len]) {
+			Some(v) => write!(buf, "{v}")?,
+			None => buf.push('-'),
+		}
+		rest = &rest[start + 3 + len..];
+	}
+	buf.push_str(rest);
+	Ok(())
+}
+
 fn api_key_name(req: Option<&cel::RequestSnapshot>) -> Option<String> {
 	req?
 		.api_key
@@ -243,6 +297,10 @@ pub struct Config {
 	pub format: crate::LoggingFormat,
 	/// Optional request log database sink.
 	pub database: Option<crate::telemetry::log_store::Config>,
+	/// Optional sink that captured LLM prompts and completions are exported to.
+	pub payload_export: Option<crate::telemetry::payload_export::Config>,
+	/// Whether to emit an `llm_summary` log line at the end of each LLM request.
+	pub llm_summary: bool,
 }
 
 #[derive(serde::Serialize, Default, Clone, Debug)]
@@ -421,6 +479,8 @@ pub struct CelLogging {
 	pub otlp_fields: LoggingFields,
 	pub database_fields: LoggingFields,
 	pub metric_fields: MetricFields,
+	pub access_log_format: Option<AccessLogFormat>,
+	pub llm_summary: bool,
 }
 
 pub struct CelLoggingExecutor<'a> {
@@ -575,6 +635,9 @@ impl CelLogging {
 		if cfg.database.is_some() {
 			cel_context.register_log_request();
 		}
+		if cfg.payload_export.is_some() {
+			cel_context.register_log_llm_payload();
+		}
 
 		Self {
 			cel_context,
@@ -584,6 +647,8 @@ impl CelLogging {
 			otlp_fields: LoggingFields::default(),
 			database_fields: cfg.database_fields,
 			metric_fields: metrics.metric_fields,
+			access_log_format: None,
+			llm_summary: cfg.llm_summary,
 		}
 	}
 
@@ -606,6 +671,8 @@ impl CelLogging {
 			otlp_fields,
 			database_fields,
 			metric_fields,
+			access_log_format: _,
+			llm_summary: _,
 		} = self;
 		let executor = if inputs.req.is_none() && inputs.source_context.is_some() {
 			// TCP case: use new_tcp_logger
@@ -813,6 +880,20 @@ impl DropOnLog {
 					})
 					.observe(cwt as f64)
 			}
+			log.metrics.identity_usage.record(
+				log
+					.jwt_sub
+					.clone()
+					.or_else(|| api_key_name(log.request_snapshot.as_deref()))
+					.map(strng::new)
+					.unwrap_or_else(|| strng::literal!("unknown")),
+				llm_response
+					.response_model
+					.clone()
+					.unwrap_or_else(|| llm_response.request_model.clone()),
+				llm_response.input_tokens.unwrap_or_default(),
+				llm_response.output_tokens.unwrap_or_default(),
+			);
 			log
 				.metrics
 				.gen_ai_request_duration
@@ -883,6 +964,58 @@ fn proxy_context(log: &RequestLog) -> cel::ProxyContext {
 	}
 }
 
+/// Emits a single `llm_summary` line with the usage, cost, and timing of a completed LLM request.
+fn log_llm_summary(log: &RequestLog, llm: &LLMContext, duration: Duration) {
+	let total_tokens = llm
+		.total_tokens
+		.or_else(|| Some(llm.input_tokens? + llm.output_tokens?));
+	let cost = llm.cost.as_ref().map(|b| b.total().to_string());
+	let ttft = llm
+		.time_to_first_token
+		.and_then(|d| d.0.to_std().ok())
+		.map(|d| format!("{}ms", d.as_millis()));
+	let dur = format!("{}ms", duration.as_millis());
+	let trace_id = log.outgoing_span.as_ref().map(|id| id.trace_id());
+	agent_core::telemetry::log(
+		"info",
+		"llm_summary",
+		&[
+			("request.id", log.request_id.map(Into::into)),
+			("trace.id", trace_id.display()),
+			(
+				"gen_ai.response.upstream_request_id",
+				log.upstream_request_id.display(),
+			),
+			("gen_ai.provider.name", Some(display(&llm.provider))),
+			("gen_ai.request.model", Some(display(&llm.request_model))),
+			("gen_ai.response.model", llm.response_model.display()),
+			(
+				"gen_ai.usage.input_tokens",
+				llm.input_tokens.map(Into::into),
+			),
+			(
+				"gen_ai.usage.output_tokens",
+				llm.output_tokens.map(Into::into),
+			),
+			("gen_ai.usage.total_tokens", total_tokens.map(Into::into)),
+			(
+				"gen_ai.usage.cache_read.input_tokens",
+				llm.cached_input_tokens.map(Into::into),
+			),
+			("agw.ai.usage.cost.total", cost.as_deref().map(Into::into)),
+			("duration", Some(dur.as_str().into())),
+			(
+				"gen_ai.time_to_first_token",
+				ttft.as_deref().map(Into::into),
+			),
+			(
+				"http.status",
+				log.status.as_ref().map(|s| s.as_u16().into()),
+			),
+		],
+	);
+}
+
 impl RequestLog {
 	pub fn new(
 		cel: CelLogging,
@@ -905,6 +1038,7 @@ impl RequestLog {
 			request_id: current_request_id(),
 			tcp_info,
 			tls_info: None,
+			upstream_tls: None,
 			tracer: None,
 			trace_spans: Arc::new(Mutex::new(Default::default())),
 			otel_logger: None,
@@ -912,6 +1046,7 @@ impl RequestLog {
 			bind_name: None,
 			listener_name: None,
 			route_name: None,
+			route_policies: Vec::new(),
 			backend_info: None,
 			backend_protocol: None,
 			host: None,
@@ -933,6 +1068,7 @@ impl RequestLog {
 			outgoing_span: None,
 			llm_request: None,
 			llm_response: Default::default(),
+			upstream_request_id: None,
 			a2a_method: None,
 			a2a_response: None,
 			inference_pool: None,
@@ -1041,6 +1177,8 @@ pub struct RequestLog {
 
 	// Set only for TLS traffic
 	pub tls_info: Option<TLSConnectionInfo>,
+	// Set only if the upstream connection used TLS
+	pub upstream_tls: Option<UpstreamTLSInfo>,
 
 	// Set only if the trace is sampled
 	pub tracer: Option<std::sync::Arc<trc::Tracer>>,
@@ -1056,6 +1194,8 @@ pub struct RequestLog {
 	pub bind_name: Option<BindKey>,
 	pub listener_name: Option<ListenerName>,
 	pub route_name: Option<RouteName>,
+	/// Types of the policies that applied to the matched route.
+	pub route_policies: Vec<&'static str>,
 	pub backend_info: Option<BackendInfo>,
 	pub backend_protocol: Option<cel::BackendProtocol>,
 
@@ -1086,6 +1226,8 @@ pub struct RequestLog {
 
 	pub llm_request: Option<llm::LLMRequest>,
 	pub llm_response: AsyncLog<llm::LLMInfo>,
+	/// The LLM provider's own id for the request, from its provider-specific response header.
+	pub upstream_request_id: Option<Strng>,
 
 	pub a2a_method: Option<Strng>,
 	pub a2a_response: Option<a2a::ResponseInfo>,
@@ -1160,9 +1302,25 @@ impl Drop for DropOnLog {
 			let duration = end_time.duration_since(&log.start);
 			let enable_trace = log.tracer.is_some();
 
-			let mut llm_response: Option<LLMContext> = log
-				.llm_response
-				.take()
+			let llm_info = log.llm_response.take();
+			// An abnormal end of an LLM stream (e.g. an upstream reset) is otherwise invisible, as the
+			// client already received a 200.
+			let llm_error_type = llm_info
+				.as_ref()
+				.and_then(|info| info.response.error_type.clone());
+			if let Some(error_type) = &llm_error_type
+				&& log.error.is_none()
+			{
+				log.error = Some(format!("llm response stream failed: {error_type}"));
+			}
+			let llm_guard_modifications = llm_info
+				.as_ref()
+				.map(|info| info.response.guard_modifications.clone())
+				.filter(|m| !m.is_empty());
+			let llm_usage_estimated = llm_info
+				.as_ref()
+				.is_some_and(|info| info.response.usage_estimated);
+			let mut llm_response: Option<LLMContext> = llm_info
 				.map(|llm_info| LLMContext::from_llm_info(llm_info, Some(log.model_catalog.as_ref())));
 			if let Some(llm_response) = llm_response.as_mut() {
 				llm_response.set_token_timing(log.start.as_instant(), end_time.as_instant());
@@ -1278,6 +1436,51 @@ impl Drop for DropOnLog {
 						custom: custom_metric_fields.clone(),
 					})
 					.inc();
+				if let Some(client) = &mcp.client {
+					log
+						.metrics
+						.mcp_client_initializations
+						.get_or_create(&MCPClientLabels {
+							client_name: RichStrng::from(client.name.as_str()).into(),
+							client_version: RichStrng::from(client.version_bucket()).into(),
+							route: route_identifier.clone(),
+						})
+						.inc();
+				}
+			}
+
+			// Export is independent of access logging and its filter; the exporter only queues here.
+			if let Some(llm) = llm_response.as_ref()
+				&& (llm.prompt.is_some() || llm.completion.is_some())
+				&& payload_export::enabled()
+			{
+				payload_export::emit(payload_export::Record {
+					id: uuid::Uuid::now_v7().to_string(),
+					completed_at: end_time.as_datetime().with_timezone(&chrono::Utc),
+					trace_id: log
+						.outgoing_span
+						.as_ref()
+						.map(|span| span.trace_id().to_string()),
+					provider: log
+						.llm_request
+						.as_ref()
+						.map(|request| request.provider.to_string()),
+					request_model: log
+						.llm_request
+						.as_ref()
+						.map(|request| request.request_model.to_string()),
+					response_model: llm.response_model.as_ref().map(ToString::to_string),
+					input_tokens: llm.input_tokens,
+					output_tokens: llm.output_tokens,
+					prompt: llm.prompt.clone(),
+					completion: llm.completion.clone(),
+				});
+			}
+
+			if log.cel.llm_summary
+				&& let Some(llm) = llm_response.as_ref()
+			{
+				log_llm_summary(&log, llm, duration);
 			}
 
 			let maybe_enable_log = agent_core::telemetry::enabled("request", &Level::INFO);
@@ -1349,6 +1552,8 @@ impl Drop for DropOnLog {
 				}
 			});
 
+			let route_policies = (!log.route_policies.is_empty()).then(|| log.route_policies.join(","));
+
 			let emit_ids = agent_core::telemetry::enabled("request", &Level::DEBUG);
 			let mut kv = vec![
 				(
@@ -1372,7 +1577,19 @@ impl Drop for DropOnLog {
 					route_identifier.route_rule.as_deref().map(display),
 				),
 				("route", route_identifier.route.as_deref().map(display)),
+				("route.policies", route_policies.as_deref().map(Into::into)),
 				("endpoint", log.endpoint.display()),
+				(
+					"upstream.tls.version",
+					log.upstream_tls.as_ref().map(|t| t.version.into()),
+				),
+				(
+					"upstream.tls.fingerprint",
+					emit_ids
+						.then_some(log.upstream_tls.as_ref())
+						.flatten()
+						.and_then(|t| t.peer_certificate_fingerprint.display()),
+				),
 				("src.addr", Some(display(&log.tcp_info.peer_addr))),
 				("http.method", log.method.display()),
 				("http.host", log.host.display()),
@@ -1444,6 +1661,20 @@ impl Drop for DropOnLog {
 						.and_then(|m| m.session_id.as_ref())
 						.map(display),
 				),
+				(
+					"mcp.client.name",
+					mcp
+						.as_ref()
+						.and_then(|m| m.client.as_ref())
+						.map(|c| display(&c.name)),
+				),
+				(
+					"mcp.client.version",
+					mcp
+						.as_ref()
+						.and_then(|m| m.client.as_ref())
+						.map(|c| display(&c.version)),
+				),
 				(
 					"inferencepool.selected_endpoint",
 					log.inference_pool.display(),
@@ -1473,6 +1704,20 @@ impl Drop for DropOnLog {
 						.as_ref()
 						.and_then(|l| l.response_model.display()),
 				),
+				(
+					"gen_ai.response.upstream_request_id",
+					log.upstream_request_id.display(),
+				),
+				(
+					"openai.response.service_tier",
+					llm_response.as_ref().and_then(|l| l.service_tier.display()),
+				),
+				(
+					"openai.response.system_fingerprint",
+					llm_response
+						.as_ref()
+						.and_then(|l| l.system_fingerprint.display()),
+				),
 				("gen_ai.usage.input_tokens", input_tokens.map(Into::into)),
 				(
 					"gen_ai.usage.cache_creation.input_tokens",
@@ -1508,6 +1753,11 @@ impl Drop for DropOnLog {
 						.map(Into::into),
 				),
 				// Not part of official semconv
+				(
+					"gen_ai.usage.estimated",
+					llm_usage_estimated.then_some(true.into()),
+				),
+				// Not part of official semconv
 				(
 					"gen_ai.usage.output_audio_tokens",
 					llm_response
@@ -1580,6 +1830,8 @@ impl Drop for DropOnLog {
 				),
 				("retry.attempt", log.retry_attempt.display()),
 				("error", log.error.quoted()),
+				("error.type", llm_error_type.display()),
+				("guardrail.modifications", llm_guard_modifications.quoted()),
 				("reason", reason.display()),
 				("duration", Some(dur.as_str().into())),
 			];
@@ -1653,7 +1905,28 @@ impl Drop for DropOnLog {
 				}
 
 				if maybe_enable_log {
-					agent_core::telemetry::log("info", "request", &kv);
+					match &log.cel.access_log_format {
+						None => agent_core::telemetry::log("info", "request", &kv),
+						Some(format) => {
+							let response_bytes = log.response_bytes;
+							let req = log.request_snapshot.as_deref();
+							let time = log.start.as_datetime().format(CLF_TIME_FORMAT).to_string();
+							let builtins = [
+								("time", Some(display(&time))),
+								("http.response_bytes", Some(response_bytes.into())),
+								("http.referer", request_header(req, ::http::header::REFERER)),
+								(
+									"http.user_agent",
+									request_header(req, ::http::header::USER_AGENT),
+								),
+							];
+							let mut line = String::new();
+							match render_access_log(&mut line, format, &kv, &builtins) {
+								Ok(()) => agent_core::telemetry::log_line("info", "request", &line, &kv),
+								Err(e) => debug!("failed to render access log: {e}"),
+							}
+						},
+					}
 				}
 
 				if log_store_enabled {
@@ -2254,6 +2527,8 @@ mod tests {
 			otlp_fields: LoggingFields::default(),
 			metric_fields: MetricFields::default(),
 			database_fields: LoggingFields::default(),
+			access_log_format: None,
+			llm_summary: false,
 		};
 		let mut registry = Registry::default();
 		let metrics = Arc::new(Metrics::new(&mut registry, Default::default()));
@@ -2282,6 +2557,52 @@ mod tests {
 		assert!(!DropOnLog::default_unhealthy(&log));
 	}
 
+	#[test]
+	fn access_log_renders_json_and_template() {
+		let addr: SocketAddr = "127.0.0.1:12345".parse().unwrap();
+		let version = ::http::Version::HTTP_11;
+		let kv = [
+			("src.addr", Some(display(&addr))),
+			("http.method", Some("GET".into())),
+			("http.path", Some("/v1/models".into())),
+			("http.version", Some(debug(&version))),
+			("http.status", Some(200u16.into())),
+			("jwt.sub", None),
+			("model", Some("gpt-4o".into())),
+		];
+		let builtins = [
+			("time", Some("10/Oct/2025:13:55:36 +0000".into())),
+			("http.response_bytes", Some(512u64.into())),
+			("http.referer", None),
+			("http.user_agent", Some("curl/8.5.0".into())),
+		];
+
+		let mut json = String::new();
+		render_access_log(&mut json, &AccessLogFormat::Json, &kv, &builtins).unwrap();
+		let json: Value = serde_json::from_str(&json).unwrap();
+		assert_eq!(json["scope"], "request");
+		assert_eq!(json["src.addr"], "127.0.0.1:12345");
+		assert_eq!(json["http.method"], "GET");
+		assert_eq!(json["http.path"], "/v1/models");
+		assert_eq!(json["http.status"], 200);
+		assert_eq!(json["model"], "gpt-4o");
+		assert!(json.get("jwt.sub").is_none());
+		assert!(json.get("http.user_agent").is_none());
+
+		let mut combined = String::new();
+		render_access_log(&mut combined, &AccessLogFormat::Combined, &kv, &builtins).unwrap();
+		assert_eq!(
+			combined,
+			r#"127.0.0.1:12345 - - [10/Oct/2025:13:55:36 +0000] "GET /v1/models HTTP/1.1" 200 512 "-" "curl/8.5.0""#
+		);
+
+		let mut custom = String::new();
+		let template =
+			AccessLogFormat::Template(strng::new("%{http.status} %{model} %{missing} %{unclosed"));
+		render_access_log(&mut custom, &template, &kv, &builtins).unwrap();
+		assert_eq!(custom, "200 gpt-4o - %{unclosed");
+	}
+
 	#[test]
 	fn span_writer_flushes_recorded_spans_as_children_of_request_span() {
 		let (tracer, exporter) = test_tracer();
@@ -2412,6 +2733,57 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn llm_response_service_tier_and_fingerprint_span_attributes() {
+		let (tracer, exporter) = test_tracer();
+		let mut log = test_request_log();
+		log.tracer = Some(tracer.clone());
+		let mut outgoing = trc::TraceParent::new();
+		outgoing.flags = 1;
+		log.outgoing_span = Some(outgoing);
+		let request = llm::LLMRequest {
+			input_tokens: None,
+			input_format: InputFormat::Completions,
+			cache_convention: llm::CacheTokenConvention::InputIncludesCache,
+			request_model: strng::literal!("gpt-4o"),
+			provider: strng::literal!("openai"),
+			streaming: false,
+			params: llm::LLMRequestParams::default(),
+			prompt: None,
+			provider_state: None,
+		};
+		let response = llm::LLMResponse {
+			service_tier: Some(strng::literal!("default")),
+			system_fingerprint: Some(strng::literal!("fp_44709d6fcb")),
+			..Default::default()
+		};
+		log.llm_request = Some(request.clone());
+		log
+			.llm_response
+			.store(Some(llm::LLMInfo::new(request, response)));
+
+		drop(DropOnLog::from(log));
+		let _ = tracer.provider.force_flush();
+
+		let spans = exporter.finished_spans();
+		let span = spans
+			.iter()
+			.find(|span| span.name.as_ref() == "unknown")
+			.expect("request span should be exported");
+		let attr = |key: &str| {
+			span
+				.attributes
+				.iter()
+				.find(|attr| attr.key.as_str() == key)
+				.unwrap_or_else(|| panic!("expected {key} span attribute"))
+				.value
+				.as_str()
+				.to_string()
+		};
+		assert_eq!(attr("openai.response.service_tier"), "default");
+		assert_eq!(attr("openai.response.system_fingerprint"), "fp_44709d6fcb");
+	}
+
 	#[test]
 	fn a2a_response_span_attributes() {
 		let (tracer, exporter) = test_tracer();
@@ -2447,4 +2819,71 @@ mod tests {
 			assert!(has(expected), "expected {expected} span attribute");
 		}
 	}
+
+	#[test]
+	fn upstream_tls_version_span_attribute() {
+		let (tracer, exporter) = test_tracer();
+		let mut log = test_request_log();
+		log.tracer = Some(tracer.clone());
+		let mut outgoing = trc::TraceParent::new();
+		outgoing.flags = 1;
+		log.outgoing_span = Some(outgoing);
+		log.upstream_tls = Some(UpstreamTLSInfo {
+			version: "TLSv1.3",
+			peer_certificate_fingerprint: Some("abcd".to_string()),
+		});
+
+		drop(DropOnLog::from(log));
+		let _ = tracer.provider.force_flush();
+
+		let spans = exporter.finished_spans();
+		let span = spans
+			.iter()
+			.find(|span| span.name.as_ref() == "unknown")
+			.expect("request span should be exported");
+		let version = span
+			.attributes
+			.iter()
+			.find(|attr| attr.key.as_str() == "upstream.tls.version")
+			.expect("expected upstream.tls.version span attribute");
+		assert_eq!(version.value.as_str(), "TLSv1.3");
+	}
+
+	#[test]
+	fn route_name_and_policies_span_attributes() {
+		let (tracer, exporter) = test_tracer();
+		let mut log = test_request_log();
+		log.tracer = Some(tracer.clone());
+		let mut outgoing = trc::TraceParent::new();
+		outgoing.flags = 1;
+		log.outgoing_span = Some(outgoing);
+		log.route_name = Some(RouteName {
+			name: strng::new("my-route"),
+			namespace: strng::new("default"),
+			rule_name: None,
+			kind: None,
+		});
+		log.route_policies = vec!["localRateLimit", "cors"];
+
+		drop(DropOnLog::from(log));
+		let _ = tracer.provider.force_flush();
+
+		let spans = exporter.finished_spans();
+		let span = spans
+			.iter()
+			.find(|span| span.name.as_ref() == "unknown")
+			.expect("request span should be exported");
+		let attr = |key: &str| {
+			span
+				.attributes
+				.iter()
+				.find(|attr| attr.key.as_str() == key)
+				.unwrap_or_else(|| panic!("expected {key} span attribute"))
+				.value
+				.as_str()
+				.to_string()
+		};
+		assert_eq!(attr("route"), "default/my-route");
+		assert_eq!(attr("route.policies"), "localRateLimit,cors");
+	}
 }
@@ -7,6 +7,7 @@ use frozen_collections::FzHashSet;
 use prometheus_client::encoding::EncodeLabelSet;
 use prometheus_client::metrics::counter;
 use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
 use prometheus_client::metrics::histogram::Histogram as PromHistogram;
 use prometheus_client::metrics::info::Info;
 use prometheus_client::registry::{Metric, Registry, Unit};
@@ -45,12 +46,33 @@ pub enum GuardrailAction {
 	FailOpen,
 }
 
+impl GuardrailAction {
+	/// Name of the action as recorded in request logs.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			GuardrailAction::Allow => "allow",
+			GuardrailAction::Mask => "mask",
+			GuardrailAction::Reject => "reject",
+			GuardrailAction::FailOpen => "failOpen",
+		}
+	}
+}
+
 #[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
 pub struct GuardrailLabels {
 	pub phase: GuardrailPhase,
 	pub action: GuardrailAction,
 }
 
+#[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
+pub struct GuardrailRejectionLabels {
+	pub phase: GuardrailPhase,
+	pub guard: DefaultedUnknown<RichStrng>,
+	pub code: DefaultedUnknown<EncodeDisplay<u16>>,
+	pub provider: DefaultedUnknown<RichStrng>,
+	pub backend: DefaultedUnknown<RichStrng>,
+}
+
 #[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
 pub struct MinimalHTTPLabels {
 	pub backend: DefaultedUnknown<RichStrng>,
@@ -102,6 +124,17 @@ pub struct GenAILabels {
 	pub custom: CustomField,
 }
 
+#[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
+pub struct LLMModelLabels {
+	pub provider: DefaultedUnknown<RichStrng>,
+	pub model: DefaultedUnknown<RichStrng>,
+}
+
+#[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
+pub struct GenAITokenEstimateLabels {
+	pub gen_ai_request_model: DefaultedUnknown<RichStrng>,
+}
+
 #[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
 pub struct GenAILabelsTokenUsage {
 	pub gen_ai_token_type: DefaultedUnknown<RichStrng>,
@@ -118,6 +151,19 @@ pub struct CostCatalogLookupLabels {
 	pub common: EncodeArc<GenAILabels>,
 }
 
+#[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
+pub struct EndpointLabels {
+	pub backend: DefaultedUnknown<RichStrng>,
+	pub provider: DefaultedUnknown<RichStrng>,
+	pub endpoint: DefaultedUnknown<RichStrng>,
+}
+
+#[derive(Clone, Hash, Default, Debug, PartialEq, Eq, EncodeLabelSet)]
+pub struct UnsupportedConversionLabels {
+	pub input_format: DefaultedUnknown<RichStrng>,
+	pub provider: DefaultedUnknown<RichStrng>,
+}
+
 #[derive(Clone, Hash, Debug, PartialEq, Eq, EncodeLabelSet)]
 pub struct MCPCall {
 	pub method: DefaultedUnknown<RichStrng>,
@@ -133,6 +179,15 @@ pub struct MCPCall {
 	pub custom: CustomField,
 }
 
+#[derive(Clone, Hash, Debug, PartialEq, Eq, EncodeLabelSet)]
+pub struct MCPClientLabels {
+	pub client_name: DefaultedUnknown<RichStrng>,
+	pub client_version: DefaultedUnknown<RichStrng>,
+
+	#[prometheus(flatten)]
+	pub route: RouteIdentifier,
+}
+
 #[derive(Clone, Hash, Debug, PartialEq, Eq, EncodeLabelSet)]
 pub struct TCPLabels {
 	pub bind: DefaultedUnknown<RichStrng>,
@@ -175,6 +230,7 @@ pub enum OutboundCallSubtype {
 	Guardrail,
 	RateLimit,
 	Oidc,
+	Tokenizer,
 }
 
 #[derive(Clone, Hash, Debug, PartialEq, Eq, EncodeLabelSet)]
@@ -201,12 +257,17 @@ pub struct Metrics {
 	pub response_bytes: Family<HTTPLabels, counter::Counter>,
 
 	pub mcp_requests: Family<MCPCall, counter::Counter>,
+	pub mcp_client_initializations: Family<MCPClientLabels, counter::Counter>,
 
 	pub gen_ai_token_usage: Histogram<GenAILabelsTokenUsage>,
 	pub gen_ai_cost: Family<GenAILabels, counter::Counter<f64>>,
 	pub gen_ai_request_duration: Histogram<GenAILabels>,
 	pub gen_ai_time_per_output_token: Histogram<GenAILabels>,
 	pub gen_ai_time_to_first_token: Histogram<GenAILabels>,
+	// provider-reported input tokens minus the gateway's estimate, to validate the tokenizer
+	pub gen_ai_input_token_estimate_divergence: Histogram<GenAITokenEstimateLabels>,
+	// longest gap between consecutive chunks of a streamed LLM response, to detect stalls
+	pub llm_stream_max_chunk_gap: Histogram<LLMModelLabels>,
 
 	pub tls_handshake_duration: Histogram<TCPLabels>,
 
@@ -219,11 +280,25 @@ pub struct Metrics {
 
 	// metrics for guardrail checks (allow/mask/reject) for request/response
 	pub guardrail_checks: Family<GuardrailLabels, counter::Counter>,
+	// LLM requests and responses rejected by a prompt guard, by guard and rejection status code
+	pub prompt_guard_rejections: Family<GuardrailRejectionLabels, counter::Counter>,
+	// guardrail webhook calls currently in flight
+	pub guardrail_webhook_in_flight: Gauge,
+
+	// requests currently in flight, and handled in total, by the selected LLM provider endpoint
+	pub endpoint_active_requests: Family<EndpointLabels, Gauge>,
+	pub endpoint_requests: Family<EndpointLabels, counter::Counter>,
 
 	pub cost_catalog_lookups: Family<CostCatalogLookupLabels, counter::Counter>,
 
+	// LLM requests rejected because they cannot be translated for the provider
+	pub llm_unsupported_conversions: Family<UnsupportedConversionLabels, counter::Counter>,
+
 	// metrics for request retries
 	pub retries: Counter,
+
+	// token usage per identity and model, served by the admin `/usage` endpoint
+	pub identity_usage: crate::telemetry::usage::IdentityUsage,
 }
 
 // FilteredRegistry is a wrapper around Registry that allows to filter out certain metrics.
@@ -349,6 +424,26 @@ impl Metrics {
 			gen_ai_time_to_first_token.clone(),
 		);
 
+		let gen_ai_input_token_estimate_divergence =
+			Family::<GenAITokenEstimateLabels, _>::new_with_constructor(move || {
+				PromHistogram::new(TOKEN_ESTIMATE_DIVERGENCE_BUCKET)
+			});
+		registry.register(
+			"gen_ai_input_token_estimate_divergence",
+			"Provider-reported input tokens minus the estimated input tokens for a given request",
+			gen_ai_input_token_estimate_divergence.clone(),
+		);
+
+		let llm_stream_max_chunk_gap = Family::<LLMModelLabels, _>::new_with_constructor(move || {
+			PromHistogram::new(FIRST_TOKEN_BUCKET)
+		});
+		registry.register_with_unit(
+			"llm_stream_max_chunk_gap",
+			"Longest gap between consecutive chunks of a streamed LLM response",
+			Unit::Seconds,
+			llm_stream_max_chunk_gap.clone(),
+		);
+
 		Metrics {
 			requests: build(
 				&mut registry,
@@ -364,6 +459,42 @@ impl Metrics {
 				);
 				m
 			},
+			prompt_guard_rejections: {
+				let m = Family::<GuardrailRejectionLabels, _>::default();
+				registry.register(
+					"prompt_guard_rejections",
+					"Total number of LLM requests and responses rejected by a prompt guard",
+					m.clone(),
+				);
+				m
+			},
+			guardrail_webhook_in_flight: {
+				let m = Gauge::default();
+				registry.register(
+					"guardrail_webhook_in_flight",
+					"Number of guardrail webhook calls currently in flight",
+					m.clone(),
+				);
+				m
+			},
+			endpoint_active_requests: {
+				let m = Family::<EndpointLabels, Gauge>::default();
+				registry.register(
+					"endpoint_active_requests",
+					"Number of requests currently in flight to a selected LLM provider endpoint",
+					m.clone(),
+				);
+				m
+			},
+			endpoint_requests: {
+				let m = Family::<EndpointLabels, _>::default();
+				registry.register(
+					"endpoint_requests",
+					"Total number of requests sent to a selected LLM provider endpoint",
+					m.clone(),
+				);
+				m
+			},
 			cost_catalog_lookups: {
 				let m = Family::<CostCatalogLookupLabels, _>::default();
 				registry.register(
@@ -373,6 +504,15 @@ impl Metrics {
 				);
 				m
 			},
+			llm_unsupported_conversions: {
+				let m = Family::<UnsupportedConversionLabels, _>::default();
+				registry.register(
+					"llm_unsupported_conversions",
+					"Total number of LLM requests rejected because the input format cannot be translated for the provider",
+					m.clone(),
+				);
+				m
+			},
 			downstream_connection: build(
 				&mut registry,
 				"downstream_connections",
@@ -384,12 +524,19 @@ impl Metrics {
 				"mcp_requests",
 				"Total number of MCP tool calls",
 			),
+			mcp_client_initializations: build(
+				&mut registry,
+				"mcp_client_initializations",
+				"Total number of MCP initialize requests by client implementation",
+			),
 
 			gen_ai_token_usage,
 			gen_ai_cost,
 			gen_ai_request_duration,
 			gen_ai_time_per_output_token,
 			gen_ai_time_to_first_token,
+			gen_ai_input_token_estimate_divergence,
+			llm_stream_max_chunk_gap,
 
 			response_bytes: {
 				let m = Family::<HTTPLabels, _>::default();
@@ -498,6 +645,7 @@ impl Metrics {
 				"retries",
 				"The total number of request retries",
 			),
+			identity_usage: Default::default(),
 		}
 	}
 }
@@ -521,6 +669,10 @@ const TOKEN_USAGE_BUCKET: [f64; 14] = [
 const REQUEST_DURATION_BUCKET: [f64; 14] = [
 	0.01, 0.02, 0.04, 0.08, 0.16, 0.32, 0.64, 1.28, 2.56, 5.12, 10.24, 20.48, 40.96, 81.92,
 ];
+// Symmetric around zero: negative when the estimate overcounted, positive when it undercounted.
+const TOKEN_ESTIMATE_DIVERGENCE_BUCKET: [f64; 17] = [
+	-1024., -256., -64., -16., -8., -4., -2., -1., 0., 1., 2., 4., 8., 16., 64., 256., 1024.,
+];
 // Finer-grained, exponentially growing buckets for TCP/TLS connect.
 // Keep in seconds (Prometheus convention). Prioritize sub-second resolution, with a few larger outlier buckets.
 const CONNECT_DURATION_BUCKET: [f64; 10] = [
@@ -1,4 +1,6 @@
 pub mod log;
 pub mod log_store;
 pub mod metrics;
+pub mod payload_export;
 pub mod trc;
+pub mod usage;
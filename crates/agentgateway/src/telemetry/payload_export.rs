@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+use crate::llm::SimpleChatCompletionMessage;
+use crate::llm::policy::RegexRules;
+use crate::*;
+
+static PAYLOAD_EXPORTER: OnceLock<PayloadExporter> = OnceLock::new();
+
+// Bounds how long a stalled sink holds up the worker; records captured meanwhile queue up to
+// `max_queued` and are dropped beyond that.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[apply(schema!)]
+pub struct Config {
+	/// Destination that batches of captured prompt/completion records are delivered to.
+	pub sink: Sink,
+	/// Maximum number of records delivered in a single batch. Defaults to 100.
+	#[serde(default = "default_batch_size")]
+	pub batch_size: usize,
+	/// Maximum time a record waits for its batch to fill before it is delivered. Defaults to 1s.
+	#[serde(default = "default_flush_interval", with = "serde_dur")]
+	#[cfg_attr(feature = "schema", schemars(with = "String"))]
+	pub flush_interval: Duration,
+	/// Maximum number of records waiting for delivery. Records captured while the queue is full are
+	/// dropped rather than slowing down requests. Defaults to 10000.
+	#[serde(default = "default_max_queued")]
+	pub max_queued: usize,
+	/// Rules applied to prompt and completion text before it leaves the gateway. Matches are masked,
+	/// or with the `reject` action the whole message is withheld.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub redact: Option<RegexRules>,
+}
+
+#[apply(schema!)]
+pub enum Sink {
+	/// POST each batch to a URL as a JSON array of records.
+	Webhook {
+		/// URL to deliver batches to.
+		url: String,
+		/// Headers added to each delivery, e.g. for authentication.
+		#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+		headers: HashMap<String, String>,
+	},
+}
+
+fn default_batch_size() -> usize {
+	100
+}
+
+fn default_flush_interval() -> Duration {
+	Duration::from_secs(1)
+}
+
+fn default_max_queued() -> usize {
+	10_000
+}
+
+/// A captured LLM prompt and completion, as delivered to the sink.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Record {
+	pub id: String,
+	pub completed_at: DateTime<Utc>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub trace_id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub provider: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub request_model: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub response_model: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub input_tokens: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub output_tokens: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub prompt: Option<Arc<Vec<SimpleChatCompletionMessage>>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub completion: Option<Vec<String>>,
+}
+
+impl Record {
+	fn redact(&mut self, rules: &RegexRules) {
+		if let Some(prompt) = self.prompt.as_mut() {
+			let redacted: Vec<_> = prompt.iter().map(|m| rules.redact(&m.content)).collect();
+			if redacted.iter().any(Option::is_some) {
+				*prompt = Arc::new(
+					prompt
+						.iter()
+						.zip(redacted)
+						.map(|(m, r)| SimpleChatCompletionMessage {
+							role: m.role.clone(),
+							content: r.map(Into::into).unwrap_or_else(|| m.content.clone()),
+						})
+						.collect(),
+				);
+			}
+		}
+		for choice in self.completion.iter_mut().flatten() {
+			if let Some(redacted) = rules.redact(choice) {
+				*choice = redacted;
+			}
+		}
+	}
+}
+
+#[allow(clippy::large_enum_variant)]
+enum ExportMsg {
+	Record(Record),
+	Shutdown,
+}
+
+#[derive(Clone)]
+pub struct PayloadExporter {
+	tx: mpsc::Sender<ExportMsg>,
+	dropped: Arc<AtomicU64>,
+}
+
+impl PayloadExporter {
+	/// Queues a record for delivery. This never waits: when the queue is full the record is dropped.
+	pub fn emit(&self, record: Record) {
+		match self.tx.try_send(ExportMsg::Record(record)) {
+			Ok(()) => {},
+			Err(TrySendError::Full(_)) => {
+				self.dropped.fetch_add(1, Ordering::Relaxed);
+			},
+			Err(TrySendError::Closed(_)) => {
+				debug!(target: "request", "payload exporter is stopped, dropping record");
+			},
+		}
+	}
+}
+
+/// Starts the exporter worker and installs it as the process-wide exporter used by [`emit`].
+pub fn setup(cfg: &Config) -> anyhow::Result<PayloadExportGuard> {
+	let (exporter, guard) = start(cfg.clone())?;
+	let _ = PAYLOAD_EXPORTER.set(exporter);
+	Ok(guard)
+}
+
+fn start(cfg: Config) -> anyhow::Result<(PayloadExporter, PayloadExportGuard)> {
+	if cfg.batch_size == 0 {
+		anyhow::bail!("payloadExport.batchSize must be greater than 0");
+	}
+	if cfg.max_queued == 0 {
+		anyhow::bail!("payloadExport.maxQueued must be greater than 0");
+	}
+	let (tx, rx) = mpsc::channel(cfg.max_queued);
+	let dropped = Arc::new(AtomicU64::new(0));
+	let worker = ExportWorker {
+		rx,
+		client: reqwest::Client::new(),
+		dropped: dropped.clone(),
+		cfg,
+	};
+	let worker = tokio::spawn(worker.run());
+	Ok((
+		PayloadExporter {
+			tx: tx.clone(),
+			dropped,
+		},
+		PayloadExportGuard {
+			tx,
+			worker: Some(worker),
+		},
+	))
+}
+
+pub fn emit(record: Record) {
+	if let Some(exporter) = PAYLOAD_EXPORTER.get() {
+		exporter.emit(record);
+	}
+}
+
+pub fn enabled() -> bool {
+	PAYLOAD_EXPORTER.get().is_some()
+}
+
+pub struct PayloadExportGuard {
+	tx: mpsc::Sender<ExportMsg>,
+	worker: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl PayloadExportGuard {
+	/// Delivers any queued records and stops the worker.
+	pub async fn shutdown_and_wait(mut self) {
+		let _ = self.tx.send(ExportMsg::Shutdown).await;
+		if let Some(worker) = self.worker.take()
+			&& let Err(err) = worker.await
+		{
+			warn!(target: "request", ?err, "failed to join payload exporter");
+		}
+	}
+}
+
+impl Drop for PayloadExportGuard {
+	fn drop(&mut self) {
+		let _ = self.tx.try_send(ExportMsg::Shutdown);
+	}
+}
+
+struct ExportWorker {
+	rx: mpsc::Receiver<ExportMsg>,
+	client: reqwest::Client,
+	dropped: Arc<AtomicU64>,
+	cfg: Config,
+}
+
+impl ExportWorker {
+	async fn run(mut self) {
+		let mut batch = Vec::with_capacity(self.cfg.batch_size);
+		let mut shutdown = false;
+		while !shutdown {
+			match self.rx.recv().await {
+				Some(ExportMsg::Record(record)) => batch.push(record),
+				Some(ExportMsg::Shutdown) | None => break,
+			}
+			// Give the batch until the flush interval to fill up.
+			let deadline = tokio::time::Instant::now() + self.cfg.flush_interval;
+			while batch.len() < self.cfg.batch_size {
+				match tokio::time::timeout_at(deadline, self.rx.recv()).await {
+					Ok(Some(ExportMsg::Record(record))) => batch.push(record),
+					Ok(Some(ExportMsg::Shutdown) | None) => {
+						shutdown = true;
+						break;
+					},
+					Err(_) => break,
+				}
+			}
+			self.flush(&mut batch).await;
+		}
+		while let Ok(msg) = self.rx.try_recv() {
+			if let ExportMsg::Record(record) = msg {
+				batch.push(record);
+				if batch.len() == self.cfg.batch_size {
+					self.flush(&mut batch).await;
+				}
+			}
+		}
+		self.flush(&mut batch).await;
+		debug!(target: "request", "payload exporter stopped");
+	}
+
+	async fn flush(&self, batch: &mut Vec<Record>) {
+		let dropped = self.dropped.swap(0, Ordering::Relaxed);
+		if dropped > 0 {
+			warn!(target: "request", dropped, "payload export queue is full, dropped records");
+		}
+		if batch.is_empty() {
+			return;
+		}
+		if let Some(rules) = &self.cfg.redact {
+			for record in batch.iter_mut() {
+				record.redact(rules);
+			}
+		}
+		let count = batch.len();
+		if let Err(err) = self.deliver(batch).await {
+			warn!(target: "request", ?err, count, "failed to export prompt/completion batch");
+		}
+		batch.clear();
+	}
+
+	async fn deliver(&self, batch: &[Record]) -> anyhow::Result<()> {
+		match &self.cfg.sink {
+			Sink::Webhook { url, headers } => {
+				let mut req = self.client.post(url).timeout(DELIVERY_TIMEOUT).json(batch);
+				for (k, v) in headers {
+					req = req.header(k, v);
+				}
+				req.send().await?.error_for_status()?;
+			},
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Instant;
+
+	use wiremock::matchers::{method, path};
+	use wiremock::{Mock, MockServer, ResponseTemplate};
+
+	use super::*;
+
+	fn record(prompt: &str, completion: &str) -> Record {
+		Record {
+			id: uuid::Uuid::now_v7().to_string(),
+			completed_at: Utc::now(),
+			trace_id: None,
+			provider: Some("openai".to_string()),
+			request_model: Some("gpt-4o".to_string()),
+			response_model: None,
+			input_tokens: Some(10),
+			output_tokens: Some(5),
+			prompt: Some(Arc::new(vec![SimpleChatCompletionMessage {
+				role: "user".into(),
+				content: prompt.into(),
+			}])),
+			completion: Some(vec![completion.to_string()]),
+		}
+	}
+
+	fn config(url: String, max_queued: usize) -> Config {
+		serde_json::from_value(serde_json::json!({
+			"sink": {"webhook": {"url": url}},
+			"batchSize": 2,
+			"flushInterval": "20ms",
+			"maxQueued": max_queued,
+			"redact": {"rules": [{"builtin": "email"}]},
+		}))
+		.unwrap()
+	}
+
+	#[tokio::test]
+	async fn records_are_delivered_without_blocking_emit() {
+		let mock = MockServer::start().await;
+		// A slow sink must not slow down the caller.
+		Mock::given(method("POST"))
+			.and(path("/export"))
+			.respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+			.mount(&mock)
+			.await;
+		let (exporter, guard) = start(config(format!("{}/export", mock.uri()), 16)).unwrap();
+
+		let t0 = Instant::now();
+		for i in 0..3 {
+			exporter.emit(record(&format!("hi, I am user{i}@example.com"), "hello"));
+		}
+		assert!(t0.elapsed() < Duration::from_millis(50));
+
+		guard.shutdown_and_wait().await;
+		let received = mock.received_requests().await.unwrap();
+		let records: Vec<serde_json::Value> = received
+			.iter()
+			.flat_map(|r| serde_json::from_slice::<Vec<serde_json::Value>>(&r.body).unwrap())
+			.collect();
+		assert_eq!(received.len(), 2, "3 records with a batch size of 2");
+		assert_eq!(records.len(), 3);
+		for record in &records {
+			let content = record["prompt"][0]["content"].as_str().unwrap();
+			assert!(!content.contains("@example.com"), "{content}");
+			assert_eq!(record["completion"][0], "hello");
+			assert_eq!(record["requestModel"], "gpt-4o");
+		}
+	}
+
+	#[tokio::test]
+	async fn full_queue_drops_instead_of_blocking() {
+		let mock = MockServer::start().await;
+		Mock::given(method("POST"))
+			.respond_with(ResponseTemplate::new(200))
+			.mount(&mock)
+			.await;
+		let (exporter, guard) = start(config(mock.uri(), 1)).unwrap();
+
+		// The worker does not get to run between these, so only the first record fits.
+		for _ in 0..10 {
+			exporter.emit(record("hi", "hello"));
+		}
+		assert_eq!(exporter.dropped.load(Ordering::Relaxed), 9);
+
+		guard.shutdown_and_wait().await;
+		let received = mock.received_requests().await.unwrap();
+		assert_eq!(received.len(), 1);
+	}
+}
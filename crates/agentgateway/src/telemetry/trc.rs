@@ -334,8 +334,16 @@ impl Tracer {
 			_ => "unknown".to_string(),
 		});
 
+		// `error.type` is only set when the request failed after the response started, e.g. an
+		// upstream reset mid-stream, so the status code alone does not reflect it.
+		let error_type = attrs
+			.iter()
+			.find(|(k, _)| *k == "error.type")
+			.and_then(|(_, v)| v.as_ref())
+			.map(|v| v.to_string());
+
 		let out_span = request.outgoing_span.as_ref().unwrap();
-		self.processor.emit(trace_span_data(
+		let mut span = trace_span_data(
 			span_name,
 			SpanKind::Server,
 			out_span,
@@ -343,7 +351,11 @@ impl Tracer {
 			start,
 			end,
 			attributes,
-		));
+		);
+		if let Some(error_type) = error_type {
+			span.status = Status::error(error_type);
+		}
+		self.processor.emit(span);
 	}
 }
 
@@ -842,6 +854,8 @@ mod tests {
 			otlp_fields: LoggingFields::default(),
 			metric_fields: MetricFields::default(),
 			database_fields: LoggingFields::default(),
+			access_log_format: None,
+			llm_summary: false,
 		};
 		let mut registry = Registry::default();
 		let metrics = Arc::new(Metrics::new(&mut registry, Default::default()));
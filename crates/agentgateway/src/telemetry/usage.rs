@@ -0,0 +1,168 @@
+//! In-memory LLM token usage per identity and model.
+//!
+//! Recorded alongside `gen_ai_token_usage`, which cannot carry an identity label without unbounded
+//! cardinality, and queried directly through the admin `/usage` endpoint. Usage is kept in
+//! one-minute buckets for a day.
+
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, VecDeque};
+use std::hash::BuildHasher;
+use std::ops::AddAssign;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::*;
+
+const BUCKET: Duration = Duration::from_secs(60);
+/// How long usage is retained, and so the longest window that can be reported.
+pub const RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+/// Usage is recorded on every LLM response, so the map is split into independently locked shards
+/// to keep concurrent requests from contending on one lock.
+const SHARDS: usize = 16;
+
+type Buckets = HashMap<(Strng, Strng), VecDeque<(Instant, Usage)>>;
+
+#[derive(Debug, Default)]
+pub struct IdentityUsage {
+	hasher: RandomState,
+	shards: [Mutex<Buckets>; SHARDS],
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Usage {
+	pub requests: u64,
+	pub input_tokens: u64,
+	pub output_tokens: u64,
+}
+
+impl AddAssign for Usage {
+	fn add_assign(&mut self, rhs: Usage) {
+		self.requests += rhs.requests;
+		self.input_tokens += rhs.input_tokens;
+		self.output_tokens += rhs.output_tokens;
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+	pub identity: Strng,
+	pub model: Strng,
+	#[serde(flatten)]
+	pub usage: Usage,
+}
+
+impl IdentityUsage {
+	pub fn record(&self, identity: Strng, model: Strng, input_tokens: u64, output_tokens: u64) {
+		self.record_at(
+			Instant::now(),
+			identity,
+			model,
+			Usage {
+				requests: 1,
+				input_tokens,
+				output_tokens,
+			},
+		)
+	}
+
+	fn record_at(&self, now: Instant, identity: Strng, model: Strng, usage: Usage) {
+		let key = (identity, model);
+		let shard = self.hasher.hash_one(&key) as usize % SHARDS;
+		let mut buckets = self.shards[shard].lock();
+		let b = buckets.entry(key).or_default();
+		match b.back_mut() {
+			Some((start, u)) if now.duration_since(*start) < BUCKET => *u += usage,
+			_ => b.push_back((now, usage)),
+		}
+		expire(b, now);
+	}
+
+	/// Usage per identity and model over the last `window`, rounded out to whole buckets. Sorted by
+	/// identity, then model.
+	pub fn report(&self, window: Duration) -> Vec<UsageReport> {
+		self.report_at(Instant::now(), window)
+	}
+
+	fn report_at(&self, now: Instant, window: Duration) -> Vec<UsageReport> {
+		let mut out = Vec::new();
+		for shard in &self.shards {
+			let mut buckets = shard.lock();
+			buckets.retain(|_, b| {
+				expire(b, now);
+				!b.is_empty()
+			});
+			out.extend(buckets.iter().filter_map(|((identity, model), b)| {
+				let mut usage = Usage::default();
+				for (_, u) in b
+					.iter()
+					.rev()
+					.take_while(|(start, _)| now.duration_since(*start) < window + BUCKET)
+				{
+					usage += *u;
+				}
+				(usage.requests > 0).then(|| UsageReport {
+					identity: identity.clone(),
+					model: model.clone(),
+					usage,
+				})
+			}));
+		}
+		out.sort_by(|a, b| (&a.identity, &a.model).cmp(&(&b.identity, &b.model)));
+		out
+	}
+}
+
+fn expire(b: &mut VecDeque<(Instant, Usage)>, now: Instant) {
+	while b
+		.front()
+		.is_some_and(|(start, _)| now.duration_since(*start) >= RETENTION)
+	{
+		b.pop_front();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn usage(requests: u64, input_tokens: u64, output_tokens: u64) -> Usage {
+		Usage {
+			requests,
+			input_tokens,
+			output_tokens,
+		}
+	}
+
+	#[test]
+	fn windows_and_retention() {
+		let u = IdentityUsage::default();
+		let start = Instant::now();
+		let at = |secs| start + Duration::from_secs(secs);
+		u.record_at(at(0), "alice".into(), "gpt".into(), usage(1, 10, 1));
+		u.record_at(at(30), "alice".into(), "gpt".into(), usage(1, 20, 2));
+		u.record_at(at(600), "alice".into(), "gpt".into(), usage(1, 40, 4));
+
+		let now = at(660);
+		assert_eq!(
+			u.report_at(now, Duration::from_secs(60))[0].usage,
+			usage(1, 40, 4)
+		);
+		assert_eq!(
+			u.report_at(now, Duration::from_secs(3600))[0].usage,
+			usage(3, 70, 7)
+		);
+
+		let later = at(30) + RETENTION;
+		assert_eq!(
+			u.report_at(later, RETENTION)[0].usage,
+			usage(1, 40, 4),
+			"buckets older than the retention are dropped"
+		);
+		assert!(u.report_at(later, Duration::from_secs(60)).is_empty());
+		assert!(u.report_at(at(600) + RETENTION, RETENTION).is_empty());
+		assert!(u.shards.iter().all(|s| s.lock().is_empty()));
+	}
+}
@@ -189,6 +189,13 @@ pub fn llm_named_provider(
 		path_override: None,
 		path_prefix: None,
 		tokenize,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: None,
+		request_size: None,
 		policies: None,
 	}
 }
@@ -225,6 +232,13 @@ pub fn custom_llm_backend_with_formats(
 		path_override: None,
 		path_prefix: None,
 		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: 1,
+		request_size: None,
 		inline_policies: vec![],
 	};
 	let providers = EndpointSet::new(vec![vec![(provider.name.clone(), provider)]]);
@@ -711,35 +725,152 @@ impl TestBind {
 		legacy_sse: bool,
 		policies: Vec<BackendTrafficPolicy>,
 		target_policies: Vec<BackendTrafficPolicy>,
+	) -> Self {
+		self.with_mcp_backend_config(b, stateful, legacy_sse, policies, target_policies, |_| {})
+	}
+
+	// Like `with_mcp_backend`, but limits the size of JSON-RPC messages accepted from clients.
+	pub fn with_mcp_backend_max_message_size(self, b: SocketAddr, max_message_size: usize) -> Self {
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.max_message_size = Some(max_message_size)
+		})
+	}
+
+	// Like `with_mcp_backend`, but limits the number of active sessions per identity.
+	pub fn with_mcp_backend_max_sessions_per_identity(self, b: SocketAddr, limit: usize) -> Self {
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.max_sessions_per_identity = Some(limit)
+		})
+	}
+
+	// Like `with_mcp_backend`, but de-duplicates repeated tool calls within the given window.
+	pub fn with_mcp_backend_tool_call_dedup_window(
+		self,
+		b: SocketAddr,
+		stateful: bool,
+		window: std::time::Duration,
+	) -> Self {
+		self.with_mcp_backend_config(b, stateful, false, vec![], vec![], |m| {
+			m.tool_call_dedup_window = Some(window)
+		})
+	}
+
+	// Like `with_mcp_backend`, but limits the tokens each session's tool calls may spend.
+	pub fn with_mcp_backend_session_token_budget(self, b: SocketAddr, budget: u64) -> Self {
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.session_token_budget = Some(budget)
+		})
+	}
+
+	// Like `with_mcp_backend`, but rewrites listed tool descriptions with the given expression.
+	pub fn with_mcp_backend_tool_description(self, b: SocketAddr, expression: &str) -> Self {
+		let expression = Arc::new(crate::cel::Expression::new_strict(expression).unwrap());
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.tool_description = Some(expression)
+		})
+	}
+
+	// Like `with_mcp_backend`, but adds the given tools to `tools/list`.
+	pub fn with_mcp_backend_injected_tools(
+		self,
+		b: SocketAddr,
+		injected_tools: Vec<crate::types::agent::McpInjectedTool>,
+	) -> Self {
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.injected_tools = injected_tools
+		})
+	}
+
+	// Like `with_mcp_backend`, but redacts the given tool argument fields from logs.
+	pub fn with_mcp_backend_sensitive_tool_arguments(
+		self,
+		b: SocketAddr,
+		sensitive_tool_arguments: std::collections::HashMap<String, Vec<String>>,
+	) -> Self {
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.sensitive_tool_arguments = sensitive_tool_arguments
+		})
+	}
+
+	// Like `with_mcp_backend`, but exchanges session ids with clients in the given header.
+	pub fn with_mcp_backend_session_header(self, b: SocketAddr, header: &str) -> Self {
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.session_header = Some(strng::new(header))
+		})
+	}
+
+	// Like `with_mcp_backend` in stateless mode, but reuses upstream initialize handshakes for `ttl`.
+	pub fn with_mcp_backend_initialize_cache_ttl(
+		self,
+		b: SocketAddr,
+		ttl: std::time::Duration,
+	) -> Self {
+		self.with_mcp_backend_config(b, false, false, vec![], vec![], |m| {
+			m.initialize_cache_ttl = Some(ttl)
+		})
+	}
+
+	// Like `with_mcp_backend`, but sets the given correlation headers on upstream requests.
+	pub fn with_mcp_backend_correlation_headers(
+		self,
+		b: SocketAddr,
+		correlation_headers: Vec<crate::types::agent::McpCorrelationHeader>,
+	) -> Self {
+		self.with_mcp_backend_config(b, true, false, vec![], vec![], |m| {
+			m.correlation_headers = correlation_headers
+		})
+	}
+
+	fn with_mcp_backend_config(
+		self,
+		b: SocketAddr,
+		stateful: bool,
+		legacy_sse: bool,
+		policies: Vec<BackendTrafficPolicy>,
+		target_policies: Vec<BackendTrafficPolicy>,
+		configure: impl FnOnce(&mut McpBackend),
 	) -> Self {
 		let opb = Backend::Opaque(
 			ResourceName::new(strng::format!("basic-{}", b), "".into()),
 			Target::Address(b),
 		);
 		let sb = SimpleBackendReference::Backend(strng::format!("/basic-{}", b));
-		let b = Backend::MCP(
-			ResourceName::new(strng::format!("{}", b), "".into()),
-			McpBackend {
-				targets: vec![Arc::new(McpTarget {
-					name: "mcp".into(),
-					spec: if !legacy_sse {
-						McpTargetSpec::Mcp(StreamableHTTPTargetSpec {
-							backend: sb,
-							path: "/mcp".to_string(),
-						})
-					} else {
-						McpTargetSpec::Sse(SseTargetSpec {
-							backend: sb,
-							path: "/sse".to_string(),
-						})
-					},
-				})],
-				stateful,
-				prefix_mode: Default::default(),
-				failure_mode: FailureMode::FailClosed,
-				session_idle_ttl: crate::mcp::DEFAULT_SESSION_IDLE_TTL,
-			},
-		);
+		let mut mcp = McpBackend {
+			targets: vec![Arc::new(McpTarget {
+				name: "mcp".into(),
+				spec: if !legacy_sse {
+					McpTargetSpec::Mcp(StreamableHTTPTargetSpec {
+						backend: sb,
+						path: "/mcp".to_string(),
+					})
+				} else {
+					McpTargetSpec::Sse(SseTargetSpec {
+						backend: sb,
+						path: "/sse".to_string(),
+					})
+				},
+			})],
+			stateful,
+			prefix_mode: Default::default(),
+			failure_mode: FailureMode::FailClosed,
+			notification_routing: Default::default(),
+			session_idle_ttl: crate::mcp::DEFAULT_SESSION_IDLE_TTL,
+			sse_keep_alive: Some(crate::mcp::DEFAULT_SSE_KEEP_ALIVE),
+			session_header: None,
+			initialize_cache_ttl: None,
+			max_message_size: None,
+			max_sessions_per_identity: None,
+			max_targets: None,
+			max_fanout_notifications: None,
+			tool_call_dedup_window: None,
+			session_token_budget: None,
+			sensitive_tool_arguments: Default::default(),
+			correlation_headers: Default::default(),
+			tool_description: None,
+			injected_tools: Default::default(),
+		};
+		configure(&mut mcp);
+		let b = Backend::MCP(ResourceName::new(strng::format!("{}", b), "".into()), mcp);
 		{
 			let mut bw = self.pi.stores.binds.write();
 			bw.insert_backend(
@@ -819,7 +950,21 @@ impl TestBind {
 				stateful,
 				prefix_mode,
 				failure_mode: FailureMode::FailClosed,
+				notification_routing: Default::default(),
 				session_idle_ttl: crate::mcp::DEFAULT_SESSION_IDLE_TTL,
+				sse_keep_alive: Some(crate::mcp::DEFAULT_SSE_KEEP_ALIVE),
+				session_header: None,
+				initialize_cache_ttl: None,
+				max_message_size: None,
+				max_sessions_per_identity: None,
+				max_targets: None,
+				max_fanout_notifications: None,
+				tool_call_dedup_window: None,
+				session_token_budget: None,
+				sensitive_tool_arguments: Default::default(),
+				correlation_headers: Default::default(),
+				tool_description: None,
+				injected_tools: Default::default(),
 			},
 		);
 		{
@@ -1254,6 +1399,7 @@ pub fn setup_proxy_test_with_config(config: crate::Config) -> TestBind {
 		upstream: client.clone(),
 		ca: None,
 
+		llm_in_flight: Default::default(),
 		mcp_state: mcp::App::new(stores.clone(), encoder),
 	});
 	TestBind {
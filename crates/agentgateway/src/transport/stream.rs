@@ -60,6 +60,34 @@ pub struct TLSConnectionInfo {
 	pub negotiated_alpn: Option<Alpn>,
 }
 
+/// Details of the TLS session established with an upstream, surfaced on responses so they can be
+/// attached to the request log.
+#[derive(Debug, Clone)]
+pub struct UpstreamTLSInfo {
+	pub version: &'static str,
+	/// Hex-encoded SHA-256 fingerprint of the upstream leaf certificate.
+	pub peer_certificate_fingerprint: Option<String>,
+}
+
+impl UpstreamTLSInfo {
+	pub fn from_connection(conn: &rustls::CommonState) -> Self {
+		use sha2::{Digest, Sha256};
+		let version = match conn.protocol_version() {
+			Some(rustls::ProtocolVersion::TLSv1_2) => "TLSv1.2",
+			Some(rustls::ProtocolVersion::TLSv1_3) => "TLSv1.3",
+			_ => "unknown",
+		};
+		let peer_certificate_fingerprint = conn
+			.peer_certificates()
+			.and_then(|certs| certs.first())
+			.map(|cert| hex::encode(Sha256::digest(cert.as_ref())));
+		UpstreamTLSInfo {
+			version,
+			peer_certificate_fingerprint,
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct HBONEConnectionInfo {
 	pub hbone_address: SocketAddr,
@@ -144,6 +172,9 @@ impl agent_pool::connect::Connection for Socket {
 			Some(Alpn::Http11) => con = con.negotiated_h1(),
 			_ => {},
 		}
+		if let Some(tls) = self.ext.get::<UpstreamTLSInfo>() {
+			con = con.extra(tls.clone());
+		}
 		con
 	}
 }
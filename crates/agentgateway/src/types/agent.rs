@@ -31,7 +31,7 @@ use crate::http::{
 	HeaderOrPseudo, HeaderValue, ext_authz, ext_proc, filters, health, remoteratelimit, retry,
 	timeout,
 };
-use crate::mcp::{FailureMode, McpAuthorization};
+use crate::mcp::{FailureMode, McpAuthorization, NotificationRouting};
 use crate::proxy::httpproxy::PolicyClient;
 use crate::store::RequestPolicy;
 use crate::telemetry::log::OrderedStringMap;
@@ -976,6 +976,24 @@ impl<'a> From<&'a BackendTarget> for BackendTargetRef<'a> {
 	}
 }
 
+impl BackendTarget {
+	/// Returns the backend name in the same form as `Backend::name`.
+	pub fn name(&self) -> Strng {
+		match self {
+			BackendTarget::Backend {
+				name, namespace, ..
+			} => strng::format!("{namespace}/{name}"),
+			BackendTarget::Service {
+				hostname,
+				port: Some(port),
+				..
+			} => strng::format!("{hostname}:{port}"),
+			BackendTarget::Service { hostname, .. } => hostname.clone(),
+			BackendTarget::Invalid => strng::literal!("invalid"),
+		}
+	}
+}
+
 impl BackendTargetRef<'_> {
 	pub fn strip_section(&self) -> BackendTargetRef {
 		match self {
@@ -1707,12 +1725,139 @@ pub struct McpBackend {
 	/// Behavior when one or more MCP targets fail to initialize or fail during fanout.
 	/// Defaults to `failClosed`.
 	pub failure_mode: FailureMode,
+	/// How client notifications are routed to targets. Defaults to `targeted`.
+	#[serde(default)]
+	pub notification_routing: NotificationRouting,
 	#[serde(with = "crate::serdes::serde_dur")]
 	#[cfg_attr(feature = "schema", schemars(with = "String"))]
 	pub session_idle_ttl: Duration,
+	/// Interval between keep-alive comments on GET and legacy SSE streams. If unset, none are
+	/// sent.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub sse_keep_alive: Option<Duration>,
+	/// Header carrying the session id between clients and the gateway. Defaults to
+	/// `mcp-session-id`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub session_header: Option<Strng>,
+	/// In stateless mode, how long an upstream initialize handshake is reused by later requests.
+	/// If unset, every request initializes the upstreams.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub initialize_cache_ttl: Option<Duration>,
+	/// Maximum size, in bytes, of a JSON-RPC message accepted from clients.
+	/// If unset, the HTTP buffer limit applies.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_message_size: Option<usize>,
+	/// Maximum number of active sessions a single authenticated identity may hold.
+	/// If unset, sessions are not limited.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_sessions_per_identity: Option<usize>,
+	/// Maximum number of targets allowed when multiplexing.
+	/// If unset, the number of targets is not limited.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_targets: Option<usize>,
+	/// Maximum number of notifications forwarded while aggregating a fanout response.
+	/// If unset, notifications are not limited.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_fanout_notifications: Option<usize>,
+	/// How long a completed `tools/call` result is replayed for identical calls by the same
+	/// caller. If unset, tool calls are not de-duplicated.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub tool_call_dedup_window: Option<Duration>,
+	/// Total tokens the tool calls of a session may report spending before further calls are
+	/// rejected. If unset, usage is not limited.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub session_token_budget: Option<u64>,
+	/// Tool argument fields redacted from access logs and traces, keyed by the tool name clients
+	/// call. Guards and the upstream still receive the original values.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	pub sensitive_tool_arguments: HashMap<String, Vec<String>>,
+	/// Correlation headers set on every request sent to the MCP upstreams.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub correlation_headers: Vec<McpCorrelationHeader>,
+	/// Expression rewriting the description of each tool returned by `tools/list`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tool_description: Option<Arc<cel::Expression>>,
+	/// Tools added to `tools/list` by the gateway, regardless of what the targets list.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub injected_tools: Vec<McpInjectedTool>,
+}
+
+/// A tool the gateway lists to clients, whether or not any target serves it.
+#[apply(schema!)]
+pub struct McpInjectedTool {
+	/// Name of the tool, as listed to clients. It is not prefixed with a target name, and
+	/// replaces any target tool listed under the same name.
+	pub name: Strng,
+	/// Description of the tool.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub description: Option<Strng>,
+	/// JSON schema of the tool arguments. Defaults to an object accepting any arguments.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub input_schema: Option<serde_json::Map<String, serde_json::Value>>,
+	/// How calls to the tool are handled.
+	pub handler: McpInjectedToolHandler,
+}
+
+#[apply(schema!)]
+pub enum McpInjectedToolHandler {
+	/// Respond to calls with this text, without contacting any target.
+	Respond(String),
+	/// Forward calls to `tool` on `target`, subject to the same policies as calling it directly.
+	Forward { target: Strng, tool: Strng },
+}
+
+/// A header carried from the incoming request (or generated) onto upstream MCP requests, so a
+/// single id can be followed across MCP hops.
+#[apply(schema!)]
+pub struct McpCorrelationHeader {
+	/// Header to set on upstream MCP requests.
+	pub name: Strng,
+	/// Incoming request header to take the value from. Defaults to `name`, so an id set by an
+	/// earlier hop is propagated. If the incoming request does not have it, a UUID is generated.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub from: Option<Strng>,
+}
+
+impl McpCorrelationHeader {
+	/// Sets the correlation header on `headers`, which hold the incoming request headers.
+	pub fn apply(&self, headers: &mut ::http::HeaderMap) {
+		let Ok(name) = ::http::HeaderName::from_bytes(self.name.as_bytes()) else {
+			return;
+		};
+		let source = self.from.as_ref().unwrap_or(&self.name);
+		let value = headers.get(source.as_str()).cloned().unwrap_or_else(|| {
+			::http::HeaderValue::try_from(uuid::Uuid::new_v4().to_string())
+				.expect("uuid is a valid header value")
+		});
+		headers.insert(name, value);
+	}
 }
 
 impl McpBackend {
+	/// The header carrying the session id to and from clients.
+	pub fn session_header(&self) -> anyhow::Result<::http::HeaderName> {
+		match &self.session_header {
+			Some(h) => ::http::HeaderName::from_bytes(h.as_bytes())
+				.map_err(|e| anyhow::anyhow!("invalid MCP session header {h:?}: {e}")),
+			None => Ok(::http::HeaderName::from_static("mcp-session-id")),
+		}
+	}
+
 	pub fn find(&self, name: &str) -> Option<Arc<McpTarget>> {
 		self
 			.targets
@@ -2117,6 +2262,10 @@ impl RouteSet {
 		a_key.cmp(b_key)
 	}
 
+	pub fn get(&self, key: &RouteKey) -> Option<&Arc<Route>> {
+		self.all.get(key)
+	}
+
 	pub fn contains(&self, key: &RouteKey) -> bool {
 		self.all.contains_key(key)
 	}
@@ -2667,6 +2816,7 @@ pub enum TrafficPolicy {
 	RequestMirror(Vec<filters::RequestMirror>),
 	DirectResponse(RequestPolicy<filters::DirectResponse>),
 	Buffer(RequestPolicy<http::buffer::Buffer>),
+	ContentType(RequestPolicy<http::contenttype::ContentTypeEnforcement>),
 	#[serde(rename = "cors")]
 	CORS(RequestPolicy<http::cors::Cors>),
 }
@@ -554,6 +554,7 @@ fn convert_route_type(proto_rt: i32, diagnostics: &mut Diagnostics) -> llm::Rout
 		Ok(ProtoRT::Embeddings) => llm::RouteType::Embeddings,
 		Ok(ProtoRT::Realtime) => llm::RouteType::Realtime,
 		Ok(ProtoRT::Rerank) => llm::RouteType::Rerank,
+		Ok(ProtoRT::TextCompletions) => llm::RouteType::TextCompletions,
 		Err(_) => {
 			diagnostics.add_warning(format!(
 				"unknown proto RouteType value {}, defaulting to Completions",
@@ -946,7 +947,10 @@ fn convert_backend_ai_policy(
 					.collect::<Result<_, _>>()?,
 			)
 		},
-		prompts: ai.prompts.as_ref().map(convert_prompt_enrichment),
+		prompts: ai
+			.prompts
+			.as_ref()
+			.map(|p| convert_prompt_enrichment(diagnostics, p)),
 		model_aliases: ai
 			.model_aliases
 			.iter()
@@ -959,6 +963,35 @@ fn convert_backend_ai_policy(
 			.iter()
 			.map(|(k, v)| (strng::new(k), convert_route_type(*v, diagnostics)))
 			.collect(),
+		tokenize: None,
+		external_tokenizer: None,
+		tokenize_failure: None,
+		default_params: None,
+		param_limits: None,
+		max_output_tokens_cap: ai.max_output_tokens_cap,
+		usage_trailers: None,
+		normalize_tool_call_ids: None,
+		model_fallbacks: Default::default(),
+		stream_transformation: None,
+		empty_choices: None,
+		deprecated_functions: None,
+		unsupported_logit_bias: None,
+		synthesize_stream_done: None,
+		default_retry_after: None,
+		max_prompt_chars: None,
+		max_json_depth: None,
+		provider_affinity_header: None,
+		streaming_requests: None,
+		echo_upstream_request_id: None,
+		stream_oversized_responses: None,
+		deduplicate_requests: None,
+		guard_modifications_header: None,
+		image_tokens: None,
+		allowed_models: None,
+		denied_models: None,
+		audit_mirror: None,
+		estimate_missing_stream_usage: None,
+		model_deprecation_header: None,
 	};
 
 	// Compile wildcard patterns from model_aliases
@@ -1442,10 +1475,12 @@ pub(crate) fn backend_with_policies_from_proto(
 							model: vertex.model.as_deref().map(strng::new),
 							region: (!vertex.region.is_empty()).then(|| strng::new(&vertex.region)),
 							project_id: strng::new(&vertex.project_id),
+							anthropic_version: None,
 						}),
 						Some(provider::Provider::Anthropic(anthropic)) => {
 							AIProvider::Anthropic(llm::anthropic::Provider {
 								model: anthropic.model.as_deref().map(strng::new),
+								api_version: None,
 							})
 						},
 						Some(provider::Provider::Bedrock(bedrock)) => {
@@ -1454,6 +1489,7 @@ pub(crate) fn backend_with_policies_from_proto(
 								region: strng::new(&bedrock.region),
 								guardrail_identifier: bedrock.guardrail_identifier.as_deref().map(strng::new),
 								guardrail_version: bedrock.guardrail_version.as_deref().map(strng::new),
+								anthropic_version: None,
 							})
 						},
 						Some(provider::Provider::Azure(azure)) => {
@@ -1522,6 +1558,32 @@ pub(crate) fn backend_with_policies_from_proto(
 						)));
 					}
 
+					let retry = provider_config
+						.retry
+						.as_ref()
+						.map(|r| {
+							let max_retries = u8::try_from(r.max_retries).map_err(|_| {
+								ProtoError::Generic(format!(
+									"AI backend provider at index {provider_idx} has invalid retry maxRetries {}",
+									r.max_retries
+								))
+							})?;
+							Ok::<_, ProtoError>(crate::llm::retry::Retry {
+								max_retries,
+								base_backoff: r
+									.base_backoff
+									.as_ref()
+									.map(|d| (*d).try_into())
+									.transpose()?,
+								max_backoff: r
+									.max_backoff
+									.as_ref()
+									.map(|d| (*d).try_into())
+									.transpose()?,
+							})
+						})
+						.transpose()?;
+
 					let np = NamedAIProvider {
 						name: provider_name.clone(),
 						provider,
@@ -1530,6 +1592,19 @@ pub(crate) fn backend_with_policies_from_proto(
 						host_override,
 						path_override: provider_config.path_override.as_ref().map(strng::new),
 						path_prefix: provider_config.path_prefix.as_ref().map(strng::new),
+						user_agent: provider_config
+							.user_agent
+							.as_deref()
+							.map(::http::HeaderValue::from_str)
+							.transpose()
+							.map_err(|e| ProtoError::Generic(format!("invalid user agent: {e}")))?,
+						accept_encoding: None,
+						retry,
+						// Body size limits are not yet exposed via the XDS API.
+						max_request_bytes: None,
+						max_response_bytes: None,
+						weight: 1,
+						request_size: None,
 						inline_policies: pols,
 					};
 					local_provider_group.push((provider_name, np));
@@ -1549,30 +1624,50 @@ pub(crate) fn backend_with_policies_from_proto(
 			let es = crate::types::loadbalancer::EndpointSet::new(provider_groups);
 			Backend::AI(name.into(), AIBackend { providers: es })
 		},
-		Some(proto::agent::backend::Kind::Mcp(m)) => Backend::MCP(
-			name.into(),
-			McpBackend {
-				targets: m
-					.targets
-					.iter()
-					.map(|t| mcp_target_from_proto(t, diagnostics).map(Arc::new))
-					.collect::<Result<Vec<_>, _>>()?,
-				stateful: match m.stateful_mode() {
-					proto::agent::mcp_backend::StatefulMode::Stateful => true,
-					proto::agent::mcp_backend::StatefulMode::Stateless => false,
-				},
-				prefix_mode: match m.prefix_mode() {
-					proto::agent::mcp_backend::PrefixMode::Always => McpPrefixMode::Always,
-					proto::agent::mcp_backend::PrefixMode::Conditional => McpPrefixMode::Conditional,
-					proto::agent::mcp_backend::PrefixMode::Never => McpPrefixMode::Never,
-				},
-				failure_mode: match m.failure_mode() {
-					proto::agent::mcp_backend::FailureMode::FailOpen => FailureMode::FailOpen,
-					proto::agent::mcp_backend::FailureMode::FailClosed => FailureMode::FailClosed,
+		Some(proto::agent::backend::Kind::Mcp(m)) => {
+			if let Some(h) = &m.session_header {
+				::http::HeaderName::from_bytes(h.as_bytes())
+					.map_err(|e| ProtoError::Generic(format!("invalid MCP session header {h:?}: {e}")))?;
+			}
+			Backend::MCP(
+				name.into(),
+				McpBackend {
+					targets: m
+						.targets
+						.iter()
+						.map(|t| mcp_target_from_proto(t, diagnostics).map(Arc::new))
+						.collect::<Result<Vec<_>, _>>()?,
+					stateful: match m.stateful_mode() {
+						proto::agent::mcp_backend::StatefulMode::Stateful => true,
+						proto::agent::mcp_backend::StatefulMode::Stateless => false,
+					},
+					prefix_mode: match m.prefix_mode() {
+						proto::agent::mcp_backend::PrefixMode::Always => McpPrefixMode::Always,
+						proto::agent::mcp_backend::PrefixMode::Conditional => McpPrefixMode::Conditional,
+						proto::agent::mcp_backend::PrefixMode::Never => McpPrefixMode::Never,
+					},
+					failure_mode: match m.failure_mode() {
+						proto::agent::mcp_backend::FailureMode::FailOpen => FailureMode::FailOpen,
+						proto::agent::mcp_backend::FailureMode::FailClosed => FailureMode::FailClosed,
+					},
+					notification_routing: Default::default(),
+					session_idle_ttl: crate::mcp::DEFAULT_SESSION_IDLE_TTL,
+					sse_keep_alive: Some(crate::mcp::DEFAULT_SSE_KEEP_ALIVE),
+					session_header: m.session_header.as_ref().map(strng::new),
+					initialize_cache_ttl: m.initialize_cache_ttl.map(convert_duration),
+					max_message_size: None,
+					max_sessions_per_identity: None,
+					max_targets: None,
+					max_fanout_notifications: None,
+					tool_call_dedup_window: None,
+					session_token_budget: None,
+					sensitive_tool_arguments: Default::default(),
+					correlation_headers: Default::default(),
+					tool_description: None,
+					injected_tools: Default::default(),
 				},
-				session_idle_ttl: crate::mcp::DEFAULT_SESSION_IDLE_TTL,
-			},
-		),
+			)
+		},
 		Some(backend::Kind::Guardrail(_)) => {
 			diagnostics.add_warning("guardrail backends are not yet implemented and will be ignored");
 			Backend::Invalid
@@ -1999,6 +2094,7 @@ fn traffic_policy_from_proto(
 			let attempts = std::num::NonZeroU8::new(r.attempts as u8)
 				.unwrap_or_else(|| std::num::NonZeroU8::new(1).unwrap());
 			let backoff = r.backoff.as_ref().map(|d| (*d).try_into()).transpose()?;
+			let budget = r.budget.as_ref().map(|d| (*d).try_into()).transpose()?;
 			let codes = r
 				.retry_status_codes
 				.iter()
@@ -2025,6 +2121,7 @@ fn traffic_policy_from_proto(
 			TrafficPolicy::Retry(http::retry::Policy {
 				attempts,
 				backoff,
+				budget,
 				codes: codes.into_boxed_slice(),
 				precondition,
 				condition,
@@ -2965,6 +3062,7 @@ fn frontend_policy_from_proto(
 				remove: Arc::new(FzHashSet::new(rm)),
 				otlp,
 				database: None,
+				format: None,
 				access_log_policy: None,
 			};
 			logging_policy.init_access_log_policy();
@@ -3307,6 +3405,7 @@ fn conditional_traffic_policy_to_policy(
 		TrafficPolicy::DirectResponse(_) => build!(DirectResponse),
 		TrafficPolicy::CORS(_) => build!(CORS),
 		TrafficPolicy::Buffer(_) => build!(Buffer),
+		TrafficPolicy::ContentType(_) => build!(ContentType),
 		other => Err(ProtoError::Generic(format!(
 			"conditional traffic policy kind {} is not supported",
 			traffic_policy_kind_name(other)
@@ -3339,6 +3438,7 @@ fn traffic_policy_kind_name(policy: &TrafficPolicy) -> &'static str {
 		TrafficPolicy::RequestMirror(_) => "requestMirror",
 		TrafficPolicy::DirectResponse(_) => "directResponse",
 		TrafficPolicy::Buffer(_) => "buffer",
+		TrafficPolicy::ContentType(_) => "contentType",
 		TrafficPolicy::CORS(_) => "cors",
 	}
 }
@@ -3418,11 +3518,16 @@ fn convert_message(
 }
 
 fn convert_prompt_enrichment(
+	diagnostics: &mut Diagnostics,
 	prompts: &proto::agent::backend_policy_spec::ai::PromptEnrichment,
 ) -> llm::policy::PromptEnrichment {
 	llm::policy::PromptEnrichment {
 		append: prompts.append.iter().map(convert_message).collect(),
 		prepend: prompts.prepend.iter().map(convert_message).collect(),
+		bypass: prompts
+			.bypass
+			.as_ref()
+			.map(|b| permissive_cel_expression_arc(diagnostics, "backend.ai.prompts.bypass", b)),
 	}
 }
 
@@ -3465,6 +3570,10 @@ fn convert_webhook(
 		headers: Default::default(),
 		forward_header_matches,
 		failure_mode,
+		// Concurrency limits are not yet exposed via the XDS API.
+		max_concurrency: None,
+		// Timeouts are not yet exposed via the XDS API.
+		timeout: None,
 	})
 }
 
@@ -4112,9 +4221,14 @@ mod tests {
 					),
 					("/v1/messages".to_string(), RouteType::Messages as i32),
 					("/v1/detect".to_string(), RouteType::Detect as i32),
+					(
+						"/v1/completions".to_string(),
+						RouteType::TextCompletions as i32,
+					),
 				]
 				.into_iter()
 				.collect(),
+				max_output_tokens_cap: Some(4096),
 			})),
 		};
 
@@ -4159,7 +4273,7 @@ mod tests {
 			assert!(transformation_policy.get("system").is_some());
 
 			// Verify routes conversion
-			assert_eq!(ai_policy.routes.len(), 3);
+			assert_eq!(ai_policy.routes.len(), 4);
 			assert_eq!(
 				ai_policy.routes.get("/v1/chat/completions"),
 				Some(&llm::RouteType::Completions)
@@ -4172,6 +4286,11 @@ mod tests {
 				ai_policy.routes.get("/v1/detect"),
 				Some(&llm::RouteType::Detect)
 			);
+			assert_eq!(
+				ai_policy.routes.get("/v1/completions"),
+				Some(&llm::RouteType::TextCompletions)
+			);
+			assert_eq!(ai_policy.max_output_tokens_cap, Some(4096));
 		} else {
 			panic!("Expected AI policy variant");
 		}
@@ -4283,6 +4402,40 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_backend_kind_mcp_session_header() -> Result<(), ProtoError> {
+		let mcp_backend = |session_header: &str| proto::agent::Backend {
+			key: "test-ns/mcp-backend".to_string(),
+			name: Some(proto::agent::ResourceName {
+				name: "mcp-backend".to_string(),
+				namespace: "test-ns".to_string(),
+			}),
+			kind: Some(proto::agent::backend::Kind::Mcp(proto::agent::McpBackend {
+				session_header: Some(session_header.to_string()),
+				initialize_cache_ttl: Some(prost_types::Duration {
+					seconds: 30,
+					nanos: 0,
+				}),
+				..Default::default()
+			})),
+			inline_policies: vec![],
+		};
+
+		let bw =
+			backend_with_policies_from_proto(&mcp_backend("x-session"), &mut Diagnostics::default())?;
+		let Backend::MCP(_, mcp) = &bw.backend else {
+			panic!("Expected Backend::MCP, got {:?}", bw.backend);
+		};
+		assert_eq!(mcp.session_header().unwrap().as_str(), "x-session");
+		assert_eq!(mcp.initialize_cache_ttl, Some(Duration::from_secs(30)));
+
+		assert!(
+			backend_with_policies_from_proto(&mcp_backend("bad header"), &mut Diagnostics::default())
+				.is_err()
+		);
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn test_vertex_provider_empty_region_is_none() -> Result<(), ProtoError> {
 		use proto::agent::ai_backend::Vertex;
@@ -4308,6 +4461,8 @@ mod tests {
 							project_id: "my-project".to_string(),
 						})),
 						inline_policies: vec![],
+						user_agent: None,
+						retry: None,
 					}],
 				}],
 			})),
@@ -4352,6 +4507,8 @@ mod tests {
 							project_id: "my-project".to_string(),
 						})),
 						inline_policies: vec![],
+						user_agent: None,
+						retry: None,
 					}],
 				}],
 			})),
@@ -4371,6 +4528,55 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn test_provider_retry_from_xds() -> Result<(), ProtoError> {
+		use proto::agent::ai_backend::OpenAi;
+		use proto::agent::ai_backend::provider::{Provider, Retry};
+
+		let proto_backend = proto::agent::Backend {
+			key: "test-ns/openai-backend".to_string(),
+			name: Some(proto::agent::ResourceName {
+				name: "openai-backend".to_string(),
+				namespace: "test-ns".to_string(),
+			}),
+			kind: Some(proto::agent::backend::Kind::Ai(proto::agent::AiBackend {
+				provider_groups: vec![proto::agent::ai_backend::ProviderGroup {
+					providers: vec![proto::agent::ai_backend::Provider {
+						name: "openai".to_string(),
+						host_override: None,
+						path_override: None,
+						path_prefix: None,
+						provider_backend: None,
+						provider: Some(Provider::Openai(OpenAi { model: None })),
+						inline_policies: vec![],
+						user_agent: None,
+						retry: Some(Retry {
+							max_retries: 2,
+							base_backoff: Some(prost_types::Duration {
+								seconds: 1,
+								nanos: 0,
+							}),
+							max_backoff: None,
+						}),
+					}],
+				}],
+			})),
+			inline_policies: vec![],
+		};
+
+		let bw = backend_with_policies_from_proto(&proto_backend, &mut Diagnostics::default())?;
+		let Backend::AI(_, ai_backend) = &bw.backend else {
+			panic!("Expected Backend::AI, got {:?}", bw.backend);
+		};
+		let providers = ai_backend.providers.iter();
+		let (provider, _) = providers.iter().next().unwrap();
+		let retry = provider.retry.as_ref().expect("retry should be set");
+		assert_eq!(retry.max_retries, 2);
+		assert_eq!(retry.base_backoff, Some(Duration::from_secs(1)));
+		assert_eq!(retry.max_backoff, None);
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn test_custom_provider_state_from_xds() -> Result<(), ProtoError> {
 		use proto::agent::ai_backend::provider::Provider;
@@ -4414,6 +4620,8 @@ mod tests {
 							provider_override: None,
 						})),
 						inline_policies: vec![],
+						user_agent: None,
+						retry: None,
 					}],
 				}],
 			})),
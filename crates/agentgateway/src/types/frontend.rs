@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use agent_core::strng::Strng;
 use frozen_collections::{FzHashSet, Len};
 use serde::{Deserialize, Serialize};
 
@@ -288,11 +289,28 @@ pub struct LoggingPolicy {
 	/// Database-specific access log settings.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub database: Option<DatabaseLoggingConfig>,
+	/// Access log line format. If unset, the process-wide log format is used.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub format: Option<AccessLogFormat>,
 	#[serde(skip)]
 	#[cfg_attr(feature = "schema", schemars(skip))]
 	pub access_log_policy: Option<Arc<super::agent::AccessLogPolicy>>,
 }
 
+#[apply(schema!)]
+#[derive(PartialEq, Eq)]
+pub enum AccessLogFormat {
+	/// One JSON object per request, regardless of the process-wide log format.
+	Json,
+	/// Apache Common Log Format.
+	Common,
+	/// Apache Combined Log Format: the common format plus the referer and user agent.
+	Combined,
+	/// Text template where `%{name}` is replaced by the access log field `name`, or `-` if unset.
+	/// `%{time}`, `%{http.response_bytes}`, `%{http.referer}` and `%{http.user_agent}` are also available.
+	Template(Strng),
+}
+
 #[apply(schema!)]
 pub struct DatabaseLoggingConfig {
 	/// Database-only fields to add, computed from CEL expressions.
@@ -7,6 +7,7 @@ use arc_swap::ArcSwap;
 use futures_util::SinkExt;
 use indexmap::IndexMap;
 use itertools::Itertools;
+use prometheus_client::metrics::gauge::Gauge;
 use rand::RngExt;
 use rand::distr::Distribution;
 use rand::distr::weighted::WeightedIndex;
@@ -41,6 +42,30 @@ impl<T> EndpointWithInfo<T> {
 	}
 }
 
+/// Why an endpoint was passed over during selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+	/// Ejected by outlier detection after unhealthy responses.
+	Ejected,
+	/// Has a weight of 0, so it only serves traffic when nothing else is available.
+	ZeroWeight,
+	/// In a lower priority group than the one selected from.
+	LowerPriority,
+	/// Has a request size range that excludes the request body.
+	RequestSize,
+}
+
+impl SkipReason {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			SkipReason::Ejected => "ejected",
+			SkipReason::ZeroWeight => "zero_weight",
+			SkipReason::LowerPriority => "lower_priority",
+			SkipReason::RequestSize => "request_size",
+		}
+	}
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum Sampler {
 	/// Every endpoint has the default capacity of 1. No need for weighted sampling.
@@ -526,14 +551,20 @@ impl<T: Clone + Sync + Send + 'static> Default for EndpointSet<T> {
 
 impl<T: Clone + Sync + Send + 'static> EndpointSet<T> {
 	pub fn new(initial_set: Vec<Vec<(EndpointKey, T)>>) -> Self {
+		Self::new_with_capacity(initial_set, |_| 1)
+	}
+	/// Like [`EndpointSet::new`], with each endpoint's sampling capacity given by `capacity`.
+	pub fn new_with_capacity(
+		initial_set: Vec<Vec<(EndpointKey, T)>>,
+		capacity: impl Fn(&T) -> u32,
+	) -> Self {
 		let buckets = initial_set
 			.into_iter()
 			.map(|items| {
-				let active = IndexMap::from_iter(
-					items
-						.into_iter()
-						.map(|(k, v)| (k, EndpointWithInfo::new(v))),
-				);
+				let active = IndexMap::from_iter(items.into_iter().map(|(k, v)| {
+					let cap = capacity(&v);
+					(k, EndpointWithInfo::with_capacity(v, cap))
+				}));
 				let eg = EndpointGroup::from_pools(active, IndexMap::new());
 				Arc::new(ArcSwap::new(Arc::new(eg)))
 			})
@@ -591,6 +622,38 @@ impl<T: Clone + Sync + Send + 'static> EndpointSet<T> {
 		})
 	}
 
+	/// Endpoints that selection currently passes over, and why. Intended for diagnostics; it walks
+	/// every bucket, so only call it when the result will be used.
+	pub fn skipped(&self) -> Vec<(EndpointKey, SkipReason)> {
+		let buckets = self.buckets.iter().map(|b| b.load_full()).collect_vec();
+		let best = buckets
+			.iter()
+			.position(|b| !b.active.is_empty())
+			.unwrap_or(0);
+		let mut skipped = Vec::new();
+		for (i, b) in buckets.iter().enumerate() {
+			if i != best {
+				skipped.extend(
+					b.active
+						.keys()
+						.map(|k| (k.clone(), SkipReason::LowerPriority)),
+				);
+			} else if !b.sampler.is_drained() {
+				skipped.extend(
+					b.active
+						.iter()
+						.filter(|(_, ep)| ep.capacity == 0)
+						.map(|(k, _)| (k.clone(), SkipReason::ZeroWeight)),
+				);
+			}
+			// The best bucket falls back to its rejected endpoints when none are active.
+			if i != best || !b.active.is_empty() {
+				skipped.extend(b.rejected.keys().map(|k| (k.clone(), SkipReason::Ejected)));
+			}
+		}
+		skipped
+	}
+
 	fn best_bucket(&self) -> Arc<EndpointGroup<T>> {
 		// find the first bucket with healthy endpoints
 		self
@@ -628,6 +691,22 @@ impl<T: Clone + Sync + Send + 'static> EndpointSet<T> {
 		ActiveEndpointsIter(self.best_bucket())
 	}
 
+	/// Every endpoint across all buckets, both active and rejected.
+	pub fn all_endpoints(&self) -> Vec<Arc<T>> {
+		self
+			.buckets
+			.iter()
+			.flat_map(|b| {
+				let bb = b.load_full();
+				bb.active
+					.values()
+					.chain(bb.rejected.values())
+					.map(|ewi| ewi.endpoint.clone())
+					.collect_vec()
+			})
+			.collect()
+	}
+
 	/// Visit every endpoint, returning the first `Some` produced by `f`. Active
 	/// endpoints from all buckets are visited before any rejected endpoint, e.g.:
 	///   active in bucket 0
@@ -908,6 +987,7 @@ impl EndpointInfo {
 			tx: tx_sender,
 			eviction_starter,
 			counter: self.pending_requests.0.clone(),
+			in_flight: None,
 		}
 	}
 }
@@ -959,9 +1039,25 @@ pub struct ActiveHandle {
 	eviction_starter: Arc<dyn EvictionStarter>,
 	#[allow(dead_code)]
 	counter: Arc<()>,
+	in_flight: Option<Arc<InFlightGauge>>,
+}
+
+// Decrements the gauge once the last clone of the handle holding it is dropped.
+#[derive(Debug)]
+struct InFlightGauge(Gauge);
+
+impl Drop for InFlightGauge {
+	fn drop(&mut self) {
+		self.0.dec();
+	}
 }
 
 impl ActiveHandle {
+	/// Counts this request in `gauge` until the handle, and all of its clones, are dropped.
+	pub fn track_in_flight(&mut self, gauge: Gauge) {
+		gauge.inc();
+		self.in_flight = Some(Arc::new(InFlightGauge(gauge)));
+	}
 	/// Current endpoint health score (0.0–1.0) for eviction threshold checks.
 	pub fn health_score(&self) -> f64 {
 		self.info.health_score()
@@ -1240,6 +1336,25 @@ mod tests {
 		assert_eq!(ep_info.health_score(), 1.0, "health should be reset to 1.0");
 	}
 
+	#[test]
+	fn active_handle_tracks_in_flight_gauge() {
+		let key: Strng = "ep1".into();
+		let eps = EndpointSet::new(vec![vec![(key.clone(), "backend1")]]);
+		let info = eps.best_bucket().active.get(&key).unwrap().info.clone();
+		let gauge = Gauge::default();
+
+		let mut handle = eps.start_request(key.clone(), &info);
+		handle.track_in_flight(gauge.clone());
+		assert_eq!(gauge.get(), 1);
+
+		// Clones share the same in-flight request
+		let clone = handle.clone();
+		drop(handle);
+		assert_eq!(gauge.get(), 1);
+		drop(clone);
+		assert_eq!(gauge.get(), 0);
+	}
+
 	#[tokio::test]
 	async fn endpoint_set_repeated_failure_during_window_does_not_bump_times_ejected() {
 		let key: Strng = "ep1".into();
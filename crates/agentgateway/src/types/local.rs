@@ -20,20 +20,23 @@ use crate::http::backendtls::{LocalBackendTLS, ResolvedBackendTLS};
 use crate::http::transformation_cel::{LocalTransformationConfig, Transformation};
 use crate::http::{filters, health, retry, timeout, transformation_cel};
 use crate::llm::policy::{PromptCachingConfig, PromptGuard};
-use crate::llm::{AIBackend, AIProvider, NamedAIProvider, anthropic, copilot, custom, openai};
-use crate::mcp::{FailureMode, McpAuthorization};
+use crate::llm::{
+	AIBackend, AIProvider, NamedAIProvider, anthropic, copilot, custom, mistral, openai,
+};
+use crate::mcp::{FailureMode, McpAuthorization, NotificationRouting};
 use crate::store::{LocalWorkload, RequestPolicy};
 use crate::types::agent::{
 	A2aPolicy, Authorization, Backend, BackendKey, BackendReference, BackendTrafficPolicy,
 	BackendWithPolicies, Bind, BindMode, BindProtocol, FrontendPolicy, HeaderMatch,
 	JwtAuthentication, Listener, ListenerKey, ListenerName, ListenerProtocol, ListenerSet,
-	ListenerTarget, LocalMcpAuthentication, McpAuthentication, McpBackend, McpPrefixMode, McpTarget,
-	McpTargetName, McpTargetSpec, OpenAPITarget, PathMatch, PolicyPhase, PolicyTarget, PolicyType,
-	ResourceName, Route, RouteBackendReference, RouteBackendTarget, RouteGroupKey, RouteMatch,
-	RouteName, ServerTLSConfig, SimpleBackend, SimpleBackendReference,
-	SimpleBackendReferenceWithPolicies, SimpleBackendWithPolicies, SseTargetSpec,
-	StreamableHTTPTargetSpec, TCPRoute, TCPRouteBackendReference, Target, TargetedPolicy,
-	TracingConfig, TrafficPolicy, TunnelProtocol, TypedResourceName, validate_mcp_target_name,
+	ListenerTarget, LocalMcpAuthentication, McpAuthentication, McpBackend, McpCorrelationHeader,
+	McpInjectedTool, McpInjectedToolHandler, McpPrefixMode, McpTarget, McpTargetName, McpTargetSpec,
+	OpenAPITarget, PathMatch, PolicyPhase, PolicyTarget, PolicyType, ResourceName, Route,
+	RouteBackendReference, RouteBackendTarget, RouteGroupKey, RouteMatch, RouteName, ServerTLSConfig,
+	SimpleBackend, SimpleBackendReference, SimpleBackendReferenceWithPolicies,
+	SimpleBackendWithPolicies, SseTargetSpec, StreamableHTTPTargetSpec, TCPRoute,
+	TCPRouteBackendReference, Target, TargetedPolicy, TracingConfig, TrafficPolicy, TunnelProtocol,
+	TypedResourceName, validate_mcp_target_name,
 };
 use crate::types::discovery::{NamespacedHostname, Service};
 use crate::types::{backend, frontend};
@@ -189,6 +192,7 @@ fn merge_deprecated_frontend_policies(
 			remove: log.fields.remove.clone(),
 			otlp: None,
 			database: None,
+			format: None,
 			access_log_policy: None,
 		});
 	}
@@ -757,6 +761,11 @@ pub struct LocalLLMModels {
 	/// In this mode, requests must be sent in the native format of the provider.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	passthrough: Option<LocalLLMPassthrough>,
+	/// routes overrides the route type inferred from the request path, keyed by path suffix ("*" matches any path).
+	/// Entries take precedence over the built-in path mapping and `passthrough`, so unusual provider paths
+	/// (for example, a custom `/v1/chat`) can be classified correctly.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	routes: HashMap<Strng, crate::llm::RouteType>,
 	/// authorization configures HTTP authorization rules for requests to this model.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	authorization: Option<Authorization>,
@@ -797,6 +806,9 @@ pub struct LocalLLMModels {
 	/// promptCaching configures cache point insertion for supported LLM providers.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	prompt_caching: Option<PromptCachingConfig>,
+	/// maxOutputTokensCap caps the output tokens requested from this model, replacing any larger client value.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	max_output_tokens_cap: Option<u64>,
 
 	/// matches specifies the conditions under which this model should be used in addition to matching the model name.
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -841,6 +853,7 @@ pub enum LocalModelAIProvider {
 	Bedrock,
 	Azure,
 	Copilot,
+	Mistral,
 	Custom(custom::Provider),
 	// Providers below are synthetic conversions to custom with preconfigured defaults.
 	Cohere,
@@ -851,7 +864,6 @@ pub enum LocalModelAIProvider {
 	Deepseek,
 	Groq,
 	Huggingface,
-	Mistral,
 	Openrouter,
 	Togetherai,
 	XAI,
@@ -911,6 +923,12 @@ pub struct LocalLLMParams {
 	/// Whether to tokenize the request before forwarding it upstream.
 	#[serde(default)]
 	tokenize: bool,
+	/// User-Agent sent to the provider. Defaults to `agentgateway/<version>`.
+	#[serde(default)]
+	user_agent: Option<Strng>,
+	/// Retry failed provider calls with jittered exponential backoff.
+	#[serde(default)]
+	retry: Option<crate::llm::retry::Retry>,
 }
 
 impl LocalLLMModels {
@@ -931,6 +949,8 @@ impl LocalLLMModels {
 			path_override: None,
 			path_prefix: None,
 			tokenize: false,
+			user_agent: None,
+			retry: None,
 		} = std::mem::take(&mut self.params)
 		else {
 			bail!(
@@ -1023,12 +1043,6 @@ impl LocalLLMModels {
 					.base_url
 					.get_or_insert_with(|| strng::new("https://router.huggingface.co/v1"));
 			},
-			LocalModelAIProvider::Mistral => {
-				self
-					.params
-					.base_url
-					.get_or_insert_with(|| strng::new("https://api.mistral.ai/v1"));
-			},
 			LocalModelAIProvider::Openrouter => {
 				self
 					.params
@@ -1530,7 +1544,8 @@ pub struct LocalNamedAIProvider {
 	pub provider: AIProvider,
 	/// Override the upstream host for this provider.
 	pub host_override: Option<Target>,
-	/// Override the upstream path for this provider.
+	/// Override the upstream path for this provider. May contain `{model}`, `{region}`,
+	/// `{project}` and `{version}` placeholders, resolved from the request and provider.
 	pub path_override: Option<Strng>,
 	/// Override the default base path prefix for this provider.
 	pub path_prefix: Option<Strng>,
@@ -1539,6 +1554,33 @@ pub struct LocalNamedAIProvider {
 	/// This comes with the cost of an expensive operation.
 	#[serde(default)]
 	pub tokenize: bool,
+	/// User-Agent sent to this provider. Defaults to `agentgateway/<version>`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub user_agent: Option<Strng>,
+	/// Accept-Encoding sent to this provider in place of the client's, such as `identity` to skip
+	/// decompression on latency sensitive routes. Only `identity`, `gzip`, `deflate`, `br` and `zstd`
+	/// are allowed, as the gateway must be able to decompress the response.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub accept_encoding: Option<Strng>,
+	/// Retry failed calls to this provider: `5xx` responses, and `429` responses carrying
+	/// `retry-after`, with jittered exponential backoff.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub retry: Option<crate::llm::retry::Retry>,
+	/// Maximum size of a request body buffered for this provider. Defaults to 2 MiB.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_request_bytes: Option<usize>,
+	/// Maximum size of a response body buffered from this provider. Defaults to 2 MiB.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_response_bytes: Option<usize>,
+	/// Relative share of traffic sent to this provider within its group. Defaults to 1.
+	/// A weight of 0 is only used when every other provider in the group is unhealthy.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub weight: Option<u32>,
+	/// Only select this provider for requests whose body size falls within this range, such as
+	/// sending large embedding batches to a high-throughput provider. The size is read from the
+	/// request's `content-length`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub request_size: Option<crate::llm::RequestSizeRange>,
 	/// Backend policies applied to traffic to this provider.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub policies: Option<LocalBackendPolicies>,
@@ -1563,6 +1605,14 @@ impl LocalAIBackend {
 					p.policies.as_ref(),
 					InferenceRoutingScope::AIProviderPolicies,
 				)?;
+				if let Some(ae) = &p.accept_encoding
+					&& !http::compression::is_decodable_accept_encoding(ae)
+				{
+					bail!(
+						"provider {} acceptEncoding {ae} lists a coding that cannot be decompressed",
+						p.name
+					);
+				}
 				let policies = match p.policies {
 					Some(p) => p.translate(resources).await?,
 					None => Vec::new(),
@@ -1577,13 +1627,25 @@ impl LocalAIBackend {
 						path_override: p.path_override,
 						path_prefix: p.path_prefix,
 						tokenize: p.tokenize,
+						user_agent: p
+							.user_agent
+							.as_deref()
+							.map(::http::HeaderValue::from_str)
+							.transpose()
+							.map_err(|e| anyhow!("provider {} has an invalid userAgent: {e}", p.name))?,
+						accept_encoding: p.accept_encoding,
+						retry: p.retry,
+						max_request_bytes: p.max_request_bytes,
+						max_response_bytes: p.max_response_bytes,
+						weight: p.weight.unwrap_or(1),
+						request_size: p.request_size,
 						inline_policies: policies,
 					},
 				));
 			}
 			ep_groups.push(group);
 		}
-		let es = types::loadbalancer::EndpointSet::new(ep_groups);
+		let es = types::loadbalancer::EndpointSet::new_with_capacity(ep_groups, |p| p.weight);
 		Ok(AIBackend { providers: es })
 	}
 }
@@ -1751,12 +1813,58 @@ impl LocalBackend {
 					McpStatefulMode::Stateless => false,
 					McpStatefulMode::Stateful => true,
 				};
+				if let Some(max) = tgt.max_targets
+					&& targets.len() > max
+				{
+					anyhow::bail!(
+						"MCP backend has {} targets, exceeding maxTargets of {max}",
+						targets.len()
+					);
+				}
+				if let Some(h) = &tgt.session_header {
+					::http::HeaderName::from_bytes(h.as_bytes())
+						.map_err(|e| anyhow!("invalid MCP session header {h:?}: {e}"))?;
+				}
+				for h in &tgt.correlation_headers {
+					for header in std::iter::once(&h.name).chain(h.from.as_ref()) {
+						::http::HeaderName::from_bytes(header.as_bytes())
+							.map_err(|e| anyhow!("invalid MCP correlation header {header:?}: {e}"))?;
+					}
+				}
+				for injected in &tgt.injected_tools {
+					if let McpInjectedToolHandler::Forward { target, .. } = &injected.handler
+						&& !targets.iter().any(|t| t.name == *target)
+					{
+						anyhow::bail!(
+							"MCP injected tool {:?} forwards to unknown target {target:?}",
+							injected.name
+						);
+					}
+				}
 				let m = McpBackend {
 					targets,
 					stateful,
 					prefix_mode: tgt.prefix_mode.unwrap_or_default(),
 					failure_mode: tgt.failure_mode.unwrap_or_default(),
+					notification_routing: tgt.notification_routing.unwrap_or_default(),
 					session_idle_ttl: mcp_session_ttl,
+					sse_keep_alive: match tgt.sse_keep_alive {
+						None => Some(crate::mcp::DEFAULT_SSE_KEEP_ALIVE),
+						Some(d) if d.is_zero() => None,
+						d => d,
+					},
+					session_header: tgt.session_header.clone(),
+					initialize_cache_ttl: tgt.initialize_cache_ttl,
+					max_message_size: tgt.max_message_size,
+					max_sessions_per_identity: tgt.max_sessions_per_identity,
+					max_targets: tgt.max_targets,
+					max_fanout_notifications: tgt.max_fanout_notifications,
+					tool_call_dedup_window: tgt.tool_call_dedup_window,
+					session_token_budget: tgt.session_token_budget,
+					sensitive_tool_arguments: tgt.sensitive_tool_arguments.clone(),
+					correlation_headers: tgt.correlation_headers.clone(),
+					tool_description: tgt.tool_description.clone(),
+					injected_tools: tgt.injected_tools.clone(),
 				};
 				backends.push(Backend::MCP(name, m).into());
 				backends
@@ -1823,6 +1931,93 @@ pub struct LocalMcpBackend {
 	/// Defaults to `failClosed`.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub failure_mode: Option<FailureMode>,
+	/// How client notifications are routed when multiplexing. With `targeted` (the default), a
+	/// `notifications/cancelled` is sent only to the target serving the cancelled request, and
+	/// other notifications, or cancellations of unknown requests, fan out to every target. With
+	/// `fanout`, every notification goes to every target.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub notification_routing: Option<NotificationRouting>,
+	/// Interval between keep-alive comments sent on GET and legacy SSE streams, so idle
+	/// connections are not dropped by clients or intermediaries. Defaults to `30s`; `0s` disables
+	/// them.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub sse_keep_alive: Option<Duration>,
+	/// Header carrying the session id between clients and the gateway, for clients or
+	/// intermediaries that cannot use the default `mcp-session-id`. Upstream targets still receive
+	/// their own session ids in `mcp-session-id`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub session_header: Option<Strng>,
+	/// In stateless mode, reuse the initialize handshake with a target for this long instead of
+	/// repeating it before every request. Only targets that assign no session id are cached, per
+	/// protocol version; a failed request drops the cached handshake. Stdio and SSE targets are
+	/// always initialized. If unset, every request initializes the targets.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub initialize_cache_ttl: Option<Duration>,
+	/// Maximum size, in bytes, of a JSON-RPC message accepted from clients.
+	/// Oversized messages are rejected with a JSON-RPC `invalid request` error.
+	/// If unset, the HTTP buffer limit applies.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_message_size: Option<usize>,
+	/// Maximum number of active sessions a single authenticated identity (JWT subject, basic auth
+	/// username, or API key) may hold. Further `initialize` requests from that identity are
+	/// rejected until one of its sessions ends. Unauthenticated sessions are not limited.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_sessions_per_identity: Option<usize>,
+	/// Maximum number of targets allowed when multiplexing, bounding the fanout cost of each
+	/// session. Configurations with more targets are rejected.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_targets: Option<usize>,
+	/// Maximum number of notifications, such as progress updates, forwarded while aggregating the
+	/// responses of a request fanned out to every target. An upstream exceeding it ends the
+	/// response with an error. If unset, notifications are not limited.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_fanout_notifications: Option<usize>,
+	/// De-duplicate repeated `tools/call` requests from the same caller for this long, across
+	/// sessions and stateless requests. Calls sharing an `idempotency-key` header, or with
+	/// identical tool and arguments when the header is absent, are sent upstream once; duplicates
+	/// wait for and receive the first call's result.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "serde_dur_option"
+	)]
+	#[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+	pub tool_call_dedup_window: Option<Duration>,
+	/// Maximum total tokens the tool calls of a session may spend, as reported by tools in
+	/// `_meta.usage` of their results (`total_tokens`, or `input_tokens` plus `output_tokens`).
+	/// Once reached, further tool calls in the session are rejected.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub session_token_budget: Option<u64>,
+	/// Tool argument fields to redact from access logs and traces, keyed by the tool name clients
+	/// call (including any target prefix). Values are replaced with `<redacted>`; guards and the
+	/// upstream still see the original arguments.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	pub sensitive_tool_arguments: HashMap<String, Vec<String>>,
+	/// Headers to set on requests sent to the MCP targets, for following a request across MCP
+	/// hops. Each value is copied from the incoming request (the `from` header, defaulting to
+	/// `name`) or generated as a UUID when absent.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub correlation_headers: Vec<McpCorrelationHeader>,
+	/// CEL expression rewriting the description of each tool returned by `tools/list`, for example
+	/// to append a notice. `mcp.tool.description`, `mcp.tool.name`, and `mcp.tool.target` describe
+	/// the tool; the result must be a string. It is applied after authorization filtering.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tool_description: Option<Arc<cel::Expression>>,
+	/// Tools the gateway adds to `tools/list`, whatever the targets list, such as a tool for
+	/// reporting unsafe output. Each is either answered by the gateway (`respond`) or forwarded
+	/// to a tool on one of the targets (`forward`).
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub injected_tools: Vec<McpInjectedTool>,
 }
 
 #[apply(schema_de!)]
@@ -2600,6 +2795,7 @@ impl LocalBackendPolicies {
 			pols.push(BackendTrafficPolicy::Authorization(p))
 		}
 		if let Some(mut p) = ai {
+			p.validate_default_params()?;
 			p.compile_model_alias_patterns();
 			pols.push(BackendTrafficPolicy::AI(Arc::new(p)))
 		}
@@ -2776,6 +2972,9 @@ pub struct FilterOrPolicy {
 	/// Buffer request and response bodies.
 	#[serde(default)]
 	buffer: Option<http::buffer::Buffer>,
+	/// Reject requests and upstream responses with unexpected content types.
+	#[serde(default)]
+	content_type: Option<http::contenttype::ContentTypeEnforcement>,
 	/// Set request timeout limits.
 	#[serde(default)]
 	timeout: Option<timeout::Policy>,
@@ -4055,6 +4254,10 @@ fn llm_route_types(
 			strng::new("/v1/embeddings"),
 			crate::llm::RouteType::Embeddings,
 		),
+		(
+			strng::new("/v1/completions"),
+			crate::llm::RouteType::TextCompletions,
+		),
 		(strng::new("/v1/rerank"), crate::llm::RouteType::Rerank),
 		(strng::new("/v2/rerank"), crate::llm::RouteType::Rerank),
 		(strng::new("*"), crate::llm::RouteType::Passthrough),
@@ -4067,6 +4270,7 @@ fn ensure_ai_provider_model(provider: &mut AIProvider, model: &str) {
 		AIProvider::Anthropic(p) => p.model = p.model.clone().or_else(model),
 		AIProvider::OpenAI(p) => p.model = p.model.clone().or_else(model),
 		AIProvider::Copilot(p) => p.model = p.model.clone().or_else(model),
+		AIProvider::Mistral(p) => p.model = p.model.clone().or_else(model),
 		AIProvider::Gemini(p) => p.model = p.model.clone().or_else(model),
 		AIProvider::Custom(p) => p.model = p.model.clone().or_else(model),
 		AIProvider::Vertex(p) => p.model = p.model.clone().or_else(model),
@@ -4202,7 +4406,8 @@ async fn convert_llm_config(
 		};
 		let p = model_config.params.clone();
 		let model = p.model;
-		let llm_routes = llm_route_types(model_config.passthrough.as_ref());
+		let mut llm_routes = llm_route_types(model_config.passthrough.as_ref());
+		llm_routes.extend(model_config.routes.iter().map(|(k, v)| (k.clone(), *v)));
 
 		// Use provider from config and set the model name
 		let provider = match &model_config.provider {
@@ -4213,9 +4418,13 @@ async fn convert_llm_config(
 					reference
 				)
 			},
-			LocalModelAIProvider::Anthropic => AIProvider::Anthropic(anthropic::Provider { model }),
+			LocalModelAIProvider::Anthropic => AIProvider::Anthropic(anthropic::Provider {
+				model,
+				api_version: None,
+			}),
 			LocalModelAIProvider::OpenAI => AIProvider::OpenAI(openai::Provider { model }),
 			LocalModelAIProvider::Copilot => AIProvider::Copilot(copilot::Provider { model }),
+			LocalModelAIProvider::Mistral => AIProvider::Mistral(mistral::Provider { model }),
 			LocalModelAIProvider::Gemini => AIProvider::Gemini(crate::llm::gemini::Provider { model }),
 			LocalModelAIProvider::Custom(custom_provider) => {
 				if custom_provider.formats.is_empty() {
@@ -4314,14 +4523,6 @@ async fn convert_llm_config(
 					custom_provider_format(custom::ProviderFormat::Responses, None),
 				],
 			}),
-			LocalModelAIProvider::Mistral => AIProvider::Custom(custom::Provider {
-				model,
-				provider_override: Some(strng::literal!("mistral")),
-				formats: vec![
-					custom_provider_format(custom::ProviderFormat::Completions, None),
-					custom_provider_format(custom::ProviderFormat::Embeddings, None),
-				],
-			}),
 			LocalModelAIProvider::Openrouter => AIProvider::Custom(custom::Provider {
 				model,
 				provider_override: Some(strng::literal!("openrouter")),
@@ -4366,12 +4567,14 @@ async fn convert_llm_config(
 				model,
 				region: p.vertex_region,
 				project_id: p.vertex_project.context("vertex requires vertex_project")?,
+				anthropic_version: None,
 			}),
 			LocalModelAIProvider::Bedrock => AIProvider::bedrock(crate::llm::bedrock::Provider {
 				model,
 				region: p.aws_region.context("bedrock requires aws_region")?,
 				guardrail_identifier: None,
 				guardrail_version: None,
+				anthropic_version: None,
 			}),
 			LocalModelAIProvider::Azure => AIProvider::azure(crate::llm::azure::Provider {
 				model,
@@ -4405,6 +4608,18 @@ async fn convert_llm_config(
 			path_override: p.path_override,
 			path_prefix: p.path_prefix,
 			tokenize: p.tokenize,
+			user_agent: p
+				.user_agent
+				.as_deref()
+				.map(::http::HeaderValue::from_str)
+				.transpose()
+				.map_err(|e| anyhow!("model {model_name} has an invalid userAgent: {e}"))?,
+			accept_encoding: None,
+			retry: p.retry,
+			max_request_bytes: None,
+			max_response_bytes: None,
+			weight: 1,
+			request_size: None,
 			inline_policies: pols,
 		};
 		let resolved_provider = named_provider.clone();
@@ -4454,6 +4669,35 @@ async fn convert_llm_config(
 			wildcard_patterns: Arc::new(vec![]),
 			prompt_caching: model_config.prompt_caching.clone(),
 			routes: Default::default(),
+			tokenize: None,
+			external_tokenizer: None,
+			tokenize_failure: None,
+			default_params: None,
+			param_limits: None,
+			max_output_tokens_cap: model_config.max_output_tokens_cap,
+			usage_trailers: None,
+			normalize_tool_call_ids: None,
+			model_fallbacks: Default::default(),
+			stream_transformation: None,
+			empty_choices: None,
+			deprecated_functions: None,
+			unsupported_logit_bias: None,
+			synthesize_stream_done: None,
+			default_retry_after: None,
+			max_prompt_chars: None,
+			max_json_depth: None,
+			provider_affinity_header: None,
+			streaming_requests: None,
+			echo_upstream_request_id: None,
+			stream_oversized_responses: None,
+			deduplicate_requests: None,
+			guard_modifications_header: None,
+			image_tokens: None,
+			allowed_models: None,
+			denied_models: None,
+			audit_mirror: None,
+			estimate_missing_stream_usage: None,
+			model_deprecation_header: None,
 		})));
 		let resolved_inline_policies = pols.clone();
 		let backend_with_policies = BackendWithPolicies {
@@ -5166,6 +5410,7 @@ pub(crate) async fn split_policies_for_target(
 		ext_authz,
 		ext_proc,
 		buffer,
+		content_type,
 		timeout,
 		retry,
 		delay,
@@ -5250,6 +5495,7 @@ pub(crate) async fn split_policies_for_target(
 
 	// Route policies (AI is dual-role when targeting a backend)
 	if let Some(mut p) = ai {
+		p.validate_default_params()?;
 		p.compile_model_alias_patterns();
 		if backend_target {
 			backend_policies.push(BackendTrafficPolicy::AI(Arc::new(p)));
@@ -5346,6 +5592,9 @@ pub(crate) async fn split_policies_for_target(
 	if let Some(p) = buffer {
 		route_policies.push(TrafficPolicy::Buffer(RequestPolicy::single(p)));
 	}
+	if let Some(p) = content_type {
+		route_policies.push(TrafficPolicy::ContentType(RequestPolicy::single(p)));
+	}
 	if let Some(p) = timeout {
 		route_policies.push(TrafficPolicy::Timeout(p));
 	}
@@ -879,6 +879,51 @@ llm:
 	assert_eq!(provider.path_prefix.as_deref(), Some("/inference/v1"));
 }
 
+#[tokio::test]
+async fn test_llm_route_override_wins_over_path_inference() {
+	let normalized = normalize_test_config(
+		r#"
+llm:
+  models:
+  - name: custom-path
+    provider: openAI
+    passthrough: detect
+    routes:
+      /v1/chat: completions
+"#,
+	)
+	.await
+	.expect("LLM model with route overrides should normalize");
+
+	let router = normalized
+		.backends
+		.iter()
+		.find_map(|backend| match &backend.backend {
+			Backend::LLMRouter(_, router) => Some(router.clone()),
+			_ => None,
+		})
+		.expect("expected LLMRouter backend");
+	let resolve = |path: &'static str| {
+		let router = router.clone();
+		async move {
+			let mut req = ::http::Request::builder()
+				.method("POST")
+				.uri(path)
+				.header(::http::header::CONTENT_TYPE, "application/json")
+				.body(http::Body::from(r#"{"model":"custom-path"}"#))
+				.unwrap();
+			let llm::model_router::ResolveResult::Backend(resolved) = router.resolve(&mut req).await
+			else {
+				panic!("expected model to resolve to a backend");
+			};
+			resolved.llm_policy.resolve_route(path)
+		}
+	};
+
+	assert_eq!(resolve("/v1/chat").await, llm::RouteType::Completions);
+	assert_eq!(resolve("/v1/other").await, llm::RouteType::Detect);
+}
+
 #[tokio::test]
 async fn test_mcp_simple_config() {
 	test_config_parsing("mcp_simple").await;
@@ -1629,6 +1674,73 @@ binds:
 	assert_eq!(buffer.response.as_ref().unwrap().max_bytes, Some(20));
 }
 
+#[tokio::test]
+async fn route_local_rate_limit_buckets_survive_reload() {
+	use crate::store::{BindPreviousState, BindStore, BindStoreUpdater};
+
+	let config = |max_tokens: u64| {
+		format!(
+			r#"
+binds:
+- port: 3000
+  listeners:
+  - routes:
+    - policies:
+        localRateLimit:
+        - maxTokens: {max_tokens}
+          tokensPerFill: 1
+          fillInterval: 1h
+      backends:
+      - host: 127.0.0.1:8000
+"#
+		)
+	};
+	let updater = BindStoreUpdater::new(Arc::new(std::sync::RwLock::new(BindStore::default())));
+	let reload = |prev, cfg: NormalizedLocalConfig| {
+		updater.sync_local(
+			cfg.binds,
+			cfg.listener_routes,
+			cfg.listener_tcp_routes,
+			cfg.policies,
+			cfg.backends,
+			cfg.route_groups,
+			prev,
+		)
+	};
+	let remaining = || {
+		let limits = updater.rate_limits();
+		let (_, statuses) = limits.routes.iter().next().expect("route rate limit");
+		statuses[0].remaining
+	};
+
+	let cfg = normalize_test_yaml(&config(10)).await.unwrap();
+	// Limits share their bucket with clones, so drain it through the route we keep.
+	let route = cfg.listener_routes[0].1[0].clone();
+	let prev = reload(BindPreviousState::default(), cfg);
+	let rl = route
+		.inline_policies
+		.iter()
+		.find_map(|p| match p {
+			TrafficPolicy::LocalRateLimit(p) => p.iter().next().map(|e| e.pol[0].clone()),
+			_ => None,
+		})
+		.expect("expected local rate limit");
+	for _ in 0..4 {
+		rl.check_request().unwrap();
+	}
+	assert_eq!(remaining(), 6);
+
+	let prev = reload(prev, normalize_test_yaml(&config(10)).await.unwrap());
+	assert_eq!(remaining(), 6, "identical reload keeps the drained bucket");
+
+	reload(prev, normalize_test_yaml(&config(20)).await.unwrap());
+	assert_eq!(
+		remaining(),
+		20,
+		"a changed limit starts with a fresh bucket"
+	);
+}
+
 #[tokio::test]
 async fn test_delay_policy() {
 	let input = r#"
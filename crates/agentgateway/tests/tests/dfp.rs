@@ -63,10 +63,8 @@ fn setup_dfp_https() -> (TestBind, MemoryClient) {
 	(t, io)
 }
 
-/// DFP and inference routing are orthogonal: DFP chooses the upstream from the request authority,
-/// while inference routing expects an endpoint picker to choose the upstream endpoint.
-#[tokio::test]
-async fn dfp_rejects_inference_routing() {
+/// Helper to set up a DFP backend with an inference routing policy, which is rejected at request time.
+fn setup_dfp_inference_routing(config: &str) -> (TestBind, MemoryClient) {
 	let backend_name = ResourceName::new("dynamic".into(), "".into());
 	let dynamic_backend = BackendWithPolicies {
 		backend: Backend::Dynamic(backend_name, ()),
@@ -85,7 +83,7 @@ async fn dfp_rejects_inference_routing() {
 	};
 
 	let route = basic_named_route("/dynamic".into());
-	let t = setup_proxy_test("{}").unwrap();
+	let t = setup_proxy_test(config).unwrap();
 	let pi = t.inputs();
 	pi.stores
 		.binds
@@ -93,6 +91,14 @@ async fn dfp_rejects_inference_routing() {
 		.insert_backend(dynamic_backend.backend.name(), dynamic_backend);
 	let t = t.with_bind(simple_bind()).with_route(route);
 	let io = t.serve_http(BIND_KEY);
+	(t, io)
+}
+
+/// DFP and inference routing are orthogonal: DFP chooses the upstream from the request authority,
+/// while inference routing expects an endpoint picker to choose the upstream endpoint.
+#[tokio::test]
+async fn dfp_rejects_inference_routing() {
+	let (_t, io) = setup_dfp_inference_routing(r#"{"config":{"errorVerbosity":"verbose"}}"#);
 
 	let res = send_request(io, Method::GET, "http://example.com/dynamic").await;
 	assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
@@ -103,6 +109,28 @@ async fn dfp_rejects_inference_routing() {
 	);
 }
 
+/// By default the client only sees a generic message, while the request log keeps the full error.
+#[tokio::test]
+async fn terse_errors_keep_detail_in_log() {
+	let (_t, io) = setup_dfp_inference_routing("{}");
+
+	let res = send_request(io, Method::GET, "http://example.com/dynamic/terse").await;
+	assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+	let body = res.into_body().collect().await.unwrap().to_bytes();
+	assert_eq!(String::from_utf8_lossy(&body), "processing failed");
+
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("scope", "request"),
+		("http.path", "/dynamic/terse"),
+	])
+	.await
+	.unwrap();
+	assert_eq!(
+		log["error"],
+		"processing failed: inferenceRouting is not supported with dynamic backends"
+	);
+}
+
 /// DFP resolves the destination from the request's Host/URI authority, including the port.
 #[tokio::test]
 async fn dfp_uses_host_port() {
@@ -63,6 +63,307 @@ async fn llm_openai_tokenize() {
 	.await;
 }
 
+#[tokio::test]
+async fn llm_openai_tokenize_failure_lenient() {
+	let body = serde_json::to_vec(&json!({
+		"model": "text-davinci-003",
+		"messages": [{"role": "user", "content": "hello"}]
+	}))
+	.unwrap();
+	for (policy, want) in [
+		(json!({}), StatusCode::SERVICE_UNAVAILABLE),
+		(json!({"tokenizeFailure": "lenient"}), StatusCode::OK),
+	] {
+		let mock = body_mock(include_bytes!(
+			"../../../llm/src/tests/response/completions/basic.json"
+		))
+		.await;
+		let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+			&mock,
+			AIProvider::OpenAI(openai::Provider { model: None }),
+			true,
+		);
+		let provider = agentgateway::types::local::LocalNamedAIProvider {
+			policies: serde_json::from_value(json!({ "ai": policy })).unwrap(),
+			..provider
+		};
+		let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+		let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+			.header(header::CONTENT_TYPE, "application/json")
+			.body(Body::from(body.clone()))
+			.send(io)
+			.await
+			.unwrap();
+		assert_eq!(res.status(), want, "{policy}");
+		let requests = mock.received_requests().await.unwrap();
+		assert_eq!(
+			requests.len(),
+			usize::from(want == StatusCode::OK),
+			"{policy}"
+		);
+	}
+}
+
+#[tokio::test]
+async fn llm_completions_rejects_unexpected_content_type() {
+	let mock = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let (mock, mut bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+	bind
+		.attach_route_policy(json!({
+			"contentType": {"request": ["json"]},
+		}))
+		.await;
+
+	let body = include_bytes!("../../../llm/src/tests/requests/completions/basic.json");
+	for (content_type, want) in [
+		("text/plain", StatusCode::UNSUPPORTED_MEDIA_TYPE),
+		("application/json; charset=utf-8", StatusCode::OK),
+	] {
+		let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+			.header(header::CONTENT_TYPE, content_type)
+			.body(Body::from(body.to_vec()))
+			.send(io.clone())
+			.await
+			.unwrap();
+		assert_eq!(res.status(), want, "{content_type}");
+	}
+	let requests = mock.received_requests().await.unwrap();
+	assert_eq!(requests.len(), 1);
+}
+
+#[tokio::test]
+async fn llm_openai_sends_configured_user_agent() {
+	let mock = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+		&mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+	);
+	let provider = agentgateway::types::local::LocalNamedAIProvider {
+		user_agent: Some(strng::new("agentgateway-staging/1.0")),
+		..provider
+	};
+	let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.header(header::CONTENT_TYPE, "application/json")
+		.header(header::USER_AGENT, "client/1.0")
+		.body(Body::from(
+			include_bytes!("../../../llm/src/tests/requests/completions/basic.json").to_vec(),
+		))
+		.send(io)
+		.await
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 1);
+	assert_eq!(
+		requests[0].headers.get(header::USER_AGENT).unwrap(),
+		"agentgateway-staging/1.0"
+	);
+}
+
+#[tokio::test]
+async fn llm_openai_sends_configured_accept_encoding() {
+	let mock = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+		&mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+	);
+	let provider = agentgateway::types::local::LocalNamedAIProvider {
+		accept_encoding: Some(strng::new("identity")),
+		..provider
+	};
+	let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.header(header::CONTENT_TYPE, "application/json")
+		.header(header::ACCEPT_ENCODING, "gzip, br")
+		.body(Body::from(
+			include_bytes!("../../../llm/src/tests/requests/completions/basic.json").to_vec(),
+		))
+		.send(io)
+		.await
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 1);
+	assert_eq!(
+		requests[0].headers.get(header::ACCEPT_ENCODING).unwrap(),
+		"identity"
+	);
+}
+
+#[tokio::test]
+async fn llm_openai_surfaces_model_deprecation() {
+	let mock = wiremock::MockServer::start().await;
+	Mock::given(wiremock::matchers::path_regex("/.*"))
+		.respond_with(
+			ResponseTemplate::new(200)
+				.insert_header("deprecation", "@1767225600")
+				.set_body_raw(
+					include_bytes!("../../../llm/src/tests/response/completions/basic.json").to_vec(),
+					"application/json",
+				),
+		)
+		.mount(&mock)
+		.await;
+	let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+		&mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+	);
+	let provider = agentgateway::types::local::LocalNamedAIProvider {
+		policies: serde_json::from_value(json!({
+			"ai": {"modelDeprecationHeader": true}
+		}))
+		.unwrap(),
+		..provider
+	};
+	let (_mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			include_bytes!("../../../llm/src/tests/requests/completions/basic.json").to_vec(),
+		))
+		.send(io)
+		.await
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+	assert_eq!(
+		res.headers().get("x-model-deprecation").unwrap(),
+		"deprecation=@1767225600"
+	);
+}
+
+#[tokio::test]
+async fn llm_provider_body_limits_override_listener_limit() {
+	let body = include_bytes!("../../../llm/src/tests/requests/completions/basic.json");
+	let response = include_bytes!("../../../llm/src/tests/response/completions/basic.json");
+	let setup = async |body: &[u8], response: &[u8], max_request_bytes, max_response_bytes| {
+		let mock = body_mock(response).await;
+		let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+			&mock,
+			AIProvider::OpenAI(openai::Provider { model: None }),
+			false,
+		);
+		let provider = agentgateway::types::local::LocalNamedAIProvider {
+			max_request_bytes,
+			max_response_bytes,
+			..provider
+		};
+		let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+		let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+			.header(header::CONTENT_TYPE, "application/json")
+			.body(Body::from(body.to_vec()))
+			.send(io)
+			.await
+			.unwrap();
+		let upstream_requests = mock.received_requests().await.unwrap().len();
+		(res.status(), upstream_requests)
+	};
+
+	// A request over the provider's request limit is rejected before reaching the provider.
+	assert_eq!(
+		setup(body, response, Some(body.len() - 1), None).await,
+		(StatusCode::SERVICE_UNAVAILABLE, 0)
+	);
+	// A response over the provider's response limit fails rather than being buffered.
+	let (status, upstream_requests) = setup(body, response, None, Some(64)).await;
+	assert!(!status.is_success(), "got {status}");
+	assert_eq!(upstream_requests, 1);
+	// Larger limits are honored too, in place of the listener's 2 MiB default.
+	let large = "a".repeat(3 * 1024 * 1024);
+	let mut large_body: serde_json::Value = serde_json::from_slice(body).unwrap();
+	large_body["messages"][1]["content"] = json!(large);
+	let large_body = serde_json::to_vec(&large_body).unwrap();
+	let mut large_response: serde_json::Value = serde_json::from_slice(response).unwrap();
+	large_response["choices"][0]["message"]["content"] = json!(large);
+	let large_response = serde_json::to_vec(&large_response).unwrap();
+	assert_eq!(
+		setup(&large_body, &large_response, None, None).await,
+		(StatusCode::SERVICE_UNAVAILABLE, 0),
+		"the default limit should reject the large request"
+	);
+	assert_eq!(
+		setup(
+			&large_body,
+			&large_response,
+			Some(16 * 1024 * 1024),
+			Some(16 * 1024 * 1024)
+		)
+		.await,
+		(StatusCode::OK, 1)
+	);
+}
+
+#[tokio::test]
+#[cfg(feature = "tls-aws-lc")]
+async fn llm_provider_trusts_custom_ca_bundle() {
+	let (mock, certs) = agentgateway::test_helpers::proxymock::tls_mock().await;
+	Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(ResponseTemplate::new(200).set_body_raw(
+			include_bytes!("../../../llm/src/tests/response/completions/basic.json").to_vec(),
+			"application/json",
+		))
+		// Takes precedence over the request echo mounted by `tls_mock`.
+		.with_priority(1)
+		.mount(&mock)
+		.await;
+	let ca = tempfile::NamedTempFile::new().unwrap();
+	std::fs::write(ca.path(), certs.root_cert.pem()).unwrap();
+
+	let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+		&mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+	);
+	let provider = agentgateway::types::local::LocalNamedAIProvider {
+		policies: serde_json::from_value(json!({
+			"tls": {"root": ca.path(), "hostname": "localhost"}
+		}))
+		.unwrap(),
+		..provider
+	};
+	let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(
+			include_bytes!("../../../llm/src/tests/requests/completions/basic.json").to_vec(),
+		))
+		.send(io)
+		.await
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+	assert_eq!(mock.received_requests().await.unwrap().len(), 1);
+}
+
 #[tokio::test]
 async fn llm_detect_mode_passthrough_without_rewrite() {
 	let mock = body_mock(include_bytes!(
@@ -76,6 +377,13 @@ async fn llm_detect_mode_passthrough_without_rewrite() {
 		path_override: None,
 		path_prefix: None,
 		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: None,
+		request_size: None,
 		policies: serde_json::from_value(json!({
 			"ai": {
 				"routes": {
@@ -141,6 +449,13 @@ async fn llm_detect_mode_respects_model_rewrite() {
 		path_override: None,
 		path_prefix: None,
 		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: None,
+		request_size: None,
 		policies: serde_json::from_value(json!({
 			"ai": {
 				"routes": {
@@ -420,6 +735,95 @@ llm:
 	);
 }
 
+#[tokio::test]
+async fn llm_route_header_match_selects_ai_backend() {
+	let premium = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let standard = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let config = format!(
+		r#"
+binds:
+- port: 3000
+  listeners:
+  - routes:
+    - name: premium
+      matches:
+      - path:
+          pathPrefix: /
+        headers:
+        - name: x-model-tier
+          value:
+            exact: premium
+      backends:
+      - ai:
+          name: premium
+          hostOverride: {}
+          provider:
+            openAI:
+              model: gpt-4o
+    - name: standard
+      backends:
+      - ai:
+          name: standard
+          hostOverride: {}
+          provider:
+            openAI:
+              model: gpt-4o-mini
+"#,
+		premium.address(),
+		standard.address()
+	);
+	let t = setup_local_llm_config(&config).await;
+	let io = t.serve_http(strng::literal!("bind/3000"));
+
+	let res = send_completions_with_model(io.clone(), "any", &[("x-model-tier", "premium")]).await;
+	assert_eq!(res.status(), StatusCode::OK);
+	let premium_requests = premium
+		.received_requests()
+		.await
+		.expect("upstream requests");
+	assert_eq!(premium_requests.len(), 1);
+	let upstream_body: Value =
+		serde_json::from_slice(&premium_requests[0].body).expect("upstream request JSON");
+	assert_eq!(upstream_body["model"], "gpt-4o");
+	assert!(
+		standard
+			.received_requests()
+			.await
+			.expect("upstream requests")
+			.is_empty()
+	);
+
+	// Missing or non-matching header falls through to the default route.
+	for headers in [&[][..], &[("x-model-tier", "basic")][..]] {
+		let res = send_completions_with_model(io.clone(), "any", headers).await;
+		assert_eq!(res.status(), StatusCode::OK);
+	}
+	let standard_requests = standard
+		.received_requests()
+		.await
+		.expect("upstream requests");
+	assert_eq!(standard_requests.len(), 2);
+	for request in &standard_requests {
+		let upstream_body: Value =
+			serde_json::from_slice(&request.body).expect("upstream request JSON");
+		assert_eq!(upstream_body["model"], "gpt-4o-mini");
+	}
+	assert_eq!(
+		premium
+			.received_requests()
+			.await
+			.expect("upstream requests")
+			.len(),
+		1
+	);
+}
+
 #[tokio::test]
 async fn llm_model_router_handles_multipart_audio_detect_request() {
 	let mock = body_mock(include_bytes!(
@@ -493,6 +897,80 @@ llm:
 	assert!(is_json_subset(&want, &log), "want={want:#?} got={log:#?}");
 }
 
+#[tokio::test]
+async fn llm_openai_text_completions() {
+	let mock = body_mock(
+		json!({
+			"id": "cmpl-123",
+			"object": "text_completion",
+			"created": 1_700_000_000,
+			"model": "gpt-3.5-turbo-instruct",
+			"choices": [{"text": " world", "index": 0, "logprobs": null, "finish_reason": "length"}],
+			"usage": {"prompt_tokens": 5, "completion_tokens": 7, "total_tokens": 12}
+		})
+		.to_string()
+		.as_bytes(),
+	)
+	.await;
+	let config = format!(
+		r#"
+llm:
+  port: 4000
+  models:
+  - name: instruct
+    provider: openAI
+    params:
+      baseUrl: http://{}/v1
+"#,
+		mock.address()
+	);
+	let t = setup_local_llm_config(&config).await;
+	let io = t.serve_http(strng::literal!("bind/4000"));
+
+	let res = send_request_body(
+		io,
+		Method::POST,
+		"http://lo/v1/completions",
+		json!({"model": "instruct", "prompt": "hello", "max_tokens": 7})
+			.to_string()
+			.as_bytes(),
+	)
+	.await;
+	assert_eq!(res.status(), StatusCode::OK);
+	let body: Value =
+		serde_json::from_slice(&res.into_body().collect().await.unwrap().to_bytes()).unwrap();
+	assert_eq!(body["choices"][0]["text"], " world");
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 1);
+	assert_eq!(
+		&requests[0].url[Position::BeforePath..Position::AfterPath],
+		"/v1/completions"
+	);
+	let upstream_body: Value =
+		serde_json::from_slice(&requests[0].body).expect("upstream request should be JSON");
+	assert_eq!(upstream_body["prompt"], "hello");
+	assert_eq!(upstream_body["max_tokens"], 7);
+
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("scope", "request"),
+		("http.path", "/v1/completions"),
+	])
+	.await
+	.unwrap();
+	let want = json!({
+		"gen_ai.provider.name": "openai",
+		"gen_ai.request.max_tokens": 7,
+		"gen_ai.response.model": "gpt-3.5-turbo-instruct",
+		"gen_ai.usage.input_tokens": 5,
+		"gen_ai.usage.output_tokens": 7
+	});
+	assert!(is_json_subset(&want, &log), "want={want:#?} got={log:#?}");
+}
+
 #[tokio::test]
 async fn llm_custom_rerank() {
 	let mock = body_mock(include_bytes!(
@@ -513,6 +991,13 @@ async fn llm_custom_rerank() {
 		path_override: None,
 		path_prefix: None,
 		tokenize: false,
+		user_agent: None,
+		accept_encoding: None,
+		retry: None,
+		max_request_bytes: None,
+		max_response_bytes: None,
+		weight: None,
+		request_size: None,
 		policies: serde_json::from_value(json!({
 			"ai": {"routes": {"/v1/rerank": "rerank"}}
 		}))
@@ -579,17 +1064,78 @@ fn setup_custom_llm_provider_backend_mock_with_formats(
 }
 
 #[tokio::test]
-async fn llm_custom_provider_routes_to_provider_backend() {
+async fn llm_unsupported_conversion_returns_structured_error() {
 	let mock = body_mock(include_bytes!(
 		"../../../llm/src/tests/response/completions/basic.json"
 	))
 	.await;
-	let (mock, _bind, io) =
-		setup_custom_llm_provider_backend_mock(mock, vec![custom::ProviderFormat::Completions]);
-
-	let res = send_completions_with_model(io, "replaceme", &[]).await;
-	assert_eq!(res.status(), 200);
-	let _ = res.into_body().collect().await.unwrap();
+	let (mock, mut bind, io) =
+		setup_custom_llm_provider_backend_mock(mock, vec![custom::ProviderFormat::Embeddings]);
+	bind
+		.attach_route_policy(json!({
+			"ai": {"routes": {"/v1/messages": "messages"}},
+		}))
+		.await;
+
+	let res = send_request_body(
+		io,
+		Method::POST,
+		"http://lo/v1/messages",
+		include_bytes!("../../../llm/src/tests/requests/messages/basic.json"),
+	)
+	.await;
+	assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+	let body: Value = serde_json::from_slice(&read_body_raw(res.into_body()).await).unwrap();
+	let want = json!({
+		"error": {
+			"type": "invalid_request_error",
+			"code": "unsupported_conversion",
+			"input_format": "messages",
+			"provider": "custom",
+			"supported_formats": ["embeddings"],
+		}
+	});
+	assert!(is_json_subset(&want, &body), "want={want:#?} got={body:#?}");
+	assert!(
+		body["error"]["suggestion"]
+			.as_str()
+			.unwrap()
+			.contains("embeddings")
+	);
+	assert!(
+		mock
+			.received_requests()
+			.await
+			.expect("request recording should be enabled")
+			.is_empty()
+	);
+
+	let rejected = bind
+		.pi
+		.metrics
+		.llm_unsupported_conversions
+		.get_or_create(
+			&agentgateway::telemetry::metrics::UnsupportedConversionLabels {
+				input_format: strng::literal!("messages").into(),
+				provider: strng::literal!("custom").into(),
+			},
+		)
+		.get();
+	assert_eq!(rejected, 1);
+}
+
+#[tokio::test]
+async fn llm_custom_provider_routes_to_provider_backend() {
+	let mock = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let (mock, _bind, io) =
+		setup_custom_llm_provider_backend_mock(mock, vec![custom::ProviderFormat::Completions]);
+
+	let res = send_completions_with_model(io, "replaceme", &[]).await;
+	assert_eq!(res.status(), 200);
+	let _ = res.into_body().collect().await.unwrap();
 
 	let requests = mock
 		.received_requests()
@@ -676,11 +1222,10 @@ async fn llm_custom_provider_rejects_unsupported_format_before_upstream_call() {
 		setup_custom_llm_provider_backend_mock(mock, vec![custom::ProviderFormat::Embeddings]);
 
 	let res = send_completions_with_model(io, "replaceme", &[]).await;
-	assert_eq!(res.status(), 503);
+	assert_eq!(res.status(), 400);
 	let body = res.into_body().collect().await.unwrap().to_bytes();
 	assert!(
-		String::from_utf8_lossy(&body)
-			.contains("unsupported conversion: from Completions to provider custom"),
+		String::from_utf8_lossy(&body).contains("from Completions to provider custom"),
 		"unexpected response body: {}",
 		String::from_utf8_lossy(&body)
 	);
@@ -703,6 +1248,597 @@ async fn recv_rate_limit_request(
 		.expect("rate limit request sender should be open")
 }
 
+#[tokio::test]
+async fn llm_unavailable_model_falls_back_to_substitute() {
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::body_partial_json(
+		json!({"model": "retired-model"}),
+	))
+	.respond_with(wiremock::ResponseTemplate::new(404).set_body_json(json!({
+		"error": {
+			"message": "The model `retired-model` does not exist",
+			"type": "invalid_request_error",
+			"code": "model_not_found",
+		}
+	})))
+	.mount(&mock)
+	.await;
+	wiremock::Mock::given(wiremock::matchers::body_partial_json(
+		json!({"model": "gpt-4o"}),
+	))
+	.respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+		include_bytes!("../../../llm/src/tests/response/completions/basic.json").to_vec(),
+		"application/json",
+	))
+	.mount(&mock)
+	.await;
+	let (mock, mut bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+	bind
+		.attach_route_policy(json!({
+			"ai": {
+				"modelFallbacks": {
+					"retired-model": "gpt-4o",
+				},
+			},
+		}))
+		.await;
+
+	let res = send_completions_with_model(io, "retired-model", &[]).await;
+	assert_eq!(res.status(), StatusCode::OK);
+	let body: Value = serde_json::from_slice(&read_body_raw(res.into_body()).await).unwrap();
+	assert_eq!(body["model"], "gpt-3.5-turbo-0125");
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	let models = requests
+		.iter()
+		.map(|r| serde_json::from_slice::<Value>(&r.body).unwrap()["model"].clone())
+		.collect::<Vec<_>>();
+	assert_eq!(models, vec![json!("retired-model"), json!("gpt-4o")]);
+
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("scope", "request"),
+		("gen_ai.request.model", "gpt-4o"),
+	])
+	.await
+	.unwrap();
+	assert_eq!(log["http.status"], json!(200), "got={log:#?}");
+}
+
+#[tokio::test]
+async fn llm_denied_fallback_model_is_not_tried() {
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::body_partial_json(
+		json!({"model": "retired-model"}),
+	))
+	.respond_with(wiremock::ResponseTemplate::new(404).set_body_json(json!({
+		"error": {
+			"message": "The model `retired-model` does not exist",
+			"type": "invalid_request_error",
+			"code": "model_not_found",
+		}
+	})))
+	.mount(&mock)
+	.await;
+	let (mock, mut bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+	bind
+		.attach_route_policy(json!({
+			"ai": {
+				"modelFallbacks": {
+					"retired-model": "gpt-4o",
+				},
+				"deniedModels": ["gpt-4*"],
+			},
+		}))
+		.await;
+
+	let res = send_completions_with_model(io, "retired-model", &[]).await;
+	assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 1);
+}
+
+fn retrying_provider(
+	mock: &wiremock::MockServer,
+) -> agentgateway::types::local::LocalNamedAIProvider {
+	let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+	);
+	agentgateway::types::local::LocalNamedAIProvider {
+		retry: Some(agentgateway::llm::retry::Retry {
+			max_retries: 2,
+			base_backoff: Some(Duration::from_millis(1)),
+			max_backoff: None,
+		}),
+		..provider
+	}
+}
+
+#[tokio::test]
+async fn llm_provider_retries_server_error() {
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(wiremock::ResponseTemplate::new(503))
+		.up_to_n_times(1)
+		.mount(&mock)
+		.await;
+	wiremock::Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+			include_bytes!("../../../llm/src/tests/response/completions/basic.json").to_vec(),
+			"application/json",
+		))
+		.mount(&mock)
+		.await;
+	let provider = retrying_provider(&mock);
+	let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let res = send_completions_with_model(io, "gpt-4o", &[]).await;
+	assert_eq!(res.status(), StatusCode::OK);
+	let body: Value = serde_json::from_slice(&read_body_raw(res.into_body()).await).unwrap();
+	assert_eq!(body["model"], "gpt-3.5-turbo-0125");
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 2);
+	assert_eq!(requests[0].body, requests[1].body);
+}
+
+#[tokio::test]
+async fn llm_provider_retries_rate_limit_after_reset() {
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(
+			wiremock::ResponseTemplate::new(429).insert_header("x-ratelimit-reset-requests", "1ms"),
+		)
+		.up_to_n_times(1)
+		.mount(&mock)
+		.await;
+	wiremock::Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+			include_bytes!("../../../llm/src/tests/response/completions/basic.json").to_vec(),
+			"application/json",
+		))
+		.mount(&mock)
+		.await;
+	let provider = retrying_provider(&mock);
+	let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let res = send_completions_with_model(io, "gpt-4o", &[]).await;
+	assert_eq!(res.status(), StatusCode::OK);
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 2);
+}
+
+#[tokio::test]
+async fn llm_provider_does_not_retry_started_stream() {
+	let stream = concat!(
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n",
+		"data: {\"error\":{\"message\":\"overloaded\",\"type\":\"server_error\"}}\n\n",
+	);
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(
+			wiremock::ResponseTemplate::new(200).set_body_raw(stream.as_bytes(), "text/event-stream"),
+		)
+		.up_to_n_times(1)
+		.mount(&mock)
+		.await;
+	wiremock::Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(wiremock::ResponseTemplate::new(503))
+		.mount(&mock)
+		.await;
+	let provider = retrying_provider(&mock);
+	let (mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let mut request: Value =
+		serde_json::from_slice(&completions_request_body_with_model("gpt-4o")).unwrap();
+	request["stream"] = json!(true);
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(serde_json::to_vec(&request).unwrap()))
+		.send(io)
+		.await
+		.unwrap();
+	// The failure arrived mid-stream, after the response had started, so it is passed through.
+	assert_eq!(res.status(), StatusCode::OK);
+	let body = String::from_utf8(read_body_raw(res.into_body()).await.to_vec()).unwrap();
+	assert!(body.contains("\"content\":\"Hi\""), "got: {body}");
+	assert!(body.contains("overloaded"), "got: {body}");
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 1);
+}
+
+#[tokio::test]
+async fn llm_summary_logged_for_streaming_request() {
+	let stream = concat!(
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o-2024-08-06\",",
+		"\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"finish_reason\":\"stop\"}]}\n\n",
+		"data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o-2024-08-06\",",
+		"\"choices\":[],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":3,\"total_tokens\":15,",
+		"\"prompt_tokens_details\":{\"cached_tokens\":4}}}\n\n",
+		"data: [DONE]\n\n",
+	);
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::method("POST"))
+		.respond_with(
+			wiremock::ResponseTemplate::new(200).set_body_raw(stream.as_bytes(), "text/event-stream"),
+		)
+		.mount(&mock)
+		.await;
+	let config = serde_json::to_string(&json!({
+		"config": {
+			"logging": {
+				"llmSummary": true
+			}
+		}
+	}))
+	.unwrap();
+	let (_mock, _bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		config.as_str(),
+	);
+
+	let mut request: Value =
+		serde_json::from_slice(&completions_request_body_with_model("summary-model")).unwrap();
+	request["stream"] = json!(true);
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(serde_json::to_vec(&request).unwrap()))
+		.send(io)
+		.await
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+	let _ = read_body_raw(res.into_body()).await;
+
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("scope", "llm_summary"),
+		("gen_ai.request.model", "summary-model"),
+	])
+	.await
+	.unwrap();
+	let want = json!({
+		"gen_ai.provider.name": "openai",
+		"gen_ai.response.model": "gpt-4o-2024-08-06",
+		"gen_ai.usage.input_tokens": 12,
+		"gen_ai.usage.output_tokens": 3,
+		"gen_ai.usage.total_tokens": 15,
+		"gen_ai.usage.cache_read.input_tokens": 4,
+		"http.status": 200
+	});
+	assert!(is_json_subset(&want, &log), "want={want:#?} got={log:#?}");
+	for field in ["request.id", "duration", "gen_ai.time_to_first_token"] {
+		assert!(log.get(field).is_some(), "missing {field}: {log:#?}");
+	}
+	// Cost is only estimated when a model catalog prices the model.
+	assert!(log.get("agw.ai.usage.cost.total").is_none());
+}
+
+#[tokio::test]
+async fn llm_default_retry_after_injected_for_bare_rate_limit() {
+	let rate_limited = json!({"error": {"message": "rate limited", "type": "rate_limit_error"}});
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::body_partial_json(
+		json!({"model": "gpt-4o"}),
+	))
+	.respond_with(wiremock::ResponseTemplate::new(429).set_body_json(rate_limited.clone()))
+	.mount(&mock)
+	.await;
+	wiremock::Mock::given(wiremock::matchers::body_partial_json(
+		json!({"model": "gpt-4o-mini"}),
+	))
+	.respond_with(
+		wiremock::ResponseTemplate::new(429)
+			.insert_header("retry-after", "7")
+			.set_body_json(rate_limited),
+	)
+	.mount(&mock)
+	.await;
+	let provider = agentgateway::test_helpers::proxymock::llm_named_provider(
+		&mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+	);
+	let provider = agentgateway::types::local::LocalNamedAIProvider {
+		policies: serde_json::from_value(json!({
+			"ai": {"defaultRetryAfter": {"backoff": "2s"}}
+		}))
+		.unwrap(),
+		..provider
+	};
+	let (_mock, _bind, io) = setup_llm_named_provider_mock(mock, provider, "{}");
+
+	let res = send_completions_with_model(io.clone(), "gpt-4o", &[]).await;
+	assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+	assert_eq!(res.headers().get("retry-after-ms").unwrap(), "2000");
+
+	// The provider's own hint is left alone.
+	let res = send_completions_with_model(io, "gpt-4o-mini", &[]).await;
+	assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+	assert_eq!(res.headers().get("retry-after").unwrap(), "7");
+	assert!(res.headers().get("retry-after-ms").is_none());
+}
+
+#[tokio::test]
+async fn llm_max_output_tokens_cap_clamps_client_value() {
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::any())
+		.respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+			include_bytes!("../../../llm/src/tests/response/completions/basic.json").to_vec(),
+			"application/json",
+		))
+		.mount(&mock)
+		.await;
+	let (mock, mut bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+	bind
+		.attach_route_policy(json!({
+			"ai": {
+				"maxOutputTokensCap": 100,
+			},
+		}))
+		.await;
+
+	let mut body: Value =
+		serde_json::from_slice(&completions_request_body_with_model("llama-3.1-8b")).unwrap();
+	body["max_tokens"] = json!(1000);
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.body(Body::from(serde_json::to_vec(&body).unwrap()))
+		.send(io.clone())
+		.await
+		.expect("completions request");
+	assert_eq!(res.status(), StatusCode::OK);
+	assert_eq!(res.headers().get("x-max-output-tokens-cap").unwrap(), "100");
+
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	let sent: Value = serde_json::from_slice(&requests[0].body).unwrap();
+	assert_eq!(sent["max_tokens"], json!(100));
+
+	// Without a client limit, the cap is sent as `max_completion_tokens`.
+	body.as_object_mut().unwrap().remove("max_tokens");
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.body(Body::from(serde_json::to_vec(&body).unwrap()))
+		.send(io)
+		.await
+		.expect("completions request");
+	assert_eq!(res.status(), StatusCode::OK);
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	let sent: Value = serde_json::from_slice(&requests[1].body).unwrap();
+	assert!(sent.get("max_tokens").is_none(), "{sent}");
+	assert_eq!(sent["max_completion_tokens"], json!(100));
+}
+
+fn empty_choices_response_body() -> Vec<u8> {
+	let mut body: Value = serde_json::from_slice(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.expect("response fixture should be valid JSON");
+	body["choices"] = json!([]);
+	serde_json::to_vec(&body).expect("response fixture should serialize")
+}
+
+#[tokio::test]
+async fn llm_empty_choices_error_returns_structured_error() {
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::any())
+		.respond_with(
+			wiremock::ResponseTemplate::new(200)
+				.set_body_raw(empty_choices_response_body(), "application/json"),
+		)
+		.mount(&mock)
+		.await;
+	let (_mock, mut bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+	bind
+		.attach_route_policy(json!({
+			"ai": {
+				"emptyChoices": "error",
+			},
+		}))
+		.await;
+
+	let r = rand::rng().random::<u128>();
+	let res = RequestBuilder::new(Method::POST, &format!("http://lo/{r}"))
+		.body(Body::from(completions_request_body(false)))
+		.send(io)
+		.await
+		.expect("completions request");
+	assert_eq!(res.status(), StatusCode::BAD_GATEWAY);
+	let body: Value = serde_json::from_slice(&read_body_raw(res.into_body()).await).unwrap();
+	assert_eq!(body["error"]["code"], json!("empty_choices"));
+
+	// The upstream still billed the request, so its usage must be recorded.
+	let log = agent_core::telemetry::testing::eventually_find(&[
+		("scope", "request"),
+		("http.path", &format!("/{r}")),
+	])
+	.await
+	.unwrap();
+	let want = json!({
+		"gen_ai.usage.input_tokens": 17,
+		"gen_ai.usage.output_tokens": 23
+	});
+	assert!(is_json_subset(&want, &log), "want={want:#?} got={log:#?}");
+}
+
+#[tokio::test]
+async fn llm_empty_choices_retry_retries_once() {
+	let mock = wiremock::MockServer::start().await;
+	wiremock::Mock::given(wiremock::matchers::any())
+		.respond_with(
+			wiremock::ResponseTemplate::new(200)
+				.set_body_raw(empty_choices_response_body(), "application/json"),
+		)
+		.up_to_n_times(1)
+		.with_priority(1)
+		.mount(&mock)
+		.await;
+	wiremock::Mock::given(wiremock::matchers::any())
+		.respond_with(wiremock::ResponseTemplate::new(200).set_body_raw(
+			include_bytes!("../../../llm/src/tests/response/completions/basic.json").to_vec(),
+			"application/json",
+		))
+		.mount(&mock)
+		.await;
+	let (mock, mut bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+	bind
+		.attach_route_policy(json!({
+			"ai": {
+				"emptyChoices": "retry",
+			},
+		}))
+		.await;
+
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.body(Body::from(completions_request_body(false)))
+		.send(io)
+		.await
+		.expect("completions request");
+	assert_eq!(res.status(), StatusCode::OK);
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 2);
+}
+
+fn completions_request_body_with_functions() -> Vec<u8> {
+	let mut body: Value = serde_json::from_slice(&completions_request_body(false)).unwrap();
+	let function = json!({
+		"name": "get_weather",
+		"parameters": {"type": "object", "properties": {}},
+	});
+	body["tools"] = json!([{"type": "function", "function": function}]);
+	body["functions"] = json!([function]);
+	body["function_call"] = json!("auto");
+	serde_json::to_vec(&body).expect("request should serialize")
+}
+
+#[tokio::test]
+async fn llm_deprecated_functions_are_dropped_in_favor_of_tools() {
+	let mock = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let (mock, _bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.body(Body::from(completions_request_body_with_functions()))
+		.send(io)
+		.await
+		.expect("completions request");
+	assert_eq!(res.status(), StatusCode::OK);
+	let requests = mock
+		.received_requests()
+		.await
+		.expect("request recording should be enabled");
+	assert_eq!(requests.len(), 1);
+	let sent: Value = serde_json::from_slice(&requests[0].body).unwrap();
+	assert_eq!(sent["tools"][0]["function"]["name"], json!("get_weather"));
+	assert!(sent.get("functions").is_none());
+	assert!(sent.get("function_call").is_none());
+	agent_core::telemetry::testing::eventually_find(&[
+		("level", "warn"),
+		(
+			"message",
+			"request sets both `tools` and deprecated `functions`; dropping `functions`",
+		),
+	])
+	.await
+	.expect("dropping `functions` should be logged as a warning");
+}
+
+#[tokio::test]
+async fn llm_deprecated_functions_error_rejects_request() {
+	let mock = body_mock(include_bytes!(
+		"../../../llm/src/tests/response/completions/basic.json"
+	))
+	.await;
+	let (mock, mut bind, io) = setup_llm_mock(
+		mock,
+		AIProvider::OpenAI(openai::Provider { model: None }),
+		false,
+		"{}",
+	);
+	bind
+		.attach_route_policy(json!({
+			"ai": {
+				"deprecatedFunctions": "error",
+			},
+		}))
+		.await;
+
+	let res = RequestBuilder::new(Method::POST, "http://lo/v1/chat/completions")
+		.body(Body::from(completions_request_body_with_functions()))
+		.send(io)
+		.await
+		.expect("completions request");
+	assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+	let body: Value = serde_json::from_slice(&read_body_raw(res.into_body()).await).unwrap();
+	assert_eq!(body["error"]["param"], json!("functions"));
+	assert!(
+		mock
+			.received_requests()
+			.await
+			.expect("request recording should be enabled")
+			.is_empty()
+	);
+}
+
 fn completions_request_body(streaming: bool) -> Vec<u8> {
 	let mut body: Value = serde_json::from_slice(include_bytes!(
 		"../../../llm/src/tests/requests/completions/basic.json"
@@ -387,3 +387,28 @@ async fn inline_backend_policies() {
 		b"backend-route-req"
 	);
 }
+
+#[tokio::test]
+async fn retry_stops_when_budget_is_exhausted() {
+	let mock = MockServer::start().await;
+	Mock::given(wiremock::matchers::any())
+		.respond_with(ResponseTemplate::new(503).set_delay(Duration::from_millis(200)))
+		.mount(&mock)
+		.await;
+	let (mock, mut bind, io) = setup_mock(mock);
+	bind
+		.attach_route_policy(json!({
+			"retry": {
+				"attempts": 5,
+				"codes": [503],
+				"budget": "300ms",
+			},
+		}))
+		.await;
+
+	let res = send_request(io, Method::GET, "http://lo/p").await;
+	// The second attempt is cut off when the budget runs out, with attempts to spare.
+	assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+	let received = mock.received_requests().await.unwrap();
+	assert_eq!(received.len(), 2);
+}
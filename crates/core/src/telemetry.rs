@@ -219,7 +219,23 @@ pub fn log(level: &str, target: &str, kv: &[(&str, Option<ValueBag>)]) {
 	}
 }
 
-fn write_json_log(
+// log_line is like log, but writes a line that was already rendered by the caller instead of using the
+// process-wide text/json format. The k/v pairs are still forwarded to the OTEL log sink.
+pub fn log_line(level: &str, target: &str, line: &str, kv: &[(&str, Option<ValueBag>)]) {
+	let Some((nb, _)) = NON_BLOCKING.get() else {
+		return;
+	};
+	let mut buf = Vec::with_capacity(line.len() + 1);
+	buf.extend_from_slice(line.as_bytes());
+	buf.push(b'\n');
+	let _ = nb.write_vec(buf);
+
+	if let Some(sink) = OTEL_LOG_SINK.get() {
+		sink.emit(level, target, kv);
+	}
+}
+
+pub fn write_json_log(
 	buf: &mut String,
 	level: &str,
 	target: &str,
@@ -9,16 +9,28 @@ pub struct Provider {
 	/// Model ID to send to Anthropic, overriding the model in the client request.
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub model: Option<Strng>,
+	/// `anthropic-version` header sent to Anthropic. Defaults to `2023-06-01`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub api_version: Option<Strng>,
 }
 
 impl super::Provider for Provider {
 	const NAME: Strng = strng::literal!("anthropic");
 }
+
+impl Provider {
+	pub fn api_version(&self) -> &str {
+		self.api_version.as_deref().unwrap_or(DEFAULT_API_VERSION)
+	}
+}
 pub const DEFAULT_HOST_STR: &str = "api.anthropic.com";
 pub const DEFAULT_HOST: Strng = strng::literal!(DEFAULT_HOST_STR);
 
 pub const OAUTH_TOKEN_PREFIX: &str = "sk-ant-oat";
 
+// https://docs.anthropic.com/en/api/versioning
+pub const DEFAULT_API_VERSION: &str = "2023-06-01";
+
 pub const DEFAULT_BASE_PATH: &str = "/v1";
 
 pub fn path_suffix(route: RouteType) -> &'static str {
@@ -80,7 +80,7 @@ impl Provider {
 				strng::format!("/openai/responses?api-version={version}")
 			},
 			version => {
-				let model = self.model.as_deref().unwrap_or(model);
+				let model = crate::encode_path_segment(self.model.as_deref().unwrap_or(model));
 				strng::format!("/openai/deployments/{model}/{suffix}?api-version={version}")
 			},
 		}
@@ -89,6 +89,7 @@ impl Provider {
 	fn openai_suffix(route: RouteType) -> Strng {
 		match route {
 			RouteType::Embeddings => strng::literal!("embeddings"),
+			RouteType::TextCompletions => strng::literal!("completions"),
 			RouteType::Responses => strng::literal!("responses"),
 			_ => strng::literal!("chat/completions"),
 		}
@@ -22,6 +22,10 @@ pub struct Provider {
 	/// Version of the Bedrock guardrail to apply.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub guardrail_version: Option<Strng>,
+	/// `anthropic_version` set in the body of requests to Anthropic models, overriding the
+	/// client's `anthropic-version` header.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub anthropic_version: Option<Strng>,
 }
 
 impl super::Provider for Provider {
@@ -45,10 +49,7 @@ impl Provider {
 		streaming: bool,
 		model: &str,
 	) -> Strng {
-		let model = self.model.as_deref().unwrap_or(model);
-		const MODEL_SEGMENT: &percent_encoding::AsciiSet =
-			&percent_encoding::CONTROLS.add(b'/').add(b'%');
-		let model = percent_encoding::utf8_percent_encode(model, MODEL_SEGMENT);
+		let model = crate::encode_path_segment(self.model.as_deref().unwrap_or(model));
 		match route_type {
 			super::RouteType::AnthropicTokenCount => strng::format!("/model/{model}/count-tokens"),
 			super::RouteType::Embeddings => strng::format!("/model/{model}/invoke"),
@@ -10,6 +10,9 @@ use crate::AIError;
 use crate::types::completions::typed::UsagePromptDetails;
 use crate::types::{bedrock, messages, responses};
 
+/// Error message sent to the client when a stream is aborted after repeated parse failures.
+const STREAM_ABORTED_MESSAGE: &str = "Stream aborted after repeated processing errors";
+
 #[cfg(test)]
 #[path = "bedrock_tests.rs"]
 mod tests;
@@ -968,6 +971,7 @@ pub mod from_completions {
 		model: &str,
 		message_id: &str,
 		tool_name_map: Option<super::BedrockToolNameMap>,
+		include_usage: bool,
 	) -> Body {
 		// This is static for all chunks!
 		let created = chrono::Utc::now().timestamp() as u32;
@@ -976,10 +980,15 @@ pub mod from_completions {
 		let mut tool_calls: HashMap<i32, String> = HashMap::new();
 		let model = model.to_string();
 		let message_id = message_id.to_string();
+		let mut parse_failures = parse::aws_sse::ParseFailures::default();
 		let body = parse::aws_sse::transform(b, buffer_limit, move |f| {
-			let res = bedrock::ConverseStreamOutput::deserialize(f).ok()?;
+			let res = match parse_failures.check(bedrock::ConverseStreamOutput::deserialize(f)) {
+				parse::aws_sse::Parsed::Ok(res) => res,
+				parse::aws_sse::Parsed::Abort => return stream_error(super::STREAM_ABORTED_MESSAGE),
+				parse::aws_sse::Parsed::Failed | parse::aws_sse::Parsed::Aborted => return None,
+			};
 			let mk = |choices: Vec<completions::ChatChoiceStream>, usage: Option<completions::Usage>| {
-				Some(completions::StreamResponse {
+				Some(StreamChunk::Chunk(completions::StreamResponse {
 					id: message_id.to_string(),
 					model: model.to_string(),
 					object: "chat.completion.chunk".to_string(),
@@ -988,7 +997,7 @@ pub mod from_completions {
 					created,
 					choices,
 					usage,
-				})
+				}))
 			};
 
 			match res {
@@ -1029,6 +1038,14 @@ pub mod from_completions {
 						});
 					}
 
+					if let Some(bedrock::ContentBlockDelta::ToolUse(tu)) = &d.delta
+						&& let Some(json_buffer) = tool_calls.get_mut(&d.content_block_index)
+						&& !parse::append_tool_arguments(json_buffer, &tu.input, buffer_limit)
+					{
+						parse_failures.abort();
+						return stream_error(parse::TOOL_ARGUMENTS_TOO_LARGE_MESSAGE);
+					}
+
 					let delta = d.delta.map(|delta| {
 						let mut dr = completions::StreamResponseDelta::default();
 						match delta {
@@ -1062,9 +1079,8 @@ pub mod from_completions {
 								dr.content = Some(t);
 							},
 							bedrock::ContentBlockDelta::ToolUse(tu) => {
-								// Accumulate tool call JSON and emit deltas
-								if let Some(json_buffer) = tool_calls.get_mut(&d.content_block_index) {
-									json_buffer.push_str(&tu.input);
+								// Tool call JSON was accumulated above; emit the delta
+								if tool_calls.contains_key(&d.content_block_index) {
 									dr.tool_calls = Some(vec![completions::ChatCompletionMessageToolCallChunk {
 										index: d.content_block_index as u32,
 										id: None, // Only sent in the first chunk
@@ -1135,6 +1151,10 @@ pub mod from_completions {
 							r.response.cache_creation_input_tokens =
 								usage.cache_write_input_tokens.map(|i| i as u64);
 						});
+						if !include_usage {
+							// Accounting above still uses the metadata; the client opted out of the chunk.
+							return None;
+						}
 
 						mk(
 							vec![],
@@ -1163,6 +1183,30 @@ pub mod from_completions {
 		append_done_on_success(body.into_data_stream())
 	}
 
+	/// A translated stream chunk. OpenAI reports a failure mid-stream as a bare `error` object in
+	/// place of a chunk.
+	#[derive(serde::Serialize)]
+	#[serde(untagged)]
+	enum StreamChunk {
+		Chunk(completions::StreamResponse),
+		Error(completions::ChatCompletionErrorResponse),
+	}
+
+	fn stream_error(message: &str) -> Option<StreamChunk> {
+		Some(StreamChunk::Error(
+			completions::ChatCompletionErrorResponse {
+				event_id: None,
+				error: completions::ChatCompletionError {
+					r#type: Some("api_error".to_string()),
+					message: message.to_string(),
+					param: None,
+					code: None,
+					event_id: None,
+				},
+			},
+		))
+	}
+
 	pub(super) fn append_done_on_success<S>(stream: S) -> Body
 	where
 		S: futures_core::Stream<Item = Result<Bytes, axum_core::Error>> + Send + 'static,
@@ -1717,18 +1761,25 @@ pub mod from_messages {
 		let mut pending_usage: Option<bedrock::TokenUsage> = None;
 		let mut completion = include_completion_in_log.then(String::new);
 		let model = model.to_string();
+		let mut parse_failures = parse::aws_sse::ParseFailures::default();
 		parse::aws_sse::transform_multi(b, buffer_limit, move |aws_event| {
-			let event = match bedrock::ConverseStreamOutput::deserialize(aws_event) {
+			let parsed = match parse_failures.check(bedrock::ConverseStreamOutput::deserialize(aws_event))
+			{
+				parse::aws_sse::Parsed::Ok(e) => Ok(e),
+				parse::aws_sse::Parsed::Failed => Err("Stream processing error"),
+				parse::aws_sse::Parsed::Abort => Err(super::STREAM_ABORTED_MESSAGE),
+				parse::aws_sse::Parsed::Aborted => return vec![],
+			};
+			let event = match parsed {
 				Ok(e) => e,
-				Err(e) => {
-					tracing::error!(error = %e, "failed to deserialize bedrock stream event");
+				Err(message) => {
 					return vec![(
 						"error",
 						serde_json::json!({
 							"type": "error",
 							"error": {
 								"type": "api_error",
-								"message": "Stream processing error"
+								"message": message
 							}
 						}),
 					)];
@@ -2753,23 +2804,30 @@ pub mod from_responses {
 			})
 		};
 
+		let mut parse_failures = parse::aws_sse::ParseFailures::default();
 		parse::aws_sse::transform_multi(b, buffer_limit, move |aws_event| {
 			tracing::debug!("Raw AWS event - headers: {:?}", aws_event.headers());
 			if let Ok(body_str) = std::str::from_utf8(aws_event.payload()) {
 				tracing::debug!("AWS event body: {}", body_str);
 			}
 
-			let event = match bedrock::ConverseStreamOutput::deserialize(aws_event) {
+			let parsed = match parse_failures.check(bedrock::ConverseStreamOutput::deserialize(aws_event))
+			{
+				parse::aws_sse::Parsed::Ok(e) => Ok(e),
+				parse::aws_sse::Parsed::Failed => Err("Stream processing error"),
+				parse::aws_sse::Parsed::Abort => Err(super::STREAM_ABORTED_MESSAGE),
+				parse::aws_sse::Parsed::Aborted => return vec![],
+			};
+			let event = match parsed {
 				Ok(e) => e,
-				Err(e) => {
-					tracing::error!(error = %e, "failed to deserialize bedrock stream event");
+				Err(message) => {
 					sequence_number += 1;
 					return vec![(
 						"error",
 						ResponseStreamEvent::ResponseError(ResponseErrorEvent {
 							sequence_number,
 							code: None,
-							message: "Stream processing error".to_string(),
+							message: message.to_string(),
 							param: None,
 						}),
 					)];
@@ -2910,7 +2968,20 @@ pub mod from_responses {
 								if let Some((item_id, _name, buffer, output_index)) =
 									tool_calls.get_mut(&delta.content_block_index)
 								{
-									buffer.push_str(&tu.input);
+									if !parse::append_tool_arguments(buffer, &tu.input, buffer_limit) {
+										parse_failures.abort();
+										sequence_number += 1;
+										out.push((
+											"error",
+											ResponseStreamEvent::ResponseError(ResponseErrorEvent {
+												sequence_number,
+												code: None,
+												message: parse::TOOL_ARGUMENTS_TOO_LARGE_MESSAGE.to_string(),
+												param: None,
+											}),
+										));
+										return out;
+									}
 
 									sequence_number += 1;
 									let delta_event = ResponseStreamEvent::ResponseFunctionCallArgumentsDelta(
@@ -3068,12 +3139,16 @@ pub mod from_anthropic_token_count {
 	pub fn translate(
 		req: &types::count_tokens::Request,
 		headers: &http::HeaderMap,
+		anthropic_version: Option<&str>,
 	) -> Result<Vec<u8>, AIError> {
 		use base64::Engine;
-		let anthropic_version = headers
-			.get("anthropic-version")
-			.and_then(|v| v.to_str().ok())
-			.unwrap_or("2023-06-01");
+		let anthropic_version = anthropic_version
+			.or_else(|| {
+				headers
+					.get("anthropic-version")
+					.and_then(|v| v.to_str().ok())
+			})
+			.unwrap_or(crate::anthropic::DEFAULT_API_VERSION);
 
 		let body = serde_json::to_vec(req).map_err(AIError::RequestMarshal)?;
 		let mut body: serde_json::Map<String, serde_json::Value> =
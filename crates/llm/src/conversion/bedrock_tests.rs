@@ -135,6 +135,7 @@ fn test_metadata_from_header() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	// Simulate transformation CEL setting x-bedrock-metadata header
@@ -191,6 +192,7 @@ fn test_messages_metadata_is_preserved_in_additional_model_request_fields() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let json_encoded_user_id = r#"{"device_id":"704cb53c2074e9","account_uuid":"","session_id":"180423cd-fe24-4f48-bbde-b4ab5bfd36e7"}"#;
@@ -241,6 +243,7 @@ fn test_output_config_effort_without_thinking_is_passed_through() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::typed::Request {
@@ -294,6 +297,7 @@ fn test_output_config_format_maps_to_converse_output_config() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let schema = json!({
@@ -368,6 +372,7 @@ fn test_explicit_empty_output_config_is_preserved() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::typed::Request {
@@ -423,6 +428,7 @@ fn test_thinking_and_output_config_are_both_passed_through() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::typed::Request {
@@ -478,6 +484,7 @@ fn test_adaptive_thinking_preserves_sampling_and_tool_choice() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::typed::Request {
@@ -552,6 +559,7 @@ fn test_enabled_thinking_applies_sampling_and_tool_choice_constraints() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::typed::Request {
@@ -615,6 +623,7 @@ fn test_messages_image_url_to_bedrock_returns_error() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::typed::Request {
@@ -661,6 +670,7 @@ fn test_completions_image_data_url_maps_to_converse_image_block() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::completions::Request = serde_json::from_value(json!({
@@ -704,6 +714,7 @@ fn test_completions_request_metadata_only_uses_bedrock_header() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = types::completions::typed::Request {
@@ -788,6 +799,7 @@ fn test_completions_json_schema_response_format_maps_to_converse_output_config()
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let schema = json!({
@@ -883,6 +895,7 @@ fn test_completions_reasoning_effort_maps_to_enabled_thinking_budget() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = types::completions::typed::Request {
@@ -957,6 +970,7 @@ fn test_completions_explicit_thinking_budget_forces_enabled_thinking() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = types::completions::typed::Request {
@@ -1034,6 +1048,7 @@ fn test_responses_json_schema_text_format_maps_to_converse_output_config() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let schema = json!({
@@ -1089,6 +1104,7 @@ fn test_responses_request_metadata_only_uses_bedrock_header() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1133,6 +1149,7 @@ fn test_responses_reasoning_effort_maps_to_enabled_thinking_budget() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1168,6 +1185,7 @@ fn test_responses_explicit_thinking_budget_forces_enabled_thinking() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1206,6 +1224,7 @@ fn test_responses_vendor_extension_thinking_budget_forces_enabled_thinking() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1241,6 +1260,7 @@ fn test_embeddings_translation_titan() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = types::embeddings::Request {
@@ -1267,6 +1287,7 @@ fn test_embeddings_titan_with_encoding_format() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = types::embeddings::Request {
@@ -1296,6 +1317,7 @@ fn test_embeddings_titan_rejects_array_input() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = types::embeddings::Request {
@@ -1320,6 +1342,7 @@ fn test_embeddings_cohere_with_passthrough_fields() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = types::embeddings::Request {
@@ -1346,6 +1369,7 @@ fn test_embeddings_rejects_invalid_input() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	for input in [json!(["hello", 42]), json!(42)] {
@@ -1454,6 +1478,87 @@ fn test_completions_error_translation_wraps_non_json_body() {
 	);
 }
 
+#[tokio::test]
+async fn test_messages_stream_aborts_after_repeated_malformed_events() {
+	use aws_smithy_eventstream::frame::write_message_to;
+
+	use crate::parse::aws_sse::{MAX_CONSECUTIVE_PARSE_FAILURES, Message};
+
+	// Frames without an `:event-type` header decode fine but fail to deserialize.
+	let mut encoded = bytes::BytesMut::new();
+	for _ in 0..MAX_CONSECUTIVE_PARSE_FAILURES * 3 {
+		write_message_to(&Message::new(Bytes::from_static(b"{}")), &mut encoded)
+			.expect("message should encode");
+	}
+
+	let body = from_messages::translate_stream(
+		axum_core::body::Body::from(encoded.freeze()),
+		1024 * 1024,
+		crate::StreamingUsageGuard::default(),
+		"test-model",
+		"msg_123",
+		false,
+		None,
+	);
+	let output = body
+		.collect()
+		.await
+		.expect("stream should complete cleanly")
+		.to_bytes();
+	let output = std::str::from_utf8(&output).unwrap();
+
+	let errors: Vec<&str> = output
+		.split("\n\n")
+		.filter(|event| event.starts_with("event: error"))
+		.collect();
+	assert_eq!(errors.len(), MAX_CONSECUTIVE_PARSE_FAILURES);
+	assert!(
+		errors[..errors.len() - 1]
+			.iter()
+			.all(|e| e.contains("Stream processing error"))
+	);
+	assert!(errors.last().unwrap().contains(STREAM_ABORTED_MESSAGE));
+}
+
+#[tokio::test]
+async fn test_completions_stream_reports_abort_as_error_chunk() {
+	use aws_smithy_eventstream::frame::write_message_to;
+
+	use crate::parse::aws_sse::{MAX_CONSECUTIVE_PARSE_FAILURES, Message};
+
+	let mut encoded = bytes::BytesMut::new();
+	for _ in 0..MAX_CONSECUTIVE_PARSE_FAILURES * 3 {
+		write_message_to(&Message::new(Bytes::from_static(b"{}")), &mut encoded)
+			.expect("message should encode");
+	}
+
+	let body = from_completions::translate_stream(
+		axum_core::body::Body::from(encoded.freeze()),
+		1024 * 1024,
+		crate::StreamingUsageGuard::default(),
+		"test-model",
+		"msg_123",
+		None,
+		true,
+	);
+	let output = body
+		.collect()
+		.await
+		.expect("stream should complete cleanly")
+		.to_bytes();
+	let output = std::str::from_utf8(&output).unwrap();
+
+	let events: Vec<&str> = output
+		.split("\n\n")
+		.filter(|event| !event.is_empty())
+		.collect();
+	assert_eq!(events.len(), 2, "{output}");
+	let error: serde_json::Value =
+		serde_json::from_str(events[0].strip_prefix("data: ").unwrap()).unwrap();
+	assert_eq!(error["error"]["message"], json!(STREAM_ABORTED_MESSAGE));
+	assert_eq!(events[1], "data: [DONE]");
+}
+
 fn make_message(role: types::bedrock::Role, text: &str) -> types::bedrock::Message {
 	types::bedrock::Message {
 		role,
@@ -1571,6 +1676,7 @@ fn test_messages_long_tool_names_fit_bedrock_tool_config() {
 		region: strng::new("us-west-2"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::Request {
@@ -1627,6 +1733,7 @@ fn test_messages_long_tool_name_round_trip_response() {
 		region: strng::new("us-west-2"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req = messages::Request {
@@ -1718,6 +1825,7 @@ fn test_responses_input_image_data_url_maps_to_converse_image_block() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1760,6 +1868,7 @@ fn test_responses_assistant_input_image_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1792,6 +1901,7 @@ fn test_responses_input_image_remote_url_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1824,6 +1934,7 @@ fn test_responses_input_image_non_base64_data_url_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1856,6 +1967,7 @@ fn test_responses_input_image_non_image_data_url_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1888,6 +2000,7 @@ fn test_responses_input_image_empty_media_type_data_url_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1920,6 +2033,7 @@ fn test_responses_input_image_file_id_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1947,6 +2061,7 @@ fn test_responses_system_input_file_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -1980,6 +2095,7 @@ fn test_responses_input_file_id_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -2010,6 +2126,7 @@ fn test_responses_input_file_remote_url_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -2041,6 +2158,7 @@ fn test_responses_input_file_data_url_maps_to_document_block() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	// PDF via file_data data URL — format derived from MIME type
@@ -2080,6 +2198,7 @@ fn test_responses_input_file_data_url_in_file_url_field_maps_to_document_block()
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	// CSV via file_url data URL — format derived from filename extension
@@ -2114,6 +2233,7 @@ fn test_responses_input_file_format_from_extension_fallback() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	// Unknown MIME type but known extension — format derived from filename
@@ -2146,6 +2266,7 @@ fn test_responses_input_file_unknown_format_is_rejected() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	let req: types::responses::Request = serde_json::from_value(json!({
@@ -2179,6 +2300,7 @@ fn test_responses_input_file_duplicate_names_are_deduplicated() {
 		region: strng::new("us-east-1"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 
 	// Bedrock requires unique document names within a request
@@ -2217,3 +2339,59 @@ fn test_responses_input_file_duplicate_names_are_deduplicated() {
 	assert_eq!(content[2]["document"]["name"], json!("report [2]"));
 	assert_eq!(content[3]["document"]["name"], json!("document"));
 }
+
+#[tokio::test]
+async fn test_completions_stream_honors_include_usage() {
+	use aws_smithy_eventstream::frame::write_message_to;
+	use aws_smithy_types::event_stream::{Header, HeaderValue};
+
+	use crate::parse::aws_sse::Message;
+
+	fn event(event_type: &'static str, payload: serde_json::Value) -> Message {
+		Message::new(Bytes::from(serde_json::to_vec(&payload).unwrap()))
+			.add_header(Header::new(
+				":message-type",
+				HeaderValue::String("event".into()),
+			))
+			.add_header(Header::new(
+				":event-type",
+				HeaderValue::String(event_type.into()),
+			))
+	}
+
+	async fn run(include_usage: bool) -> String {
+		let mut encoded = bytes::BytesMut::new();
+		for m in [
+			event(
+				"contentBlockDelta",
+				json!({"contentBlockIndex": 0, "delta": {"text": "Hi"}}),
+			),
+			event("messageStop", json!({"stopReason": "end_turn"})),
+			event(
+				"metadata",
+				json!({"usage": {"inputTokens": 5, "outputTokens": 1, "totalTokens": 6}}),
+			),
+		] {
+			write_message_to(&m, &mut encoded).expect("message should encode");
+		}
+		let body = from_completions::translate_stream(
+			axum_core::body::Body::from(encoded.freeze()),
+			1024 * 1024,
+			crate::StreamingUsageGuard::default(),
+			"test-model",
+			"msg_123",
+			None,
+			include_usage,
+		);
+		let output = body.collect().await.unwrap().to_bytes();
+		String::from_utf8(output.to_vec()).unwrap()
+	}
+
+	let with_usage = run(true).await;
+	assert!(with_usage.contains("\"total_tokens\":6"), "{with_usage}");
+
+	let without_usage = run(false).await;
+	assert!(without_usage.contains("Hi"), "{without_usage}");
+	assert!(without_usage.contains("[DONE]"), "{without_usage}");
+	assert!(!without_usage.contains("total_tokens"), "{without_usage}");
+}
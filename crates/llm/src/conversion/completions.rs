@@ -8,6 +8,10 @@ use tracing::debug;
 
 use crate::{StreamingUsageGuard, logged_response_parsing, parse, types};
 
+#[cfg(test)]
+#[path = "completions_tests.rs"]
+mod tests;
+
 /// Parse a Google error response, handling both single object and array-wrapped formats.
 /// Google's OpenAI-compatible endpoints consistently return `[{"error": {...}}]`
 /// rather than `{"error": {...}}` when using the Vertex AI shim.
@@ -135,7 +139,7 @@ pub mod from_messages {
 
 	use crate::parse::sse::SseJsonEvent;
 	use crate::types::ResponseType;
-	use crate::{AIError, StreamingUsageGuard, json, logged_response_parsing, types};
+	use crate::{AIError, StreamingUsageGuard, json, logged_response_parsing, parse, types};
 
 	/// translate an Anthropic messages to an OpenAI completions request
 	pub fn translate(req: &types::messages::Request) -> Result<Vec<u8>, AIError> {
@@ -259,6 +263,7 @@ pub mod from_messages {
 			pending_tool_calls: HashMap<u32, PendingToolCall>,
 			pending_stop_reason: Option<messages::StopReason>,
 			pending_usage: Option<completions::Usage>,
+			aborted: bool,
 		}
 
 		fn push_event(
@@ -442,6 +447,9 @@ pub mod from_messages {
 			_,
 		>(b, buffer_limit, move |evt| {
 			let mut events: Vec<(&'static str, messages::MessagesStreamEvent)> = Vec::new();
+			if state.aborted {
+				return events;
+			}
 			match evt {
 				SseJsonEvent::Done => {
 					flush_message_end(&mut state, &mut events, &log, true);
@@ -523,8 +531,21 @@ pub mod from_messages {
 										if let Some(name) = &function.name {
 											entry.name = Some(name.clone());
 										}
-										if let Some(args) = &function.arguments {
-											entry.pending_json.push_str(args);
+										if let Some(args) = &function.arguments
+											&& !parse::append_tool_arguments(&mut entry.pending_json, args, buffer_limit)
+										{
+											// The stream ends with an error event in place of a message_stop.
+											state.aborted = true;
+											push_event(
+												&mut events,
+												messages::MessagesStreamEvent::Error {
+													error: messages::MessagesError {
+														r#type: "api_error".to_string(),
+														message: parse::TOOL_ARGUMENTS_TOO_LARGE_MESSAGE.to_string(),
+													},
+												},
+											);
+											return events;
 										}
 									}
 
@@ -956,9 +977,12 @@ pub mod from_messages {
 	}
 }
 
+/// Passes a chat completions stream through, recording its usage. With `estimate_missing_usage`,
+/// usage the stream does not fully report (common for tool calls) is estimated when it ends.
 pub fn passthrough_stream(
 	mut log: StreamingUsageGuard,
 	include_completion_in_log: bool,
+	estimate_missing_usage: bool,
 	resp: Response<Body>,
 ) -> Response<Body> {
 	let mut completion = include_completion_in_log.then(String::new);
@@ -966,6 +990,7 @@ pub fn passthrough_stream(
 	resp.map(|b| {
 		let mut seen_provider = false;
 		let mut saw_token = false;
+		let mut streamed_output_bytes = 0;
 		parse::sse::json_passthrough::<types::completions::typed::StreamResponse>(
 			b,
 			buffer_limit,
@@ -977,6 +1002,17 @@ pub fn passthrough_stream(
 						{
 							c.push_str(delta);
 						}
+						if estimate_missing_usage && let Some(choice) = f.choices.first() {
+							streamed_output_bytes += choice.delta.content.as_deref().map_or(0, str::len);
+							streamed_output_bytes += choice
+								.delta
+								.tool_calls
+								.iter()
+								.flatten()
+								.filter_map(|tc| tc.function.as_ref()?.arguments.as_deref())
+								.map(str::len)
+								.sum::<usize>();
+						}
 						if !saw_token {
 							saw_token = true;
 							log.update(|r| {
@@ -988,6 +1024,7 @@ pub fn passthrough_stream(
 							log.update(|r| {
 								r.response.provider_model = Some(strng::new(&f.model));
 								r.response.service_tier = f.service_tier.as_deref().map(Into::into);
+								r.response.system_fingerprint = f.system_fingerprint.as_deref().map(Into::into);
 							});
 						}
 						if let Some(u) = f.usage {
@@ -1017,7 +1054,13 @@ pub fn passthrough_stream(
 								}
 							});
 
-							log.report_usage();
+							// A usage report missing either side is completed when the stream ends, so
+							// rate limits are only amended once, with the estimate.
+							let partial =
+								u.prompt_tokens == 0 || (u.completion_tokens == 0 && streamed_output_bytes > 0);
+							if !(estimate_missing_usage && partial) {
+								log.report_usage();
+							}
 						}
 					},
 					Some(Err(e)) => {
@@ -1030,6 +1073,9 @@ pub fn passthrough_stream(
 							if let Some(c) = completion.take() {
 								r.response.completion = Some(vec![c]);
 							}
+							if estimate_missing_usage {
+								r.estimate_missing_usage(streamed_output_bytes);
+							}
 						});
 					},
 				}
@@ -0,0 +1,93 @@
+use axum_core::body::Body;
+use http_body_util::BodyExt;
+use serde_json::json;
+
+use super::*;
+
+fn tool_call_chunk(id: Option<&str>, name: Option<&str>, arguments: &str) -> String {
+	let chunk = json!({
+		"id": "chatcmpl-1",
+		"object": "chat.completion.chunk",
+		"created": 1,
+		"model": "gpt-4o",
+		"choices": [{
+			"index": 0,
+			"delta": {
+				"tool_calls": [{
+					"index": 0,
+					"id": id,
+					"type": id.map(|_| "function"),
+					"function": {"name": name, "arguments": arguments},
+				}],
+			},
+			"finish_reason": null,
+		}],
+	});
+	format!("data: {chunk}\n\n")
+}
+
+async fn translate(input: String, buffer_limit: usize) -> String {
+	let body = from_messages::translate_stream(
+		Body::from(input),
+		buffer_limit,
+		crate::StreamingUsageGuard::default(),
+	);
+	let output = body
+		.collect()
+		.await
+		.expect("stream should complete cleanly")
+		.to_bytes();
+	String::from_utf8(output.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_messages_stream_reports_oversized_tool_arguments_as_error_event() {
+	let fragment = "x".repeat(600);
+	let input = [
+		tool_call_chunk(Some("call_1"), Some("lookup"), ""),
+		tool_call_chunk(None, None, &fragment),
+		tool_call_chunk(None, None, &fragment),
+		"data: [DONE]\n\n".to_string(),
+	]
+	.concat();
+
+	let output = translate(input, 1024).await;
+	let error = output
+		.split("\n\n")
+		.find(|event| event.starts_with("event: error"))
+		.unwrap_or_else(|| panic!("missing error event:\n{output}"));
+	let data: serde_json::Value = serde_json::from_str(
+		error
+			.lines()
+			.nth(1)
+			.unwrap()
+			.strip_prefix("data: ")
+			.unwrap(),
+	)
+	.unwrap();
+	assert_eq!(
+		data,
+		json!({
+			"type": "error",
+			"error": {"type": "api_error", "message": crate::parse::TOOL_ARGUMENTS_TOO_LARGE_MESSAGE},
+		})
+	);
+	assert!(
+		!output.contains("message_stop"),
+		"aborted stream must not end normally:\n{output}"
+	);
+}
+
+#[tokio::test]
+async fn test_messages_stream_ends_with_message_stop_within_limit() {
+	let input = [
+		tool_call_chunk(Some("call_1"), Some("lookup"), "{\"q\":"),
+		tool_call_chunk(None, None, "1}"),
+		"data: [DONE]\n\n".to_string(),
+	]
+	.concat();
+
+	let output = translate(input, 1024).await;
+	assert!(!output.contains("event: error"), "{output}");
+	assert!(output.contains("event: message_stop"), "{output}");
+}
@@ -232,7 +232,10 @@ pub mod from_completions {
 		serde_json::to_vec(&xlated).map_err(AIError::RequestMarshal)
 	}
 
-	fn translate_internal(req: completions::Request, model_id: String) -> messages::Request {
+	pub(crate) fn translate_internal(
+		req: completions::Request,
+		model_id: String,
+	) -> messages::Request {
 		let max_tokens = req.max_tokens();
 		let stop_sequences = req.stop_sequence();
 		// Anthropic has all system prompts in a single field. Join them
@@ -418,7 +421,9 @@ pub mod from_completions {
 		Ok(Box::new(passthrough))
 	}
 
-	fn translate_response_internal(resp: messages::MessagesResponse) -> completions::Response {
+	pub(crate) fn translate_response_internal(
+		resp: messages::MessagesResponse,
+	) -> completions::Response {
 		// Convert Anthropic content blocks to OpenAI message content
 		let mut tool_calls: Vec<completions::MessageToolCalls> = Vec::new();
 		let mut content = None;
@@ -726,13 +731,333 @@ pub mod from_completions {
 						None
 					},
 					messages::MessagesStreamEvent::MessageStop => None,
-					messages::MessagesStreamEvent::Ping => None,
+					messages::MessagesStreamEvent::Ping | messages::MessagesStreamEvent::Error { .. } => None,
 				}
 			},
 		)
 	}
 }
 
+pub mod from_responses {
+	use std::collections::HashMap;
+	use std::time::Instant;
+
+	use agent_core::strng;
+	use axum_core::body::Body;
+	use bytes::Bytes;
+	use rand::RngExt;
+
+	use crate::conversion::openai_compat;
+	use crate::parse::sse::SseJsonEvent;
+	use crate::types::ResponseType;
+	use crate::types::completions::typed as completions;
+	use crate::types::completions::typed::UsagePromptDetails;
+	use crate::types::messages::typed as messages;
+	use crate::types::responses::typed as responses;
+	use crate::{AIError, StreamingUsageGuard, json, logged_response_parsing, parse, types};
+
+	/// Translate an OpenAI Responses request into an Anthropic messages request.
+	///
+	/// The request is lowered to chat completions first so that it shares the completions to
+	/// messages mapping (system prompts, tools, reasoning budgets).
+	pub fn translate(req: &types::responses::Request) -> Result<Vec<u8>, AIError> {
+		let typed =
+			json::convert::<_, responses::CreateResponse>(req).map_err(AIError::RequestMarshal)?;
+		let completions = openai_compat::from_responses::translate_internal(typed);
+		let model_id = completions.model.clone().unwrap_or_default();
+		let xlated = super::from_completions::translate_internal(completions, model_id);
+		serde_json::to_vec(&xlated).map_err(AIError::RequestMarshal)
+	}
+
+	/// Translate an Anthropic messages response into an OpenAI Responses response.
+	pub fn translate_response(bytes: &Bytes, model: &str) -> Result<Box<dyn ResponseType>, AIError> {
+		let resp = serde_json::from_slice::<messages::MessagesResponse>(bytes)
+			.map_err(logged_response_parsing(bytes))?;
+		let completions = super::from_completions::translate_response_internal(resp);
+		openai_compat::to_responses::translate_typed_response(completions, model)
+	}
+
+	/// Translate an Anthropic messages error into an OpenAI error, which Responses clients expect.
+	pub fn translate_error(bytes: &Bytes) -> Result<Bytes, AIError> {
+		super::from_completions::translate_error(bytes)
+	}
+
+	fn stream_usage(
+		input_tokens: u64,
+		output_tokens: u64,
+		cache_read_input_tokens: Option<u64>,
+		cache_creation_input_tokens: Option<u64>,
+	) -> completions::Usage {
+		completions::Usage {
+			prompt_tokens: input_tokens as u32,
+			completion_tokens: output_tokens as u32,
+			total_tokens: (input_tokens + output_tokens) as u32,
+			cache_read_input_tokens,
+			prompt_tokens_details: cache_read_input_tokens.map(|i| UsagePromptDetails {
+				cached_tokens: Some(i),
+				audio_tokens: None,
+				rest: Default::default(),
+			}),
+			cache_creation_input_tokens,
+			completion_tokens_details: None,
+		}
+	}
+
+	/// Translate an Anthropic messages stream into OpenAI Responses stream events.
+	pub fn translate_stream(b: Body, buffer_limit: usize, log: StreamingUsageGuard) -> Body {
+		use responses::{
+			AssistantRole, FunctionToolCall, OutputContent, OutputItem, OutputMessage, OutputStatus,
+			OutputTextContent, ResponseContentPartAddedEvent, ResponseErrorEvent,
+			ResponseFunctionCallArgumentsDeltaEvent, ResponseOutputItemAddedEvent, ResponseStreamEvent,
+			ResponseTextDeltaEvent,
+		};
+
+		let mut saw_token = false;
+		let mut aborted = false;
+		let mut sent_content_part = false;
+		let mut flushed = false;
+
+		let mut sequence_number: u64 = 0;
+		let response_id = format!("resp_{:016x}", rand::rng().random::<u64>());
+		let message_item_id = format!("msg_{:016x}", rand::rng().random::<u64>());
+		let mut model = String::new();
+
+		let mut next_output_index: u32 = 1;
+		// Keyed by the Anthropic content block index.
+		let mut tool_calls: HashMap<u32, (String, String, String, u32)> = HashMap::new();
+		let mut pending_stop_reason: Option<completions::FinishReason> = None;
+		let mut input_tokens = 0u64;
+		let mut output_tokens = 0u64;
+		let mut cache_read_input_tokens = None;
+		let mut cache_creation_input_tokens = None;
+
+		// https://docs.anthropic.com/en/docs/build-with-claude/streaming
+		parse::sse::json_transform_multi::<messages::MessagesStreamEvent, ResponseStreamEvent, _>(
+			b,
+			buffer_limit,
+			move |evt| {
+				let mut events: Vec<(&'static str, ResponseStreamEvent)> = Vec::new();
+				if aborted || flushed {
+					return events;
+				}
+
+				let event = match evt {
+					// Anthropic terminates with message_stop rather than [DONE]; handle both.
+					SseJsonEvent::Done => messages::MessagesStreamEvent::MessageStop,
+					SseJsonEvent::Data(Err(e)) => {
+						tracing::warn!(
+							"Failed to parse Anthropic stream response during translation: {}",
+							e
+						);
+						return events;
+					},
+					SseJsonEvent::Data(Ok(event)) => event,
+				};
+
+				match event {
+					messages::MessagesStreamEvent::MessageStart { message } => {
+						model = message.model.clone();
+						input_tokens = message.usage.input_tokens as u64;
+						output_tokens = message.usage.output_tokens as u64;
+						cache_read_input_tokens = message.usage.cache_read_input_tokens.map(|i| i as u64);
+						cache_creation_input_tokens =
+							message.usage.cache_creation_input_tokens.map(|i| i as u64);
+						log.update(|r| {
+							r.response.cache_creation_input_tokens = cache_creation_input_tokens;
+							r.response.service_tier = message.usage.service_tier.as_deref().map(Into::into);
+							r.response.provider_model = Some(strng::new(&message.model))
+						});
+
+						let response_builder =
+							types::responses::ResponseBuilder::new(response_id.clone(), model.clone());
+						sequence_number += 1;
+						events.push(("event", response_builder.created_event(sequence_number)));
+
+						sequence_number += 1;
+						events.push((
+							"event",
+							ResponseStreamEvent::ResponseOutputItemAdded(ResponseOutputItemAddedEvent {
+								sequence_number,
+								output_index: 0,
+								item: OutputItem::Message(OutputMessage {
+									content: Vec::new(),
+									id: message_item_id.clone(),
+									role: AssistantRole::Assistant,
+									phase: None,
+									status: OutputStatus::InProgress,
+								}),
+							}),
+						));
+					},
+					messages::MessagesStreamEvent::ContentBlockStart {
+						index,
+						content_block,
+					} => match content_block {
+						messages::ContentBlock::ToolUse { id, name, .. }
+						| messages::ContentBlock::ServerToolUse { id, name, .. } => {
+							if !saw_token {
+								saw_token = true;
+								log.update(|r| {
+									r.response.first_token = Some(Instant::now());
+								});
+							}
+							let output_index = next_output_index;
+							next_output_index += 1;
+							tool_calls.insert(
+								index as u32,
+								(id.clone(), name.clone(), String::new(), output_index),
+							);
+
+							sequence_number += 1;
+							events.push((
+								"event",
+								ResponseStreamEvent::ResponseOutputItemAdded(ResponseOutputItemAddedEvent {
+									sequence_number,
+									output_index,
+									item: OutputItem::FunctionCall(FunctionToolCall {
+										arguments: String::new(),
+										call_id: id.clone(),
+										namespace: None,
+										name,
+										id: Some(id),
+										status: Some(OutputStatus::InProgress),
+									}),
+								}),
+							));
+						},
+						_ => {},
+					},
+					messages::MessagesStreamEvent::ContentBlockDelta { index, delta } => match delta {
+						messages::ContentBlockDelta::TextDelta { text } => {
+							if !sent_content_part {
+								sent_content_part = true;
+								sequence_number += 1;
+								events.push((
+									"event",
+									ResponseStreamEvent::ResponseContentPartAdded(ResponseContentPartAddedEvent {
+										sequence_number,
+										item_id: message_item_id.clone(),
+										output_index: 0,
+										content_index: 0,
+										part: OutputContent::OutputText(OutputTextContent {
+											text: String::new(),
+											annotations: Vec::new(),
+											logprobs: None,
+										}),
+									}),
+								));
+							}
+
+							if !saw_token {
+								saw_token = true;
+								log.update(|r| {
+									r.response.first_token = Some(Instant::now());
+								});
+							}
+
+							sequence_number += 1;
+							events.push((
+								"event",
+								ResponseStreamEvent::ResponseOutputTextDelta(ResponseTextDeltaEvent {
+									sequence_number,
+									item_id: message_item_id.clone(),
+									output_index: 0,
+									content_index: 0,
+									delta: text,
+									logprobs: None,
+								}),
+							));
+						},
+						messages::ContentBlockDelta::InputJsonDelta { partial_json } => {
+							let Some(entry) = tool_calls.get_mut(&(index as u32)) else {
+								return events;
+							};
+							if !parse::append_tool_arguments(&mut entry.2, &partial_json, buffer_limit) {
+								aborted = true;
+								sequence_number += 1;
+								events.push((
+									"error",
+									ResponseStreamEvent::ResponseError(ResponseErrorEvent {
+										sequence_number,
+										code: None,
+										message: parse::TOOL_ARGUMENTS_TOO_LARGE_MESSAGE.to_string(),
+										param: None,
+									}),
+								));
+								return events;
+							}
+							if !partial_json.is_empty() {
+								sequence_number += 1;
+								events.push((
+									"event",
+									ResponseStreamEvent::ResponseFunctionCallArgumentsDelta(
+										ResponseFunctionCallArgumentsDeltaEvent {
+											sequence_number,
+											item_id: entry.0.clone(),
+											output_index: entry.3,
+											delta: partial_json,
+										},
+									),
+								));
+							}
+						},
+						// Reasoning is not surfaced in the translated stream.
+						messages::ContentBlockDelta::ThinkingDelta { .. }
+						| messages::ContentBlockDelta::SignatureDelta { .. }
+						| messages::ContentBlockDelta::CitationsDelta { .. } => {},
+					},
+					messages::MessagesStreamEvent::MessageDelta { usage, delta } => {
+						if let Some(reason) = delta.stop_reason.as_ref() {
+							pending_stop_reason = Some(super::translate_stop_reason(reason));
+						}
+						if let Some(i) = usage.input_tokens {
+							input_tokens = i as u64;
+						}
+						if let Some(o) = usage.output_tokens {
+							output_tokens = o as u64;
+						}
+						if let Some(crt) = usage.cache_read_input_tokens {
+							cache_read_input_tokens = Some(crt as u64);
+						}
+						if let Some(cwt) = usage.cache_creation_input_tokens {
+							cache_creation_input_tokens = Some(cwt as u64);
+							log.update(|r| {
+								r.response.cache_creation_input_tokens = Some(cwt as u64);
+							});
+						}
+					},
+					messages::MessagesStreamEvent::MessageStop => {
+						flushed = true;
+						let mut pending_usage = Some(stream_usage(
+							input_tokens,
+							output_tokens,
+							cache_read_input_tokens,
+							cache_creation_input_tokens,
+						));
+						openai_compat::to_responses::flush_end(
+							&mut events,
+							&mut sequence_number,
+							&mut tool_calls,
+							&mut pending_stop_reason,
+							&mut pending_usage,
+							&message_item_id,
+							&sent_content_part,
+							&log,
+							&response_id,
+							&model,
+						);
+					},
+					messages::MessagesStreamEvent::ContentBlockStop { .. }
+					| messages::MessagesStreamEvent::Ping
+					| messages::MessagesStreamEvent::Error { .. } => {},
+				}
+
+				events
+			},
+		)
+	}
+}
+
 fn translate_stop_reason(resp: &messages::StopReason) -> completions::FinishReason {
 	match resp {
 		messages::StopReason::EndTurn => completions::FinishReason::Stop,
@@ -818,7 +1143,8 @@ pub fn passthrough_stream(
 			messages::MessagesStreamEvent::ContentBlockStart { .. }
 			| messages::MessagesStreamEvent::ContentBlockStop { .. }
 			| messages::MessagesStreamEvent::MessageStop
-			| messages::MessagesStreamEvent::Ping => {},
+			| messages::MessagesStreamEvent::Ping
+			| messages::MessagesStreamEvent::Error { .. } => {},
 		}
 	})
 }
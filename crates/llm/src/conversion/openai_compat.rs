@@ -12,7 +12,7 @@ pub mod from_responses {
 		serde_json::to_vec(&xlated).map_err(AIError::RequestMarshal)
 	}
 
-	fn translate_internal(req: responses::CreateResponse) -> completions::Request {
+	pub(crate) fn translate_internal(req: responses::CreateResponse) -> completions::Request {
 		use responses::{
 			EasyInputContent, InputContent, InputItem, InputMessage, InputParam, InputRole,
 			InputTextContent, Item, MessageItem, OutputMessageContent, Role as ResponsesRole,
@@ -379,6 +379,14 @@ pub mod to_responses {
 	pub fn translate_response(bytes: &Bytes, model: &str) -> Result<Box<dyn ResponseType>, AIError> {
 		let resp = serde_json::from_slice::<completions::Response>(bytes)
 			.map_err(logged_response_parsing(bytes))?;
+		translate_typed_response(resp, model)
+	}
+
+	/// Translate an already parsed chat completions response into an OpenAI Responses response.
+	pub(crate) fn translate_typed_response(
+		resp: completions::Response,
+		model: &str,
+	) -> Result<Box<dyn ResponseType>, AIError> {
 		let typed = translate_response_internal(resp, model);
 		let mut passthrough =
 			json::convert::<_, types::responses::Response>(&typed).map_err(AIError::ResponseParsing)?;
@@ -495,11 +503,13 @@ pub mod to_responses {
 	pub fn translate_stream(b: Body, buffer_limit: usize, log: StreamingUsageGuard) -> Body {
 		use responses::{
 			AssistantRole, FunctionToolCall, OutputContent, OutputItem, OutputMessage, OutputStatus,
-			OutputTextContent, ResponseContentPartAddedEvent, ResponseFunctionCallArgumentsDeltaEvent,
-			ResponseOutputItemAddedEvent, ResponseStreamEvent, ResponseTextDeltaEvent,
+			OutputTextContent, ResponseContentPartAddedEvent, ResponseErrorEvent,
+			ResponseFunctionCallArgumentsDeltaEvent, ResponseOutputItemAddedEvent, ResponseStreamEvent,
+			ResponseTextDeltaEvent,
 		};
 
 		let mut saw_token = false;
+		let mut aborted = false;
 		let mut sent_created = false;
 		let mut sent_content_part = false;
 		let mut flushed = false;
@@ -519,6 +529,9 @@ pub mod to_responses {
 			buffer_limit,
 			move |evt| {
 				let mut events: Vec<(&'static str, ResponseStreamEvent)> = Vec::new();
+				if aborted {
+					return events;
+				}
 
 				match evt {
 					SseJsonEvent::Done => {
@@ -578,6 +591,9 @@ pub mod to_responses {
 								if let Some(st) = &chunk.service_tier {
 									r.response.service_tier = Some(strng::new(st));
 								}
+								if let Some(fp) = &chunk.system_fingerprint {
+									r.response.system_fingerprint = Some(strng::new(fp));
+								}
 							});
 						}
 
@@ -644,8 +660,21 @@ pub mod to_responses {
 										if let Some(name) = &function.name {
 											entry.1 = name.clone();
 										}
-										if let Some(args) = &function.arguments {
-											entry.2.push_str(args);
+										if let Some(args) = &function.arguments
+											&& !parse::append_tool_arguments(&mut entry.2, args, buffer_limit)
+										{
+											aborted = true;
+											sequence_number += 1;
+											events.push((
+												"error",
+												ResponseStreamEvent::ResponseError(ResponseErrorEvent {
+													sequence_number,
+													code: None,
+													message: parse::TOOL_ARGUMENTS_TOO_LARGE_MESSAGE.to_string(),
+													param: None,
+												}),
+											));
+											return events;
 										}
 									}
 
@@ -731,7 +760,7 @@ pub mod to_responses {
 	}
 
 	#[allow(clippy::too_many_arguments)]
-	fn flush_end(
+	pub(crate) fn flush_end(
 		events: &mut Vec<(&'static str, responses::ResponseStreamEvent)>,
 		sequence_number: &mut u64,
 		tool_calls: &mut HashMap<u32, (String, String, String, u32)>,
@@ -10,6 +10,7 @@ fn bedrock_provider(model: &str, region: &str) -> crate::bedrock::Provider {
 		region: agent_core::strng::new(region),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	}
 }
 
@@ -94,6 +95,7 @@ fn vertex_provider(project: &str, region: &str) -> crate::vertex::Provider {
 		model: None,
 		region: Some(agent_core::strng::new(region)),
 		project_id: agent_core::strng::new(project),
+		anthropic_version: None,
 	}
 }
 
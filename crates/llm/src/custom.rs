@@ -68,6 +68,7 @@ pub enum ProviderFormat {
 	AnthropicTokenCount,
 	Realtime,
 	Rerank,
+	TextCompletions,
 }
 
 impl ProviderFormat {
@@ -80,6 +81,7 @@ impl ProviderFormat {
 			RouteType::AnthropicTokenCount => Self::AnthropicTokenCount,
 			RouteType::Realtime => Self::Realtime,
 			RouteType::Rerank => Self::Rerank,
+			RouteType::TextCompletions => Self::TextCompletions,
 			RouteType::Models | RouteType::Passthrough | RouteType::Detect => return None,
 		})
 	}
@@ -93,6 +95,7 @@ impl ProviderFormat {
 			Self::AnthropicTokenCount => InputFormat::CountTokens,
 			Self::Realtime => InputFormat::Realtime,
 			Self::Rerank => InputFormat::Rerank,
+			Self::TextCompletions => InputFormat::TextCompletions,
 		}
 	}
 
@@ -105,6 +108,7 @@ impl ProviderFormat {
 			Self::AnthropicTokenCount => RouteType::AnthropicTokenCount,
 			Self::Realtime => RouteType::Realtime,
 			Self::Rerank => RouteType::Rerank,
+			Self::TextCompletions => RouteType::TextCompletions,
 		}
 	}
 }
@@ -149,34 +149,40 @@ fn request_conversion_golden() {
 		region: strng::new("us-west-2"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 	let bedrock_titan = bedrock::Provider {
 		model: Some(strng::new("amazon.titan-embed-text-v2:0")),
 		region: strng::new("us-west-2"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 	let bedrock_cohere = bedrock::Provider {
 		model: Some(strng::new("cohere.embed-english-v3")),
 		region: strng::new("us-west-2"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 	let bedrock_rerank = bedrock::Provider {
 		model: Some(strng::new("cohere.rerank-v3-5:0")),
 		region: strng::new("us-west-2"),
 		guardrail_identifier: None,
 		guardrail_version: None,
+		anthropic_version: None,
 	};
 	let vertex_anthropic = vertex::Provider {
 		model: Some(strng::new("anthropic/claude-sonnet-4-5")),
 		region: Some(strng::new("us-central1")),
 		project_id: strng::new("test-project-123"),
+		anthropic_version: None,
 	};
 	let vertex_rerank = vertex::Provider {
 		model: Some(strng::new("semantic-ranker-default@latest")),
 		region: Some(strng::new("global")),
 		project_id: strng::new("test-project-123"),
+		anthropic_version: None,
 	};
 
 	for name in ["basic", "full", "tool-call", "reasoning", "reasoning_max"] {
@@ -275,7 +281,7 @@ fn request_conversion_golden() {
 			serde_json::to_vec(&i).map_err(AIError::RequestMarshal)
 		});
 		test_request(BEDROCK, &path, |i: types::count_tokens::Request| {
-			conversion::bedrock::from_anthropic_token_count::translate(&i, &headers)
+			conversion::bedrock::from_anthropic_token_count::translate(&i, &headers, None)
 		});
 		test_request(VERTEX, &path, |i: types::count_tokens::Request| {
 			let body = serde_json::to_vec(&i).map_err(AIError::RequestMarshal)?;
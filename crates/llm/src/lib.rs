@@ -15,10 +15,13 @@ pub mod conversion;
 pub mod copilot;
 pub mod custom;
 pub mod gemini;
+pub mod mistral;
 pub mod openai;
 pub mod parse;
 pub mod tokenizer;
+pub mod tool_call_id;
 pub mod types;
+pub mod upstream_schema;
 pub mod vertex;
 
 #[cfg(test)]
@@ -28,6 +31,19 @@ pub trait Provider {
 	const NAME: Strng;
 }
 
+/// Characters escaped in a value placed into a single URL path segment, so it cannot add
+/// segments or start a query or fragment.
+const PATH_SEGMENT: &percent_encoding::AsciiSet = &percent_encoding::CONTROLS
+	.add(b'/')
+	.add(b'%')
+	.add(b'?')
+	.add(b'#');
+
+/// Percent-encodes a value, such as a model name, for use within one URL path segment.
+pub fn encode_path_segment(s: &str) -> String {
+	percent_encoding::utf8_percent_encode(s, PATH_SEGMENT).to_string()
+}
+
 pub mod json {
 	use serde::Serialize;
 	use serde::de::DeserializeOwned;
@@ -104,6 +120,27 @@ pub enum RouteType {
 	AnthropicTokenCount,
 	/// Cohere /v2/rerank (document reranking)
 	Rerank,
+	/// OpenAI /v1/completions (legacy text completions)
+	TextCompletions,
+}
+
+impl RouteType {
+	/// The configuration name of the route type, as used in `routes`.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			RouteType::Completions => "completions",
+			RouteType::Messages => "messages",
+			RouteType::Models => "models",
+			RouteType::Passthrough => "passthrough",
+			RouteType::Detect => "detect",
+			RouteType::Responses => "responses",
+			RouteType::Embeddings => "embeddings",
+			RouteType::Realtime => "realtime",
+			RouteType::AnthropicTokenCount => "anthropicTokenCount",
+			RouteType::Rerank => "rerank",
+			RouteType::TextCompletions => "textCompletions",
+		}
+	}
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -116,6 +153,7 @@ pub enum InputFormat {
 	CountTokens,
 	Detect,
 	Rerank,
+	TextCompletions,
 }
 
 impl InputFormat {
@@ -136,6 +174,7 @@ impl InputFormat {
 			InputFormat::CountTokens => false,
 			InputFormat::Detect => false,
 			InputFormat::Rerank => false,
+			InputFormat::TextCompletions => true,
 		}
 	}
 }
@@ -165,6 +204,9 @@ pub struct LLMRequest {
 pub enum ProviderState {
 	Bedrock {
 		tool_names: Arc<conversion::bedrock::BedrockToolNameMap>,
+		/// Whether the client asked for the usage chunk on a chat completions stream. Bedrock always
+		/// reports usage in its metadata event; this only controls what the client sees.
+		include_usage: bool,
 	},
 }
 
@@ -227,6 +269,28 @@ impl LLMInfo {
 	pub fn input_tokens(&self) -> Option<u64> {
 		self.response.input_tokens.or(self.request.input_tokens)
 	}
+
+	/// Fills in token usage a stream did not report, or only partially reported. Input tokens fall
+	/// back to the request estimate and output tokens are approximated from the streamed output,
+	/// at roughly four bytes per token. Marks the usage as estimated if anything was filled in.
+	pub fn estimate_missing_usage(&mut self, streamed_output_bytes: usize) {
+		let resp = &mut self.response;
+		if resp.input_tokens.unwrap_or_default() == 0
+			&& let Some(estimate) = self.request.input_tokens
+		{
+			resp.input_tokens = Some(estimate);
+			resp.usage_estimated = true;
+		}
+		if resp.output_tokens.unwrap_or_default() == 0 && streamed_output_bytes > 0 {
+			resp.output_tokens = Some(streamed_output_bytes.div_ceil(4) as u64);
+			resp.usage_estimated = true;
+		}
+		if resp.usage_estimated
+			&& let (Some(input), Some(output)) = (resp.input_tokens, resp.output_tokens)
+		{
+			resp.total_tokens = Some(input + output);
+		}
+	}
 }
 
 #[derive(Debug, Clone, Default, serde::Serialize)]
@@ -260,11 +324,50 @@ pub struct LLMResponse {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub service_tier: Option<Strng>,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	pub system_fingerprint: Option<Strng>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub provider_model: Option<Strng>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub completion: Option<Vec<String>>,
 	#[serde(skip)]
 	pub first_token: Option<Instant>,
+	/// Set when the response stream ended abnormally, e.g. `connection_reset`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error_type: Option<Strng>,
+	/// Changes response guards made to the response body.
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub guard_modifications: Vec<GuardModification>,
+	/// Set when some of the token usage was estimated because the stream ended without a complete
+	/// usage report.
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	pub usage_estimated: bool,
+}
+
+/// A change a response guard made to the response. Only names the affected fields; the
+/// original values are never recorded.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GuardModification {
+	/// Position of the guard in the configured response guard list.
+	pub index: usize,
+	/// Kind of guard, e.g. `regex` or `webhook`.
+	pub guard: Strng,
+	/// Action the guard applied, e.g. `mask`.
+	pub action: Strng,
+	/// Response fields the guard rewrote, e.g. `choices[0].message.content`.
+	pub fields: Vec<Strng>,
+}
+
+impl std::fmt::Display for GuardModification {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{};index={};action={};fields=\"{}\"",
+			self.guard,
+			self.index,
+			self.action,
+			self.fields.join(" ")
+		)
+	}
 }
 
 pub trait StreamingUsageReporter: Send {
@@ -340,6 +443,8 @@ pub enum AIError {
 	UnsupportedConversion(Strng),
 	#[error("request was too large")]
 	RequestTooLarge,
+	#[error("request JSON is nested deeper than the maximum of {0}")]
+	RequestTooDeep(usize),
 	#[error("response was too large")]
 	ResponseTooLarge,
 	#[error("prompt guard failed")]
@@ -348,6 +453,8 @@ pub enum AIError {
 	RequestParsing(serde_json::Error),
 	#[error("failed to marshal request: {0}")]
 	RequestMarshal(serde_json::Error),
+	#[error("translated request does not match the provider schema: {0}")]
+	InvalidUpstreamRequest(Strng),
 	#[error("failed to parse response: {0}")]
 	ResponseParsing(serde_json::Error),
 	#[error("invalid response: {0}")]
@@ -0,0 +1,28 @@
+use agent_core::strng;
+use agent_core::strng::Strng;
+
+use crate::{RouteType, apply};
+
+#[apply(schema!)]
+#[cfg_attr(feature = "schema", schemars(rename = "MistralProvider"))]
+pub struct Provider {
+	/// Model ID to send to Mistral, overriding the model in the client request.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub model: Option<Strng>,
+}
+
+impl super::Provider for Provider {
+	const NAME: Strng = strng::literal!("mistral");
+}
+pub const DEFAULT_HOST_STR: &str = "api.mistral.ai";
+pub const DEFAULT_HOST: Strng = strng::literal!(DEFAULT_HOST_STR);
+
+pub const DEFAULT_BASE_PATH: &str = "/v1";
+
+pub fn path_suffix(route: RouteType) -> &'static str {
+	match route {
+		RouteType::Embeddings => "/embeddings",
+		// Mistral serves the OpenAI chat completions API natively; everything else is translated to it.
+		_ => "/chat/completions",
+	}
+}
@@ -23,6 +23,7 @@ pub fn path_suffix(route: RouteType) -> &'static str {
 	match route {
 		RouteType::Responses => "/responses",
 		RouteType::Embeddings => "/embeddings",
+		RouteType::TextCompletions => "/completions",
 		RouteType::Rerank => "/rerank",
 		RouteType::Realtime => "/realtime",
 		// All others get translated down to completions
@@ -135,6 +135,65 @@ impl Decoder for EventStreamCodec {
 	}
 }
 
+/// Maximum number of consecutive messages that may fail to parse before a stream is aborted.
+pub const MAX_CONSECUTIVE_PARSE_FAILURES: usize = 5;
+
+/// Result of checking a parsed message with [`ParseFailures::check`].
+pub enum Parsed<T> {
+	Ok(T),
+	/// The message failed to parse, but the stream can continue.
+	Failed,
+	/// Too many consecutive messages failed to parse; the stream should end with an error.
+	Abort,
+	/// The stream was already aborted; the message should be dropped.
+	Aborted,
+}
+
+/// Tracks message parse failures on a single stream, so a run of malformed frames ends the
+/// stream with one error instead of an unbounded run of errors and log lines.
+#[derive(Debug, Default)]
+pub struct ParseFailures {
+	consecutive: usize,
+	aborted: bool,
+}
+
+impl ParseFailures {
+	pub fn check<T, E: std::fmt::Display>(&mut self, res: Result<T, E>) -> Parsed<T> {
+		if self.aborted {
+			return Parsed::Aborted;
+		}
+		let e = match res {
+			Ok(t) => {
+				self.consecutive = 0;
+				return Parsed::Ok(t);
+			},
+			Err(e) => e,
+		};
+		self.consecutive += 1;
+		if self.consecutive >= MAX_CONSECUTIVE_PARSE_FAILURES {
+			self.aborted = true;
+			tracing::error!(
+				error = %e,
+				failures = self.consecutive,
+				"aborting event stream after repeated parse failures"
+			);
+			return Parsed::Abort;
+		}
+		// Only the first failure in a run is logged loudly; the rest would just repeat it.
+		if self.consecutive == 1 {
+			tracing::error!(error = %e, "failed to parse event stream message");
+		} else {
+			tracing::debug!(error = %e, failures = self.consecutive, "failed to parse event stream message");
+		}
+		Parsed::Failed
+	}
+
+	/// Aborts the stream for a reason other than parse failures; later messages are dropped.
+	pub fn abort(&mut self) {
+		self.aborted = true;
+	}
+}
+
 pub fn transform<O: Serialize>(
 	b: Body,
 	buffer_limit: usize,
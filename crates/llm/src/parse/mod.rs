@@ -22,6 +22,132 @@ pub fn encode_sse_event(event_name: &str, data: Bytes) -> Bytes {
 	out.freeze()
 }
 
+/// Error reported to the client when a streamed tool call's arguments outgrow the buffer limit.
+pub const TOOL_ARGUMENTS_TOO_LARGE_MESSAGE: &str =
+	"Tool call arguments exceeded the stream buffer limit";
+
+/// Appends a streamed tool-call argument fragment to `buf`, unless that would grow it past `limit`
+/// bytes.
+///
+/// Translators that accumulate arguments across events use this so a provider streaming an
+/// unbounded argument cannot grow gateway memory without bound. Returns false, leaving `buf`
+/// unchanged, when the limit would be exceeded; the caller should then abort the stream.
+pub fn append_tool_arguments(buf: &mut String, fragment: &str, limit: usize) -> bool {
+	if buf.len().saturating_add(fragment.len()) > limit {
+		tracing::error!(
+			buffered = buf.len(),
+			fragment = fragment.len(),
+			limit,
+			"aborting stream: tool call arguments exceeded buffer limit"
+		);
+		return false;
+	}
+	buf.push_str(fragment);
+	true
+}
+
+/// Rejoins multibyte characters that an upstream split across two stream events.
+///
+/// Some providers flush text on token boundaries that fall inside a UTF-8 sequence, leaving the
+/// leading bytes at the end of one event's text and the continuation bytes at the start of the
+/// next event's text. Neither event is valid UTF-8 on its own, so both would fail to parse as JSON.
+/// The truncated leading bytes are held back from the first event and spliced in front of the
+/// first run of continuation bytes in a later one. Only bytes that are invalid in their own right,
+/// or a held sequence that a newer truncated sequence displaces, become U+FFFD.
+#[derive(Debug, Default)]
+pub struct SplitUtf8 {
+	pending: Vec<u8>,
+}
+
+impl SplitUtf8 {
+	/// Returns the repaired event payload, or `None` if `data` needs no changes.
+	pub fn rejoin(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+		if std::str::from_utf8(data).is_ok() {
+			return None;
+		}
+		let mut out = Vec::with_capacity(data.len() + self.pending.len() + REPLACEMENT_CHARACTER.len());
+		let mut rest = data;
+		while !rest.is_empty() {
+			let err = match std::str::from_utf8(rest) {
+				Ok(_) => {
+					out.extend_from_slice(rest);
+					break;
+				},
+				Err(err) => err,
+			};
+			let (valid, invalid) = rest.split_at(err.valid_up_to());
+			out.extend_from_slice(valid);
+			if is_utf8_continuation(invalid[0]) && !self.pending.is_empty() {
+				let mut held = std::mem::take(&mut self.pending);
+				let needed = utf8_sequence_len(held[0]).saturating_sub(held.len());
+				let continuation = invalid
+					.iter()
+					.take(needed)
+					.take_while(|b| is_utf8_continuation(**b))
+					.count();
+				held.extend_from_slice(&invalid[..continuation]);
+				rest = &invalid[continuation..];
+				if continuation < needed && rest.is_empty() {
+					// Still incomplete; keep waiting for the rest of the sequence.
+					self.pending = held;
+					break;
+				}
+				if std::str::from_utf8(&held).is_ok() {
+					out.append(&mut held);
+				} else {
+					out.extend_from_slice(REPLACEMENT_CHARACTER);
+				}
+				continue;
+			}
+			match err.error_len() {
+				// A lead byte cut off by the end of the text rather than by a bad continuation byte;
+				// the rest of the sequence should arrive with a later event.
+				Some(bad_len)
+					if bad_len < utf8_sequence_len(invalid[0])
+						&& !invalid
+							.get(bad_len)
+							.is_some_and(|b| is_utf8_continuation(*b)) =>
+				{
+					self.hold(&invalid[..bad_len], &mut out);
+					rest = &invalid[bad_len..];
+				},
+				Some(bad_len) => {
+					out.extend_from_slice(REPLACEMENT_CHARACTER);
+					rest = &invalid[bad_len..];
+				},
+				None => {
+					self.hold(invalid, &mut out);
+					break;
+				},
+			}
+		}
+		Some(out)
+	}
+
+	fn hold(&mut self, truncated: &[u8], out: &mut Vec<u8>) {
+		if !self.pending.is_empty() {
+			// The previously held bytes can no longer be completed.
+			out.extend_from_slice(REPLACEMENT_CHARACTER);
+		}
+		self.pending = truncated.to_vec();
+	}
+}
+
+const REPLACEMENT_CHARACTER: &[u8] = "\u{FFFD}".as_bytes();
+
+fn is_utf8_continuation(b: u8) -> bool {
+	b & 0xC0 == 0x80
+}
+
+fn utf8_sequence_len(lead: u8) -> usize {
+	match lead {
+		0xC2..=0xDF => 2,
+		0xE0..=0xEF => 3,
+		0xF0..=0xF4 => 4,
+		_ => 1,
+	}
+}
+
 #[cfg(test)]
 #[path = "parse_tests.rs"]
 mod tests;
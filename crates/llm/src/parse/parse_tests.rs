@@ -311,3 +311,169 @@ async fn test_sse_json_transform_multi_parse_error_path() {
 		"missing done event after parse error:\n{result}"
 	);
 }
+
+#[tokio::test]
+async fn test_sse_json_transform_multi_aborts_on_oversized_event() {
+	let small = "data: {\"msg\": 1}\n\n";
+	let oversized = format!("data: {{\"msg\": \"{}\"}}\n\n", "x".repeat(4096));
+	let body = Body::from_stream(futures_util::stream::iter(vec![
+		Ok::<_, std::io::Error>(Bytes::copy_from_slice(small.as_bytes())),
+		Ok::<_, std::io::Error>(Bytes::from(oversized)),
+		Ok::<_, std::io::Error>(Bytes::copy_from_slice(small.as_bytes())),
+	]));
+
+	let events = Arc::new(Mutex::new(0));
+	let seen = events.clone();
+	let mut transformed = sse::json_transform_multi::<serde_json::Value, serde_json::Value, _>(
+		body,
+		1024,
+		move |event| {
+			*seen.lock().unwrap() += 1;
+			match event {
+				sse::SseJsonEvent::Data(Ok(v)) => vec![("delta", v)],
+				_ => vec![],
+			}
+		},
+	);
+
+	let first = transformed
+		.frame()
+		.await
+		.unwrap()
+		.unwrap()
+		.into_data()
+		.unwrap();
+	assert_eq!(
+		first,
+		Bytes::from_static(b"event: delta\ndata: {\"msg\":1}\n\n")
+	);
+	assert!(
+		transformed.frame().await.unwrap().is_err(),
+		"oversized event should abort the stream"
+	);
+	// Nothing past the oversized event is handed to the translator.
+	assert_eq!(*events.lock().unwrap(), 1);
+}
+
+#[test]
+fn test_append_tool_arguments_respects_limit() {
+	let mut buf = String::new();
+	assert!(super::append_tool_arguments(&mut buf, "{\"a\":", 8));
+	assert!(super::append_tool_arguments(&mut buf, "1}", 8));
+	assert!(!super::append_tool_arguments(&mut buf, "extra", 8));
+	assert_eq!(buf, "{\"a\":1}");
+}
+
+#[tokio::test]
+async fn test_sse_json_transform_rejoins_split_multibyte_characters() {
+	// "é" (C3 A9) and "😀" (F0 9F 98 80) each split across two events, as some upstreams do
+	// when a token boundary falls inside a character.
+	let mut msg1 = b"data: {\"text\": \"caf\xC3".to_vec();
+	msg1.extend_from_slice(b"\"}\n\n");
+	let mut msg2 = b"data: {\"text\": \"\xA9 \xF0\x9F".to_vec();
+	msg2.extend_from_slice(b"\"}\n\n");
+	let mut msg3 = b"data: {\"text\": \"\x98\x80!".to_vec();
+	msg3.extend_from_slice(b"\"}\n\n");
+	let body = Body::from_stream(futures_util::stream::iter(
+		[msg1, msg2, msg3].map(|m| Ok::<_, std::io::Error>(Bytes::from(m))),
+	));
+
+	#[derive(Deserialize, Serialize)]
+	struct Delta {
+		text: String,
+	}
+
+	let texts = Arc::new(Mutex::new(vec![]));
+	let texts_clone = texts.clone();
+	let transformed = sse::json_transform::<Delta, Delta>(body, 1024, move |delta| {
+		let delta = delta.expect("event should parse");
+		texts_clone.lock().unwrap().push(delta.text.clone());
+		Some(delta)
+	});
+	transformed.collect().await.unwrap();
+
+	assert_eq!(texts.lock().unwrap().concat(), "café 😀!");
+}
+
+#[tokio::test]
+async fn test_sse_heartbeats_are_ignored() {
+	let chunks = [
+		": connected\n\n",
+		"data: {\"msg\": 1}\n\n",
+		": keep-alive\n\n",
+		"event: ping\ndata:\n\n",
+		":\n",
+		"data: {\"msg\": 2}\n\n",
+		"data: \n\n",
+		": ping\ndata: [DONE]\n\n",
+	];
+	let body = || {
+		Body::from_stream(futures_util::stream::iter(
+			chunks.map(|c| Ok::<_, std::io::Error>(Bytes::from_static(c.as_bytes()))),
+		))
+	};
+
+	let events = Arc::new(Mutex::new(vec![]));
+	let events_clone = events.clone();
+	let passthrough = sse::json_passthrough::<Test>(body(), 1024, move |o| {
+		events_clone
+			.lock()
+			.unwrap()
+			.push(o.map(|r| r.expect("heartbeat was parsed as data")));
+	});
+	passthrough.collect().await.unwrap();
+	assert_eq!(
+		events.lock().unwrap().clone(),
+		vec![Some(Test { msg: 1 }), Some(Test { msg: 2 }), None]
+	);
+
+	let transformed =
+		sse::json_transform_multi::<Test, serde_json::Value, _>(body(), 1024, |event| match event {
+			sse::SseJsonEvent::Data(input) => {
+				let input = input.expect("heartbeat was parsed as data");
+				vec![("", serde_json::json!({"msg": input.msg}))]
+			},
+			sse::SseJsonEvent::Done => vec![],
+		});
+	let result = transformed.collect().await.unwrap().to_bytes();
+	assert_eq!(
+		String::from_utf8_lossy(&result),
+		"data: {\"msg\":1}\n\ndata: {\"msg\":2}\n\n"
+	);
+}
+
+#[test]
+fn test_split_utf8_replaces_unrecoverable_bytes() {
+	let mut split = super::SplitUtf8::default();
+	assert_eq!(split.rejoin(b"plain"), None);
+	assert_eq!(
+		split.rejoin(b"a\xFFb").as_deref(),
+		Some("a\u{FFFD}b".as_bytes())
+	);
+	// Invalid in its own right, not a truncated sequence.
+	assert_eq!(
+		split.rejoin(b"a\xE0\x80b").as_deref(),
+		Some("a\u{FFFD}\u{FFFD}b".as_bytes())
+	);
+	assert_eq!(
+		split.rejoin(b"\xACafter").as_deref(),
+		Some("\u{FFFD}after".as_bytes())
+	);
+	// A truncated sequence waits for its continuation, even across events without one.
+	assert_eq!(split.rejoin(b"x\xE2\x82").as_deref(), Some(&b"x"[..]));
+	assert_eq!(split.rejoin(b"next"), None);
+	assert_eq!(
+		split.rejoin(b"{\xAC}").as_deref(),
+		Some("{\u{20AC}}".as_bytes())
+	);
+	// A held sequence displaced by a newer truncated one can no longer be completed.
+	assert_eq!(split.rejoin(b"[\xE2\x82]").as_deref(), Some(&b"[]"[..]));
+	assert_eq!(
+		split.rejoin(b"[\xC3]").as_deref(),
+		Some("[\u{FFFD}]".as_bytes())
+	);
+	assert_eq!(
+		split.rejoin(b"[\xA9]").as_deref(),
+		Some("[\u{E9}]".as_bytes())
+	);
+}
@@ -5,6 +5,7 @@ use serde::de::DeserializeOwned;
 use tokio_sse_codec::{Event, Frame, SseDecoder};
 use tokio_util::codec::BytesCodec;
 
+use super::SplitUtf8;
 use super::passthrough::parser as passthrough_parser;
 use super::transform::parser as transform_parser;
 
@@ -14,11 +15,13 @@ pub fn json_passthrough<F: DeserializeOwned>(
 	mut f: impl FnMut(Option<anyhow::Result<F>>) + Send + 'static,
 ) -> Body {
 	let decoder = SseDecoder::<Bytes>::with_max_size(buffer_limit);
+	let mut split = SplitUtf8::default();
 
 	passthrough_parser(b, decoder, move |o| {
 		let Some(data) = unwrap_sse_data(o) else {
 			return;
 		};
+		let data = rejoin(&mut split, data);
 		if data.as_ref() == b"[DONE]" {
 			f(None);
 			return;
@@ -34,11 +37,13 @@ pub fn permissive_json_passthrough<F: DeserializeOwned>(
 	mut f: impl FnMut(Option<anyhow::Result<F>>) + Send + 'static,
 ) -> Body {
 	let decoder = SseDecoder::<Bytes>::with_max_size(buffer_limit);
+	let mut split = SplitUtf8::default();
 
 	crate::parse::passthrough::full_passthrough_parser(b, decoder, move |o| {
 		let Some(data) = unwrap_sse_data(o) else {
 			return;
 		};
+		let data = rejoin(&mut split, data);
 		if data.as_ref() == b"[DONE]" {
 			f(None);
 			return;
@@ -55,6 +60,7 @@ pub fn json_transform<I: DeserializeOwned, O: Serialize>(
 ) -> Body {
 	let decoder = SseDecoder::<Bytes>::with_max_size(buffer_limit);
 	let encoder = BytesCodec::new();
+	let mut split = SplitUtf8::default();
 
 	transform_parser(b, decoder, encoder, move |o| {
 		let data = unwrap_sse_data(o)?;
@@ -65,6 +71,7 @@ pub fn json_transform<I: DeserializeOwned, O: Serialize>(
 				Bytes::from_static(b"[DONE]"),
 			));
 		}
+		let data = rejoin(&mut split, data);
 		let obj = serde_json::from_slice::<I>(&data);
 		let transformed = f(obj.map_err(anyhow::Error::from))?;
 		let json_bytes = serde_json::to_vec(&transformed).ok()?;
@@ -88,6 +95,7 @@ where
 {
 	let decoder = SseDecoder::<Bytes>::with_max_size(buffer_limit);
 	let encoder = BytesCodec::new();
+	let mut split = SplitUtf8::default();
 
 	transform_parser(b, decoder, encoder, move |o| {
 		let data = unwrap_sse_data(o);
@@ -109,6 +117,7 @@ where
 			return vec![];
 		};
 
+		let data = rejoin(&mut split, data);
 		let obj = serde_json::from_slice::<I>(&data);
 		f(SseJsonEvent::Data(obj.map_err(anyhow::Error::from)))
 			.into_iter()
@@ -123,10 +132,19 @@ where
 	})
 }
 
+fn rejoin(split: &mut SplitUtf8, data: Bytes) -> Bytes {
+	split.rejoin(&data).map(Bytes::from).unwrap_or(data)
+}
+
+/// The data of an SSE event. Comment lines (`: keep-alive`) and events without any data, which
+/// upstreams send as heartbeats, carry no content and are skipped.
 fn unwrap_sse_data(frame: Frame<Bytes>) -> Option<Bytes> {
 	let Frame::Event(Event::<Bytes> { data, .. }) = frame else {
 		return None;
 	};
+	if data.trim_ascii().is_empty() {
+		return None;
+	}
 	Some(data)
 }
 
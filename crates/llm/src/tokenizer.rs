@@ -3,15 +3,35 @@ use tiktoken_rs::tokenizer::{Tokenizer, get_tokenizer};
 
 use crate::{AIError, SimpleChatCompletionMessage};
 
-pub fn num_tokens_from_messages(
-	model: &str,
-	messages: &[SimpleChatCompletionMessage],
-) -> Result<u64, AIError> {
+/// Tokens counted for each image block. The real cost depends on the image size and detail
+/// level, which are unknown without decoding the image; this is the cost of a low-detail OpenAI
+/// image.
+pub const DEFAULT_IMAGE_TOKENS: u64 = 85;
+
+/// Request input that is not message text but still counts towards the prompt.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NonTextInput {
+	/// Number of image blocks in the messages.
+	pub images: u64,
+	/// Text sent alongside the messages: tool-call names and arguments, tool results that are
+	/// not message text, and tool definitions serialized as JSON.
+	pub tool_text: Vec<String>,
+}
+
+fn bpe_for_model<'a>(model: &str) -> Result<&'a CoreBPE, AIError> {
 	let tokenizer = get_tokenizer(model).unwrap_or(Tokenizer::Cl100kBase);
 	if tokenizer != Tokenizer::Cl100kBase && tokenizer != Tokenizer::O200kBase {
 		return Err(AIError::UnsupportedModel);
 	}
-	let bpe = get_bpe_from_tokenizer(tokenizer);
+	Ok(get_bpe_from_tokenizer(tokenizer))
+}
+
+/// Counts the textual content of `messages`.
+pub fn num_tokens_from_messages(
+	model: &str,
+	messages: &[SimpleChatCompletionMessage],
+) -> Result<u64, AIError> {
+	let bpe = bpe_for_model(model)?;
 	let tokens_per_message = 3;
 
 	let mut num_tokens: u64 = 0;
@@ -26,6 +46,30 @@ pub fn num_tokens_from_messages(
 	Ok(num_tokens)
 }
 
+/// Counts the non-text input of a request, charging `image_tokens` per image block.
+pub fn num_tokens_from_non_text(
+	model: &str,
+	input: &NonTextInput,
+	image_tokens: u64,
+) -> Result<u64, AIError> {
+	let bpe = bpe_for_model(model)?;
+	let text_tokens: u64 = input
+		.tool_text
+		.iter()
+		.map(|t| bpe.encode_with_special_tokens(t).len() as u64)
+		.sum();
+	Ok(text_tokens + input.images * image_tokens)
+}
+
+/// Whether the model has a known tokenizer that can be used to count its tokens locally. Other
+/// models are counted with `cl100k_base`, which is only an approximation.
+pub fn has_local_tokenizer(model: &str) -> bool {
+	matches!(
+		get_tokenizer(model),
+		Some(Tokenizer::Cl100kBase | Tokenizer::O200kBase)
+	)
+}
+
 pub fn preload_tokenizers() {
 	let _ = tiktoken_rs::cl100k_base_singleton();
 	let _ = tiktoken_rs::o200k_base_singleton();
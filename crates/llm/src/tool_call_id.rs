@@ -0,0 +1,127 @@
+/// Tool-call id format required by a provider.
+///
+/// Ids that already match the format are kept as-is, so ids issued by the provider round-trip
+/// unchanged. Other ids are mapped deterministically, so a client id maps to the same provider id on
+/// every turn without the gateway keeping per-session state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolCallIdFormat {
+	/// Exactly 9 ASCII alphanumeric characters.
+	Mistral,
+	/// 1 to 64 characters from `[a-zA-Z0-9_-]`.
+	Bedrock,
+}
+
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const MISTRAL_ID_LEN: usize = 9;
+const BEDROCK_ID_MAX_LEN: usize = 64;
+
+impl ToolCallIdFormat {
+	pub fn accepts(&self, id: &str) -> bool {
+		match self {
+			ToolCallIdFormat::Mistral => {
+				id.len() == MISTRAL_ID_LEN && id.bytes().all(|b| b.is_ascii_alphanumeric())
+			},
+			ToolCallIdFormat::Bedrock => {
+				!id.is_empty()
+					&& id.len() <= BEDROCK_ID_MAX_LEN
+					&& id
+						.bytes()
+						.all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+			},
+		}
+	}
+
+	/// Maps `id` into this format.
+	pub fn normalize(&self, id: &str) -> String {
+		if self.accepts(id) {
+			return id.to_string();
+		}
+		match self {
+			ToolCallIdFormat::Mistral => base62(fnv1a(id), MISTRAL_ID_LEN),
+			ToolCallIdFormat::Bedrock => format!("tooluse_{}", base62(fnv1a(id), 11)),
+		}
+	}
+}
+
+// FNV-1a is used over `DefaultHasher` because its output must not change between releases: clients
+// may carry a mapped id across a gateway upgrade.
+fn fnv1a(s: &str) -> u64 {
+	s.bytes().fold(0xcbf29ce484222325, |hash, b| {
+		(hash ^ b as u64).wrapping_mul(0x100000001b3)
+	})
+}
+
+fn base62(mut n: u64, len: usize) -> String {
+	(0..len)
+		.map(|_| {
+			let c = BASE62[(n % 62) as usize] as char;
+			n /= 62;
+			c
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::*;
+	use crate::types::RequestType;
+	use crate::types::completions;
+
+	fn tool_call_ids(req: &completions::Request) -> Vec<String> {
+		let mut ids = vec![];
+		for m in &req.messages {
+			for call in m.tool_calls.iter().flatten() {
+				ids.push(call["id"].as_str().unwrap().to_string());
+			}
+			ids.extend(m.tool_call_id.clone());
+		}
+		ids
+	}
+
+	#[test]
+	fn tool_call_ids_are_stable_across_turns() {
+		let first_turn = json!({
+			"model": "mistral-large",
+			"messages": [
+				{"role": "user", "content": "What's the weather in Paris?"},
+				{"role": "assistant", "tool_calls": [
+					{"id": "call_Zx9.weather-1", "type": "function", "function": {"name": "weather", "arguments": "{}"}}
+				]},
+				{"role": "tool", "tool_call_id": "call_Zx9.weather-1", "content": "sunny"},
+			]
+		});
+		let mut second_turn = first_turn.clone();
+		let messages = second_turn["messages"].as_array_mut().unwrap();
+		messages.push(json!({"role": "assistant", "tool_calls": [
+			{"id": "AbC123xyz", "type": "function", "function": {"name": "weather", "arguments": "{}"}}
+		]}));
+		messages.push(json!({"role": "tool", "tool_call_id": "AbC123xyz", "content": "rainy"}));
+
+		let mut first: completions::Request = serde_json::from_value(first_turn).unwrap();
+		let mut second: completions::Request = serde_json::from_value(second_turn).unwrap();
+		first.normalize_tool_call_ids(ToolCallIdFormat::Mistral);
+		second.normalize_tool_call_ids(ToolCallIdFormat::Mistral);
+
+		let first = tool_call_ids(&first);
+		let second = tool_call_ids(&second);
+		assert_eq!(first.len(), 2);
+		assert!(ToolCallIdFormat::Mistral.accepts(&first[0]));
+		// The call and its result still refer to the same id.
+		assert_eq!(first[0], first[1]);
+		// The client id maps to the same provider id on the next turn.
+		assert_eq!(second[..2], first[..]);
+		// Provider-issued ids are passed through untouched.
+		assert_eq!(second[2..], ["AbC123xyz", "AbC123xyz"]);
+	}
+
+	#[test]
+	fn bedrock_keeps_valid_ids() {
+		let format = ToolCallIdFormat::Bedrock;
+		assert_eq!(format.normalize("toolu_01A-b_c"), "toolu_01A-b_c");
+		let mapped = format.normalize("call.with:dots");
+		assert!(format.accepts(&mapped));
+		assert_eq!(mapped, format.normalize("call.with:dots"));
+	}
+}
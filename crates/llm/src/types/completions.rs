@@ -3,6 +3,8 @@ use agent_core::strng::Strng;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use crate::tokenizer::NonTextInput;
+use crate::tool_call_id::ToolCallIdFormat;
 use crate::types::{ResponseType, SimpleChatCompletionMessage};
 use crate::webhook::{Message, ResponseChoice};
 use crate::{AIError, InputFormat, LLMRequest, LLMRequestParams, LLMResponse, json};
@@ -57,6 +59,43 @@ impl Request {
 		self.max_tokens = None;
 	}
 
+	/// Whether the request sets the deprecated `functions` alongside `tools`.
+	pub fn has_deprecated_functions_with_tools(&self) -> bool {
+		self.tools.is_some() && self.rest.get("functions").is_some()
+	}
+
+	/// Removes the deprecated `functions` and `function_call` fields, leaving `tools` and
+	/// `tool_choice` as the only tool definitions.
+	pub fn drop_deprecated_functions(&mut self) {
+		if let Some(rest) = self.rest.as_object_mut() {
+			rest.remove("functions");
+			rest.remove("function_call");
+		}
+	}
+
+	/// Whether the request sets the OpenAI-specific `logit_bias`.
+	pub fn has_logit_bias(&self) -> bool {
+		self.rest.get("logit_bias").is_some_and(|v| !v.is_null())
+	}
+
+	/// Removes the OpenAI-specific `logit_bias` field.
+	pub fn drop_logit_bias(&mut self) {
+		if let Some(rest) = self.rest.as_object_mut() {
+			rest.remove("logit_bias");
+		}
+	}
+
+	/// Rewrites `developer` messages as `system` messages, for providers that do not accept the
+	/// `developer` role. Returns whether any message was rewritten.
+	pub fn map_developer_role_to_system(&mut self) -> bool {
+		let mut changed = false;
+		for m in self.messages.iter_mut().filter(|m| m.role == "developer") {
+			m.role = "system".to_string();
+			changed = true;
+		}
+		changed
+	}
+
 	fn requires_openai_max_completion_tokens(&self) -> bool {
 		self
 			.model
@@ -80,6 +119,8 @@ pub struct Response {
 	pub model: String,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub service_tier: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub system_fingerprint: Option<String>,
 	pub usage: Option<Usage>,
 	/// A list of chat completion choices. Can be more than one if `n` is greater than 1.
 	#[serde(default)]
@@ -191,6 +232,7 @@ impl ResponseType for Response {
 				.as_ref()
 				.and_then(|u| u.cache_creation_input_tokens),
 			service_tier: self.service_tier.as_deref().map(Into::into),
+			system_fingerprint: self.system_fingerprint.as_deref().map(Into::into),
 			provider_model: Some(strng::new(&self.model)),
 			completion: if include_completion_in_log {
 				Some(
@@ -204,6 +246,9 @@ impl ResponseType for Response {
 				None
 			},
 			first_token: Default::default(),
+			error_type: None,
+			guard_modifications: Vec::new(),
+			usage_estimated: false,
 		}
 	}
 
@@ -234,6 +279,9 @@ impl ResponseType for Response {
 	fn serialize(&self) -> serde_json::Result<Vec<u8>> {
 		serde_json::to_vec(&self)
 	}
+	fn has_no_choices(&self) -> bool {
+		self.choices.is_empty()
+	}
 }
 
 impl super::RequestType for Request {
@@ -322,9 +370,91 @@ impl super::RequestType for Request {
 			.collect()
 	}
 
+	fn get_non_text_input(&self) -> NonTextInput {
+		let mut input = NonTextInput::default();
+		for m in &self.messages {
+			if let Some(Content::Array(parts)) = &m.content {
+				input.images += parts.iter().filter(|p| p.r#type == "image_url").count() as u64;
+			}
+			for call in m.tool_calls.iter().flatten() {
+				if let Some(function) = call.get("function") {
+					input.tool_text.extend(
+						["name", "arguments"]
+							.into_iter()
+							.filter_map(|k| function.get(k)?.as_str().map(str::to_string)),
+					);
+				}
+			}
+		}
+		input
+			.tool_text
+			.extend(self.tools.iter().flatten().map(|t| t.to_string()));
+		input
+	}
+
 	fn set_messages(&mut self, messages: Vec<SimpleChatCompletionMessage>) {
 		self.messages = messages.into_iter().map(convert_message).collect();
 	}
+
+	fn apply_default_params(&mut self, defaults: &LLMRequestParams) {
+		self.temperature = self.temperature.or(defaults.temperature.map(|v| v as f32));
+		self.top_p = self.top_p.or(defaults.top_p.map(|v| v as f32));
+		self.frequency_penalty = self
+			.frequency_penalty
+			.or(defaults.frequency_penalty.map(|v| v as f32));
+		self.presence_penalty = self
+			.presence_penalty
+			.or(defaults.presence_penalty.map(|v| v as f32));
+		self.seed = self.seed.or(defaults.seed);
+		if self.max_tokens.is_none() && self.max_completion_tokens.is_none() {
+			self.max_tokens = defaults
+				.max_tokens
+				.map(|v| u32::try_from(v).unwrap_or(u32::MAX));
+		}
+	}
+
+	fn disable_streaming(&mut self) -> bool {
+		if self.stream != Some(true) {
+			return false;
+		}
+		self.stream = None;
+		// Only valid alongside `stream: true`.
+		self.stream_options = None;
+		true
+	}
+
+	fn cap_max_output_tokens(&mut self, cap: u64) -> bool {
+		let cap = u32::try_from(cap).unwrap_or(u32::MAX);
+		if self.max_tokens.is_none() && self.max_completion_tokens.is_none() {
+			// `max_tokens` is deprecated and rejected by reasoning models.
+			self.max_completion_tokens = Some(cap);
+			return true;
+		}
+		let mut changed = false;
+		for limit in [&mut self.max_tokens, &mut self.max_completion_tokens]
+			.into_iter()
+			.flatten()
+		{
+			if *limit > cap {
+				*limit = cap;
+				changed = true;
+			}
+		}
+		changed
+	}
+
+	fn normalize_tool_call_ids(&mut self, format: ToolCallIdFormat) {
+		for m in &mut self.messages {
+			if let Some(id) = &mut m.tool_call_id {
+				*id = format.normalize(id);
+			}
+			for call in m.tool_calls.iter_mut().flatten() {
+				if let Some(serde_json::Value::String(id)) = call.get_mut("id") {
+					*id = format.normalize(id);
+				}
+			}
+		}
+	}
 }
 
 fn convert_message(r: SimpleChatCompletionMessage) -> RequestMessage {
@@ -3,6 +3,7 @@ use agent_core::strng;
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
+use crate::tokenizer::NonTextInput;
 use crate::types::{RequestType, messages};
 use crate::{
 	AIError, InputFormat, LLMRequest, SimpleChatCompletionMessage, logged_response_parsing,
@@ -52,6 +53,10 @@ impl RequestType for Request {
 		messages::get_messages_helper(&self.messages, &self.system)
 	}
 
+	fn get_non_text_input(&self) -> NonTextInput {
+		messages::non_text_input_helper(&self.messages, self.rest.get("tools"))
+	}
+
 	fn set_messages(&mut self, _messages: Vec<SimpleChatCompletionMessage>) {
 		unimplemented!(
 			"set_messages is used for prompt guard; prompt guard is disable for token counting."
@@ -417,6 +417,10 @@ mod lookups {
 		// Messages
 		&["usage", "service_tier"],
 	];
+	pub const SYSTEM_FINGERPRINT: [&[&str]; 1] = [
+		// Completions
+		&["system_fingerprint"],
+	];
 }
 
 impl<'de> Deserialize<'de> for Response {
@@ -449,10 +453,16 @@ impl ResponseType for Response {
 			service_tier: self
 				.lookup(lookups::SERVICE_TIER, |v| v.as_str())
 				.map(Into::into),
+			system_fingerprint: self
+				.lookup(lookups::SYSTEM_FINGERPRINT, |v| v.as_str())
+				.map(Into::into),
 			provider_model: self.lookup(lookups::MODEL, |v| v.as_str()).map(Into::into),
 			completion: None,
 			// TODO: we could probably derive this
 			first_token: None,
+			error_type: None,
+			guard_modifications: Vec::new(),
+			usage_estimated: false,
 		}
 	}
 
@@ -584,6 +594,12 @@ pub fn amend_from_stream_response(log: &mut StreamingUsageGuard, f: &StreamRespo
 		|v| v.as_str(),
 		|l, v| l.response.service_tier = Some(v.into()),
 	);
+	f.set_if(
+		log,
+		lookups::SYSTEM_FINGERPRINT,
+		|v| v.as_str(),
+		|l, v| l.response.system_fingerprint = Some(v.into()),
+	);
 	if total_tokens.is_none()
 		&& let (Some(input), Some(output)) = (input_tokens, output_tokens)
 	{
@@ -3,6 +3,8 @@ use agent_core::strng;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use crate::tokenizer::NonTextInput;
+use crate::tool_call_id::ToolCallIdFormat;
 use crate::types::{RequestType, ResponseType, SimpleChatCompletionMessage};
 use crate::webhook::{Message, ResponseChoice};
 use crate::{AIError, InputFormat, LLMRequest, LLMRequestParams, LLMResponse};
@@ -113,6 +115,43 @@ pub struct Usage {
 	pub rest: serde_json::Value,
 }
 
+pub fn non_text_input_helper(
+	messages: &[RequestMessage],
+	tools: Option<&serde_json::Value>,
+) -> NonTextInput {
+	let mut input = NonTextInput::default();
+	let parts = messages.iter().flat_map(|m| match &m.content {
+		Some(ContentBlock::Array(parts)) => parts.as_slice(),
+		_ => &[],
+	});
+	for part in parts {
+		let ContentPart::Unknown(part) = part else {
+			continue;
+		};
+		match part.get("type").and_then(serde_json::Value::as_str) {
+			Some("image") => input.images += 1,
+			Some("tool_use") => {
+				if let Some(name) = part.get("name").and_then(serde_json::Value::as_str) {
+					input.tool_text.push(name.to_string());
+				}
+				if let Some(args) = part.get("input") {
+					input.tool_text.push(args.to_string());
+				}
+			},
+			Some("tool_result") => match part.get("content") {
+				Some(serde_json::Value::String(s)) => input.tool_text.push(s.clone()),
+				Some(content) => input.tool_text.push(content.to_string()),
+				None => {},
+			},
+			_ => {},
+		}
+	}
+	if let Some(serde_json::Value::Array(tools)) = tools {
+		input.tool_text.extend(tools.iter().map(|t| t.to_string()));
+	}
+	input
+}
+
 pub fn get_messages_helper(
 	messages: &[RequestMessage],
 	system: &Option<TextBlock>,
@@ -229,6 +268,10 @@ impl RequestType for Request {
 		get_messages_helper(&self.messages, &self.system)
 	}
 
+	fn get_non_text_input(&self) -> NonTextInput {
+		non_text_input_helper(&self.messages, self.rest.get("tools"))
+	}
+
 	fn set_messages(&mut self, messages: Vec<SimpleChatCompletionMessage>) {
 		let (system_prompts, message_prompts): (Vec<_>, Vec<_>) = messages
 			.into_iter()
@@ -250,6 +293,49 @@ impl RequestType for Request {
 		};
 		self.messages = message_prompts.into_iter().map(Into::into).collect();
 	}
+
+	fn apply_default_params(&mut self, defaults: &LLMRequestParams) {
+		self.temperature = self.temperature.or(defaults.temperature.map(|v| v as f32));
+		self.top_p = self.top_p.or(defaults.top_p.map(|v| v as f32));
+		self.max_tokens = self.max_tokens.or(defaults.max_tokens);
+	}
+
+	fn disable_streaming(&mut self) -> bool {
+		if self.stream != Some(true) {
+			return false;
+		}
+		self.stream = None;
+		true
+	}
+
+	fn cap_max_output_tokens(&mut self, cap: u64) -> bool {
+		if self.max_tokens.is_some_and(|v| v <= cap) {
+			return false;
+		}
+		self.max_tokens = Some(cap);
+		true
+	}
+
+	fn normalize_tool_call_ids(&mut self, format: ToolCallIdFormat) {
+		for m in &mut self.messages {
+			let Some(ContentBlock::Array(parts)) = &mut m.content else {
+				continue;
+			};
+			for part in parts {
+				let ContentPart::Unknown(part) = part else {
+					continue;
+				};
+				let key = match part.get("type").and_then(serde_json::Value::as_str) {
+					Some("tool_use") => "id",
+					Some("tool_result") => "tool_use_id",
+					_ => continue,
+				};
+				if let Some(serde_json::Value::String(id)) = part.get_mut(key) {
+					*id = format.normalize(id);
+				}
+			}
+		}
+	}
 }
 
 pub fn prepend_prompts_helper(
@@ -351,6 +437,7 @@ impl ResponseType for Response {
 			cache_creation_input_tokens: self.usage.cache_creation_input_tokens,
 			cached_input_tokens: self.usage.cache_read_input_tokens,
 			service_tier: self.usage.service_tier.as_deref().map(Into::into),
+			system_fingerprint: None,
 			completion: if include_completion_in_log {
 				Some(
 					self
@@ -363,6 +450,9 @@ impl ResponseType for Response {
 				None
 			},
 			first_token: Default::default(),
+			error_type: None,
+			guard_modifications: Vec::new(),
+			usage_estimated: false,
 		}
 	}
 
@@ -790,6 +880,9 @@ pub mod typed {
 		},
 		MessageStop,
 		Ping,
+		Error {
+			error: MessagesError,
+		},
 	}
 
 	impl MessagesStreamEvent {
@@ -804,6 +897,7 @@ pub mod typed {
 				Self::MessageDelta { .. } => "message_delta",
 				Self::MessageStop => "message_stop",
 				Self::Ping => "ping",
+				Self::Error { .. } => "error",
 			}
 		}
 
@@ -990,6 +1084,7 @@ pub mod typed {
 				cache_creation_input_tokens: self.usage.cache_creation_input_tokens.map(|i| i as u64),
 				cached_input_tokens: self.usage.cache_read_input_tokens.map(|i| i as u64),
 				service_tier: self.usage.service_tier.as_deref().map(Into::into),
+				system_fingerprint: None,
 				provider_model: Some(agent_core::strng::new(&self.model)),
 				count_tokens: None,
 				completion: if include_completion_in_log {
@@ -1007,6 +1102,9 @@ pub mod typed {
 					None
 				},
 				first_token: Default::default(),
+				error_type: None,
+				guard_modifications: Vec::new(),
+				usage_estimated: false,
 			}
 		}
 
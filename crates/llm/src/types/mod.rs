@@ -6,13 +6,16 @@ pub mod embeddings;
 pub mod messages;
 pub mod rerank;
 pub mod responses;
+pub mod text_completions;
 pub mod vertex;
 
 use agent_core::prelude::Strng;
 use agent_core::strng;
 use serde::Serialize;
 
-use crate::{AIError, LLMRequest, LLMResponse, apply};
+use crate::tokenizer::NonTextInput;
+use crate::tool_call_id::ToolCallIdFormat;
+use crate::{AIError, LLMRequest, LLMRequestParams, LLMResponse, apply};
 
 pub enum ChatRequest<'a> {
 	Completions(&'a completions::Request),
@@ -30,6 +33,10 @@ pub trait ResponseType: Send + Sync {
 		resp: Vec<crate::webhook::ResponseChoice>,
 	) -> anyhow::Result<()>;
 	fn serialize(&self) -> serde_json::Result<Vec<u8>>;
+	/// True when a successful response carries no choices at all.
+	fn has_no_choices(&self) -> bool {
+		false
+	}
 }
 
 /// RequestType is an abstraction over provider/endpoint specific request formats that enables
@@ -43,7 +50,25 @@ pub trait RequestType: Send + Sync {
 	fn append_prompts(&mut self, prompts: Vec<SimpleChatCompletionMessage>);
 	fn to_llm_request(&self, provider: Strng, tokenize: bool) -> Result<LLMRequest, AIError>;
 	fn get_messages(&self) -> Vec<SimpleChatCompletionMessage>;
+	/// Images, tool calls and tool definitions, which `get_messages` leaves out, for token counting.
+	fn get_non_text_input(&self) -> NonTextInput {
+		NonTextInput::default()
+	}
 	fn set_messages(&mut self, messages: Vec<SimpleChatCompletionMessage>);
+	/// Fill in request parameters the client left unset.
+	fn apply_default_params(&mut self, _defaults: &LLMRequestParams) {}
+	/// Lower the requested output token limit to `cap`, or set it if the client left it unset.
+	/// Returns true if the request was changed.
+	fn cap_max_output_tokens(&mut self, _cap: u64) -> bool {
+		false
+	}
+	/// Rewrite tool-call ids in the conversation history into the provider's format.
+	fn normalize_tool_call_ids(&mut self, _format: ToolCallIdFormat) {}
+	/// Turn a streaming request into a non-streaming one. Returns true if the request asked to
+	/// stream.
+	fn disable_streaming(&mut self) -> bool {
+		false
+	}
 }
 
 /// SimpleChatCompletionMessage is a simplified chat message
@@ -7,6 +7,8 @@ use self::typed::{
 	Role,
 };
 use super::*;
+use crate::tokenizer::NonTextInput;
+use crate::tool_call_id::ToolCallIdFormat;
 use crate::{
 	AIError, InputFormat, LLMRequest, LLMRequestParams, LLMResponse, RequestType, ResponseType,
 };
@@ -369,6 +371,38 @@ impl RequestType for Request {
 		}
 	}
 
+	fn get_non_text_input(&self) -> NonTextInput {
+		let mut input = NonTextInput::default();
+		if let RequestInput::Items(items) = &self.input {
+			for RawInputItem(item) in items {
+				match item.get("type").and_then(Value::as_str) {
+					Some("function_call") => input.tool_text.extend(
+						["name", "arguments"]
+							.into_iter()
+							.filter_map(|k| item.get(k)?.as_str().map(str::to_string)),
+					),
+					Some("function_call_output") => match item.get("output") {
+						Some(Value::String(s)) => input.tool_text.push(s.clone()),
+						Some(output) => input.tool_text.push(output.to_string()),
+						None => {},
+					},
+					_ => {
+						if let Some(Value::Array(parts)) = item.get("content") {
+							input.images += parts
+								.iter()
+								.filter(|p| p.get("type").and_then(Value::as_str) == Some("input_image"))
+								.count() as u64;
+						}
+					},
+				}
+			}
+		}
+		if let Some(Value::Array(tools)) = self.rest.get("tools") {
+			input.tool_text.extend(tools.iter().map(|t| t.to_string()));
+		}
+		input
+	}
+
 	fn set_messages(&mut self, messages: Vec<SimpleChatCompletionMessage>) {
 		self.input = RequestInput::Items(
 			messages
@@ -377,6 +411,50 @@ impl RequestType for Request {
 				.collect(),
 		);
 	}
+
+	fn apply_default_params(&mut self, defaults: &LLMRequestParams) {
+		self.temperature = self.temperature.or(defaults.temperature.map(|v| v as f32));
+		self.top_p = self.top_p.or(defaults.top_p.map(|v| v as f32));
+		self.max_output_tokens = self.max_output_tokens.or(
+			defaults
+				.max_tokens
+				.map(|v| u32::try_from(v).unwrap_or(u32::MAX)),
+		);
+	}
+
+	fn disable_streaming(&mut self) -> bool {
+		if self.stream != Some(true) {
+			return false;
+		}
+		self.stream = None;
+		true
+	}
+
+	fn cap_max_output_tokens(&mut self, cap: u64) -> bool {
+		let cap = u32::try_from(cap).unwrap_or(u32::MAX);
+		if self.max_output_tokens.is_some_and(|v| v <= cap) {
+			return false;
+		}
+		self.max_output_tokens = Some(cap);
+		true
+	}
+
+	fn normalize_tool_call_ids(&mut self, format: ToolCallIdFormat) {
+		let RequestInput::Items(items) = &mut self.input else {
+			return;
+		};
+		for RawInputItem(item) in items {
+			if !matches!(
+				item.get("type").and_then(Value::as_str),
+				Some("function_call" | "function_call_output")
+			) {
+				continue;
+			}
+			if let Some(Value::String(id)) = item.get_mut("call_id") {
+				*id = format.normalize(id);
+			}
+		}
+	}
 }
 
 impl ResponseType for Response {
@@ -410,6 +488,7 @@ impl ResponseType for Response {
 			}),
 			cache_creation_input_tokens: None,
 			service_tier: self.service_tier.as_deref().map(Into::into),
+			system_fingerprint: None,
 			provider_model: Some(strng::new(&self.model)),
 			completion: if include_completion_in_log {
 				Some(
@@ -432,6 +511,9 @@ impl ResponseType for Response {
 				None
 			},
 			first_token: Default::default(),
+			error_type: None,
+			guard_modifications: Vec::new(),
+			usage_estimated: false,
 		}
 	}
 
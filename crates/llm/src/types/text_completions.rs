@@ -0,0 +1,196 @@
+use agent_core::prelude::Strng;
+use agent_core::strng;
+use serde::{Deserialize, Serialize};
+
+use crate::types::RequestType;
+use crate::{AIError, InputFormat, LLMRequest, LLMRequestParams, SimpleChatCompletionMessage};
+
+/// Legacy OpenAI `/v1/completions` request. Only the fields we inspect are typed; everything else
+/// is preserved via `rest` and passed through to the provider as-is.
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct Request {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub model: Option<String>,
+	/// A string, array of strings, array of tokens, or array of token arrays.
+	pub prompt: serde_json::Value,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_tokens: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stream: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub temperature: Option<f64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub top_p: Option<f64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub frequency_penalty: Option<f64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub presence_penalty: Option<f64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub seed: Option<i64>,
+
+	// Everything else - passthrough
+	#[serde(flatten, default)]
+	pub rest: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct Response {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub model: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub usage: Option<Usage>,
+	#[serde(flatten, default)]
+	pub rest: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct Usage {
+	pub prompt_tokens: u32,
+	#[serde(default)]
+	pub completion_tokens: u32,
+	pub total_tokens: u32,
+	#[serde(flatten, default)]
+	pub rest: serde_json::Value,
+}
+
+impl RequestType for Request {
+	fn model(&mut self) -> &mut Option<String> {
+		&mut self.model
+	}
+
+	fn prepend_prompts(&mut self, _prompts: Vec<SimpleChatCompletionMessage>) {
+		// Ignored
+	}
+
+	fn append_prompts(&mut self, _prompts: Vec<SimpleChatCompletionMessage>) {
+		// Ignored
+	}
+
+	fn to_llm_request(&self, provider: Strng, _tokenize: bool) -> Result<LLMRequest, AIError> {
+		let model = strng::new(self.model.as_deref().unwrap_or_default());
+		Ok(LLMRequest {
+			// The prompt may be pre-tokenized, so we rely on the provider reported usage instead.
+			input_tokens: None,
+			input_format: InputFormat::TextCompletions,
+			cache_convention: crate::CacheTokenConvention::pending(),
+			request_model: model,
+			provider,
+			streaming: self.stream.unwrap_or_default(),
+			params: LLMRequestParams {
+				temperature: self.temperature,
+				top_p: self.top_p,
+				frequency_penalty: self.frequency_penalty,
+				presence_penalty: self.presence_penalty,
+				seed: self.seed,
+				max_tokens: self.max_tokens,
+				encoding_format: None,
+				dimensions: None,
+			},
+			prompt: Default::default(),
+			provider_state: None,
+		})
+	}
+
+	fn get_messages(&self) -> Vec<SimpleChatCompletionMessage> {
+		// Each text prompt is a user message. Pre-tokenized prompts carry no text.
+		self
+			.prompt_texts()
+			.map(|text| SimpleChatCompletionMessage {
+				role: strng::literal!("user"),
+				content: text.into(),
+			})
+			.collect()
+	}
+
+	fn set_messages(&mut self, messages: Vec<SimpleChatCompletionMessage>) {
+		// Messages line up with the text prompts returned by `get_messages`.
+		for (text, message) in self.prompt_texts_mut().zip(messages) {
+			*text = message.content.to_string();
+		}
+	}
+}
+
+impl Request {
+	/// The text prompts, in order. A prompt is a string or an array of strings; token arrays are
+	/// skipped.
+	fn prompt_texts(&self) -> impl Iterator<Item = &str> {
+		let prompts = match &self.prompt {
+			serde_json::Value::Array(prompts) => prompts.as_slice(),
+			prompt => std::slice::from_ref(prompt),
+		};
+		prompts.iter().filter_map(serde_json::Value::as_str)
+	}
+
+	fn prompt_texts_mut(&mut self) -> impl Iterator<Item = &mut String> {
+		let prompts = match &mut self.prompt {
+			serde_json::Value::Array(prompts) => prompts.as_mut_slice(),
+			prompt => std::slice::from_mut(prompt),
+		};
+		prompts.iter_mut().filter_map(|v| match v {
+			serde_json::Value::String(s) => Some(s),
+			_ => None,
+		})
+	}
+}
+
+impl crate::types::ResponseType for Response {
+	fn to_llm_response(&self, _include_completion_in_log: bool) -> crate::LLMResponse {
+		crate::LLMResponse {
+			input_tokens: self.usage.as_ref().map(|u| u.prompt_tokens as u64),
+			output_tokens: self.usage.as_ref().map(|u| u.completion_tokens as u64),
+			total_tokens: self.usage.as_ref().map(|u| u.total_tokens as u64),
+			provider_model: self.model.as_deref().map(strng::new),
+			..Default::default()
+		}
+	}
+
+	fn to_webhook_choices(&self) -> Vec<crate::webhook::ResponseChoice> {
+		vec![]
+	}
+
+	fn set_webhook_choices(
+		&mut self,
+		_resp: Vec<crate::webhook::ResponseChoice>,
+	) -> anyhow::Result<()> {
+		Ok(())
+	}
+
+	fn serialize(&self) -> serde_json::Result<Vec<u8>> {
+		serde_json::to_vec(self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn set_messages_rewrites_text_prompts_in_place() {
+		let mut req: Request = serde_json::from_value(serde_json::json!({
+			"model": "gpt-3.5-turbo-instruct",
+			"prompt": ["call 555-0100", [1, 2, 3], "hello"],
+		}))
+		.unwrap();
+		let mut messages = req.get_messages();
+		assert_eq!(
+			messages
+				.iter()
+				.map(|m| m.content.as_str())
+				.collect::<Vec<_>>(),
+			vec!["call 555-0100", "hello"]
+		);
+		messages[0].content = strng::new("call <PHONE>");
+		req.set_messages(messages);
+		assert_eq!(
+			req.prompt,
+			serde_json::json!(["call <PHONE>", [1, 2, 3], "hello"])
+		);
+
+		let mut req: Request = serde_json::from_value(serde_json::json!({"prompt": "hi"})).unwrap();
+		req.set_messages(vec![SimpleChatCompletionMessage {
+			role: strng::literal!("user"),
+			content: strng::new("bye"),
+		}]);
+		assert_eq!(req.prompt, serde_json::json!("bye"));
+	}
+}
@@ -0,0 +1,284 @@
+//! Validation of translated upstream request bodies against provider request contracts.
+//!
+//! This is meant for CI and integration testing, where a translation that drifts from what a
+//! provider accepts should fail loudly rather than surface as an opaque upstream 400. Each contract
+//! is a typed view of the provider request, deserialized with `deny_unknown_fields` where the
+//! provider rejects unknown fields, followed by the value checks serde cannot express. The
+//! contracts pin down the fields the translators produce, not the full provider API.
+
+use anyhow::bail;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::ChatFormat;
+use crate::types::bedrock;
+
+/// Checks a rendered upstream request body against the contract for `format`.
+///
+/// Errors name the JSON path of the offending value.
+pub fn validate(format: ChatFormat, body: &[u8]) -> anyhow::Result<()> {
+	match format {
+		ChatFormat::OpenAICompletions => parse::<OpenAICompletions>(body)?.validate(),
+		ChatFormat::OpenAIResponses => parse::<OpenAIResponses>(body)?.validate(),
+		ChatFormat::AnthropicMessages => parse::<AnthropicMessages>(body)?.validate(),
+		ChatFormat::BedrockConverse => parse::<BedrockConverse>(body)?.validate(),
+	}
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> anyhow::Result<T> {
+	Ok(serde_json_path_to_error::from_slice(body)?)
+}
+
+fn validate_model(model: Option<&str>) -> anyhow::Result<()> {
+	if model.is_some_and(str::is_empty) {
+		bail!("model: must not be empty");
+	}
+	Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct BedrockConverse {
+	#[serde(default)]
+	model_id: Option<String>,
+	messages: Vec<bedrock::Message>,
+	#[serde(default)]
+	system: Option<Vec<bedrock::SystemContentBlock>>,
+	#[serde(default)]
+	inference_config: Option<BedrockInferenceConfig>,
+	#[serde(default)]
+	tool_config: Option<BedrockToolConfig>,
+	#[serde(default)]
+	output_config: Option<Map<String, Value>>,
+	#[serde(default)]
+	guardrail_config: Option<Map<String, Value>>,
+	#[serde(default)]
+	additional_model_request_fields: Option<Map<String, Value>>,
+	#[serde(default)]
+	prompt_variables: Option<Map<String, Value>>,
+	#[serde(default)]
+	additional_model_response_field_paths: Option<Vec<String>>,
+	#[serde(default)]
+	request_metadata: Option<Map<String, Value>>,
+	#[serde(default)]
+	performance_config: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BedrockInferenceConfig {
+	max_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BedrockToolConfig {
+	tools: Vec<Map<String, Value>>,
+}
+
+impl BedrockConverse {
+	fn validate(&self) -> anyhow::Result<()> {
+		validate_model(self.model_id.as_deref())?;
+		if self.messages.is_empty() {
+			bail!("messages: must not be empty");
+		}
+		for (i, message) in self.messages.iter().enumerate() {
+			if message.content.is_empty() {
+				bail!("messages[{i}].content: must not be empty");
+			}
+		}
+		if let Some(config) = &self.inference_config
+			&& config.max_tokens == 0
+		{
+			bail!("inferenceConfig.maxTokens: must be at least 1");
+		}
+		if let Some(config) = &self.tool_config {
+			if config.tools.is_empty() {
+				bail!("toolConfig.tools: must not be empty");
+			}
+			for (i, tool) in config.tools.iter().enumerate() {
+				if tool.len() != 1 {
+					bail!("toolConfig.tools[{i}]: must have exactly one member");
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessages {
+	model: String,
+	max_tokens: u64,
+	messages: Vec<AnthropicMessage>,
+}
+
+#[allow(dead_code)] // Only deserialized to check its shape.
+#[derive(Debug, Deserialize)]
+struct AnthropicMessage {
+	role: bedrock::Role,
+	content: StringOrBlocks,
+}
+
+#[allow(dead_code)] // Only deserialized to check its shape.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StringOrBlocks {
+	String(String),
+	Blocks(Vec<ContentBlock>),
+}
+
+#[allow(dead_code)] // Only deserialized to check its shape.
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+	#[serde(rename = "type")]
+	kind: String,
+}
+
+impl AnthropicMessages {
+	fn validate(&self) -> anyhow::Result<()> {
+		validate_model(Some(&self.model))?;
+		if self.max_tokens == 0 {
+			bail!("max_tokens: must be at least 1");
+		}
+		for (i, message) in self.messages.iter().enumerate() {
+			if let StringOrBlocks::Blocks(blocks) = &message.content
+				&& blocks.is_empty()
+			{
+				bail!("messages[{i}].content: must not be empty");
+			}
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAICompletions {
+	#[serde(default)]
+	model: Option<String>,
+	messages: Vec<OpenAICompletionsMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OpenAICompletionsRole {
+	Developer,
+	System,
+	User,
+	Assistant,
+	Tool,
+	Function,
+}
+
+#[allow(dead_code)] // Only deserialized to check its shape.
+#[derive(Debug, Deserialize)]
+struct OpenAICompletionsMessage {
+	role: OpenAICompletionsRole,
+	#[serde(default)]
+	content: Option<StringOrValues>,
+}
+
+#[allow(dead_code)] // Only deserialized to check its shape.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StringOrValues {
+	String(String),
+	Values(Vec<Value>),
+}
+
+impl OpenAICompletions {
+	fn validate(&self) -> anyhow::Result<()> {
+		validate_model(self.model.as_deref())?;
+		if self.messages.is_empty() {
+			bail!("messages: must not be empty");
+		}
+		Ok(())
+	}
+}
+
+#[allow(dead_code)] // Only deserialized to check its shape.
+#[derive(Debug, Deserialize)]
+struct OpenAIResponses {
+	#[serde(default)]
+	model: Option<String>,
+	#[serde(default)]
+	input: Option<StringOrValues>,
+}
+
+impl OpenAIResponses {
+	fn validate(&self) -> anyhow::Result<()> {
+		validate_model(self.model.as_deref())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use agent_core::strng;
+	use serde_json::json;
+
+	use super::*;
+	use crate::types;
+
+	fn translated_bedrock_request() -> Value {
+		let provider = crate::bedrock::Provider {
+			model: None,
+			region: strng::new("us-east-1"),
+			guardrail_identifier: None,
+			guardrail_version: None,
+			anthropic_version: None,
+		};
+		let req: types::completions::Request = serde_json::from_value(json!({
+			"model": "anthropic.claude-3-haiku",
+			"max_tokens": 64,
+			"messages": [
+				{"role": "system", "content": "Be brief."},
+				{"role": "user", "content": "Hello"}
+			]
+		}))
+		.unwrap();
+		let body = crate::conversion::bedrock::from_completions::translate(&req, &provider, None, None)
+			.unwrap()
+			.body;
+		serde_json::from_slice(&body).unwrap()
+	}
+
+	fn validate_bedrock(req: &Value) -> anyhow::Result<()> {
+		validate(
+			ChatFormat::BedrockConverse,
+			&serde_json::to_vec(req).unwrap(),
+		)
+	}
+
+	#[test]
+	fn translated_bedrock_request_is_valid() {
+		validate_bedrock(&translated_bedrock_request()).unwrap();
+	}
+
+	#[test]
+	fn malformed_bedrock_request_is_caught() {
+		// Drift a translator could plausibly introduce: an OpenAI-style role, a bare string
+		// content, and a snake_case top-level field.
+		let mut req = translated_bedrock_request();
+		req["messages"][0]["role"] = json!("system");
+		let err = validate_bedrock(&req).unwrap_err().to_string();
+		assert!(err.starts_with("messages[0].role:"), "{err}");
+
+		let mut req = translated_bedrock_request();
+		req["messages"][0]["content"] = json!("Hello");
+		let err = validate_bedrock(&req).unwrap_err().to_string();
+		assert!(err.starts_with("messages[0].content:"), "{err}");
+
+		let mut req = translated_bedrock_request();
+		let config = req["inferenceConfig"].take();
+		req.as_object_mut().unwrap().remove("inferenceConfig");
+		req["inference_config"] = config;
+		let err = validate_bedrock(&req).unwrap_err().to_string();
+		assert!(err.contains("unknown field `inference_config`"), "{err}");
+
+		let mut req = translated_bedrock_request();
+		req["messages"][0]["content"] = json!([]);
+		let err = validate_bedrock(&req).unwrap_err().to_string();
+		assert_eq!(err, "messages[0].content: must not be empty");
+	}
+}
@@ -23,19 +23,24 @@ pub struct Provider {
 	pub region: Option<Strng>,
 	/// Google Cloud project ID for Vertex AI.
 	pub project_id: Strng,
+	/// `anthropic_version` set in the body of requests to Anthropic models. Defaults to
+	/// `vertex-2023-10-16`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub anthropic_version: Option<Strng>,
 }
 
 impl super::Provider for Provider {
 	const NAME: Strng = strng::literal!("gcp.vertex_ai");
 }
 
-pub fn prepare_anthropic_message_body(body: Vec<u8>) -> Result<Vec<u8>, AIError> {
-	prepare_anthropic_body(body, |b| {
-		b.remove("model");
-	})
-}
-
 impl Provider {
+	fn anthropic_version(&self) -> &str {
+		self
+			.anthropic_version
+			.as_deref()
+			.unwrap_or(ANTHROPIC_VERSION)
+	}
+
 	fn configured_model<'a>(&'a self, request_model: Option<&'a str>) -> Option<&'a str> {
 		self.model.as_deref().or(request_model)
 	}
@@ -45,11 +50,13 @@ impl Provider {
 	}
 
 	pub fn prepare_anthropic_message_body(&self, body: Vec<u8>) -> Result<Vec<u8>, AIError> {
-		prepare_anthropic_message_body(body)
+		prepare_anthropic_body(body, self.anthropic_version(), |b| {
+			b.remove("model");
+		})
 	}
 
 	pub fn prepare_anthropic_count_tokens_body(&self, body: Vec<u8>) -> Result<Vec<u8>, AIError> {
-		prepare_anthropic_body(body, |b| {
+		prepare_anthropic_body(body, self.anthropic_version(), |b| {
 			if let Some(Value::String(model)) = b.get("model") {
 				let normalized = self
 					.configured_model(Some(model))
@@ -65,13 +72,14 @@ impl Provider {
 /// apply caller-specific model handling, strip unsupported fields, serialize.
 fn prepare_anthropic_body(
 	body: Vec<u8>,
+	anthropic_version: &str,
 	apply: impl FnOnce(&mut Map<String, Value>),
 ) -> Result<Vec<u8>, AIError> {
 	let mut body: Map<String, Value> =
 		serde_json::from_slice(&body).map_err(AIError::RequestParsing)?;
 	body.insert(
 		"anthropic_version".to_string(),
-		Value::String(ANTHROPIC_VERSION.to_string()),
+		Value::String(anthropic_version.to_string()),
 	);
 	apply(&mut body);
 	remove_unsupported_vertex_fields(&mut body);
@@ -111,7 +119,7 @@ impl Provider {
 					"/v1/projects/{}/locations/{}/publishers/google/models/{}:predict",
 					self.project_id,
 					location,
-					model
+					crate::encode_path_segment(&model)
 				)
 			},
 			(_, Some(model)) => {
@@ -119,7 +127,7 @@ impl Provider {
 					"/v1/projects/{}/locations/{}/publishers/anthropic/models/{}:{}",
 					self.project_id,
 					location,
-					model,
+					crate::encode_path_segment(&model),
 					if streaming {
 						"streamRawPredict"
 					} else {
@@ -255,6 +263,7 @@ mod tests {
 			project_id: strng::new("test-project"),
 			model: provider.map(strng::new),
 			region: None,
+			anthropic_version: None,
 		};
 		let actual = p.anthropic_model(req).map(|m| m.to_string());
 		assert_eq!(actual.as_deref(), expected);
@@ -270,10 +279,30 @@ mod tests {
 			project_id: strng::new("test-project"),
 			model: None,
 			region: region.map(strng::new),
+			anthropic_version: None,
 		};
 		assert_eq!(p.get_host(RouteType::Completions).as_str(), expected);
 	}
 
+	#[test]
+	fn test_anthropic_version_override() {
+		let version = |anthropic_version: Option<&str>| {
+			let p = Provider {
+				project_id: strng::new("test-project"),
+				model: None,
+				region: None,
+				anthropic_version: anthropic_version.map(strng::new),
+			};
+			let body = p
+				.prepare_anthropic_message_body(br#"{"model":"claude","max_tokens":1}"#.to_vec())
+				.unwrap();
+			let body: Value = serde_json::from_slice(&body).unwrap();
+			body["anthropic_version"].clone()
+		};
+		assert_eq!(version(None), "vertex-2023-10-16");
+		assert_eq!(version(Some("vertex-2025-01-01")), "vertex-2025-01-01");
+	}
+
 	#[test]
 	fn test_output_format_removed_output_config_preserved() {
 		let mut body: Map<String, Value> = serde_json::from_value(serde_json::json!({